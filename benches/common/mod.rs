@@ -0,0 +1,52 @@
+//! Input generation shared by the benchmarks. Every input is drawn from a
+//! fixed seed, so that runs are comparable with each other.
+#![allow(dead_code)]
+
+use ralg::math::{complex::Complex, poly::Polynomial};
+use ralg::random::Pcg32;
+
+/// Seed of every generator used by the benchmarks.
+pub const SEED: u64 = 0xbe9c4;
+
+pub fn rng() -> Pcg32 {
+    Pcg32::seed(SEED)
+}
+
+/// Uniformly random integers.
+pub fn random_vec(n: usize) -> Vec<i64> {
+    let mut rng = rng();
+    (0..n).map(|_| rng.gen_range(i64::MIN..i64::MAX)).collect()
+}
+
+/// Integers from a range much smaller than `n`, so with many repetitions.
+pub fn few_distinct(n: usize) -> Vec<i64> {
+    let mut rng = rng();
+    (0..n).map(|_| rng.gen_range(0..16)).collect()
+}
+
+/// Sorted integers, with every hundredth one moved to a random position.
+pub fn nearly_sorted(n: usize) -> Vec<i64> {
+    let mut rng = rng();
+    let mut xs: Vec<i64> = (0..n as i64).collect();
+    for _ in 0..n / 100 {
+        let (i, j) = (rng.gen_range(0..n), rng.gen_range(0..n));
+        xs.swap(i, j);
+    }
+    xs
+}
+
+/// Floats uniform in `[-1, 1)`.
+pub fn random_floats(n: usize) -> Vec<f32> {
+    let mut rng = rng();
+    (0..n).map(|_| rng.gen_range(-1.0..1.0) as f32).collect()
+}
+
+pub fn random_complex(n: usize) -> Vec<Complex<f32>> {
+    let xs = random_floats(2 * n);
+    xs.chunks(2).map(|z| Complex::new(z[0], z[1])).collect()
+}
+
+/// Polynomial with `n` coefficients uniform in `[-1, 1)`.
+pub fn random_poly(n: usize) -> Polynomial<f32> {
+    Polynomial::new(random_floats(n))
+}
@@ -0,0 +1,42 @@
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion,
+};
+use ralg::math::{
+    fft::{fft, rfft, FftPlanner},
+    poly::Polynomial,
+};
+
+mod common;
+
+fn transforms(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fft");
+    for log in [6, 10, 14, 16] {
+        let n = 1 << log;
+        let xs = common::random_floats(n);
+        group.bench_with_input(BenchmarkId::new("complex", n), &xs, |b, xs| {
+            b.iter_batched(
+                || Polynomial::new(xs.clone()),
+                fft,
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("real", n), &xs, |b, xs| {
+            b.iter_batched(|| xs.clone(), rfft, BatchSize::SmallInput)
+        });
+
+        // The plan is built once, outside of the measurement
+        let plan = FftPlanner::new(n);
+        let zs = common::random_complex(n);
+        group.bench_with_input(BenchmarkId::new("planned", n), &zs, |b, zs| {
+            b.iter_batched_ref(
+                || zs.clone(),
+                |zs| plan.forward(zs),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, transforms);
+criterion_main!(benches);
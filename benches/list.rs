@@ -0,0 +1,87 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ralg::list::{double::DoubleLinked, single::SingleLinked};
+use std::collections::VecDeque;
+use std::hint::black_box;
+
+mod common;
+
+const SIZES: [usize; 3] = [100, 10_000, 1_000_000];
+
+/// Pushing `n` elements then popping them all, from the same end.
+fn stack(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list/stack");
+    for n in SIZES {
+        group.bench_with_input(BenchmarkId::new("single", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut list = SingleLinked::new();
+                (0..n).for_each(|x| list.push(x));
+                while let Some(x) = list.pop() {
+                    black_box(x);
+                }
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("vec", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut xs = Vec::new();
+                (0..n).for_each(|x| xs.push(x));
+                while let Some(x) = xs.pop() {
+                    black_box(x);
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Pushing `n` elements at the back then popping them all from the front.
+fn queue(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list/queue");
+    for n in SIZES {
+        group.bench_with_input(BenchmarkId::new("double", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut list = DoubleLinked::new();
+                (0..n).for_each(|x| list.push_back(x));
+                while let Some(x) = list.pop_front() {
+                    black_box(x);
+                }
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("vec_deque", n),
+            &n,
+            |b, &n| {
+                b.iter(|| {
+                    let mut xs = VecDeque::new();
+                    (0..n).for_each(|x| xs.push_back(x));
+                    while let Some(x) = xs.pop_front() {
+                        black_box(x);
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Summing every element, where the lists pay for chasing pointers.
+fn iteration(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list/iter");
+    for n in SIZES {
+        let xs = common::random_vec(n);
+        let list: DoubleLinked<i64> = xs.iter().copied().collect();
+        group.bench_with_input(
+            BenchmarkId::new("double", n),
+            &list,
+            |b, list| {
+                b.iter(|| list.iter().fold(0_i64, |s, &x| s.wrapping_add(x)))
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("vec", n), &xs, |b, xs| {
+            b.iter(|| xs.iter().fold(0_i64, |s, &x| s.wrapping_add(x)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, stack, queue, iteration);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion,
+};
+
+mod common;
+
+/// Naive O(n^2) multiplication against the FFT based one, to locate the
+/// degree where the latter starts paying off.
+fn multiplication(c: &mut Criterion) {
+    let mut group = c.benchmark_group("poly_mul");
+    for n in [16, 64, 256, 1024, 4096] {
+        let p = common::random_poly(n);
+        let q = common::random_poly(n);
+        let pair = (p, q);
+        group.bench_with_input(
+            BenchmarkId::new("naive", n),
+            &pair,
+            |b, (p, q)| b.iter(|| p * q),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("fft", n),
+            &pair,
+            |b, (p, q)| {
+                b.iter_batched(
+                    || (p.clone(), q.clone()),
+                    |(p, q)| p.mul_fft(q),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, multiplication);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion,
+};
+use ralg::sorting::{
+    heap::HeapSort, insertion::InsertionSort, merge::MergeSort,
+    parallel::ParallelMergeSort, quick::QuickSort, tim::TimSort, Sorter,
+};
+
+mod common;
+
+/// Sorts a fresh copy of `input` on each iteration.
+fn bench_sort(
+    c: &mut Criterion,
+    group: &str,
+    inputs: &[(usize, Vec<i64>)],
+    quadratic: bool,
+) {
+    let mut group = c.benchmark_group(group);
+    for (n, input) in inputs {
+        let mut run = |name: &str, sort: fn(&mut [i64])| {
+            group.bench_with_input(
+                BenchmarkId::new(name, n),
+                input,
+                |b, xs| {
+                    b.iter_batched_ref(
+                        || xs.clone(),
+                        |xs| sort(xs),
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+        };
+        run("merge", MergeSort::sort);
+        run("quick", QuickSort::sort);
+        run("heap", HeapSort::sort);
+        run("tim", TimSort::sort);
+        run("parallel_merge", ParallelMergeSort::sort);
+        run("std", <[i64]>::sort);
+        run("std_unstable", <[i64]>::sort_unstable);
+        if quadratic || *n <= 1000 {
+            run("insertion", InsertionSort::sort);
+        }
+    }
+    group.finish();
+}
+
+fn sorting(c: &mut Criterion) {
+    let sizes = [100, 1_000, 10_000, 100_000];
+    let inputs = |generate: fn(usize) -> Vec<i64>| -> Vec<(usize, Vec<i64>)> {
+        sizes.iter().map(|&n| (n, generate(n))).collect()
+    };
+    bench_sort(c, "sort/random", &inputs(common::random_vec), false);
+    bench_sort(c, "sort/few_distinct", &inputs(common::few_distinct), false);
+    // Insertion sort is linear on nearly sorted inputs
+    bench_sort(
+        c,
+        "sort/nearly_sorted",
+        &inputs(common::nearly_sorted),
+        true,
+    );
+}
+
+criterion_group!(benches, sorting);
+criterion_main!(benches);
@@ -0,0 +1,145 @@
+//! Coin change
+//!
+//! Coins are given by their denominations, each available in any quantity.
+use alloc::{vec, vec::Vec};
+
+/// Number of ways to make `amount` out of `coins`, regardless of the order of
+/// the coins. Repeated denominations are the same coin, so they are counted
+/// once. Takes O(n * amount) time.
+///
+/// Panics on overflow.
+///
+/// Example:
+/// ```
+/// use ralg::dp::coin_change_ways;
+///
+/// assert_eq!(coin_change_ways(&[1, 2, 5], 5), 4);
+/// assert_eq!(coin_change_ways(&[2], 3), 0);
+/// assert_eq!(coin_change_ways(&[3, 7], 0), 1);
+/// assert_eq!(coin_change_ways(&[1, 1], 15), 1);
+/// ```
+pub fn coin_change_ways(coins: &[usize], amount: usize) -> u128 {
+    let mut coins = coins.to_vec();
+    coins.sort_unstable();
+    coins.dedup();
+
+    // Ways to make each amount with the coins seen so far
+    let mut ways = vec![0_u128; amount + 1];
+    ways[0] = 1;
+    for &coin in coins.iter().filter(|&&c| c > 0) {
+        for a in coin..=amount {
+            ways[a] = ways[a]
+                .checked_add(ways[a - coin])
+                .expect("number of ways overflowed");
+        }
+    }
+    ways[amount]
+}
+
+/// Fewest coins adding up to `amount`, in decreasing order, or `None` if
+/// `amount` cannot be made. Takes O(n * amount) time.
+///
+/// Example:
+/// ```
+/// use ralg::dp::min_coins;
+///
+/// assert_eq!(min_coins(&[1, 5, 10, 25], 63), Some(vec![25, 25, 10, 1, 1, 1]));
+/// // Greedy would take 4 + 1 + 1
+/// assert_eq!(min_coins(&[1, 3, 4], 6), Some(vec![3, 3]));
+/// assert_eq!(min_coins(&[4, 6], 7), None);
+/// ```
+pub fn min_coins(coins: &[usize], amount: usize) -> Option<Vec<usize>> {
+    // Fewest coins making each amount, and the last coin used for it
+    let mut count = vec![None; amount + 1];
+    let mut last = vec![0; amount + 1];
+    count[0] = Some(0);
+    for a in 1..=amount {
+        for &coin in coins.iter().filter(|&&c| c > 0 && c <= a) {
+            if let Some(c) = count[a - coin] {
+                if count[a].is_none_or(|best| c + 1 < best) {
+                    count[a] = Some(c + 1);
+                    last[a] = coin;
+                }
+            }
+        }
+    }
+
+    count[amount]?;
+    let mut change = Vec::new();
+    let mut a = amount;
+    while a > 0 {
+        change.push(last[a]);
+        a -= last[a];
+    }
+    change.sort_unstable_by(|x, y| y.cmp(x));
+    Some(change)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    /// Fewest coins and number of ways, by trying every count of the first
+    /// coin.
+    fn naive(coins: &[usize], amount: usize) -> (Option<usize>, u128) {
+        match coins.split_first() {
+            None => (
+                if amount == 0 { Some(0) } else { None },
+                u128::from(amount == 0),
+            ),
+            Some((&c, rest)) => {
+                let mut fewest = None;
+                let mut ways = 0;
+                for k in 0..=amount / c {
+                    let (f, w) = naive(rest, amount - k * c);
+                    if let Some(f) = f {
+                        fewest =
+                            Some(fewest.map_or(f + k, |b: usize| b.min(f + k)));
+                    }
+                    ways += w;
+                }
+                (fewest, ways)
+            }
+        }
+    }
+
+    #[test]
+    fn against_naive() {
+        let mut rng = Pcg32::seed(0xbb67ae8584caa73b);
+        for _ in 0..200 {
+            let coins: Vec<usize> = (0..rng.gen_range(1..5))
+                .map(|_| rng.gen_range(1..16))
+                .collect();
+            let amount = rng.gen_range(0..60);
+            let (fewest, _) = naive(&coins, amount);
+            // The naive count takes repeated denominations as distinct coins
+            let mut distinct = coins.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            let (_, ways) = naive(&distinct, amount);
+            assert_eq!(coin_change_ways(&coins, amount), ways);
+
+            let change = min_coins(&coins, amount);
+            assert_eq!(change.as_ref().map(|c| c.len()), fewest);
+            if let Some(change) = change {
+                assert_eq!(change.iter().sum::<usize>(), amount);
+                assert!(change.iter().all(|c| coins.contains(c)));
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_denominations() {
+        assert_eq!(coin_change_ways(&[1, 1], 15), 1);
+        assert_eq!(coin_change_ways(&[2, 1, 2, 5, 1], 5), 4);
+        assert_eq!(min_coins(&[3, 3, 1], 7), Some(vec![3, 3, 1]));
+    }
+
+    #[test]
+    fn zero_coins_ignored() {
+        assert_eq!(coin_change_ways(&[0, 2], 4), 1);
+        assert_eq!(min_coins(&[0], 0), Some(vec![]));
+        assert_eq!(min_coins(&[0], 1), None);
+    }
+}
@@ -0,0 +1,187 @@
+//! Knapsack problems
+use crate::math::num::Zero;
+use alloc::{vec, vec::Vec};
+
+/// Items chosen for a knapsack, by index, along with their total value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection<V> {
+    pub value: V,
+    /// Indices of the chosen items, in increasing order. In the unbounded
+    /// problem, an item appears once per copy taken.
+    pub items: Vec<usize>,
+}
+
+/// 0/1 knapsack: chooses items, given as `(weight, value)` pairs, each at
+/// most once, of total weight at most `capacity`, maximizing their total
+/// value. Takes O(n * capacity) time and space.
+///
+/// Example:
+/// ```
+/// use ralg::dp::knapsack_01;
+///
+/// let items = [(10, 60), (20, 100), (30, 120)];
+/// let best = knapsack_01(&items, 50);
+/// assert_eq!(best.value, 220);
+/// assert_eq!(best.items, [1, 2]);
+/// ```
+pub fn knapsack_01<V>(items: &[(usize, V)], capacity: usize) -> Selection<V>
+where
+    V: Zero + Copy + PartialOrd,
+{
+    let n = items.len();
+    // Best value of the first i items within weight w
+    let mut best = vec![vec![V::zero(); capacity + 1]; n + 1];
+    for (i, &(weight, value)) in items.iter().enumerate() {
+        for w in 0..=capacity {
+            let mut b = best[i][w];
+            if weight <= w {
+                let taken = best[i][w - weight] + value;
+                if taken > b {
+                    b = taken;
+                }
+            }
+            best[i + 1][w] = b;
+        }
+    }
+
+    // Item i was taken wherever it improved on the first i items
+    let mut chosen = Vec::new();
+    let mut w = capacity;
+    for i in (0..n).rev() {
+        if best[i + 1][w] != best[i][w] {
+            chosen.push(i);
+            w -= items[i].0;
+        }
+    }
+    chosen.reverse();
+    Selection {
+        value: best[n][capacity],
+        items: chosen,
+    }
+}
+
+/// Unbounded knapsack: as `knapsack_01`, but any number of copies of each
+/// item may be taken. Takes O(n * capacity) time and O(capacity) space.
+///
+/// Panics if an item has zero weight.
+///
+/// Example:
+/// ```
+/// use ralg::dp::knapsack_unbounded;
+///
+/// let items = [(5, 10.0), (3, 7.0), (4, 8.5)];
+/// let best = knapsack_unbounded(&items, 10);
+/// assert_eq!(best.value, 22.5);
+/// assert_eq!(best.items, [1, 1, 2]);
+/// ```
+pub fn knapsack_unbounded<V>(
+    items: &[(usize, V)],
+    capacity: usize,
+) -> Selection<V>
+where
+    V: Zero + Copy + PartialOrd,
+{
+    assert!(
+        items.iter().all(|&(weight, _)| weight > 0),
+        "items must have positive weight"
+    );
+    // Best value within weight w, and the last item taken for it, if any
+    let mut best = vec![V::zero(); capacity + 1];
+    let mut last = vec![None; capacity + 1];
+    for w in 1..=capacity {
+        best[w] = best[w - 1];
+        for (i, &(weight, value)) in items.iter().enumerate() {
+            if weight <= w && best[w - weight] + value > best[w] {
+                best[w] = best[w - weight] + value;
+                last[w] = Some(i);
+            }
+        }
+    }
+
+    let mut chosen = Vec::new();
+    let mut w = capacity;
+    while w > 0 {
+        match last[w] {
+            Some(i) => {
+                chosen.push(i);
+                w -= items[i].0;
+            }
+            // Leftover capacity
+            None => w -= 1,
+        }
+    }
+    chosen.sort_unstable();
+    Selection {
+        value: best[capacity],
+        items: chosen,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    fn total(items: &[(usize, u64)], chosen: &[usize]) -> (usize, u64) {
+        chosen
+            .iter()
+            .fold((0, 0), |(w, v), &i| (w + items[i].0, v + items[i].1))
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(knapsack_01::<u32>(&[], 10).items, []);
+        assert_eq!(knapsack_01(&[(0, 3), (4, 1)], 0).items, [0]);
+        assert_eq!(knapsack_01(&[(5, 3)], 4).value, 0);
+        assert_eq!(knapsack_unbounded(&[(2, 3)], 7).items, [0, 0, 0]);
+        // Negative values are never worth taking
+        assert_eq!(knapsack_01(&[(1, -2), (1, 1)], 2).items, [1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unbounded_zero_weight() {
+        knapsack_unbounded(&[(0, 1)], 3);
+    }
+
+    #[test]
+    fn brute_force() {
+        let mut rng = Pcg32::seed(0x6a09e667f3bcc908);
+        for _ in 0..200 {
+            let n = rng.gen_range(0..10);
+            let items: Vec<(usize, u64)> = (0..n)
+                .map(|_| (rng.gen_range(1..13), rng.gen_range(0..30)))
+                .collect();
+            let capacity = rng.gen_range(0..40);
+
+            let best = knapsack_01(&items, capacity);
+            let (w, v) = total(&items, &best.items);
+            assert!(w <= capacity);
+            assert_eq!(v, best.value);
+            let expected = (0..1_u32 << n)
+                .map(|mask| {
+                    let chosen: Vec<usize> =
+                        (0..n).filter(|&i| mask >> i & 1 == 1).collect();
+                    total(&items, &chosen)
+                })
+                .filter(|&(w, _)| w <= capacity)
+                .map(|(_, v)| v)
+                .max();
+            assert_eq!(Some(best.value), expected);
+
+            // Taking each item as many times as it fits as a 0/1 problem
+            let unbounded = knapsack_unbounded(&items, capacity);
+            let (w, v) = total(&items, &unbounded.items);
+            assert!(w <= capacity);
+            assert_eq!(v, unbounded.value);
+            let copies: Vec<(usize, u64)> = items
+                .iter()
+                .flat_map(|&item| vec![item; capacity / item.0])
+                .collect();
+            if copies.len() <= 16 {
+                assert_eq!(knapsack_01(&copies, capacity).value, v);
+            }
+            assert!(v >= best.value);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Longest increasing subsequence
+use alloc::{vec, vec::Vec};
+
+/// Indices of a longest strictly increasing subsequence of `xs`, in
+/// increasing order.
+///
+/// Patience sorting: `tails[k]` holds the smallest last element of an
+/// increasing subsequence of length `k + 1` found so far, so that `tails` is
+/// increasing and each element finds its place by binary search, in
+/// O(n log(n)) time. Each element also keeps its predecessor, to recover the
+/// subsequence.
+///
+/// Example:
+/// ```
+/// use ralg::dp::lis;
+///
+/// let xs = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+/// let seq: Vec<_> = lis(&xs).into_iter().map(|i| xs[i]).collect();
+/// assert_eq!(seq, [1, 2, 3, 5]);
+/// ```
+pub fn lis<T: PartialOrd>(xs: &[T]) -> Vec<usize> {
+    // Indices into xs
+    let mut tails: Vec<usize> = Vec::new();
+    let mut pred = vec![None; xs.len()];
+    for (i, x) in xs.iter().enumerate() {
+        let k = tails.partition_point(|&t| xs[t] < *x);
+        if k > 0 {
+            pred[i] = Some(tails[k - 1]);
+        }
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
+        }
+    }
+
+    let mut seq = Vec::with_capacity(tails.len());
+    let mut next = tails.last().copied();
+    while let Some(i) = next {
+        seq.push(i);
+        next = pred[i];
+    }
+    seq.reverse();
+    seq
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    #[test]
+    fn against_quadratic() {
+        let mut rng = Pcg32::seed(0x3c6ef372fe94f82b);
+        for _ in 0..300 {
+            let xs: Vec<u64> = (0..rng.gen_range(0..50))
+                .map(|_| rng.gen_range(0..20))
+                .collect();
+            // Longest increasing subsequence ending at each index
+            let mut len = vec![1; xs.len()];
+            for j in 0..xs.len() {
+                for i in 0..j {
+                    if xs[i] < xs[j] {
+                        len[j] = len[j].max(len[i] + 1);
+                    }
+                }
+            }
+            let seq = lis(&xs);
+            assert_eq!(seq.len(), len.iter().copied().max().unwrap_or(0));
+            for w in seq.windows(2) {
+                assert!(w[0] < w[1] && xs[w[0]] < xs[w[1]]);
+            }
+        }
+        assert!(lis::<f64>(&[]).is_empty());
+        assert_eq!(lis(&[2.0, 2.0, 2.0]).len(), 1);
+    }
+}
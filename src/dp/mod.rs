@@ -0,0 +1,8 @@
+//! Dynamic programming
+pub mod coins;
+pub mod knapsack;
+pub mod lis;
+
+pub use coins::{coin_change_ways, min_coins};
+pub use knapsack::{knapsack_01, knapsack_unbounded, Selection};
+pub use lis::lis;
@@ -0,0 +1,327 @@
+//! Packed vectors of bits
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign,
+};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Vector of bits packed in 64-bit words, also usable as a set of indices.
+///
+/// The bits past the length in the last word are kept clear, so that whole
+/// words can be counted and compared. Counting, `rank` and `select` go
+/// through the words with a population count, in O(n / 64) time.
+///
+/// Example:
+/// ```
+/// use ralg::ds::bitvec::BitVec;
+///
+/// let mut bits = BitVec::new(10);
+/// bits.set(2, true);
+/// bits.set(7, true);
+/// bits.flip(3);
+/// assert!(bits.get(3));
+/// assert_eq!(bits.ones().collect::<Vec<_>>(), [2, 3, 7]);
+/// assert_eq!(bits.rank(5), 2);
+/// assert_eq!(bits.select(2), Some(7));
+///
+/// let evens: BitVec = (0..10).map(|i| i % 2 == 0).collect();
+/// assert_eq!((&bits & &evens).ones().collect::<Vec<_>>(), [2]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    /// Vector of `len` clear bits.
+    pub fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    /// Vector of `len` set bits.
+    pub fn ones_of_len(len: usize) -> Self {
+        let mut bits = Self {
+            words: vec![u64::MAX; len.div_ceil(WORD_BITS)],
+            len,
+        };
+        bits.clear_tail();
+        bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the bits of the last word past the length.
+    fn clear_tail(&mut self) {
+        let used = self.len % WORD_BITS;
+        if used > 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << used) - 1;
+            }
+        }
+    }
+
+    fn check(&self, i: usize) {
+        assert!(i < self.len, "index out of bounds");
+    }
+
+    /// Panics if `i >= len`, as do `set` and `flip`.
+    pub fn get(&self, i: usize) -> bool {
+        self.check(i);
+        self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, bit: bool) {
+        self.check(i);
+        let mask = 1 << (i % WORD_BITS);
+        if bit {
+            self.words[i / WORD_BITS] |= mask;
+        } else {
+            self.words[i / WORD_BITS] &= !mask;
+        }
+    }
+
+    pub fn flip(&mut self, i: usize) {
+        self.check(i);
+        self.words[i / WORD_BITS] ^= 1 << (i % WORD_BITS);
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(WORD_BITS) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, bit);
+    }
+
+    /// Sets every bit to `bit`.
+    pub fn fill(&mut self, bit: bool) {
+        self.words.fill(if bit { u64::MAX } else { 0 });
+        self.clear_tail();
+    }
+
+    /// Number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Number of set bits before position `i`. Panics if `i > len`.
+    pub fn rank(&self, i: usize) -> usize {
+        assert!(i <= self.len, "index out of bounds");
+        let (whole, rest) = (i / WORD_BITS, i % WORD_BITS);
+        let before: usize = self.words[..whole]
+            .iter()
+            .map(|w| w.count_ones() as usize)
+            .sum();
+        match rest {
+            0 => before,
+            _ => {
+                let partial = self.words[whole] & ((1 << rest) - 1);
+                before + partial.count_ones() as usize
+            }
+        }
+    }
+
+    /// Position of the set bit of rank `k`, the first one having rank zero,
+    /// or `None` if fewer than `k + 1` bits are set.
+    pub fn select(&self, mut k: usize) -> Option<usize> {
+        for (w, &word) in self.words.iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if k < ones {
+                // Clear the lowest k set bits of the word
+                let mut word = word;
+                for _ in 0..k {
+                    word &= word - 1;
+                }
+                return Some(w * WORD_BITS + word.trailing_zeros() as usize);
+            }
+            k -= ones;
+        }
+        None
+    }
+
+    /// Iterates over the bits.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    /// Iterates over the positions of the set bits, in increasing order,
+    /// skipping the clear bits a word at a time.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, &word)| {
+            let mut rest = word;
+            core::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(w * WORD_BITS + bit)
+            })
+        })
+    }
+
+    fn assert_same_len(&self, other: &Self) {
+        assert_eq!(self.len, other.len, "bit vectors of different lengths");
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitVec::new(0);
+        bits.extend(iter);
+        bits
+    }
+}
+
+impl Extend<bool> for BitVec {
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for bit in iter {
+            self.push(bit);
+        }
+    }
+}
+
+impl fmt::Debug for BitVec {
+    /// The bits from the first, as zeros and ones.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in self.iter() {
+            f.write_str(if bit { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+/// Implements a bitwise operator wordwise, between bit vectors of the same
+/// length, panicking otherwise.
+macro_rules! bitwise_impl {
+    ($($op: ident $f: ident $op_assign: ident $f_assign: ident $sym: tt),*) => ($(
+        impl $op_assign<&BitVec> for BitVec {
+            fn $f_assign(&mut self, rhs: &BitVec) {
+                self.assert_same_len(rhs);
+                for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+                    *a $sym *b;
+                }
+            }
+        }
+
+        impl $op<&BitVec> for &BitVec {
+            type Output = BitVec;
+
+            fn $f(self, rhs: &BitVec) -> BitVec {
+                let mut bits = self.clone();
+                bits.$f_assign(rhs);
+                bits
+            }
+        }
+
+        impl $op for BitVec {
+            type Output = Self;
+
+            fn $f(mut self, rhs: Self) -> Self {
+                self.$f_assign(&rhs);
+                self
+            }
+        }
+    )*)
+}
+
+bitwise_impl!(
+    BitAnd bitand BitAndAssign bitand_assign &=,
+    BitOr bitor BitOrAssign bitor_assign |=,
+    BitXor bitxor BitXorAssign bitxor_assign ^=
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use alloc::format;
+
+    #[test]
+    fn against_bools() {
+        let mut rng = Pcg32::seed(0xb17e5);
+        for len in [0, 1, 63, 64, 65, 200] {
+            let mut bits = BitVec::new(len);
+            let mut naive = vec![false; len];
+            for _ in 0..4 * len {
+                let i = rng.gen_range(0..len);
+                if rng.gen_range(0..2) == 0 {
+                    bits.flip(i);
+                    naive[i] = !naive[i];
+                } else {
+                    let bit = rng.gen_range(0..3) == 0;
+                    bits.set(i, bit);
+                    naive[i] = bit;
+                }
+            }
+            assert!(bits.iter().eq(naive.iter().copied()));
+            assert_eq!(bits, naive.iter().copied().collect());
+
+            let ones: Vec<usize> = (0..len).filter(|&i| naive[i]).collect();
+            assert_eq!(bits.ones().collect::<Vec<_>>(), ones);
+            assert_eq!(bits.count_ones(), ones.len());
+            for i in 0..=len {
+                assert_eq!(
+                    bits.rank(i),
+                    ones.iter().filter(|&&j| j < i).count()
+                );
+            }
+            for k in 0..=ones.len() {
+                assert_eq!(bits.select(k), ones.get(k).copied());
+            }
+        }
+    }
+
+    #[test]
+    fn bitwise() {
+        let mut rng = Pcg32::seed(0xb175);
+        let len = 150;
+        let a: BitVec = (0..len).map(|_| rng.gen_range(0..2) == 0).collect();
+        let b: BitVec = (0..len).map(|_| rng.gen_range(0..2) == 0).collect();
+        let and = &a & &b;
+        let or = &a | &b;
+        let xor = a.clone() ^ b.clone();
+        for i in 0..len {
+            assert_eq!(and.get(i), a.get(i) && b.get(i));
+            assert_eq!(or.get(i), a.get(i) || b.get(i));
+            assert_eq!(xor.get(i), a.get(i) != b.get(i));
+        }
+
+        let mut full = BitVec::ones_of_len(len);
+        assert_eq!(full.count_ones(), len);
+        full ^= &a;
+        assert_eq!(full.count_ones(), len - a.count_ones());
+        full.fill(false);
+        assert_eq!(full, BitVec::new(len));
+    }
+
+    #[test]
+    fn formatting() {
+        let bits: BitVec = [true, false, true, true].into_iter().collect();
+        assert_eq!(format!("{:?}", bits), "1011");
+    }
+
+    #[test]
+    #[should_panic]
+    fn different_lengths() {
+        let _ = &BitVec::new(3) | &BitVec::new(4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds() {
+        BitVec::new(64).get(64);
+    }
+}
@@ -0,0 +1,513 @@
+//! Hash tables resolving collisions by chaining and by open addressing
+use crate::list::single::SingleLinked;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// Number of buckets of a new table.
+const INITIAL_BUCKETS: usize = 8;
+
+/// 64-bit FNV-1a hasher, whose output is folded so that the low bits used to
+/// pick a bucket depend on every byte.
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0 ^ (self.0 >> 32)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+fn hash<K: Hash>(key: &K) -> u64 {
+    let mut hasher = Fnv(0xcbf29ce484222325);
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Bucket of `key` among a power of two number of buckets.
+fn bucket<K: Hash>(key: &K, buckets: usize) -> usize {
+    hash(key) as usize & (buckets - 1)
+}
+
+/// Hash map with separate chaining: each bucket is a linked list of the
+/// entries hashed to it. The number of buckets doubles whenever the load
+/// factor, the number of entries per bucket, would exceed 3/4, so that
+/// operations take O(1) expected time.
+///
+/// Example:
+/// ```
+/// use ralg::ds::hashmap::HashTable;
+///
+/// let mut table = HashTable::new();
+/// table.insert("one", 1);
+/// table.insert("two", 2);
+/// assert_eq!(table.insert("one", 10), Some(1));
+/// assert_eq!(table.get(&"one"), Some(&10));
+/// assert_eq!(table.remove(&"two"), Some(2));
+/// assert_eq!(table.len(), 1);
+/// ```
+pub struct HashTable<K: Hash + Eq, V> {
+    buckets: Vec<SingleLinked<(K, V)>>,
+    len: usize,
+}
+
+impl<K: Hash + Eq, V> HashTable<K, V> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Empty table holding up to `capacity` entries without resizing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let buckets = (capacity * 4)
+            .div_ceil(3)
+            .next_power_of_two()
+            .max(INITIAL_BUCKETS);
+        Self {
+            buckets: (0..buckets).map(|_| SingleLinked::new()).collect(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of buckets.
+    pub fn buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Average number of entries per bucket.
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.buckets.len() as f64
+    }
+
+    fn chain(&self, key: &K) -> &SingleLinked<(K, V)> {
+        &self.buckets[bucket(key, self.buckets.len())]
+    }
+
+    fn chain_mut(&mut self, key: &K) -> &mut SingleLinked<(K, V)> {
+        let n = self.buckets.len();
+        &mut self.buckets[bucket(key, n)]
+    }
+
+    /// Inserts `value` under `key`, returning the value previously stored
+    /// under it, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(core::mem::replace(old, value));
+        }
+        if 4 * (self.len + 1) > 3 * self.buckets.len() {
+            self.resize(2 * self.buckets.len());
+        }
+        self.chain_mut(&key).push((key, value));
+        self.len += 1;
+        None
+    }
+
+    /// Moves every entry to a table of `buckets` buckets.
+    fn resize(&mut self, buckets: usize) {
+        let old = core::mem::replace(
+            &mut self.buckets,
+            (0..buckets).map(|_| SingleLinked::new()).collect(),
+        );
+        for chain in old {
+            for (key, value) in chain {
+                self.chain_mut(&key).push((key, value));
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.chain(key)
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.chain_mut(key)
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (_, value) = self.chain_mut(key).remove_first(|(k, _)| k == key)?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Iterates over the entries in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.buckets
+            .iter()
+            .flat_map(|chain| chain.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+impl<K: Hash + Eq, V> Default for HashTable<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for HashTable<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for HashTable<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut table = HashTable::new();
+        table.extend(iter);
+        table
+    }
+}
+
+impl<K: Hash + Eq + fmt::Debug, V: fmt::Debug> fmt::Debug for HashTable<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+enum Slot<K, V> {
+    Empty,
+    /// Left by a removal, so that probing carries on past it.
+    Deleted,
+    Full(K, V),
+}
+
+/// Hash map with open addressing: entries are stored directly in an array,
+/// and a key colliding with another is placed in the next free slot, probing
+/// linearly. Removed entries leave a tombstone behind. Whenever entries and
+/// tombstones would fill more than half of the array, it is rebuilt without
+/// tombstones, doubling its size unless entries fill at most a quarter of it.
+///
+/// Example:
+/// ```
+/// use ralg::ds::hashmap::OpenHashTable;
+///
+/// let mut table: OpenHashTable<u32, char> =
+///     [(1, 'a'), (2, 'b')].into_iter().collect();
+/// assert_eq!(table.remove(&1), Some('a'));
+/// assert_eq!(table.get(&2), Some(&'b'));
+/// assert!(!table.contains_key(&1));
+/// ```
+pub struct OpenHashTable<K: Hash + Eq, V> {
+    slots: Vec<Slot<K, V>>,
+    len: usize,
+    deleted: usize,
+}
+
+impl<K: Hash + Eq, V> OpenHashTable<K, V> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Empty table holding up to `capacity` entries without resizing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let slots = (2 * capacity).next_power_of_two().max(INITIAL_BUCKETS);
+        Self {
+            slots: (0..slots).map(|_| Slot::Empty).collect(),
+            len: 0,
+            deleted: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of slots.
+    pub fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Fraction of the slots holding an entry.
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.slots.len() as f64
+    }
+
+    /// Slot holding `key`, if any.
+    fn find(&self, key: &K) -> Option<usize> {
+        let mask = self.slots.len() - 1;
+        let mut i = bucket(key, self.slots.len());
+        loop {
+            match &self.slots[i] {
+                Slot::Empty => return None,
+                Slot::Full(k, _) if k == key => return Some(i),
+                _ => i = (i + 1) & mask,
+            }
+        }
+    }
+
+    /// Inserts `value` under `key`, returning the value previously stored
+    /// under it, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(core::mem::replace(old, value));
+        }
+        if 2 * (self.len + self.deleted + 1) > self.slots.len() {
+            // Only grow if the tombstones are not what fills the table
+            let slots = if 4 * (self.len + 1) > self.slots.len() {
+                2 * self.slots.len()
+            } else {
+                self.slots.len()
+            };
+            self.resize(slots);
+        }
+
+        // The key is absent, so it can take the first reusable slot
+        let mask = self.slots.len() - 1;
+        let mut i = bucket(&key, self.slots.len());
+        while let Slot::Full(..) = self.slots[i] {
+            i = (i + 1) & mask;
+        }
+        if let Slot::Deleted = self.slots[i] {
+            self.deleted -= 1;
+        }
+        self.slots[i] = Slot::Full(key, value);
+        self.len += 1;
+        None
+    }
+
+    /// Moves every entry to a table of `slots` slots, dropping tombstones.
+    fn resize(&mut self, slots: usize) {
+        let old = core::mem::replace(
+            &mut self.slots,
+            (0..slots).map(|_| Slot::Empty).collect(),
+        );
+        let mask = slots - 1;
+        for slot in old {
+            if let Slot::Full(key, value) = slot {
+                let mut i = bucket(&key, slots);
+                while let Slot::Full(..) = self.slots[i] {
+                    i = (i + 1) & mask;
+                }
+                self.slots[i] = Slot::Full(key, value);
+            }
+        }
+        self.deleted = 0;
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match &self.slots[self.find(key)?] {
+            Slot::Full(_, value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let i = self.find(key)?;
+        match &mut self.slots[i] {
+            Slot::Full(_, value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let i = self.find(key)?;
+        match core::mem::replace(&mut self.slots[i], Slot::Deleted) {
+            Slot::Full(_, value) => {
+                self.len -= 1;
+                self.deleted += 1;
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterates over the entries in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.slots.iter().filter_map(|slot| match slot {
+            Slot::Full(key, value) => Some((key, value)),
+            _ => None,
+        })
+    }
+}
+
+impl<K: Hash + Eq, V> Default for OpenHashTable<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for OpenHashTable<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for OpenHashTable<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut table = OpenHashTable::new();
+        table.extend(iter);
+        table
+    }
+}
+
+impl<K: Hash + Eq + fmt::Debug, V: fmt::Debug> fmt::Debug
+    for OpenHashTable<K, V>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use std::collections::HashMap;
+
+    /// Key whose hash only depends on its remainder modulo 4, so that most
+    /// keys collide.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Colliding(u32);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            (self.0 % 4).hash(state);
+        }
+    }
+
+    /// Sorted entries of a table, for comparison.
+    fn sorted<'a, K: Ord + 'a, V: Ord + 'a>(
+        iter: impl Iterator<Item = (&'a K, &'a V)>,
+    ) -> Vec<(&'a K, &'a V)> {
+        let mut entries: Vec<_> = iter.collect();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn chaining() {
+        let mut table = HashTable::new();
+        assert!(table.is_empty());
+        assert_eq!(table.get(&0), None);
+        assert_eq!(table.remove(&0), None);
+
+        for k in 0..100 {
+            assert_eq!(table.insert(k, k * k), None);
+            assert!(table.load_factor() <= 0.75);
+        }
+        assert_eq!(table.len(), 100);
+        assert_eq!(table.buckets(), 256);
+        assert_eq!(table.insert(7, 0), Some(49));
+        *table.get_mut(&8).unwrap() += 1;
+        assert_eq!(table.get(&8), Some(&65));
+        for k in (0..100).step_by(2) {
+            assert!(table.remove(&k).is_some());
+        }
+        assert_eq!(table.len(), 50);
+        assert!(!table.contains_key(&4));
+        assert!(table.contains_key(&5));
+
+        let table: HashTable<&str, i32> = [("a", 1)].into_iter().collect();
+        assert_eq!(format!("{:?}", table), r#"{"a": 1}"#);
+        assert_eq!(HashTable::<u8, u8>::with_capacity(100).buckets(), 256);
+    }
+
+    #[test]
+    fn open_addressing() {
+        let mut table = OpenHashTable::new();
+        assert!(table.is_empty());
+        assert_eq!(table.get(&0), None);
+        assert_eq!(table.remove(&0), None);
+
+        for k in 0..100 {
+            assert_eq!(table.insert(k, k * k), None);
+            assert!(table.load_factor() <= 0.5);
+        }
+        assert_eq!(table.slots(), 256);
+        assert_eq!(table.insert(7, 0), Some(49));
+        *table.get_mut(&8).unwrap() += 1;
+        assert_eq!(table.get(&8), Some(&65));
+
+        // Churning through removals reuses tombstones without growing
+        for k in 100..10_000 {
+            table.insert(k, 0);
+            table.remove(&k);
+        }
+        assert_eq!(table.slots(), 512);
+        assert_eq!(table.len(), 100);
+        assert_eq!(table.get(&99), Some(&9801));
+
+        let table: OpenHashTable<&str, i32> = [("a", 1)].into_iter().collect();
+        assert_eq!(format!("{:?}", table), r#"{"a": 1}"#);
+    }
+
+    #[test]
+    fn collisions() {
+        let keys: Vec<_> = (0..200).map(Colliding).collect();
+        let mut chained = HashTable::new();
+        let mut open = OpenHashTable::new();
+        for &k in &keys {
+            chained.insert(k, k.0);
+            open.insert(k, k.0);
+        }
+        for &k in keys.iter().step_by(3) {
+            assert_eq!(chained.remove(&k), Some(k.0));
+            assert_eq!(open.remove(&k), Some(k.0));
+        }
+        for (i, k) in keys.iter().enumerate() {
+            let expected = (i % 3 != 0).then_some(&k.0);
+            assert_eq!(chained.get(k), expected);
+            assert_eq!(open.get(k), expected);
+        }
+    }
+
+    #[test]
+    fn matches_hash_map() {
+        let mut chained = HashTable::new();
+        let mut open = OpenHashTable::new();
+        let mut expected = HashMap::new();
+        let mut rng = Pcg32::seed(0xbb67ae8584caa73b);
+        for step in 0..20_000 {
+            let key = rng.gen_range(0..1000_u64);
+            if rng.gen_range(0..3) == 0 {
+                let removed = expected.remove(&key);
+                assert_eq!(chained.remove(&key), removed);
+                assert_eq!(open.remove(&key), removed);
+            } else {
+                let old = expected.insert(key, step);
+                assert_eq!(chained.insert(key, step), old);
+                assert_eq!(open.insert(key, step), old);
+            }
+            assert_eq!(chained.len(), expected.len());
+            assert_eq!(open.len(), expected.len());
+        }
+        assert_eq!(sorted(chained.iter()), sorted(expected.iter()));
+        assert_eq!(sorted(open.iter()), sorted(expected.iter()));
+    }
+}
@@ -0,0 +1,309 @@
+//! Least recently used caches
+use crate::ds::hashmap::HashTable;
+use crate::list::double::{DoubleLinked, NodeRef};
+use core::fmt;
+use core::hash::Hash;
+
+/// Map holding at most `capacity` entries, evicting the least recently used
+/// one to make room for a new key.
+///
+/// The entries are kept in a doubly linked list, from the most recently used,
+/// and a hash table maps each key to its node, so that an entry is found and
+/// moved to the front in O(1) expected time, while the entry to evict is the
+/// back of the list.
+///
+/// The table holds exactly one entry per node, even for keys whose `Hash` and
+/// `Eq` disagree: such keys may find the wrong entry or lose theirs, but never
+/// reach a removed node.
+///
+/// Example:
+/// ```
+/// use ralg::ds::lru::LruCache;
+///
+/// let mut cache = LruCache::new(2);
+/// cache.put("a", 1);
+/// cache.put("b", 2);
+/// assert_eq!(cache.get(&"a"), Some(&1));
+/// cache.put("c", 3);
+/// // "b" was the least recently used
+/// assert_eq!(cache.peek(&"b"), None);
+/// let entries: Vec<_> = cache.iter().collect();
+/// assert_eq!(entries, [(&"c", &3), (&"a", &1)]);
+/// ```
+pub struct LruCache<K: Hash + Eq + Clone, V> {
+    /// Entries from the most recently used.
+    order: DoubleLinked<(K, V)>,
+    /// Node of each entry of `order`.
+    nodes: HashTable<K, NodeRef<(K, V)>>,
+    capacity: usize,
+}
+
+// The handles in the table only point into the list owned by the cache.
+unsafe impl<K: Hash + Eq + Clone + Send, V: Send> Send for LruCache<K, V> {}
+unsafe impl<K: Hash + Eq + Clone + Sync, V: Sync> Sync for LruCache<K, V> {}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Panics if `capacity == 0`.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "cache of capacity zero");
+        Self {
+            order: DoubleLinked::new(),
+            nodes: HashTable::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Maps `key` to `node`. If the hash of a stored key changed, this may
+    /// replace the entry of another node with an equal key, which is then
+    /// evicted, so that each node keeps a single entry.
+    fn index(&mut self, key: K, node: NodeRef<(K, V)>) {
+        if let Some(old) = self.nodes.insert(key, node) {
+            // The table only holds nodes of the list
+            unsafe { self.order.remove_node(old) };
+        }
+    }
+
+    /// Removes the entry of `node`, whose key is `key`, from the table.
+    fn forget(&mut self, key: &K, node: NodeRef<(K, V)>) {
+        match self.nodes.remove(key) {
+            Some(found) if found == node => return,
+            // The hash of `key` changed, and it found another node with an
+            // equal key, which loses its entry as well
+            Some(found) => unsafe {
+                self.order.remove_node(found);
+            },
+            None => {}
+        }
+        // The entry of `node` is left under its old hash, so the table is
+        // rebuilt without it
+        let old = core::mem::replace(
+            &mut self.nodes,
+            HashTable::with_capacity(self.capacity),
+        );
+        for (k, &n) in old.iter().filter(|&(_, &n)| n != node) {
+            self.index(k.clone(), n);
+        }
+    }
+
+    /// Value of `key`, marking it as the most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|v| &*v)
+    }
+
+    /// Mutable value of `key`, marking it as the most recently used.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let node = *self.nodes.get(key)?;
+        // The nodes in the table are exactly those of the list
+        unsafe {
+            self.order.move_to_front(node);
+            Some(&mut node.key_mut().1)
+        }
+    }
+
+    /// Value of `key`, leaving the order of use unchanged.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let node = *self.nodes.get(key)?;
+        unsafe { Some(&node.key().1) }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.nodes.contains_key(key)
+    }
+
+    /// Stores `value` under `key` as the most recently used entry, returning
+    /// the value it replaces, if any. If the cache is full and `key` is new,
+    /// the least recently used entry is evicted.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(core::mem::replace(old, value));
+        }
+        if self.len() == self.capacity {
+            self.pop_lru();
+        }
+        let node = self.order.push_front_node((key.clone(), value));
+        self.index(key, node);
+        None
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let node = self.nodes.remove(key)?;
+        let (_, value) = unsafe { self.order.remove_node(node) };
+        Some(value)
+    }
+
+    /// Removes the least recently used entry.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let node = self.order.back_node()?;
+        let (key, value) = unsafe { self.order.remove_node(node) };
+        // The handle is only compared from now on
+        self.forget(&key, node);
+        Some((key, value))
+    }
+
+    /// Iterates over the entries from the most recently used.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + '_ {
+        self.order.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K: Hash + Eq + Clone + fmt::Debug, V: fmt::Debug> fmt::Debug
+    for LruCache<K, V>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use alloc::{format, rc::Rc, string::String, vec::Vec};
+    use core::cell::Cell;
+
+    #[test]
+    fn evictions() {
+        let mut cache = LruCache::new(3);
+        assert_eq!(cache.pop_lru(), None);
+        for k in 0..3 {
+            assert_eq!(cache.put(k, k * 10), None);
+        }
+        assert_eq!(cache.put(0, 1), Some(0));
+        cache.put(3, 30);
+        assert!(!cache.contains_key(&1));
+        assert_eq!(cache.peek(&2), Some(&20));
+        cache.put(4, 40);
+        // Peeking did not save 2
+        assert_eq!(cache.get(&2), None);
+        *cache.get_mut(&0).unwrap() += 1;
+        assert_eq!(format!("{:?}", cache), "{0: 2, 4: 40, 3: 30}");
+        assert_eq!(cache.remove(&4), Some(40));
+        assert_eq!(cache.pop_lru(), Some((3, 30)));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.capacity(), 3);
+    }
+
+    #[test]
+    fn against_naive() {
+        let mut rng = Pcg32::seed(0x1a2c);
+        let mut cache = LruCache::new(8);
+        // Entries from the most recently used
+        let mut naive: Vec<(u8, String)> = Vec::new();
+        for _ in 0..5000 {
+            let key = rng.gen_range(0..16);
+            let at = naive.iter().position(|(k, _)| *k == key);
+            match rng.gen_range(0..4) {
+                0 => {
+                    let value = format!("{}", rng.next_u32());
+                    let old = at.map(|i| naive.remove(i).1);
+                    if old.is_none() && naive.len() == 8 {
+                        naive.pop();
+                    }
+                    naive.insert(0, (key, value.clone()));
+                    assert_eq!(cache.put(key, value), old);
+                }
+                1 => {
+                    let old = at.map(|i| naive.remove(i).1);
+                    assert_eq!(cache.remove(&key), old);
+                }
+                2 => {
+                    let expected = at.map(|i| &naive[i].1);
+                    assert_eq!(cache.peek(&key), expected);
+                }
+                _ => {
+                    if let Some(i) = at {
+                        let entry = naive.remove(i);
+                        naive.insert(0, entry);
+                    }
+                    let expected = at.map(|_| &naive[0].1);
+                    assert_eq!(cache.get(&key), expected);
+                }
+            }
+            assert!(cache
+                .iter()
+                .map(|(k, v)| (*k, v))
+                .eq(naive.iter().map(|(k, v)| (*k, v))));
+        }
+        while let Some((key, _)) = cache.pop_lru() {
+            assert_eq!(naive.pop().map(|(k, _)| k), Some(key));
+        }
+        assert!(cache.is_empty());
+    }
+
+    /// Key whose hash can be changed after it is stored in the cache.
+    #[derive(Clone, PartialEq, Eq)]
+    struct Shifty(Rc<Cell<u64>>);
+
+    impl Hash for Shifty {
+        fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+            self.0.get().hash(state);
+        }
+    }
+
+    #[test]
+    fn inconsistent_hash() {
+        let key = Shifty(Rc::new(Cell::new(1)));
+        let other = Shifty(Rc::new(Cell::new(7)));
+        let mut cache = LruCache::new(1);
+        cache.put(key.clone(), 'a');
+        // Evicting the key now misses its entry in the table, which must not
+        // be left behind with the freed node
+        key.0.set(2);
+        cache.put(other.clone(), 'b');
+        key.0.set(1);
+        assert_eq!(cache.get(&key), None);
+        assert_eq!(cache.get(&other), Some(&'b'));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn shifting_hashes() {
+        let mut rng = Pcg32::seed(0x5e1f);
+        let keys: Vec<_> =
+            (0..12).map(|h| Shifty(Rc::new(Cell::new(h)))).collect();
+        let mut cache = LruCache::new(6);
+        for _ in 0..3000 {
+            let key = rng.choose(&keys).unwrap();
+            match rng.gen_range(0..5) {
+                0 => key.0.set(rng.gen_range(0..12)),
+                1 => {
+                    cache.remove(key);
+                }
+                2 => {
+                    cache.get(key);
+                }
+                _ => {
+                    cache.put(key.clone(), rng.next_u32());
+                }
+            }
+            // One entry per node
+            let nodes: Vec<_> = cache.nodes.iter().map(|(_, &n)| n).collect();
+            assert_eq!(nodes.len(), cache.len());
+            assert!(nodes
+                .iter()
+                .enumerate()
+                .all(|(i, n)| !nodes[..i].contains(n)));
+            assert_eq!(cache.iter().count(), cache.len());
+            assert!(cache.len() <= 6);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_capacity() {
+        LruCache::<u8, u8>::new(0);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod bitvec;
+pub mod hashmap;
+pub mod lru;
+pub mod monotonic;
+pub mod rope;
+pub mod union_find;
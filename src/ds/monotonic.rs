@@ -0,0 +1,197 @@
+//! Monotonic deques and sliding window extremes
+use crate::list::double::DoubleLinked;
+use crate::sorting::partial_cmp;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// First-in first-out queue answering for its greatest, or smallest, value.
+///
+/// Only the values which may still become the extreme are kept, along with
+/// their position in the queue: a pushed value evicts from the back every
+/// value it is at least as extreme as, since they leave the queue before it,
+/// so that the kept values are monotonic and the extreme is at the front.
+/// Each operation takes O(1) amortized time.
+///
+/// Example:
+/// ```
+/// use ralg::ds::monotonic::MonotonicDeque;
+///
+/// let mut queue = MonotonicDeque::maxima();
+/// queue.extend([3, 1, 4, 1, 5, 2]);
+/// assert_eq!(queue.front(), Some(&5));
+/// for _ in 0..5 {
+///     queue.pop_front();
+/// }
+/// assert_eq!(queue.front(), Some(&2));
+/// assert_eq!(queue.len(), 1);
+/// ```
+pub struct MonotonicDeque<T> {
+    /// Candidates with their positions, from the front of the queue.
+    entries: DoubleLinked<(usize, T)>,
+    /// How the front compares to the values behind it.
+    order: Ordering,
+    pushed: usize,
+    popped: usize,
+}
+
+impl<T: PartialOrd> MonotonicDeque<T> {
+    /// Queue whose front is its greatest value.
+    pub fn maxima() -> Self {
+        Self::with_order(Ordering::Greater)
+    }
+
+    /// Queue whose front is its smallest value.
+    pub fn minima() -> Self {
+        Self::with_order(Ordering::Less)
+    }
+
+    fn with_order(order: Ordering) -> Self {
+        Self {
+            entries: DoubleLinked::new(),
+            order,
+            pushed: 0,
+            popped: 0,
+        }
+    }
+
+    /// Number of values in the queue, evicted ones included.
+    pub fn len(&self) -> usize {
+        self.pushed - self.popped
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pushed == self.popped
+    }
+
+    /// Pushes `x` to the back, dropping the values it dominates. Of equal
+    /// values, only the last one pushed is kept.
+    pub fn push_back(&mut self, x: T) {
+        while let Some((_, back)) = self.entries.peek_back() {
+            if partial_cmp(back, &x) == self.order {
+                break;
+            }
+            self.entries.pop_back();
+        }
+        self.entries.push_back((self.pushed, x));
+        self.pushed += 1;
+    }
+
+    /// Removes the oldest value in the queue, returning whether it was
+    /// non-empty.
+    pub fn pop_front(&mut self) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        if self
+            .entries
+            .peek_front()
+            .is_some_and(|&(p, _)| p == self.popped)
+        {
+            self.entries.pop_front();
+        }
+        self.popped += 1;
+        true
+    }
+
+    /// Extreme value of the queue.
+    pub fn front(&self) -> Option<&T> {
+        self.entries.peek_front().map(|(_, x)| x)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries = DoubleLinked::new();
+        self.popped = self.pushed;
+    }
+}
+
+impl<T: PartialOrd> Extend<T> for MonotonicDeque<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push_back(x);
+        }
+    }
+}
+
+/// Maximum of each window of `k` consecutive elements of `xs`, from the
+/// leftmost, in O(n) time. There are no windows if `k > xs.len()`.
+///
+/// Panics if `k == 0`.
+///
+/// Example:
+/// ```
+/// use ralg::ds::monotonic::sliding_window_max;
+///
+/// let xs = [1, 3, -1, -3, 5, 3, 6, 7];
+/// assert_eq!(sliding_window_max(&xs, 3), [3, 3, 5, 5, 6, 7]);
+/// ```
+pub fn sliding_window_max<T: PartialOrd + Clone>(xs: &[T], k: usize) -> Vec<T> {
+    assert!(k > 0, "window of length zero");
+    let mut window = MonotonicDeque::maxima();
+    let mut maxima = Vec::with_capacity((xs.len() + 1).saturating_sub(k));
+    for (i, x) in xs.iter().enumerate() {
+        window.push_back(x);
+        if i + 1 >= k {
+            if let Some(&max) = window.front() {
+                maxima.push(max.clone());
+            }
+            window.pop_front();
+        }
+    }
+    maxima
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    #[test]
+    fn queue_against_naive() {
+        let mut rng = Pcg32::seed(0x3d9e);
+        let mut maxima = MonotonicDeque::maxima();
+        let mut minima = MonotonicDeque::minima();
+        let mut values = Vec::new();
+        for _ in 0..2000 {
+            if rng.gen_range(0..3) == 0 {
+                assert_eq!(maxima.pop_front(), !values.is_empty());
+                minima.pop_front();
+                if !values.is_empty() {
+                    values.remove(0);
+                }
+            } else {
+                let x = rng.gen_range(0..10);
+                maxima.push_back(x);
+                minima.push_back(x);
+                values.push(x);
+            }
+            assert_eq!(maxima.len(), values.len());
+            assert_eq!(maxima.front(), values.iter().max());
+            assert_eq!(minima.front(), values.iter().min());
+        }
+        maxima.clear();
+        assert!(maxima.is_empty());
+        assert_eq!(maxima.front(), None);
+        assert!(!maxima.pop_front());
+    }
+
+    #[test]
+    fn windows() {
+        assert_eq!(sliding_window_max(&[2, 1], 3), []);
+        assert_eq!(sliding_window_max(&[2, 1], 2), [2]);
+        assert_eq!(sliding_window_max(&[4.0, 1.5], 1), [4.0, 1.5]);
+
+        let mut rng = Pcg32::seed(0x7e57);
+        for k in [1, 2, 3, 8, 40] {
+            let xs: Vec<i32> = (0..200).map(|_| rng.gen_range(-9..9)).collect();
+            let naive: Vec<i32> =
+                xs.windows(k).map(|w| *w.iter().max().unwrap()).collect();
+            assert_eq!(sliding_window_max(&xs, k), naive);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_window() {
+        sliding_window_max(&[1, 2], 0);
+    }
+}
@@ -0,0 +1,308 @@
+//! Ropes: strings as balanced trees of chunks
+use crate::random::Pcg32;
+use crate::tree::treap::{self, merge, weight, Weighted};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use core::ops::Range;
+
+/// Greatest number of characters in a chunk built from a string.
+const CHUNK_CHARS: usize = 64;
+
+/// String stored as a sequence of chunks in the nodes of a treap, ordered by
+/// position, where each node also counts the characters in its subtree.
+///
+/// Positions are character indices. Finding one goes down a single path, and
+/// editing splits the tree there, cutting a chunk in two if needed, then
+/// merges the pieces back, so that insertions, removals, concatenations and
+/// splits take O(log n) expected time on top of the edited text, instead of
+/// moving the whole string.
+///
+/// Example:
+/// ```
+/// use ralg::ds::rope::Rope;
+///
+/// let mut rope = Rope::from("hello world");
+/// rope.insert(5, ", dear");
+/// rope.remove(0..1);
+/// rope.insert(0, "H");
+/// assert_eq!(rope.to_string(), "Hello, dear world");
+///
+/// let world = rope.split_off(12);
+/// assert_eq!(world.to_string(), "world");
+/// assert_eq!(rope.char_at(7), Some('d'));
+/// assert_eq!(rope.len(), 12);
+/// ```
+pub struct Rope {
+    root: Link,
+    rng: Pcg32,
+}
+
+type Link = treap::Link<Chunk>;
+type Node = treap::Node<Chunk>;
+
+struct Chunk {
+    text: String,
+    /// Number of characters in the text.
+    chars: usize,
+}
+
+impl Chunk {
+    fn new(text: String) -> Self {
+        let chars = text.chars().count();
+        Chunk { text, chars }
+    }
+}
+
+impl Weighted for Chunk {
+    fn weight(&self) -> usize {
+        self.chars
+    }
+}
+
+/// Splits `link` into its first `at` characters and the rest, cutting the
+/// chunk holding the split point in two, both halves keeping its priority.
+fn split(link: Link, at: usize) -> (Link, Link) {
+    let (low, high) = treap::split(link, 0, &mut |node, position| {
+        position + node.item.chars <= at
+    });
+    let before = weight(&low);
+    if before == at {
+        return (low, high);
+    }
+
+    // The first chunk of `high` holds the split point
+    let (first, rest) = treap::split(high, 0, &mut |_, position| position == 0);
+    let mut node = first.expect("chunk holding the split point");
+    let byte = byte_index(&node.item.text, at - before);
+    let tail = Chunk::new(node.item.text.split_off(byte));
+    let tail = Node::new(tail, node.priority);
+    node.item.chars = at - before;
+    node.update();
+    (merge(low, Some(node)), merge(Some(tail), rest))
+}
+
+/// Byte offset of the character at index `i` of `s`, or the length of `s`.
+fn byte_index(s: &str, i: usize) -> usize {
+    s.char_indices().nth(i).map_or(s.len(), |(byte, _)| byte)
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            rng: Pcg32::new(),
+        }
+    }
+
+    /// Rope of the given nodes, with a generator seeded from this one.
+    fn with_root(&mut self, root: Link) -> Self {
+        let rng = Pcg32::seed(self.rng.next_u64());
+        Self { root, rng }
+    }
+
+    /// Number of characters.
+    pub fn len(&self) -> usize {
+        weight(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Treap of the chunks of `text`, in order.
+    fn build(&mut self, text: &str) -> Link {
+        let mut link = None;
+        let mut rest = text;
+        while !rest.is_empty() {
+            let (chunk, tail) = rest.split_at(byte_index(rest, CHUNK_CHARS));
+            let node =
+                Node::new(Chunk::new(String::from(chunk)), self.rng.next_u32());
+            link = merge(link, Some(node));
+            rest = tail;
+        }
+        link
+    }
+
+    /// Inserts `text` before the character at index `at`.
+    ///
+    /// Panics if `at > len`.
+    pub fn insert(&mut self, at: usize, text: &str) {
+        assert!(at <= self.len(), "index out of bounds");
+        let (low, high) = split(self.root.take(), at);
+        let middle = self.build(text);
+        self.root = merge(merge(low, middle), high);
+    }
+
+    /// Removes the characters at indices in `range`.
+    ///
+    /// Panics if the range is decreasing or ends after `len`.
+    pub fn remove(&mut self, range: Range<usize>) {
+        assert!(range.start <= range.end, "decreasing range");
+        assert!(range.end <= self.len(), "index out of bounds");
+        let (low, rest) = split(self.root.take(), range.start);
+        let (_, high) = split(rest, range.end - range.start);
+        self.root = merge(low, high);
+    }
+
+    /// Moves the characters of `other` to the end of the rope, leaving
+    /// `other` empty.
+    pub fn append(&mut self, other: &mut Self) {
+        self.root = merge(self.root.take(), other.root.take());
+    }
+
+    /// Splits the rope, returning the characters from index `at` onwards as
+    /// a new rope.
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "index out of bounds");
+        let (low, high) = split(self.root.take(), at);
+        self.root = low;
+        self.with_root(high)
+    }
+
+    /// Character at index `i`.
+    pub fn char_at(&self, mut i: usize) -> Option<char> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            let left = weight(&node.left);
+            if i < left {
+                current = node.left.as_deref();
+            } else if i < left + node.item.chars {
+                return node.item.text.chars().nth(i - left);
+            } else {
+                i -= left + node.item.chars;
+                current = node.right.as_deref();
+            }
+        }
+        None
+    }
+
+    /// Iterates over the chunks of the rope, in order.
+    pub fn chunks(&self) -> impl Iterator<Item = &str> + '_ {
+        let mut stack: Vec<&Node> = Vec::new();
+        let mut current = self.root.as_deref();
+        core::iter::from_fn(move || {
+            while let Some(node) = current {
+                stack.push(node);
+                current = node.left.as_deref();
+            }
+            let node = stack.pop()?;
+            current = node.right.as_deref();
+            Some(node.item.text.as_str())
+        })
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chunks().flat_map(str::chars)
+    }
+}
+
+impl Default for Rope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        let mut rope = Rope::new();
+        rope.root = rope.build(text);
+        rope
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chunks().try_for_each(|chunk| f.write_str(chunk))
+    }
+}
+
+impl fmt::Debug for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string(), f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Checks the heap property and the counts of every subtree, and that
+    /// chunks are non-empty.
+    fn check(link: &Link) {
+        let node = match link {
+            None => return,
+            Some(node) => node,
+        };
+        for child in [&node.left, &node.right].into_iter().flatten() {
+            assert!(child.priority <= node.priority);
+        }
+        check(&node.left);
+        check(&node.right);
+        assert!(!node.item.text.is_empty());
+        assert_eq!(node.item.chars, node.item.text.chars().count());
+        let sum = weight(&node.left) + node.item.chars + weight(&node.right);
+        assert_eq!(node.weight, sum);
+    }
+
+    #[test]
+    fn edits_against_string() {
+        let mut rng = Pcg32::seed(0x20be);
+        let alphabet: Vec<char> = "abcdé€😀 ".chars().collect();
+        let mut rope = Rope::new();
+        let mut naive: Vec<char> = Vec::new();
+        for _ in 0..1500 {
+            let at = rng.gen_range(0..naive.len() + 1);
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let text: String = (0..rng.gen_range(0..150))
+                        .map(|_| *rng.choose(&alphabet).unwrap())
+                        .collect();
+                    rope.insert(at, &text);
+                    naive.splice(at..at, text.chars());
+                }
+                2 => {
+                    let end = rng.gen_range(at..naive.len() + 1);
+                    rope.remove(at..end);
+                    naive.drain(at..end);
+                }
+                _ => {
+                    let mut tail = rope.split_off(at);
+                    assert_eq!(tail.len(), naive.len() - at);
+                    // Put the halves back the other way round
+                    tail.append(&mut rope);
+                    rope = tail;
+                    naive.rotate_left(at);
+                }
+            }
+            assert_eq!(rope.len(), naive.len());
+            if let Some(&c) = naive.get(at) {
+                assert_eq!(rope.char_at(at), Some(c));
+            }
+        }
+        check(&rope.root);
+        assert_eq!(rope.char_at(naive.len()), None);
+        assert!(rope.chars().eq(naive.iter().copied()));
+        assert_eq!(rope.to_string(), naive.iter().collect::<String>());
+    }
+
+    #[test]
+    fn chunks_and_formatting() {
+        let text = "x".repeat(3 * CHUNK_CHARS + 1);
+        let rope = Rope::from(text.as_str());
+        assert_eq!(rope.chunks().count(), 4);
+        check(&rope.root);
+        assert_eq!(format!("{:?}", Rope::from("a\"b")), r#""a\"b""#);
+        assert!(Rope::from("").is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        Rope::from("abc").insert(4, "d");
+    }
+}
@@ -0,0 +1,198 @@
+//! Disjoint-set forest
+use alloc::{vec, vec::Vec};
+
+/// Partition of the elements `0..n` into disjoint sets, each represented by a
+/// tree whose root is the representative of the set. Finding the root
+/// compresses the path to it, and the union of two sets hangs the root of
+/// smaller rank below the other, so that any sequence of `m` operations takes
+/// `O(m α(n))` time, where `α` is the inverse Ackermann function.
+///
+/// Example:
+/// ```
+/// use ralg::ds::union_find::UnionFind;
+///
+/// let mut sets = UnionFind::new(5);
+/// assert!(sets.union(0, 1));
+/// assert!(sets.union(3, 4));
+/// assert!(!sets.union(1, 0));
+/// assert!(sets.connected(0, 1));
+/// assert!(!sets.connected(1, 3));
+/// assert_eq!(sets.count_sets(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    /// Upper bound on the height of the tree of each root.
+    rank: Vec<u8>,
+    /// Number of elements in the set of each root.
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Partition of `0..n` into singletons.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            sets: n,
+        }
+    }
+
+    /// Number of elements partitioned.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Adds a new element in a set of its own, returning it.
+    pub fn push(&mut self) -> usize {
+        let x = self.parent.len();
+        self.parent.push(x);
+        self.rank.push(0);
+        self.size.push(1);
+        self.sets += 1;
+        x
+    }
+
+    /// Representative of the set containing `x`. Every element on the way to
+    /// it is made to point directly at it.
+    ///
+    /// Panics if `x` is out of bounds.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            x = core::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `x` and `y`, returning `false` if they
+    /// were already the same set.
+    ///
+    /// Panics if `x` or `y` is out of bounds.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        if self.rank[x] < self.rank[y] {
+            core::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        if self.rank[x] == self.rank[y] {
+            self.rank[x] += 1;
+        }
+        self.sets -= 1;
+        true
+    }
+
+    /// Whether `x` and `y` belong to the same set.
+    ///
+    /// Panics if `x` or `y` is out of bounds.
+    pub fn connected(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of elements in the set containing `x`.
+    ///
+    /// Panics if `x` is out of bounds.
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of disjoint sets.
+    pub fn count_sets(&self) -> usize {
+        self.sets
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    #[test]
+    fn union_and_find() {
+        let mut sets = UnionFind::new(10);
+        assert_eq!(sets.count_sets(), 10);
+        for x in 0..10 {
+            assert_eq!(sets.find(x), x);
+        }
+
+        // Evens and odds
+        for x in 2..10 {
+            assert!(sets.union(x, x - 2));
+        }
+        assert_eq!(sets.count_sets(), 2);
+        assert!(sets.connected(0, 8));
+        assert!(sets.connected(1, 9));
+        assert!(!sets.connected(0, 9));
+        assert_eq!(sets.set_size(4), 5);
+        assert!(!sets.union(6, 2));
+
+        assert!(sets.union(3, 4));
+        assert_eq!(sets.count_sets(), 1);
+        assert_eq!(sets.set_size(0), 10);
+
+        let y = sets.push();
+        assert_eq!(y, 10);
+        assert_eq!(sets.len(), 11);
+        assert_eq!(sets.count_sets(), 2);
+        assert!(!sets.connected(y, 0));
+    }
+
+    #[test]
+    fn path_compression() {
+        let mut sets = UnionFind::new(1 << 10);
+        for step in 0..10 {
+            let half = 1 << step;
+            for x in (0..sets.len()).step_by(2 * half) {
+                sets.union(x, x + half);
+            }
+        }
+        // Union by rank keeps the trees logarithmic
+        assert!(sets.rank.iter().all(|&r| r <= 10));
+        let root = sets.find(1023);
+        assert_eq!(sets.parent[1023], root);
+        assert_eq!(sets.set_size(0), 1 << 10);
+    }
+
+    #[test]
+    fn matches_labels() {
+        let n = 300;
+        let mut sets = UnionFind::new(n);
+        let mut label: Vec<usize> = (0..n).collect();
+        let mut rng = Pcg32::seed(0x6a09e667f3bcc908);
+        for _ in 0..500 {
+            let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            let merged = label[a] != label[b];
+            assert_eq!(sets.union(a, b), merged);
+            if merged {
+                let (old, new) = (label[b], label[a]);
+                for l in label.iter_mut().filter(|l| **l == old) {
+                    *l = new;
+                }
+            }
+        }
+        let mut distinct = label.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(sets.count_sets(), distinct.len());
+        for a in 0..n {
+            for b in 0..n {
+                assert_eq!(sets.connected(a, b), label[a] == label[b]);
+            }
+        }
+    }
+}
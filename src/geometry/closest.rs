@@ -0,0 +1,155 @@
+//! Closest pair of points
+use super::point::Point2;
+use crate::sorting::partial_cmp;
+use alloc::vec::Vec;
+
+/// Closest pair of distinct entries of `points`, along with their distance,
+/// or `None` if there are fewer than two points.
+///
+/// Divide and conquer: the points sorted by `x` are split in two halves,
+/// solved recursively, and a closer pair across the split can only lie in a
+/// strip around it, where each point needs comparing with a constant number
+/// of neighbors in `y` order. Sorting by `y` is merged into the recursion, so
+/// that it takes O(n log(n)) time.
+///
+/// Example:
+/// ```
+/// use ralg::geometry::{closest_pair, Point2};
+///
+/// let points = [
+///     Point2::new(2.0, 3.0),
+///     Point2::new(12.0, 30.0),
+///     Point2::new(40.0, 50.0),
+///     Point2::new(5.0, 1.0),
+///     Point2::new(12.0, 10.0),
+///     Point2::new(3.0, 4.0),
+/// ];
+/// let (p, q, d) = closest_pair(&points).unwrap();
+/// assert_eq!((p, q), (points[0], points[5]));
+/// assert_eq!(d, 2.0_f64.sqrt());
+/// ```
+pub fn closest_pair(
+    points: &[Point2<f64>],
+) -> Option<(Point2<f64>, Point2<f64>, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|p, q| partial_cmp(&p.x, &q.x));
+    let mut buffer = Vec::with_capacity(points.len());
+    let (p, q, _) = closest(&mut sorted, &mut buffer);
+    // Report the pair in the order of the input
+    let first = points.iter().position(|&r| r == p || r == q);
+    let (p, q) = if first.is_some_and(|i| points[i] == p) {
+        (p, q)
+    } else {
+        (q, p)
+    };
+    Some((p, q, p.dist(q)))
+}
+
+/// Closest pair among `points`, sorted by `x`, of which there are at least
+/// two, with their squared distance. Leaves `points` sorted by `y`.
+fn closest(
+    points: &mut [Point2<f64>],
+    buffer: &mut Vec<Point2<f64>>,
+) -> (Point2<f64>, Point2<f64>, f64) {
+    let n = points.len();
+    if n <= 3 {
+        let best = naive(points);
+        points.sort_by(|p, q| partial_cmp(&p.y, &q.y));
+        return best;
+    }
+
+    let mid = n / 2;
+    let split = points[mid].x;
+    let (left, right) = points.split_at_mut(mid);
+    let a = closest(left, buffer);
+    let b = closest(right, buffer);
+    let mut best = if a.2 <= b.2 { a } else { b };
+
+    // Merge both halves by y
+    buffer.clear();
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < n {
+        if points[j].y < points[i].y {
+            buffer.push(points[j]);
+            j += 1;
+        } else {
+            buffer.push(points[i]);
+            i += 1;
+        }
+    }
+    buffer.extend_from_slice(&points[i..mid]);
+    buffer.extend_from_slice(&points[j..]);
+    points.copy_from_slice(buffer);
+
+    // Points near the split, in y order
+    buffer.clear();
+    buffer.extend(points.iter().filter(|p| (p.x - split).powi(2) < best.2));
+    for (i, &p) in buffer.iter().enumerate() {
+        for &q in &buffer[i + 1..] {
+            if (q.y - p.y).powi(2) >= best.2 {
+                break;
+            }
+            let d2 = p.dist_sqr(q);
+            if d2 < best.2 {
+                best = (p, q, d2);
+            }
+        }
+    }
+    best
+}
+
+/// Closest pair by comparing every pair, with their squared distance.
+fn naive(points: &[Point2<f64>]) -> (Point2<f64>, Point2<f64>, f64) {
+    let mut best = (points[0], points[1], points[0].dist_sqr(points[1]));
+    for (i, &p) in points.iter().enumerate() {
+        for &q in &points[i + 1..] {
+            let d2 = p.dist_sqr(q);
+            if d2 < best.2 {
+                best = (p, q, d2);
+            }
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    #[test]
+    fn small_inputs() {
+        assert_eq!(closest_pair(&[]), None);
+        assert_eq!(closest_pair(&[Point2::new(1.0, 1.0)]), None);
+        let (p, q) = (Point2::new(0.0, 3.0), Point2::new(4.0, 0.0));
+        assert_eq!(closest_pair(&[p, q]), Some((p, q, 5.0)));
+        assert_eq!(closest_pair(&[q, p]), Some((q, p, 5.0)));
+
+        // Repeated points are at distance zero
+        let points = [p, q, Point2::new(9.0, 9.0), q];
+        assert_eq!(closest_pair(&points), Some((q, q, 0.0)));
+    }
+
+    #[test]
+    fn against_naive() {
+        let mut rng = Pcg32::seed(0x510e527fade682d1);
+        for round in 0..300 {
+            let n = rng.gen_range(2..202);
+            // Some rounds on a coarse grid, for ties and vertical lines
+            let scale = if round % 3 == 0 { 10 } else { 1 << 20 };
+            let points: Vec<Point2<f64>> = (0..n)
+                .map(|_| {
+                    let (a, b) =
+                        (rng.gen_range(0..scale), rng.gen_range(0..scale));
+                    Point2::new(a as f64 / 7.0, b as f64 / 3.0)
+                })
+                .collect();
+            let (p, q, d) = closest_pair(&points).unwrap();
+            assert_eq!(d, p.dist(q));
+            assert_eq!(p.dist_sqr(q), naive(&points).2);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Convex hulls
+//!
+//! Both algorithms return the vertices of the convex hull counterclockwise,
+//! starting from the smallest point in `(x, y)` order. Points lying on an
+//! edge of the hull and repeated points are left out, so that collinear
+//! inputs give the two endpoints, and a single distinct point gives itself.
+use super::point::{cross, Point2};
+use crate::{math::num::Num, sorting::partial_cmp};
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+
+/// Orders points by `x`, then `y`.
+fn lexicographic<T: PartialOrd>(p: &Point2<T>, q: &Point2<T>) -> Ordering {
+    partial_cmp(&p.x, &q.x).then_with(|| partial_cmp(&p.y, &q.y))
+}
+
+/// Pops the points of `chain` which would not make a left turn towards `p`,
+/// then pushes `p`.
+fn push_turning_left<T>(chain: &mut Vec<Point2<T>>, p: Point2<T>)
+where
+    T: Num + Copy + PartialOrd,
+{
+    while let [.., a, b] = chain[..] {
+        if cross(a, b, p) > T::zero() {
+            break;
+        }
+        chain.pop();
+    }
+    chain.push(p);
+}
+
+/// Graham scan: sorts the points by angle around the smallest one, then walks
+/// through them keeping a stack of left turns, in O(n log(n)) time.
+///
+/// Example:
+/// ```
+/// use ralg::geometry::{graham_scan, Point2};
+///
+/// let points: Vec<_> = [(0, 0), (2, 0), (1, 1), (2, 2), (0, 2), (1, 0)]
+///     .into_iter()
+///     .map(Point2::from)
+///     .collect();
+/// let hull: Vec<_> = [(0, 0), (2, 0), (2, 2), (0, 2)]
+///     .into_iter()
+///     .map(Point2::from)
+///     .collect();
+/// assert_eq!(graham_scan(&points), hull);
+/// ```
+pub fn graham_scan<T>(points: &[Point2<T>]) -> Vec<Point2<T>>
+where
+    T: Num + Copy + PartialOrd,
+{
+    let Some(&pivot) = points.iter().min_by(|p, q| lexicographic(p, q)) else {
+        return Vec::new();
+    };
+    let mut rest: Vec<Point2<T>> =
+        points.iter().copied().filter(|&p| p != pivot).collect();
+    // Every other point lies in the half-plane to the right of the pivot,
+    // where the cross product orders them by angle. Points at the same angle
+    // are ordered by distance, so that the nearest ones get popped.
+    rest.sort_by(|&p, &q| {
+        partial_cmp(&T::zero(), &cross(pivot, p, q))
+            .then_with(|| partial_cmp(&pivot.dist_sqr(p), &pivot.dist_sqr(q)))
+    });
+
+    let mut hull = vec![pivot];
+    for p in rest {
+        push_turning_left(&mut hull, p);
+    }
+    hull
+}
+
+/// Andrew's monotone chain: sorts the points by `(x, y)`, then builds the
+/// lower and upper halves of the hull with stacks of left turns, in
+/// O(n log(n)) time.
+///
+/// Example:
+/// ```
+/// use ralg::geometry::{monotone_chain, Point2};
+///
+/// let points = [
+///     Point2::new(1.0, 1.0),
+///     Point2::new(0.0, 0.0),
+///     Point2::new(3.0, 3.0),
+///     Point2::new(2.0, 2.0),
+/// ];
+/// assert_eq!(monotone_chain(&points), [points[1], points[2]]);
+/// ```
+pub fn monotone_chain<T>(points: &[Point2<T>]) -> Vec<Point2<T>>
+where
+    T: Num + Copy + PartialOrd,
+{
+    let mut sorted = points.to_vec();
+    sorted.sort_by(lexicographic);
+    sorted.dedup();
+    if sorted.len() <= 2 {
+        return sorted;
+    }
+
+    let mut lower = Vec::new();
+    for &p in &sorted {
+        push_turning_left(&mut lower, p);
+    }
+    let mut upper = Vec::new();
+    for &p in sorted.iter().rev() {
+        push_turning_left(&mut upper, p);
+    }
+    // Each half ends where the other starts
+    lower.pop();
+    upper.pop();
+    lower.append(&mut upper);
+    lower
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    fn points(coords: &[(i64, i64)]) -> Vec<Point2<i64>> {
+        coords.iter().map(|&p| p.into()).collect()
+    }
+
+    #[test]
+    fn degenerate_inputs() {
+        let cases = [
+            (vec![], vec![]),
+            (vec![(3, 4)], vec![(3, 4)]),
+            (vec![(3, 4), (3, 4), (3, 4)], vec![(3, 4)]),
+            (vec![(1, 1), (0, 0)], vec![(0, 0), (1, 1)]),
+            // Collinear, in any order and with repetitions
+            (
+                vec![(2, 2), (0, 0), (3, 3), (1, 1), (3, 3), (0, 0)],
+                vec![(0, 0), (3, 3)],
+            ),
+            (vec![(0, 5), (0, 1), (0, 3)], vec![(0, 1), (0, 5)]),
+            (vec![(4, 0), (1, 0), (2, 0)], vec![(1, 0), (4, 0)]),
+            // Points on the edges and inside of a triangle
+            (
+                vec![(0, 0), (2, 0), (4, 0), (2, 2), (0, 4), (0, 2), (1, 1)],
+                vec![(0, 0), (4, 0), (0, 4)],
+            ),
+        ];
+        for (input, hull) in cases {
+            let (input, hull) = (points(&input), points(&hull));
+            assert_eq!(graham_scan(&input), hull);
+            assert_eq!(monotone_chain(&input), hull);
+        }
+    }
+
+    #[test]
+    fn random_points() {
+        let mut rng = Pcg32::seed(0xa54ff53a5f1d36f1);
+        for _ in 0..300 {
+            let input: Vec<Point2<i64>> = (0..rng.gen_range(0..40))
+                // A small grid, for many collinear points
+                .map(|_| Point2::new(rng.gen_range(0..8), rng.gen_range(0..8)))
+                .collect();
+            let hull = monotone_chain(&input);
+            assert_eq!(graham_scan(&input), hull);
+
+            // Strictly convex, with every point inside or on the boundary
+            let n = hull.len();
+            if n <= 2 {
+                continue;
+            }
+            for i in 0..n {
+                let (a, b) = (hull[i], hull[(i + 1) % n]);
+                assert!(cross(a, b, hull[(i + 2) % n]) > 0);
+                assert!(input.iter().all(|&p| cross(a, b, p) >= 0));
+            }
+        }
+    }
+}
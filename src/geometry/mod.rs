@@ -0,0 +1,12 @@
+//! Computational geometry in the plane
+#[cfg(feature = "std")]
+pub mod closest;
+pub mod hull;
+pub mod point;
+pub mod segment;
+
+#[cfg(feature = "std")]
+pub use closest::closest_pair;
+pub use hull::{graham_scan, monotone_chain};
+pub use point::{cross, orientation, Orientation, Point2};
+pub use segment::Segment;
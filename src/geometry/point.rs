@@ -0,0 +1,176 @@
+//! Points, vectors and orientation tests
+use crate::math::num::{Num, Zero};
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// Point, or vector, of the plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Num + Copy> Point2<T> {
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Cross product `self.x * other.y - self.y * other.x`, the signed area
+    /// of the parallelogram spanned by both vectors. Positive when `other`
+    /// is counterclockwise from `self`.
+    pub fn cross(self, other: Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Squared Euclidean norm.
+    pub fn norm_sqr(self) -> T {
+        self.dot(self)
+    }
+
+    /// Squared Euclidean distance to `other`.
+    pub fn dist_sqr(self, other: Self) -> T {
+        (other - self).norm_sqr()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Point2<f64> {
+    pub fn norm(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    pub fn dist(self, other: Self) -> f64 {
+        (other - self).norm()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+/// Scaling by a scalar.
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, k: T) -> Self {
+        Point2::new(self.x * k, self.y * k)
+    }
+}
+
+impl<T: Zero> Zero for Point2<T> {
+    fn zero() -> Self {
+        Point2::new(T::zero(), T::zero())
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+/// Cross product of `a - o` and `b - o`: twice the signed area of the
+/// triangle `o, a, b`, positive when it is counterclockwise.
+///
+/// Example:
+/// ```
+/// use ralg::geometry::{cross, Point2};
+///
+/// let o = Point2::new(1, 1);
+/// assert_eq!(cross(o, Point2::new(3, 1), Point2::new(1, 4)), 6);
+/// ```
+pub fn cross<T: Num + Copy>(o: Point2<T>, a: Point2<T>, b: Point2<T>) -> T {
+    (a - o).cross(b - o)
+}
+
+/// Direction of the turn made going from `a` to `b` then `c`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Clockwise,
+    Collinear,
+    CounterClockwise,
+}
+
+/// Orientation of the triangle `a, b, c`, from the sign of `cross(a, b, c)`.
+/// Exact for integer coordinates, as long as the products do not overflow.
+///
+/// Example:
+/// ```
+/// use ralg::geometry::{orientation, Orientation, Point2};
+///
+/// let (a, b) = (Point2::new(0, 0), Point2::new(2, 2));
+/// assert_eq!(orientation(a, b, Point2::new(0, 1)), Orientation::CounterClockwise);
+/// assert_eq!(orientation(a, b, Point2::new(5, 5)), Orientation::Collinear);
+/// ```
+pub fn orientation<T>(a: Point2<T>, b: Point2<T>, c: Point2<T>) -> Orientation
+where
+    T: Num + Copy + PartialOrd,
+{
+    let area = cross(a, b, c);
+    if area > T::zero() {
+        Orientation::CounterClockwise
+    } else if area < T::zero() {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let (p, q) = (Point2::new(1, 2), Point2::new(3, -1));
+        assert_eq!(p + q, Point2::new(4, 1));
+        assert_eq!(p - q, Point2::new(-2, 3));
+        assert_eq!(-p * 2, Point2::new(-2, -4));
+        assert_eq!(p.dot(q), 1);
+        assert_eq!(p.cross(q), -7);
+        assert_eq!(q.cross(p), 7);
+        assert_eq!(p.dist_sqr(q), 13);
+        assert_eq!(Point2::<i32>::zero(), Point2::from((0, 0)));
+        #[cfg(feature = "std")]
+        assert_eq!(Point2::new(3.0, 4.0).norm(), 5.0);
+    }
+
+    #[test]
+    fn orientations() {
+        let o = Point2::new(0, 0);
+        let a = Point2::new(1, 0);
+        for (b, expected) in [
+            (Point2::new(1, 1), Orientation::CounterClockwise),
+            (Point2::new(1, -1), Orientation::Clockwise),
+            (Point2::new(-3, 0), Orientation::Collinear),
+            (o, Orientation::Collinear),
+        ] {
+            assert_eq!(orientation(o, a, b), expected);
+        }
+    }
+}
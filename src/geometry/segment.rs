@@ -0,0 +1,112 @@
+//! Line segments
+use super::point::{orientation, Orientation, Point2};
+use crate::math::num::Num;
+
+/// Closed line segment between two points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Segment<T> {
+    pub a: Point2<T>,
+    pub b: Point2<T>,
+}
+
+impl<T: Num + Copy + PartialOrd> Segment<T> {
+    pub fn new(a: Point2<T>, b: Point2<T>) -> Self {
+        Segment { a, b }
+    }
+
+    /// Whether `p` lies in the bounding box of the segment, which, for a
+    /// point collinear with it, means lying on the segment.
+    fn spans(&self, p: Point2<T>) -> bool {
+        let between =
+            |u: T, v: T, w: T| (u <= w && w <= v) || (v <= w && w <= u);
+        between(self.a.x, self.b.x, p.x) && between(self.a.y, self.b.y, p.y)
+    }
+
+    /// Whether `p` lies on the segment, endpoints included.
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        orientation(self.a, self.b, p) == Orientation::Collinear
+            && self.spans(p)
+    }
+
+    /// Whether both segments have a point in common, which may be an
+    /// endpoint, or a whole overlap when they are collinear.
+    ///
+    /// The segments cross when the endpoints of each lie strictly on both
+    /// sides of the other. Otherwise, they can only meet at an endpoint of
+    /// one of them.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::geometry::{Point2, Segment};
+    ///
+    /// let s = Segment::new(Point2::new(0, 0), Point2::new(4, 4));
+    /// assert!(s.intersects(&Segment::new(Point2::new(0, 4), Point2::new(4, 0))));
+    /// assert!(s.intersects(&Segment::new(Point2::new(2, 2), Point2::new(9, 2))));
+    /// assert!(!s.intersects(&Segment::new(Point2::new(5, 5), Point2::new(6, 6))));
+    /// ```
+    pub fn intersects(&self, other: &Self) -> bool {
+        let o1 = orientation(self.a, self.b, other.a);
+        let o2 = orientation(self.a, self.b, other.b);
+        let o3 = orientation(other.a, other.b, self.a);
+        let o4 = orientation(other.a, other.b, self.b);
+        let straddles = |u: Orientation, v: Orientation| {
+            u != v && u != Orientation::Collinear && v != Orientation::Collinear
+        };
+        (straddles(o1, o2) && straddles(o3, o4))
+            || self.contains(other.a)
+            || self.contains(other.b)
+            || other.contains(self.a)
+            || other.contains(self.b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn seg(a: (i64, i64), b: (i64, i64)) -> Segment<i64> {
+        Segment::new(a.into(), b.into())
+    }
+
+    #[test]
+    fn degenerate_cases() {
+        let s = seg((0, 0), (4, 0));
+        // Collinear, disjoint or overlapping
+        assert!(!s.intersects(&seg((5, 0), (7, 0))));
+        assert!(s.intersects(&seg((4, 0), (7, 0))));
+        assert!(s.intersects(&seg((1, 0), (2, 0))));
+        assert!(s.intersects(&seg((-1, 0), (9, 0))));
+        // Touching at an endpoint, or on the line but not the segment
+        assert!(s.intersects(&seg((2, 0), (2, 5))));
+        assert!(!s.intersects(&seg((5, 0), (5, 5))));
+        // Parallel
+        assert!(!s.intersects(&seg((0, 1), (4, 1))));
+        // Single points
+        let p = seg((2, 0), (2, 0));
+        assert!(s.intersects(&p) && p.intersects(&s));
+        assert!(!p.intersects(&seg((3, 3), (3, 3))));
+        assert!(s.contains((0, 0).into()));
+        assert!(!s.contains((-1, 0).into()));
+    }
+
+    #[test]
+    fn symmetric_with_floats() {
+        let segments = [
+            Segment::new(Point2::new(0.0, 0.0), Point2::new(1.0, 1.0)),
+            Segment::new(Point2::new(0.0, 1.0), Point2::new(1.0, 0.0)),
+            Segment::new(Point2::new(0.5, 0.4), Point2::new(2.0, 2.0)),
+            Segment::new(Point2::new(-1.0, 0.0), Point2::new(-1.0, 5.0)),
+        ];
+        let expected = [
+            [true, true, false, false],
+            [true, true, true, false],
+            [false, true, true, false],
+            [false, false, false, true],
+        ];
+        for (i, s) in segments.iter().enumerate() {
+            for (j, t) in segments.iter().enumerate() {
+                assert_eq!(s.intersects(t), expected[i][j], "{} {}", i, j);
+            }
+        }
+    }
+}
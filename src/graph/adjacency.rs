@@ -0,0 +1,512 @@
+//! Graphs stored as adjacency lists or adjacency matrices
+use alloc::vec::Vec;
+use core::slice;
+
+/// Whether the edges of a graph have a direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Directed,
+    /// Every edge `{u, v}` may be traversed both from `u` and from `v`.
+    Undirected,
+}
+
+/// How the edges of a graph are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Storage {
+    /// Each node keeps a list of its outgoing edges: O(V + E) space and
+    /// neighbours iterated in O(degree), but edge lookups take O(degree).
+    List,
+    /// A `V x V` table of edges: O(V^2) space and O(1) edge lookups, but
+    /// neighbours iterated in O(V).
+    Matrix,
+}
+
+enum Adjacency<E> {
+    List(Vec<Vec<(usize, E)>>),
+    Matrix(Vec<Vec<Option<E>>>),
+}
+
+/// Graph with weights `N` on its nodes and `E` on its edges, where there is
+/// at most one edge from a node to another.
+///
+/// Nodes are identified by the index returned when adding them, which stays
+/// valid until the node is removed and is never reused, so that algorithms
+/// can store per-node data in vectors of length `node_bound()`.
+///
+/// Example:
+/// ```
+/// use ralg::graph::{Graph, Kind, Storage};
+///
+/// let mut graph = Graph::new(Kind::Undirected, Storage::List);
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// let c = graph.add_node("c");
+/// graph.add_edge(a, b, 1.5);
+/// graph.add_edge(b, c, 2.0);
+/// assert_eq!(graph.edge(c, b), Some(&2.0));
+/// assert_eq!(graph.neighbors(b).map(|(v, _)| v).collect::<Vec<_>>(), [a, c]);
+///
+/// graph.remove_node(b);
+/// assert_eq!(graph.edge_count(), 0);
+/// ```
+pub struct Graph<N, E> {
+    nodes: Vec<Option<N>>,
+    adjacency: Adjacency<E>,
+    kind: Kind,
+    node_count: usize,
+    /// Number of stored directed edges, which counts undirected edges twice
+    /// unless they are loops.
+    arcs: usize,
+    loops: usize,
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new(kind: Kind, storage: Storage) -> Self {
+        Self {
+            nodes: Vec::new(),
+            adjacency: match storage {
+                Storage::List => Adjacency::List(Vec::new()),
+                Storage::Matrix => Adjacency::Matrix(Vec::new()),
+            },
+            kind,
+            node_count: 0,
+            arcs: 0,
+            loops: 0,
+        }
+    }
+
+    /// Directed graph stored as adjacency lists.
+    pub fn directed() -> Self {
+        Self::new(Kind::Directed, Storage::List)
+    }
+
+    /// Undirected graph stored as adjacency lists.
+    pub fn undirected() -> Self {
+        Self::new(Kind::Undirected, Storage::List)
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.kind == Kind::Directed
+    }
+
+    pub fn storage(&self) -> Storage {
+        match self.adjacency {
+            Adjacency::List(_) => Storage::List,
+            Adjacency::Matrix(_) => Storage::Matrix,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Number of edges, an undirected edge counting once.
+    pub fn edge_count(&self) -> usize {
+        match self.kind {
+            Kind::Directed => self.arcs,
+            Kind::Undirected => (self.arcs + self.loops) / 2,
+        }
+    }
+
+    /// Upper bound on the indices of the nodes, removed or not.
+    pub fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn contains_node(&self, u: usize) -> bool {
+        self.node(u).is_some()
+    }
+
+    /// Indices of the nodes in increasing order.
+    pub fn node_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.nodes.len()).filter(|&u| self.nodes[u].is_some())
+    }
+
+    pub fn node(&self, u: usize) -> Option<&N> {
+        self.nodes.get(u)?.as_ref()
+    }
+
+    pub fn node_mut(&mut self, u: usize) -> Option<&mut N> {
+        self.nodes.get_mut(u)?.as_mut()
+    }
+
+    /// Adds a node with weight `weight`, returning its index.
+    pub fn add_node(&mut self, weight: N) -> usize {
+        self.nodes.push(Some(weight));
+        match &mut self.adjacency {
+            Adjacency::List(lists) => lists.push(Vec::new()),
+            Adjacency::Matrix(rows) => {
+                for row in rows.iter_mut() {
+                    row.push(None);
+                }
+                let n = rows.len() + 1;
+                rows.push((0..n).map(|_| None).collect());
+            }
+        }
+        self.node_count += 1;
+        self.nodes.len() - 1
+    }
+
+    /// Removes the node `u` along with every edge incident to it, returning
+    /// its weight if it was present.
+    pub fn remove_node(&mut self, u: usize) -> Option<N> {
+        let had_loop = self.has_edge(u, u);
+        let weight = self.nodes.get_mut(u)?.take()?;
+        // Number of directed edges removed, each counted once
+        let mut removed = 0;
+        match &mut self.adjacency {
+            Adjacency::List(lists) => {
+                removed += lists[u].len();
+                lists[u].clear();
+                for list in lists.iter_mut() {
+                    let len = list.len();
+                    list.retain(|(v, _)| *v != u);
+                    removed += len - list.len();
+                }
+            }
+            Adjacency::Matrix(rows) => {
+                removed += rows[u].iter_mut().filter_map(Option::take).count();
+                for row in rows.iter_mut() {
+                    removed += row[u].take().is_some() as usize;
+                }
+            }
+        }
+        self.arcs -= removed;
+        if had_loop && self.kind == Kind::Undirected {
+            self.loops -= 1;
+        }
+        self.node_count -= 1;
+        Some(weight)
+    }
+
+    /// Panics unless `u` is a node of the graph.
+    pub(super) fn check_node(&self, u: usize) {
+        assert!(self.contains_node(u), "node {} not in the graph", u);
+    }
+
+    /// Rows of the adjacency matrix, if the edges are stored in one.
+    pub(super) fn matrix(&self) -> Option<&[Vec<Option<E>>]> {
+        match &self.adjacency {
+            Adjacency::List(_) => None,
+            Adjacency::Matrix(rows) => Some(rows),
+        }
+    }
+
+    /// Stores the directed edge `(u, v)`, returning the weight it replaces.
+    fn insert_arc(&mut self, u: usize, v: usize, weight: E) -> Option<E> {
+        let old = match &mut self.adjacency {
+            Adjacency::List(lists) => {
+                match lists[u].iter_mut().find(|(w, _)| *w == v) {
+                    Some((_, e)) => Some(core::mem::replace(e, weight)),
+                    None => {
+                        lists[u].push((v, weight));
+                        None
+                    }
+                }
+            }
+            Adjacency::Matrix(rows) => rows[u][v].replace(weight),
+        };
+        if old.is_none() {
+            self.arcs += 1;
+        }
+        old
+    }
+
+    /// Removes the directed edge `(u, v)`, returning its weight.
+    fn remove_arc(&mut self, u: usize, v: usize) -> Option<E> {
+        let old = match &mut self.adjacency {
+            Adjacency::List(lists) => {
+                let i = lists[u].iter().position(|(w, _)| *w == v)?;
+                Some(lists[u].remove(i).1)
+            }
+            Adjacency::Matrix(rows) => rows[u][v].take(),
+        };
+        if old.is_some() {
+            self.arcs -= 1;
+        }
+        old
+    }
+
+    /// Adds an edge from `u` to `v` with weight `weight`, returning the
+    /// weight of the edge it replaces, if any. In an undirected graph, the
+    /// edge also goes from `v` to `u`.
+    ///
+    /// Panics if `u` or `v` is not a node of the graph.
+    pub fn add_edge(&mut self, u: usize, v: usize, weight: E) -> Option<E>
+    where
+        E: Clone,
+    {
+        self.check_node(u);
+        self.check_node(v);
+        if self.kind == Kind::Undirected && u != v {
+            self.insert_arc(v, u, weight.clone());
+        }
+        let old = self.insert_arc(u, v, weight);
+        if self.kind == Kind::Undirected && u == v && old.is_none() {
+            self.loops += 1;
+        }
+        old
+    }
+
+    /// Removes the edge from `u` to `v`, returning its weight if it was
+    /// present.
+    pub fn remove_edge(&mut self, u: usize, v: usize) -> Option<E> {
+        if !self.contains_node(u) || !self.contains_node(v) {
+            return None;
+        }
+        if self.kind == Kind::Undirected && u != v {
+            self.remove_arc(v, u);
+        }
+        let old = self.remove_arc(u, v);
+        if self.kind == Kind::Undirected && u == v && old.is_some() {
+            self.loops -= 1;
+        }
+        old
+    }
+
+    /// Weight of the edge from `u` to `v`, if any.
+    pub fn edge(&self, u: usize, v: usize) -> Option<&E> {
+        if !self.contains_node(u) || !self.contains_node(v) {
+            return None;
+        }
+        match &self.adjacency {
+            Adjacency::List(lists) => {
+                lists[u].iter().find(|(w, _)| *w == v).map(|(_, e)| e)
+            }
+            Adjacency::Matrix(rows) => rows[u][v].as_ref(),
+        }
+    }
+
+    pub fn edge_mut(&mut self, u: usize, v: usize) -> Option<&mut E> {
+        if !self.contains_node(u) || !self.contains_node(v) {
+            return None;
+        }
+        match &mut self.adjacency {
+            Adjacency::List(lists) => {
+                lists[u].iter_mut().find(|(w, _)| *w == v).map(|(_, e)| e)
+            }
+            Adjacency::Matrix(rows) => rows[u][v].as_mut(),
+        }
+    }
+
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.edge(u, v).is_some()
+    }
+
+    /// Nodes reached by an edge from `u`, along with the weight of the edge.
+    /// Adjacency lists yield them in insertion order, and adjacency matrices
+    /// in increasing order of index.
+    ///
+    /// Panics if `u` is not a node of the graph.
+    pub fn neighbors(&self, u: usize) -> Neighbors<'_, E> {
+        self.check_node(u);
+        Neighbors(match &self.adjacency {
+            Adjacency::List(lists) => NeighborsInner::List(lists[u].iter()),
+            Adjacency::Matrix(rows) => {
+                NeighborsInner::Matrix(rows[u].iter().enumerate())
+            }
+        })
+    }
+
+    /// Number of edges leaving `u`.
+    ///
+    /// Panics if `u` is not a node of the graph.
+    pub fn degree(&self, u: usize) -> usize {
+        match &self.adjacency {
+            Adjacency::List(lists) => {
+                self.check_node(u);
+                lists[u].len()
+            }
+            Adjacency::Matrix(_) => self.neighbors(u).count(),
+        }
+    }
+
+    /// Every edge as `(u, v, weight)`, an undirected edge appearing once with
+    /// `u <= v`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, &E)> + '_ {
+        self.node_ids().flat_map(move |u| {
+            self.neighbors(u)
+                .filter(move |&(v, _)| self.is_directed() || u <= v)
+                .map(move |(v, e)| (u, v, e))
+        })
+    }
+
+    /// Converts the graph to the given storage, in O(V + E) time for lists
+    /// and O(V^2) time for matrices.
+    pub fn set_storage(&mut self, storage: Storage) {
+        if storage == self.storage() {
+            return;
+        }
+        let n = self.nodes.len();
+        let old = core::mem::replace(
+            &mut self.adjacency,
+            Adjacency::List(Vec::new()),
+        );
+        self.adjacency = match old {
+            Adjacency::List(lists) => {
+                let mut rows: Vec<Vec<Option<E>>> =
+                    (0..n).map(|_| (0..n).map(|_| None).collect()).collect();
+                for (u, list) in lists.into_iter().enumerate() {
+                    for (v, e) in list {
+                        rows[u][v] = Some(e);
+                    }
+                }
+                Adjacency::Matrix(rows)
+            }
+            Adjacency::Matrix(rows) => Adjacency::List(
+                rows.into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .enumerate()
+                            .filter_map(|(v, e)| Some((v, e?)))
+                            .collect()
+                    })
+                    .collect(),
+            ),
+        };
+    }
+}
+
+pub struct Neighbors<'a, E>(NeighborsInner<'a, E>);
+
+enum NeighborsInner<'a, E> {
+    List(slice::Iter<'a, (usize, E)>),
+    Matrix(core::iter::Enumerate<slice::Iter<'a, Option<E>>>),
+}
+
+impl<'a, E> Iterator for Neighbors<'a, E> {
+    type Item = (usize, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            NeighborsInner::List(iter) => iter.next().map(|(v, e)| (*v, e)),
+            NeighborsInner::Matrix(iter) => {
+                iter.find_map(|(v, e)| e.as_ref().map(|e| (v, e)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn neighbors<N, E>(graph: &Graph<N, E>, u: usize) -> Vec<usize> {
+        let mut vs: Vec<_> = graph.neighbors(u).map(|(v, _)| v).collect();
+        vs.sort();
+        vs
+    }
+
+    #[test]
+    fn directed() {
+        for storage in [Storage::List, Storage::Matrix] {
+            let mut graph = Graph::new(Kind::Directed, storage);
+            let ids: Vec<_> = (0..4).map(|i| graph.add_node(i * 10)).collect();
+            assert_eq!(ids, [0, 1, 2, 3]);
+            assert_eq!(graph.add_edge(0, 1, 'a'), None);
+            assert_eq!(graph.add_edge(1, 2, 'b'), None);
+            assert_eq!(graph.add_edge(2, 0, 'c'), None);
+            assert_eq!(graph.add_edge(0, 1, 'd'), Some('a'));
+            graph.add_edge(0, 3, 'e');
+            graph.add_edge(3, 3, 'f');
+
+            assert_eq!(graph.edge_count(), 5);
+            assert_eq!(graph.edge(0, 1), Some(&'d'));
+            assert_eq!(graph.edge(1, 0), None);
+            assert_eq!(neighbors(&graph, 0), [1, 3]);
+            assert_eq!(graph.degree(3), 1);
+            *graph.edge_mut(1, 2).unwrap() = 'z';
+            assert!(graph.has_edge(1, 2));
+            assert!(graph.is_directed());
+            assert_eq!(graph.storage(), storage);
+
+            assert_eq!(graph.remove_edge(2, 0), Some('c'));
+            assert_eq!(graph.remove_edge(2, 0), None);
+            assert_eq!(graph.edge_count(), 4);
+
+            assert_eq!(graph.remove_node(3), Some(30));
+            assert_eq!(graph.remove_node(3), None);
+            assert_eq!(graph.edge_count(), 2);
+            assert_eq!(graph.node_count(), 3);
+            assert_eq!(graph.node_bound(), 4);
+            assert_eq!(graph.node_ids().collect::<Vec<_>>(), [0, 1, 2]);
+
+            // Indices are never reused
+            assert_eq!(graph.add_node(40), 4);
+            *graph.node_mut(4).unwrap() += 1;
+            assert_eq!(graph.node(4), Some(&41));
+            assert_eq!(graph.node(3), None);
+            let edges: Vec<_> = graph.edges().collect();
+            assert_eq!(edges, [(0, 1, &'d'), (1, 2, &'z')]);
+        }
+    }
+
+    #[test]
+    fn undirected() {
+        for storage in [Storage::List, Storage::Matrix] {
+            let mut graph = Graph::new(Kind::Undirected, storage);
+            for _ in 0..4 {
+                graph.add_node(());
+            }
+            graph.add_edge(0, 1, 1);
+            graph.add_edge(2, 1, 2);
+            graph.add_edge(2, 2, 3);
+            assert_eq!(graph.add_edge(1, 0, 4), Some(1));
+            assert_eq!(graph.edge(0, 1), Some(&4));
+            assert_eq!(graph.edge_count(), 3);
+            assert_eq!(neighbors(&graph, 1), [0, 2]);
+            assert_eq!(neighbors(&graph, 2), [1, 2]);
+            let mut edges: Vec<_> = graph.edges().collect();
+            edges.sort();
+            assert_eq!(edges, [(0, 1, &4), (1, 2, &2), (2, 2, &3)]);
+
+            assert_eq!(graph.remove_edge(1, 0), Some(4));
+            assert!(!graph.has_edge(0, 1));
+            assert_eq!(graph.edge_count(), 2);
+            assert_eq!(graph.remove_node(2), Some(()));
+            assert_eq!(graph.edge_count(), 0);
+            assert!(graph.neighbors(1).next().is_none());
+        }
+    }
+
+    #[test]
+    fn switch_storage() {
+        let mut graph = Graph::directed();
+        for i in 0..5 {
+            graph.add_node(i);
+        }
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (0, 2)] {
+            graph.add_edge(u, v, u + v);
+        }
+        graph.remove_node(3);
+        let edges: Vec<_> = graph.edges().map(|(u, v, &e)| (u, v, e)).collect();
+
+        graph.set_storage(Storage::Matrix);
+        assert_eq!(graph.storage(), Storage::Matrix);
+        let mut matrix: Vec<_> =
+            graph.edges().map(|(u, v, &e)| (u, v, e)).collect();
+        matrix.sort();
+        let mut sorted = edges.clone();
+        sorted.sort();
+        assert_eq!(matrix, sorted);
+
+        graph.set_storage(Storage::List);
+        let mut list: Vec<_> =
+            graph.edges().map(|(u, v, &e)| (u, v, e)).collect();
+        list.sort();
+        assert_eq!(list, sorted);
+        assert_eq!(graph.edge_count(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn edge_to_missing_node() {
+        let mut graph = Graph::undirected();
+        let u = graph.add_node(());
+        graph.add_edge(u, 1, ());
+    }
+}
@@ -0,0 +1,317 @@
+//! All-pairs shortest paths
+use super::{
+    shortest_path::{bellman_ford, dijkstra_by, relax_rounds, NegativeCycle},
+    Graph,
+};
+use crate::math::num::Zero;
+use alloc::{vec, vec::Vec};
+use core::ops::Sub;
+
+/// Shortest paths between every pair of nodes, as computed by
+/// `floyd_warshall` or `johnson`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllPairs<E> {
+    /// Length of a shortest path from `u` to `v` at `dist[u][v]`, or `None`
+    /// if `v` is unreachable from `u`.
+    pub dist: Vec<Vec<Option<E>>>,
+    /// Node following `u` on a shortest path from `u` to `v` at `next[u][v]`.
+    pub next: Vec<Vec<Option<usize>>>,
+}
+
+impl<E> AllPairs<E> {
+    /// Length of a shortest path from `u` to `v`, if `v` is reachable.
+    pub fn distance(&self, u: usize, v: usize) -> Option<&E> {
+        self.dist.get(u)?.get(v)?.as_ref()
+    }
+
+    /// Nodes of a shortest path from `u` to `v`, both included, if `v` is
+    /// reachable from `u`.
+    pub fn path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+        self.distance(u, v)?;
+        let mut path = vec![u];
+        let mut w = u;
+        while w != v {
+            w = self.next[w][v].expect("reachable nodes have a next hop");
+            path.push(w);
+        }
+        Some(path)
+    }
+}
+
+/// Floyd-Warshall algorithm: improves the paths between every pair of nodes
+/// by allowing each node in turn as an intermediate one, in O(V^3) time. Edge
+/// weights may be negative. With `Storage::Matrix`, the initial distances are
+/// read straight from the adjacency matrix.
+///
+/// Returns a cycle of negative length if there is one.
+///
+/// Example:
+/// ```
+/// use ralg::graph::{apsp::floyd_warshall, Graph, Kind, Storage};
+///
+/// let mut graph = Graph::new(Kind::Directed, Storage::Matrix);
+/// for _ in 0..3 {
+///     graph.add_node(());
+/// }
+/// graph.add_edge(0, 1, 4);
+/// graph.add_edge(1, 2, -1);
+/// graph.add_edge(2, 0, 2);
+/// graph.add_edge(0, 2, 5);
+/// let paths = floyd_warshall(&graph).unwrap();
+/// assert_eq!(paths.distance(0, 2), Some(&3));
+/// assert_eq!(paths.path(2, 1), Some(vec![2, 0, 1]));
+///
+/// graph.add_edge(1, 2, -7);
+/// assert!(floyd_warshall(&graph).is_err());
+/// ```
+pub fn floyd_warshall<N, E>(
+    graph: &Graph<N, E>,
+) -> Result<AllPairs<E>, NegativeCycle>
+where
+    E: Zero + Copy + PartialOrd,
+{
+    let n = graph.node_bound();
+    let ids: Vec<usize> = graph.node_ids().collect();
+    let mut dist: Vec<Vec<Option<E>>> = vec![vec![None; n]; n];
+    let mut next = vec![vec![None; n]; n];
+    for &u in &ids {
+        match graph.matrix() {
+            Some(rows) => dist[u].clone_from_slice(&rows[u]),
+            None => {
+                for (v, &w) in graph.neighbors(u) {
+                    dist[u][v] = Some(w);
+                }
+            }
+        }
+        for &v in &ids {
+            if dist[u][v].is_some() {
+                next[u][v] = Some(v);
+            }
+        }
+        // Loops only matter when negative
+        if dist[u][u].is_none_or(|w| w > E::zero()) {
+            dist[u][u] = Some(E::zero());
+            next[u][u] = Some(u);
+        }
+    }
+
+    for &k in &ids {
+        for &i in &ids {
+            let Some(dik) = dist[i][k] else { continue };
+            for &j in &ids {
+                let Some(dkj) = dist[k][j] else { continue };
+                let candidate = dik + dkj;
+                if dist[i][j].is_none_or(|old| candidate < old) {
+                    dist[i][j] = Some(candidate);
+                    next[i][j] = next[i][k];
+                }
+            }
+        }
+        // Stop before the lengths around a negative cycle blow up
+        if let Some(&u) = ids
+            .iter()
+            .find(|&&u| dist[u][u].is_some_and(|d| d < E::zero()))
+        {
+            return match bellman_ford(graph, u) {
+                Err(cycle) => Err(cycle),
+                Ok(_) => unreachable!("a negative cycle goes through {}", u),
+            };
+        }
+    }
+    Ok(AllPairs { dist, next })
+}
+
+/// Johnson's algorithm: reweights the edges to make them non-negative, using
+/// potentials found by Bellman-Ford, then runs Dijkstra's algorithm from every
+/// node, in O(VE log(V)) time. Faster than `floyd_warshall` on sparse graphs.
+///
+/// Returns a cycle of negative length if there is one.
+///
+/// Example:
+/// ```
+/// use ralg::graph::{apsp::johnson, Graph};
+///
+/// let mut graph = Graph::directed();
+/// for _ in 0..4 {
+///     graph.add_node(());
+/// }
+/// graph.add_edge(0, 1, 3);
+/// graph.add_edge(1, 2, -2);
+/// graph.add_edge(0, 2, 2);
+/// graph.add_edge(2, 3, 1);
+/// let paths = johnson(&graph).unwrap();
+/// assert_eq!(paths.distance(0, 3), Some(&2));
+/// assert_eq!(paths.path(0, 3), Some(vec![0, 1, 2, 3]));
+/// assert_eq!(paths.distance(3, 0), None);
+/// ```
+pub fn johnson<N, E>(graph: &Graph<N, E>) -> Result<AllPairs<E>, NegativeCycle>
+where
+    E: Zero + Copy + PartialOrd + Sub<Output = E>,
+{
+    let n = graph.node_bound();
+
+    // Distances from a virtual source with an edge of length zero to every
+    // node, so that w(u, v) + h(u) - h(v) >= 0
+    let mut h = vec![None; n];
+    for u in graph.node_ids() {
+        h[u] = Some(E::zero());
+    }
+    relax_rounds(graph, &mut h)?;
+    let h = |u: usize| h[u].expect("every node is a source");
+
+    let mut dist = vec![vec![None; n]; n];
+    let mut next = vec![vec![None; n]; n];
+    for u in graph.node_ids() {
+        let paths = dijkstra_by(graph, u, |x, y, w| w + h(x) - h(y));
+        for v in graph.node_ids() {
+            dist[u][v] = paths.dist[v].map(|d| d - h(u) + h(v));
+        }
+
+        // The next hop towards v is the next hop towards its predecessor
+        next[u][u] = Some(u);
+        for v in graph.node_ids() {
+            let mut stack = Vec::new();
+            let mut w = v;
+            while next[u][w].is_none() && paths.dist[w].is_some() {
+                stack.push(w);
+                w = paths.pred[w].expect("reached nodes have predecessors");
+            }
+            while let Some(x) = stack.pop() {
+                next[u][x] = if w == u { Some(x) } else { next[u][w] };
+                w = x;
+            }
+        }
+    }
+    Ok(AllPairs { dist, next })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::{Kind, Storage};
+    use crate::random::Pcg32;
+
+    fn graph(
+        kind: Kind,
+        storage: Storage,
+        n: usize,
+        edges: &[(usize, usize, i64)],
+    ) -> Graph<(), i64> {
+        let mut graph = Graph::new(kind, storage);
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for &(u, v, w) in edges {
+            graph.add_edge(u, v, w);
+        }
+        graph
+    }
+
+    /// Checks that the paths have the lengths given by the distances.
+    fn check_paths(graph: &Graph<(), i64>, paths: &AllPairs<i64>) {
+        for u in graph.node_ids() {
+            for v in graph.node_ids() {
+                let length = paths.path(u, v).map(|path| {
+                    assert_eq!((path[0], path[path.len() - 1]), (u, v));
+                    path.windows(2)
+                        .map(|w| graph.edge(w[0], w[1]).unwrap())
+                        .sum::<i64>()
+                });
+                assert_eq!(length.as_ref(), paths.distance(u, v));
+            }
+        }
+    }
+
+    #[test]
+    fn clrs() {
+        // Figure 25.1 of Cormen et al., with nodes numbered from 0
+        let edges = [
+            (0, 1, 3),
+            (0, 2, 8),
+            (0, 4, -4),
+            (1, 3, 1),
+            (1, 4, 7),
+            (2, 1, 4),
+            (3, 0, 2),
+            (3, 2, -5),
+            (4, 3, 6),
+        ];
+        let expected = [
+            [0, 1, -3, 2, -4],
+            [3, 0, -4, 1, -1],
+            [7, 4, 0, 5, 3],
+            [2, -1, -5, 0, -2],
+            [8, 5, 1, 6, 0],
+        ];
+        for storage in [Storage::List, Storage::Matrix] {
+            let graph = graph(Kind::Directed, storage, 5, &edges);
+            let paths = floyd_warshall(&graph).unwrap();
+            for (u, row) in expected.iter().enumerate() {
+                for (v, d) in row.iter().enumerate() {
+                    assert_eq!(paths.distance(u, v), Some(d));
+                }
+            }
+            assert_eq!(paths.path(2, 0), Some(vec![2, 1, 3, 0]));
+            check_paths(&graph, &paths);
+            assert_eq!(johnson(&graph).unwrap().dist, paths.dist);
+        }
+    }
+
+    #[test]
+    fn removed_nodes() {
+        let mut graph = graph(
+            Kind::Undirected,
+            Storage::List,
+            4,
+            &[(0, 1, 1), (1, 2, 1), (2, 3, 1), (0, 3, 5)],
+        );
+        graph.remove_node(1);
+        for paths in [floyd_warshall(&graph), johnson(&graph)] {
+            let paths = paths.unwrap();
+            assert_eq!(paths.distance(0, 2), Some(&6));
+            assert_eq!(paths.path(2, 0), Some(vec![2, 3, 0]));
+            assert_eq!(paths.distance(0, 1), None);
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let edges = [(0, 1, 1), (1, 2, -2), (2, 1, 1)];
+        let mut graph = graph(Kind::Directed, Storage::Matrix, 4, &edges);
+        for mut cycle in [johnson(&graph), floyd_warshall(&graph)] {
+            let cycle = &mut cycle.as_mut().unwrap_err().0;
+            cycle.sort();
+            assert_eq!(cycle, &[1, 2]);
+        }
+        graph.add_edge(2, 1, 2);
+        graph.add_edge(3, 3, -1);
+        assert_eq!(floyd_warshall(&graph), Err(NegativeCycle(vec![3])));
+        assert_eq!(johnson(&graph), Err(NegativeCycle(vec![3])));
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        let mut rng = Pcg32::seed(0xbb67ae8584caa73b);
+        for storage in [Storage::List, Storage::Matrix] {
+            let n = 40;
+            let mut edges = Vec::new();
+            for _ in 0..150 {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                // Only forward edges can be negative, and a backward edge
+                // outweighs any forward path, so there is no negative cycle
+                let w = rng.gen_range(0..50_i64);
+                edges.push((u, v, if u < v { w - 10 } else { w + 400 }));
+            }
+            let graph = graph(Kind::Directed, storage, n, &edges);
+            let slow = floyd_warshall(&graph).unwrap();
+            let fast = johnson(&graph).unwrap();
+            assert_eq!(fast.dist, slow.dist);
+            check_paths(&graph, &slow);
+            check_paths(&graph, &fast);
+            for u in 0..n {
+                let single = bellman_ford(&graph, u).unwrap();
+                assert_eq!(single.dist, slow.dist[u]);
+            }
+        }
+    }
+}
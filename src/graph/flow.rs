@@ -0,0 +1,396 @@
+//! Maximum flows and minimum cuts
+use super::Graph;
+use crate::math::num::Zero;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::ops::Sub;
+
+/// Flow network: a directed graph whose edges have capacities, along with a
+/// flow on each edge. Every edge is stored next to a reverse edge of zero
+/// capacity, and the algorithms work on the residual capacities of both.
+///
+/// Example:
+/// ```
+/// use ralg::graph::flow::FlowNetwork;
+///
+/// let mut network = FlowNetwork::new(4);
+/// network.add_edge(0, 1, 3);
+/// network.add_edge(0, 2, 2);
+/// let e = network.add_edge(1, 2, 5);
+/// network.add_edge(1, 3, 2);
+/// network.add_edge(2, 3, 3);
+/// assert_eq!(network.max_flow(0, 3), 5);
+/// assert_eq!(network.flow(e), 1);
+/// assert_eq!(network.min_cut(0), [0, 1]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FlowNetwork<C> {
+    /// Edges leaving each node, as indices into `edges`.
+    adj: Vec<Vec<usize>>,
+    /// Edge `2i` is the `i`th edge added, and `2i + 1` is its reverse.
+    edges: Vec<Edge<C>>,
+}
+
+#[derive(Clone, Debug)]
+struct Edge<C> {
+    to: usize,
+    capacity: C,
+    residual: C,
+}
+
+impl<C> FlowNetwork<C>
+where
+    C: Zero + Copy + PartialOrd + Sub<Output = C>,
+{
+    /// Network of `n` nodes, numbered from zero, and no edges.
+    pub fn new(n: usize) -> Self {
+        Self {
+            adj: vec![Vec::new(); n],
+            edges: Vec::new(),
+        }
+    }
+
+    /// Network with an edge for each edge of `graph`, with its weight as
+    /// capacity. The edges of an undirected graph give one edge in each
+    /// direction.
+    pub fn from_graph<N>(graph: &Graph<N, C>) -> Self {
+        let mut network = Self::new(graph.node_bound());
+        for (u, v, &capacity) in graph.edges() {
+            network.add_edge(u, v, capacity);
+            if !graph.is_directed() && u != v {
+                network.add_edge(v, u, capacity);
+            }
+        }
+        network
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.adj.push(Vec::new());
+        self.adj.len() - 1
+    }
+
+    /// Adds an edge from `u` to `v` with capacity `capacity`, returning its
+    /// index. Edges are numbered from zero in the order they are added.
+    ///
+    /// Panics if `u` or `v` is not a node of the network.
+    pub fn add_edge(&mut self, u: usize, v: usize, capacity: C) -> usize {
+        assert!(
+            u < self.adj.len() && v < self.adj.len(),
+            "node out of bounds"
+        );
+        let e = self.edges.len();
+        self.edges.push(Edge {
+            to: v,
+            capacity,
+            residual: capacity,
+        });
+        self.edges.push(Edge {
+            to: u,
+            capacity: C::zero(),
+            residual: C::zero(),
+        });
+        self.adj[u].push(e);
+        self.adj[v].push(e + 1);
+        e / 2
+    }
+
+    /// Endpoints of the edge `e`.
+    pub fn endpoints(&self, e: usize) -> (usize, usize) {
+        (self.edges[2 * e + 1].to, self.edges[2 * e].to)
+    }
+
+    pub fn capacity(&self, e: usize) -> C {
+        self.edges[2 * e].capacity
+    }
+
+    /// Flow currently going through the edge `e`.
+    pub fn flow(&self, e: usize) -> C {
+        let edge = &self.edges[2 * e];
+        edge.capacity - edge.residual
+    }
+
+    /// Removes all flow from the network.
+    pub fn reset(&mut self) {
+        for edge in &mut self.edges {
+            edge.residual = edge.capacity;
+        }
+    }
+
+    /// Pushes `amount` more flow through the residual edge `e`.
+    fn push(&mut self, e: usize, amount: C) {
+        self.edges[e].residual = self.edges[e].residual - amount;
+        self.edges[e ^ 1].residual = self.edges[e ^ 1].residual + amount;
+    }
+
+    /// Pushes as much flow as possible along the residual path `path`,
+    /// returning the amount.
+    fn augment(&mut self, path: &[usize]) -> C {
+        let mut bottleneck = self.edges[path[0]].residual;
+        for &e in path {
+            if self.edges[e].residual < bottleneck {
+                bottleneck = self.edges[e].residual;
+            }
+        }
+        for &e in path {
+            self.push(e, bottleneck);
+        }
+        bottleneck
+    }
+
+    /// Nodes reachable from `source` through edges of positive residual
+    /// capacity, along with the residual edge each was reached by.
+    fn residual_bfs(&self, source: usize) -> Vec<Option<Option<usize>>> {
+        let mut reached = vec![None; self.adj.len()];
+        reached[source] = Some(None);
+        let mut queue = VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for &e in &self.adj[u] {
+                let v = self.edges[e].to;
+                if reached[v].is_none() && self.edges[e].residual > C::zero() {
+                    reached[v] = Some(Some(e));
+                    queue.push_back(v);
+                }
+            }
+        }
+        reached
+    }
+
+    /// Maximum flow from `source` to `sink`, computed with Dinic's algorithm,
+    /// which is added on top of the current flow of the network.
+    ///
+    /// Panics if `source == sink`.
+    pub fn max_flow(&mut self, source: usize, sink: usize) -> C {
+        self.dinic(source, sink)
+    }
+
+    /// Edmonds-Karp algorithm: augments the flow along shortest residual
+    /// paths, found by breadth-first search, until the sink is unreachable.
+    /// Takes O(VE^2) time. Returns the value of the flow added.
+    ///
+    /// Panics if `source == sink`.
+    pub fn edmonds_karp(&mut self, source: usize, sink: usize) -> C {
+        assert_ne!(source, sink, "the source cannot be the sink");
+        let mut total = C::zero();
+        loop {
+            let reached = self.residual_bfs(source);
+            if reached[sink].is_none() {
+                return total;
+            }
+            let mut path = Vec::new();
+            let mut v = sink;
+            while let Some(Some(e)) = reached[v] {
+                path.push(e);
+                v = self.edges[e ^ 1].to;
+            }
+            total = total + self.augment(&path);
+        }
+    }
+
+    /// Dinic's algorithm: repeatedly layers the nodes by their residual
+    /// distance from the source, and saturates the paths going forward
+    /// through the layers, until the sink is unreachable. Takes O(V^2 E)
+    /// time. Returns the value of the flow added.
+    ///
+    /// Panics if `source == sink`.
+    pub fn dinic(&mut self, source: usize, sink: usize) -> C {
+        assert_ne!(source, sink, "the source cannot be the sink");
+        let n = self.adj.len();
+        let mut total = C::zero();
+        loop {
+            // Residual distance from the source
+            let mut level = vec![usize::MAX; n];
+            level[source] = 0;
+            let mut queue = VecDeque::from([source]);
+            while let Some(u) = queue.pop_front() {
+                for &e in &self.adj[u] {
+                    let v = self.edges[e].to;
+                    if level[v] == usize::MAX
+                        && self.edges[e].residual > C::zero()
+                    {
+                        level[v] = level[u] + 1;
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if level[sink] == usize::MAX {
+                return total;
+            }
+
+            // Next edge to try out of each node, the previous ones leading
+            // to dead ends
+            let mut next = vec![0; n];
+            while let Some(path) =
+                self.layered_path(source, sink, &level, &mut next)
+            {
+                total = total + self.augment(&path);
+            }
+        }
+    }
+
+    /// Path from `source` to `sink` going forward through the layers, using
+    /// the edges from `next` on.
+    fn layered_path(
+        &self,
+        source: usize,
+        sink: usize,
+        level: &[usize],
+        next: &mut [usize],
+    ) -> Option<Vec<usize>> {
+        let mut path = Vec::new();
+        let mut u = source;
+        while u != sink {
+            let forward = self.adj[u][next[u]..].iter().position(|&e| {
+                let v = self.edges[e].to;
+                self.edges[e].residual > C::zero() && level[v] == level[u] + 1
+            });
+            match forward {
+                Some(i) => {
+                    next[u] += i;
+                    let e = self.adj[u][next[u]];
+                    path.push(e);
+                    u = self.edges[e].to;
+                }
+                None => {
+                    // Dead end: retreat and skip the edge leading here
+                    next[u] = self.adj[u].len();
+                    let e = path.pop()?;
+                    u = self.edges[e ^ 1].to;
+                    next[u] += 1;
+                }
+            }
+        }
+        Some(path)
+    }
+
+    /// Nodes on the source side of a minimum cut, once a maximum flow was
+    /// found: those reachable from `source` in the residual network.
+    pub fn source_side(&self, source: usize) -> Vec<bool> {
+        self.residual_bfs(source)
+            .into_iter()
+            .map(|r| r.is_some())
+            .collect()
+    }
+
+    /// Edges of a minimum cut, once a maximum flow from `source` was found:
+    /// those going from the source side to the sink side. Their capacities
+    /// add up to the value of the flow.
+    pub fn min_cut(&self, source: usize) -> Vec<usize> {
+        let side = self.source_side(source);
+        (0..self.edges.len() / 2)
+            .filter(|&e| {
+                let (u, v) = self.endpoints(e);
+                side[u] && !side[v]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    /// Figure 26.1 of Cormen et al., with s = 0, v1..v4 = 1..4 and t = 5.
+    fn clrs() -> FlowNetwork<i32> {
+        let mut network = FlowNetwork::new(6);
+        for (u, v, c) in [
+            (0, 1, 16),
+            (0, 2, 13),
+            (1, 3, 12),
+            (2, 1, 4),
+            (2, 4, 14),
+            (3, 2, 9),
+            (3, 5, 20),
+            (4, 3, 7),
+            (4, 5, 4),
+        ] {
+            network.add_edge(u, v, c);
+        }
+        network
+    }
+
+    /// Checks the capacity constraints and flow conservation, returning the
+    /// value of the flow.
+    fn check(network: &FlowNetwork<i64>, source: usize, sink: usize) -> i64 {
+        let mut excess = vec![0; network.node_count()];
+        for e in 0..network.edges.len() / 2 {
+            let f = network.flow(e);
+            assert!(0 <= f && f <= network.capacity(e));
+            let (u, v) = network.endpoints(e);
+            excess[u] -= f;
+            excess[v] += f;
+        }
+        for (u, &x) in excess.iter().enumerate() {
+            if u != source && u != sink {
+                assert_eq!(x, 0);
+            }
+        }
+        excess[sink]
+    }
+
+    #[test]
+    fn clrs_network() {
+        let mut network = clrs();
+        assert_eq!(network.edmonds_karp(0, 5), 23);
+        let cut = network.min_cut(0);
+        assert_eq!(cut.iter().map(|&e| network.capacity(e)).sum::<i32>(), 23);
+        assert_eq!(
+            network.source_side(0),
+            [true, true, true, false, true, false]
+        );
+
+        // No augmenting path is left
+        assert_eq!(network.dinic(0, 5), 0);
+        network.reset();
+        assert_eq!(network.flow(0), 0);
+        assert_eq!(network.max_flow(0, 5), 23);
+        assert_eq!(network.min_cut(0), cut);
+    }
+
+    #[test]
+    fn from_graph() {
+        let mut graph = Graph::undirected();
+        for _ in 0..4 {
+            graph.add_node(());
+        }
+        graph.add_edge(0, 1, 2.5);
+        graph.add_edge(1, 3, 1.0);
+        graph.add_edge(0, 2, 1.0);
+        graph.add_edge(2, 3, 4.0);
+        graph.add_edge(1, 2, 0.5);
+        let mut network = FlowNetwork::from_graph(&graph);
+        assert_eq!(network.max_flow(3, 0), 2.5);
+        // The edges leaving {2, 3}
+        assert_eq!(network.min_cut(3).len(), 3);
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        let mut rng = Pcg32::seed(0xa54ff53a5f1d36f1);
+        for _ in 0..20 {
+            let n = 20;
+            let mut network = FlowNetwork::new(n);
+            for _ in 0..80 {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                if u != v {
+                    network.add_edge(u, v, rng.gen_range(0..50));
+                }
+            }
+            let mut other = network.clone();
+            let flow = network.edmonds_karp(0, n - 1);
+            assert_eq!(other.dinic(0, n - 1), flow);
+            assert_eq!(check(&network, 0, n - 1), flow);
+            assert_eq!(check(&other, 0, n - 1), flow);
+
+            // Max-flow min-cut theorem
+            for net in [&network, &other] {
+                let cut: i64 =
+                    net.min_cut(0).iter().map(|&e| net.capacity(e)).sum();
+                assert_eq!(cut, flow);
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod adjacency;
+pub mod apsp;
+pub mod flow;
+pub mod shortest_path;
+pub mod traversal;
+
+pub use adjacency::{Graph, Kind, Storage};
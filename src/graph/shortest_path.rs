@@ -0,0 +1,352 @@
+//! Single-source shortest paths
+use super::Graph;
+use crate::heap::MinIndexedHeap;
+use crate::math::num::Zero;
+use alloc::{vec, vec::Vec};
+use core::{error::Error, fmt};
+
+/// Shortest paths from a source node to every node reachable from it, as
+/// computed by `dijkstra` or `bellman_ford`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShortestPaths<E> {
+    pub source: usize,
+    /// Length of a shortest path to each node, indexed by node, or `None` if
+    /// the node is unreachable.
+    pub dist: Vec<Option<E>>,
+    /// Node preceding each node on its shortest path, indexed by node.
+    pub pred: Vec<Option<usize>>,
+}
+
+impl<E> ShortestPaths<E> {
+    /// Length of a shortest path from the source to `v`, if `v` is reachable.
+    pub fn distance(&self, v: usize) -> Option<&E> {
+        self.dist.get(v)?.as_ref()
+    }
+
+    /// Nodes of a shortest path from the source to `v`, both included, if `v`
+    /// is reachable.
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        self.distance(v)?;
+        let mut path = vec![v];
+        let mut u = v;
+        while let Some(p) = self.pred[u] {
+            path.push(p);
+            u = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Error returned by `bellman_ford` when a cycle of negative length is
+/// reachable from the source, so that some nodes have no shortest path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegativeCycle(
+    /// Nodes of the cycle in order, the last one leading back to the first.
+    pub Vec<usize>,
+);
+
+impl fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "negative cycle through nodes {:?}", self.0)
+    }
+}
+
+impl Error for NegativeCycle {}
+
+/// Dijkstra's algorithm: settles the nodes by increasing distance from
+/// `source`, keeping the tentative distances of the others in an indexed
+/// heap, so that it runs in O((V + E) log(V)) time. The edge weights must be
+/// non-negative, otherwise the distances may be wrong.
+///
+/// Panics if `source` is not a node of the graph.
+///
+/// Example:
+/// ```
+/// use ralg::graph::{shortest_path::dijkstra, Graph};
+///
+/// let mut graph = Graph::directed();
+/// for _ in 0..4 {
+///     graph.add_node(());
+/// }
+/// graph.add_edge(0, 1, 4);
+/// graph.add_edge(0, 2, 1);
+/// graph.add_edge(2, 1, 2);
+/// graph.add_edge(1, 3, 5);
+/// let paths = dijkstra(&graph, 0);
+/// assert_eq!(paths.distance(3), Some(&8));
+/// assert_eq!(paths.path_to(3), Some(vec![0, 2, 1, 3]));
+/// ```
+pub fn dijkstra<N, E>(graph: &Graph<N, E>, source: usize) -> ShortestPaths<E>
+where
+    E: Zero + Copy + PartialOrd,
+{
+    dijkstra_by(graph, source, |_, _, w| w)
+}
+
+/// Dijkstra's algorithm with the weight of each edge `(u, v, w)` taken to be
+/// `weight(u, v, w)`.
+pub(super) fn dijkstra_by<N, E, F>(
+    graph: &Graph<N, E>,
+    source: usize,
+    weight: F,
+) -> ShortestPaths<E>
+where
+    E: Zero + Copy + PartialOrd,
+    F: Fn(usize, usize, E) -> E,
+{
+    graph.check_node(source);
+    let n = graph.node_bound();
+    let mut dist: Vec<Option<E>> = vec![None; n];
+    let mut pred = vec![None; n];
+    let mut settled = vec![false; n];
+    let mut heap = MinIndexedHeap::with_capacity(n);
+    dist[source] = Some(E::zero());
+    heap.push(source, E::zero());
+
+    while let Some((u, d)) = heap.pop() {
+        settled[u] = true;
+        for (v, &w) in graph.neighbors(u) {
+            if settled[v] {
+                continue;
+            }
+            let candidate = d + weight(u, v, w);
+            match dist[v] {
+                None => {
+                    heap.push(v, candidate);
+                }
+                Some(old) if candidate < old => {
+                    heap.decrease_key(v, candidate);
+                }
+                Some(_) => continue,
+            }
+            dist[v] = Some(candidate);
+            pred[v] = Some(u);
+        }
+    }
+    ShortestPaths { source, dist, pred }
+}
+
+/// Bellman-Ford algorithm: relaxes every edge `V - 1` times, which finds the
+/// shortest paths even with negative edge weights, in O(VE) time. Stops early
+/// once a round changes nothing.
+///
+/// Returns a cycle of negative length reachable from `source` if there is
+/// one, in which case shortest paths are not defined.
+///
+/// Panics if `source` is not a node of the graph.
+///
+/// Example:
+/// ```
+/// use ralg::graph::{shortest_path::bellman_ford, Graph};
+///
+/// let mut graph = Graph::directed();
+/// for _ in 0..3 {
+///     graph.add_node(());
+/// }
+/// graph.add_edge(0, 1, 4);
+/// graph.add_edge(0, 2, 5);
+/// graph.add_edge(2, 1, -3);
+/// let paths = bellman_ford(&graph, 0).unwrap();
+/// assert_eq!(paths.distance(1), Some(&2));
+///
+/// graph.add_edge(1, 2, 1);
+/// assert_eq!(bellman_ford(&graph, 0).unwrap_err().0.len(), 2);
+/// ```
+pub fn bellman_ford<N, E>(
+    graph: &Graph<N, E>,
+    source: usize,
+) -> Result<ShortestPaths<E>, NegativeCycle>
+where
+    E: Zero + Copy + PartialOrd,
+{
+    graph.check_node(source);
+    let mut dist = vec![None; graph.node_bound()];
+    dist[source] = Some(E::zero());
+    let pred = relax_rounds(graph, &mut dist)?;
+    Ok(ShortestPaths { source, dist, pred })
+}
+
+/// Rounds of Bellman-Ford, starting from the distances `dist`, which may have
+/// more than one source, which get updated. Returns the predecessors found.
+pub(super) fn relax_rounds<N, E>(
+    graph: &Graph<N, E>,
+    dist: &mut [Option<E>],
+) -> Result<Vec<Option<usize>>, NegativeCycle>
+where
+    E: Zero + Copy + PartialOrd,
+{
+    let mut pred = vec![None; dist.len()];
+
+    // Relaxes every edge, returning the last node whose distance decreased
+    let relax = |dist: &mut [Option<E>], pred: &mut [Option<usize>]| {
+        let mut changed = None;
+        for u in graph.node_ids() {
+            let d = match dist[u] {
+                Some(d) => d,
+                None => continue,
+            };
+            for (v, &w) in graph.neighbors(u) {
+                let candidate = d + w;
+                if dist[v].is_none_or(|old| candidate < old) {
+                    dist[v] = Some(candidate);
+                    pred[v] = Some(u);
+                    changed = Some(v);
+                }
+            }
+        }
+        changed
+    };
+
+    for _ in 1..graph.node_count() {
+        if relax(dist, &mut pred).is_none() {
+            return Ok(pred);
+        }
+    }
+    match relax(dist, &mut pred) {
+        None => Ok(pred),
+        Some(mut v) => {
+            // Going back far enough along predecessors lands on the cycle
+            for _ in 0..graph.node_count() {
+                v = pred[v].expect("reached nodes have predecessors");
+            }
+            let mut cycle = vec![v];
+            let mut u = pred[v].unwrap();
+            while u != v {
+                cycle.push(u);
+                u = pred[u].unwrap();
+            }
+            cycle.reverse();
+            Err(NegativeCycle(cycle))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::{Kind, Storage};
+    use crate::random::Pcg32;
+
+    fn graph<E: Clone>(
+        kind: Kind,
+        storage: Storage,
+        n: usize,
+        edges: &[(usize, usize, E)],
+    ) -> Graph<(), E> {
+        let mut graph = Graph::new(kind, storage);
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for (u, v, w) in edges {
+            graph.add_edge(*u, *v, w.clone());
+        }
+        graph
+    }
+
+    #[test]
+    fn dijkstra_clrs() {
+        // Figure 24.6 of Cormen et al. with s, t, x, y, z numbered from 0
+        let edges = [
+            (0, 1, 10),
+            (0, 3, 5),
+            (1, 2, 1),
+            (1, 3, 2),
+            (2, 4, 4),
+            (3, 1, 3),
+            (3, 2, 9),
+            (3, 4, 2),
+            (4, 0, 7),
+            (4, 2, 6),
+        ];
+        for storage in [Storage::List, Storage::Matrix] {
+            let graph = graph(Kind::Directed, storage, 6, &edges);
+            let paths = dijkstra(&graph, 0);
+            let dist: Vec<_> =
+                (0..5).map(|v| *paths.distance(v).unwrap()).collect();
+            assert_eq!(dist, [0, 8, 9, 5, 7]);
+            assert_eq!(paths.path_to(2), Some(vec![0, 3, 1, 2]));
+            assert_eq!(paths.path_to(0), Some(vec![0]));
+            assert_eq!(paths.path_to(5), None);
+            assert_eq!(bellman_ford(&graph, 0), Ok(paths));
+        }
+    }
+
+    #[test]
+    fn bellman_ford_clrs() {
+        // Figure 24.4 of Cormen et al. with s, t, x, y, z numbered from 0
+        let edges = [
+            (0, 1, 6),
+            (0, 3, 7),
+            (1, 2, 5),
+            (1, 3, 8),
+            (1, 4, -4),
+            (2, 1, -2),
+            (3, 2, -3),
+            (3, 4, 9),
+            (4, 0, 2),
+            (4, 2, 7),
+        ];
+        let graph = graph(Kind::Directed, Storage::List, 5, &edges);
+        let paths = bellman_ford(&graph, 0).unwrap();
+        let dist: Vec<_> =
+            (0..5).map(|v| *paths.distance(v).unwrap()).collect();
+        assert_eq!(dist, [0, 2, 4, 7, -2]);
+        assert_eq!(paths.path_to(4), Some(vec![0, 3, 2, 1, 4]));
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let edges = [(0, 1, 2), (1, 2, -1)];
+        let undirected = graph(Kind::Undirected, Storage::List, 3, &edges);
+        let edges = [
+            (0, 1, 1.0),
+            (1, 2, 1.0),
+            (2, 3, -1.0),
+            (3, 1, -0.5),
+            (4, 0, -10.0),
+        ];
+        let mut graph = graph(Kind::Directed, Storage::Matrix, 5, &edges);
+        let cycle = bellman_ford(&graph, 0).unwrap_err().0;
+        let start = cycle.iter().position(|&v| v == 1).unwrap();
+        let mut rotated = cycle[start..].to_vec();
+        rotated.extend(&cycle[..start]);
+        assert_eq!(rotated, [1, 2, 3]);
+
+        // The cycle is also reachable from 4, until its length becomes zero
+        assert!(bellman_ford(&graph, 4).is_err());
+        graph.add_edge(3, 1, 0.0);
+        assert!(bellman_ford(&graph, 4).is_ok());
+
+        // Any negative undirected edge is a cycle
+        assert_eq!(bellman_ford(&undirected, 0).unwrap_err().0.len(), 2);
+    }
+
+    #[test]
+    fn dijkstra_matches_bellman_ford() {
+        let mut rng = Pcg32::seed(0x3c6ef372fe94f82b);
+        for kind in [Kind::Directed, Kind::Undirected] {
+            let n = 60;
+            let mut edges = Vec::new();
+            for _ in 0..300 {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                edges.push((u, v, rng.gen_range(0..100_u64)));
+            }
+            let graph = graph(kind, Storage::List, n, &edges);
+            for source in [0, 17, 42] {
+                let fast = dijkstra(&graph, source);
+                let slow = bellman_ford(&graph, source).unwrap();
+                assert_eq!(fast.dist, slow.dist);
+                for v in 0..n {
+                    // Paths may differ, but not their lengths
+                    let length = fast.path_to(v).map(|path| {
+                        path.windows(2)
+                            .map(|w| graph.edge(w[0], w[1]).unwrap())
+                            .sum::<u64>()
+                    });
+                    assert_eq!(length.as_ref(), fast.distance(v));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Breadth-first and depth-first traversals
+use super::Graph;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+/// Step of a traversal reported to a visitor. The edge events classify the
+/// edge `(u, v)` according to the state of `v` when the edge is examined.
+///
+/// In an undirected graph, the edge leading back from a node to its parent is
+/// examined as well, and is reported as a `BackEdge` by a depth-first search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// The node is reached for the first time.
+    Discover(usize),
+    /// The node `v` is discovered through the edge `(u, v)`.
+    TreeEdge(usize, usize),
+    /// The node `v` was discovered but not finished yet. For a depth-first
+    /// search, this means `v` is an ancestor of `u`, so that the edge closes
+    /// a cycle.
+    BackEdge(usize, usize),
+    /// The node `v` was already finished.
+    CrossEdge(usize, usize),
+    /// Every edge leaving the node was examined.
+    Finish(usize),
+}
+
+/// What a traversal should do after a visitor handles an event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// Stops the traversal right away.
+    Break,
+}
+
+/// Nodes in the order a traversal discovered them, along with the node each
+/// of them was discovered from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Traversal {
+    pub order: Vec<usize>,
+    /// Predecessor of each node in the traversal tree, indexed by node. The
+    /// start node and the nodes never reached have none.
+    pub pred: Vec<Option<usize>>,
+}
+
+impl Traversal {
+    /// Path of the traversal tree from its root to `v`, if `v` was reached.
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        if !self.order.contains(&v) {
+            return None;
+        }
+        let mut path = vec![v];
+        let mut u = v;
+        while let Some(p) = self.pred[u] {
+            path.push(p);
+            u = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Undiscovered,
+    Discovered,
+    Finished,
+}
+
+/// Event for the examination of the edge `(u, v)`, given the color of `v`.
+fn edge_event(u: usize, v: usize, color: Color) -> Event {
+    match color {
+        Color::Undiscovered => Event::TreeEdge(u, v),
+        Color::Discovered => Event::BackEdge(u, v),
+        Color::Finished => Event::CrossEdge(u, v),
+    }
+}
+
+impl<N, E> Graph<N, E> {
+    /// Breadth-first search from `start`: nodes are discovered by increasing
+    /// number of edges from `start`, so that the traversal tree holds
+    /// shortest paths in number of edges. Takes O(V + E) time.
+    ///
+    /// Panics if `start` is not a node of the graph.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::graph::Graph;
+    ///
+    /// let mut graph = Graph::undirected();
+    /// for _ in 0..5 {
+    ///     graph.add_node(());
+    /// }
+    /// for (u, v) in [(0, 1), (1, 2), (2, 3), (0, 4), (4, 3)] {
+    ///     graph.add_edge(u, v, ());
+    /// }
+    /// let bfs = graph.bfs(0);
+    /// assert_eq!(bfs.order, [0, 1, 4, 2, 3]);
+    /// assert_eq!(bfs.path_to(3), Some(vec![0, 4, 3]));
+    /// ```
+    pub fn bfs(&self, start: usize) -> Traversal {
+        self.traversal(|graph, visitor| graph.bfs_visit([start], visitor))
+    }
+
+    /// Depth-first search from `start`, going as deep as possible along each
+    /// branch before backtracking, neighbours being explored in the order
+    /// given by `neighbors`. Takes O(V + E) time.
+    ///
+    /// Panics if `start` is not a node of the graph.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::graph::Graph;
+    ///
+    /// let mut graph = Graph::directed();
+    /// for _ in 0..4 {
+    ///     graph.add_node(());
+    /// }
+    /// for (u, v) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+    ///     graph.add_edge(u, v, ());
+    /// }
+    /// let dfs = graph.dfs(0);
+    /// assert_eq!(dfs.order, [0, 1, 3, 2]);
+    /// assert_eq!(dfs.path_to(3), Some(vec![0, 1, 3]));
+    /// ```
+    pub fn dfs(&self, start: usize) -> Traversal {
+        self.traversal(|graph, visitor| graph.dfs_visit([start], visitor))
+    }
+
+    /// Records the discovery order and the tree edges of a traversal.
+    fn traversal<F>(&self, run: F) -> Traversal
+    where
+        F: FnOnce(&Self, &mut dyn FnMut(Event) -> Control) -> Control,
+    {
+        let mut order = Vec::new();
+        let mut pred = vec![None; self.node_bound()];
+        run(self, &mut |event| {
+            match event {
+                Event::Discover(u) => order.push(u),
+                Event::TreeEdge(u, v) => pred[v] = Some(u),
+                _ => {}
+            }
+            Control::Continue
+        });
+        Traversal { order, pred }
+    }
+
+    /// Breadth-first search reporting each step to `visitor`. A search starts
+    /// from each node of `starts` not yet discovered by the previous ones.
+    ///
+    /// Panics if a start is not a node of the graph.
+    pub fn bfs_visit<I, F>(&self, starts: I, mut visitor: F) -> Control
+    where
+        I: IntoIterator<Item = usize>,
+        F: FnMut(Event) -> Control,
+    {
+        let mut color = vec![Color::Undiscovered; self.node_bound()];
+        let mut queue = VecDeque::new();
+        for start in starts {
+            self.check_node(start);
+            if color[start] != Color::Undiscovered {
+                continue;
+            }
+            color[start] = Color::Discovered;
+            if visitor(Event::Discover(start)) == Control::Break {
+                return Control::Break;
+            }
+            queue.push_back(start);
+
+            while let Some(u) = queue.pop_front() {
+                for (v, _) in self.neighbors(u) {
+                    if visitor(edge_event(u, v, color[v])) == Control::Break {
+                        return Control::Break;
+                    }
+                    if color[v] == Color::Undiscovered {
+                        color[v] = Color::Discovered;
+                        if visitor(Event::Discover(v)) == Control::Break {
+                            return Control::Break;
+                        }
+                        queue.push_back(v);
+                    }
+                }
+                color[u] = Color::Finished;
+                if visitor(Event::Finish(u)) == Control::Break {
+                    return Control::Break;
+                }
+            }
+        }
+        Control::Continue
+    }
+
+    /// Depth-first search reporting each step to `visitor`. A search starts
+    /// from each node of `starts` not yet discovered by the previous ones, so
+    /// that passing `node_ids()` visits the whole graph.
+    ///
+    /// Panics if a start is not a node of the graph.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::graph::traversal::{Control, Event};
+    /// use ralg::graph::Graph;
+    ///
+    /// /// Whether a directed graph has a cycle, that is, a back edge.
+    /// fn has_cycle<N, E>(graph: &Graph<N, E>) -> bool {
+    ///     let control = graph.dfs_visit(graph.node_ids(), |event| match event {
+    ///         Event::BackEdge(..) => Control::Break,
+    ///         _ => Control::Continue,
+    ///     });
+    ///     control == Control::Break
+    /// }
+    ///
+    /// let mut graph = Graph::directed();
+    /// for _ in 0..3 {
+    ///     graph.add_node(());
+    /// }
+    /// graph.add_edge(0, 1, ());
+    /// graph.add_edge(1, 2, ());
+    /// assert!(!has_cycle(&graph));
+    /// graph.add_edge(2, 0, ());
+    /// assert!(has_cycle(&graph));
+    /// ```
+    pub fn dfs_visit<I, F>(&self, starts: I, mut visitor: F) -> Control
+    where
+        I: IntoIterator<Item = usize>,
+        F: FnMut(Event) -> Control,
+    {
+        let mut color = vec![Color::Undiscovered; self.node_bound()];
+        // Nodes on the current path, along with their unexamined edges
+        let mut stack = Vec::new();
+        for start in starts {
+            self.check_node(start);
+            if color[start] != Color::Undiscovered {
+                continue;
+            }
+            color[start] = Color::Discovered;
+            if visitor(Event::Discover(start)) == Control::Break {
+                return Control::Break;
+            }
+            stack.push((start, self.neighbors(start)));
+
+            while let Some((u, edges)) = stack.last_mut() {
+                let u = *u;
+                match edges.next() {
+                    Some((v, _)) => {
+                        if visitor(edge_event(u, v, color[v])) == Control::Break
+                        {
+                            return Control::Break;
+                        }
+                        if color[v] == Color::Undiscovered {
+                            color[v] = Color::Discovered;
+                            if visitor(Event::Discover(v)) == Control::Break {
+                                return Control::Break;
+                            }
+                            stack.push((v, self.neighbors(v)));
+                        }
+                    }
+                    None => {
+                        stack.pop();
+                        color[u] = Color::Finished;
+                        if visitor(Event::Finish(u)) == Control::Break {
+                            return Control::Break;
+                        }
+                    }
+                }
+            }
+        }
+        Control::Continue
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::{Kind, Storage};
+
+    fn graph(
+        kind: Kind,
+        storage: Storage,
+        n: usize,
+        edges: &[(usize, usize)],
+    ) -> Graph<(), ()> {
+        let mut graph = Graph::new(kind, storage);
+        for _ in 0..n {
+            graph.add_node(());
+        }
+        for &(u, v) in edges {
+            graph.add_edge(u, v, ());
+        }
+        graph
+    }
+
+    #[test]
+    fn bfs() {
+        // Grid of 3 by 3 nodes
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (3, 4),
+            (4, 5),
+            (6, 7),
+            (7, 8),
+            (0, 3),
+            (3, 6),
+            (1, 4),
+            (4, 7),
+            (2, 5),
+            (5, 8),
+        ];
+        for storage in [Storage::List, Storage::Matrix] {
+            let graph = graph(Kind::Undirected, storage, 10, &edges);
+            let bfs = graph.bfs(0);
+            assert_eq!(bfs.order.len(), 9);
+            // Distances never decrease along the order
+            let dist: Vec<_> = bfs
+                .order
+                .iter()
+                .map(|&v| bfs.path_to(v).unwrap().len() - 1)
+                .collect();
+            assert!(dist.windows(2).all(|w| w[0] <= w[1]));
+            assert_eq!(bfs.path_to(8).unwrap().len(), 5);
+            assert_eq!(bfs.path_to(9), None);
+            assert_eq!(bfs.pred[0], None);
+        }
+    }
+
+    #[test]
+    fn dfs() {
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (4, 3)];
+        for storage in [Storage::List, Storage::Matrix] {
+            let graph = graph(Kind::Directed, storage, 5, &edges);
+            let dfs = graph.dfs(0);
+            assert_eq!(dfs.order, [0, 1, 2, 3]);
+            assert_eq!(dfs.path_to(3), Some(vec![0, 1, 2, 3]));
+            assert_eq!(dfs.pred[4], None);
+            assert_eq!(graph.dfs(4).order, [4, 3]);
+        }
+    }
+
+    #[test]
+    fn events() {
+        let graph = graph(
+            Kind::Directed,
+            Storage::List,
+            4,
+            &[(0, 1), (1, 2), (2, 0), (0, 3), (3, 2)],
+        );
+        let mut events = Vec::new();
+        graph.dfs_visit([0], |event| {
+            events.push(event);
+            Control::Continue
+        });
+        use Event::*;
+        assert_eq!(
+            events,
+            [
+                Discover(0),
+                TreeEdge(0, 1),
+                Discover(1),
+                TreeEdge(1, 2),
+                Discover(2),
+                BackEdge(2, 0),
+                Finish(2),
+                Finish(1),
+                TreeEdge(0, 3),
+                Discover(3),
+                CrossEdge(3, 2),
+                Finish(3),
+                Finish(0),
+            ]
+        );
+
+        // Breaking stops the traversal right away
+        let mut discovered = 0;
+        let control = graph.bfs_visit(graph.node_ids(), |event| {
+            if let Discover(_) = event {
+                discovered += 1;
+            }
+            if discovered == 2 {
+                Control::Break
+            } else {
+                Control::Continue
+            }
+        });
+        assert_eq!(control, Control::Break);
+        assert_eq!(discovered, 2);
+    }
+
+    #[test]
+    fn topological_order() {
+        // Reverse finishing order of a depth-first search of a dag
+        let graph = graph(
+            Kind::Directed,
+            Storage::List,
+            6,
+            &[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)],
+        );
+        let mut order = Vec::new();
+        graph.dfs_visit(graph.node_ids(), |event| {
+            if let Event::Finish(u) = event {
+                order.push(u);
+            }
+            Control::Continue
+        });
+        order.reverse();
+        let position = |u| order.iter().position(|&v| v == u).unwrap();
+        for (u, v, _) in graph.edges() {
+            assert!(position(u) < position(v));
+        }
+        assert_eq!(order.len(), 6);
+    }
+
+    #[test]
+    fn connected_components() {
+        let edges = [(0, 1), (1, 2), (3, 4), (5, 5)];
+        let graph = graph(Kind::Undirected, Storage::Matrix, 7, &edges);
+        // Each search restarts at a node no tree edge leads to
+        let mut roots = Vec::new();
+        let mut reached = [false; 7];
+        graph.bfs_visit(graph.node_ids(), |event| {
+            match event {
+                Event::TreeEdge(_, v) => reached[v] = true,
+                Event::Discover(u) if !reached[u] => roots.push(u),
+                _ => {}
+            }
+            Control::Continue
+        });
+        assert_eq!(roots, [0, 3, 5, 6]);
+    }
+}
@@ -0,0 +1,265 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Order of a binary heap, determining which element sits at its root.
+pub trait HeapOrder {
+    /// Returns `true` if `a` should be closer to the root of the heap than
+    /// `b`.
+    fn precedes<T: PartialOrd>(a: &T, b: &T) -> bool;
+}
+
+/// The greatest element is at the root of the heap.
+pub struct Max;
+
+/// The smallest element is at the root of the heap.
+pub struct Min;
+
+impl HeapOrder for Max {
+    fn precedes<T: PartialOrd>(a: &T, b: &T) -> bool {
+        a > b
+    }
+}
+
+impl HeapOrder for Min {
+    fn precedes<T: PartialOrd>(a: &T, b: &T) -> bool {
+        a < b
+    }
+}
+
+/// Binary heap stored as an implicit complete binary tree in a vector: the
+/// children of the node at index `i` are found at the indices `2i + 1` and
+/// `2i + 2`.
+pub struct BinaryHeap<T, O: HeapOrder> {
+    data: Vec<T>,
+    order: PhantomData<O>,
+}
+
+/// Heap whose root is its greatest element.
+pub type MaxHeap<T> = BinaryHeap<T, Max>;
+
+/// Heap whose root is its smallest element.
+pub type MinHeap<T> = BinaryHeap<T, Min>;
+
+impl<T: PartialOrd, O: HeapOrder> BinaryHeap<T, O> {
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            order: PhantomData,
+        }
+    }
+
+    /// Builds a heap out of the elements of `data` in O(n) time.
+    pub fn from_vec(mut data: Vec<T>) -> Self {
+        heapify::<T, O>(&mut data);
+        Self {
+            data,
+            order: PhantomData,
+        }
+    }
+
+    /// Builds a heap out of a copy of the elements of `xs` in O(n) time.
+    pub fn from_slice(xs: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec(xs.to_vec())
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Inserts `key` into the heap in O(log(n)) time.
+    pub fn push(&mut self, key: T) {
+        self.data.push(key);
+        let last = self.data.len() - 1;
+        sift_up::<T, O>(&mut self.data, last);
+    }
+
+    /// Removes and returns the root of the heap in O(log(n)) time.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let last = self.data.len() - 1;
+        self.data.swap(0, last);
+        let root = self.data.pop();
+        sift_down::<T, O>(&mut self.data, 0);
+        root
+    }
+
+    /// Returns a reference to the root of the heap.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Consumes the heap, returning its underlying vector in arbitrary order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Consumes the heap, returning its elements sorted so that the root
+    /// comes last: ascending order for a `MaxHeap` and descending order for a
+    /// `MinHeap`. Takes O(n log(n)) time and no extra space.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            sift_down::<T, O>(&mut self.data[..end], 0);
+        }
+        self.data
+    }
+}
+
+impl<T: PartialOrd, O: HeapOrder> Default for BinaryHeap<T, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd, O: HeapOrder> From<Vec<T>> for BinaryHeap<T, O> {
+    fn from(data: Vec<T>) -> Self {
+        Self::from_vec(data)
+    }
+}
+
+/// Rearranges `xs` so that it satisfies the heap property for the order `O`.
+/// Takes time `O(n)` where `n = xs.len()`.
+pub fn heapify<T: PartialOrd, O: HeapOrder>(xs: &mut [T]) {
+    heapify_by(xs, &mut O::precedes)
+}
+
+/// Same as `heapify`, but the order of the heap is given by `precedes`, which
+/// returns `true` if its first argument should be closer to the root than its
+/// second argument.
+pub fn heapify_by<T, F: FnMut(&T, &T) -> bool>(xs: &mut [T], precedes: &mut F) {
+    for i in (0..xs.len() / 2).rev() {
+        sift_down_by(xs, i, precedes);
+    }
+}
+
+/// Moves the element at index `i` down the heap `xs` until both of its
+/// children are preceded by it. Assumes both subtrees of `i` are heaps.
+pub fn sift_down<T: PartialOrd, O: HeapOrder>(xs: &mut [T], i: usize) {
+    sift_down_by(xs, i, &mut O::precedes)
+}
+
+/// Same as `sift_down`, but the order of the heap is given by `precedes`.
+pub fn sift_down_by<T, F: FnMut(&T, &T) -> bool>(
+    xs: &mut [T],
+    mut i: usize,
+    precedes: &mut F,
+) {
+    loop {
+        let (left, right) = (2 * i + 1, 2 * i + 2);
+        let mut top = i;
+        if left < xs.len() && precedes(&xs[left], &xs[top]) {
+            top = left;
+        }
+        if right < xs.len() && precedes(&xs[right], &xs[top]) {
+            top = right;
+        }
+        if top == i {
+            return;
+        }
+        xs.swap(i, top);
+        i = top;
+    }
+}
+
+/// Moves the element at index `i` up the heap `xs` until it is preceded by
+/// its parent.
+pub fn sift_up<T: PartialOrd, O: HeapOrder>(xs: &mut [T], mut i: usize) {
+    while i > 0 {
+        let parent = (i - 1) / 2;
+        if !O::precedes(&xs[i], &xs[parent]) {
+            return;
+        }
+        xs.swap(i, parent);
+        i = parent;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_n_pop() {
+        let mut heap = MaxHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+
+        for x in [3, 9, 1, 4, 7, 7, 0] {
+            heap.push(x);
+        }
+        assert_eq!(heap.len(), 7);
+
+        for x in [9, 7, 7, 4, 3, 1, 0] {
+            assert_eq!(heap.pop(), Some(x));
+        }
+        assert_eq!(heap.pop(), None);
+
+        let mut heap = MinHeap::new();
+        for x in [3, 9, 1, 4, 7, 7, 0] {
+            heap.push(x);
+        }
+        for x in [0, 1, 3, 4, 7, 7, 9] {
+            assert_eq!(heap.pop(), Some(x));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn peek() {
+        let mut heap = MinHeap::new();
+        assert_eq!(heap.peek(), None);
+
+        heap.push(5);
+        assert_eq!(heap.peek(), Some(&5));
+        heap.push(2);
+        assert_eq!(heap.peek(), Some(&2));
+        heap.push(8);
+        assert_eq!(heap.peek(), Some(&2));
+
+        heap.pop();
+        assert_eq!(heap.peek(), Some(&5));
+    }
+
+    #[test]
+    fn from_slice() {
+        let xs = [12, -3, 45, 0, 8, 8, 21, -17];
+        let mut heap = MaxHeap::from_slice(&xs);
+        assert_eq!(heap.len(), xs.len());
+        for x in [45, 21, 12, 8, 8, 0, -3, -17] {
+            assert_eq!(heap.pop(), Some(x));
+        }
+
+        let heap: MinHeap<String> =
+            vec!["b".to_string(), "c".to_string(), "a".to_string()].into();
+        assert_eq!(heap.peek().map(|s| s.as_str()), Some("a"));
+    }
+
+    #[test]
+    fn into_sorted_vec() {
+        let xs = vec![5.5, -1.0, 3.25, 0.0, 10.0, 3.25];
+        let heap = MaxHeap::from_vec(xs.clone());
+        assert_eq!(
+            heap.into_sorted_vec(),
+            vec![-1.0, 0.0, 3.25, 3.25, 5.5, 10.0]
+        );
+
+        let heap = MinHeap::from_vec(xs);
+        assert_eq!(
+            heap.into_sorted_vec(),
+            vec![10.0, 5.5, 3.25, 3.25, 0.0, -1.0]
+        );
+
+        let heap: MaxHeap<i32> = MaxHeap::new();
+        assert!(heap.into_sorted_vec().is_empty());
+    }
+}
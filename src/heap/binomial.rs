@@ -0,0 +1,511 @@
+use super::next_heap_id;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+/// Binomial min-heap: a list of binomial trees of distinct degrees, sorted by
+/// increasing degree, where the tree of degree `k` has `2^k` nodes and each
+/// node is no greater than its children. Inserting, extracting the minimum,
+/// merging two heaps and decreasing a key all take O(log(n)) time.
+///
+/// The nodes are stored in a vector and linked by their indices. Keys are
+/// referred to by the `Handle` returned on insertion, which stays valid until
+/// the key is extracted. Handles of extracted keys are reused, but told apart
+/// from the new ones by their generation. Handles of other heaps are
+/// rejected, even after merging them.
+///
+/// Example:
+/// ```
+/// use ralg::heap::binomial::BinomialHeap;
+///
+/// let mut heap = BinomialHeap::new();
+/// heap.push(5);
+/// let h = heap.push(8);
+/// heap.push(3);
+/// assert!(heap.decrease_key(h, 1));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), Some(3));
+/// ```
+pub struct BinomialHeap<T> {
+    /// Identifier carried by the handles of this heap.
+    id: usize,
+    nodes: Vec<Option<Node<T>>>,
+    /// Node holding the key of each handle, as keys move between nodes when
+    /// decreased.
+    node_of: Vec<Option<usize>>,
+    /// Number of times each handle was freed.
+    generations: Vec<u32>,
+    free_nodes: Vec<usize>,
+    free_handles: Vec<usize>,
+    /// First root of the list of trees.
+    head: Link,
+    len: usize,
+}
+
+/// Reference to a key of a heap, used to decrease it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Handle {
+    heap: usize,
+    index: usize,
+    generation: u32,
+}
+
+type Link = Option<usize>;
+
+struct Node<T> {
+    key: T,
+    handle: usize,
+    degree: usize,
+    parent: Link,
+    /// Child of greatest degree.
+    child: Link,
+    /// Next root, or next child of the parent, of smaller degree.
+    sibling: Link,
+}
+
+impl<T: PartialOrd> BinomialHeap<T> {
+    pub fn new() -> Self {
+        Self {
+            id: next_heap_id(),
+            nodes: Vec::new(),
+            node_of: Vec::new(),
+            generations: Vec::new(),
+            free_nodes: Vec::new(),
+            free_handles: Vec::new(),
+            head: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn node(&self, i: usize) -> &Node<T> {
+        self.nodes[i].as_ref().expect("linked binomial heap node")
+    }
+
+    fn node_mut(&mut self, i: usize) -> &mut Node<T> {
+        self.nodes[i].as_mut().expect("linked binomial heap node")
+    }
+
+    /// Node holding the key of `handle`, if it is still in the heap.
+    fn index_of(&self, handle: Handle) -> Option<usize> {
+        if handle.heap != self.id {
+            return None;
+        }
+        let generation = *self.generations.get(handle.index)?;
+        if generation != handle.generation {
+            return None;
+        }
+        self.node_of[handle.index]
+    }
+
+    /// Key referred to by `handle`, if it is still in the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.index_of(handle).map(|i| &self.node(i).key)
+    }
+
+    /// Inserts `key` in O(log(n)) time, returning a handle to it.
+    pub fn push(&mut self, key: T) -> Handle {
+        let handle = self.free_handles.pop().unwrap_or_else(|| {
+            self.node_of.push(None);
+            self.generations.push(0);
+            self.node_of.len() - 1
+        });
+        let node = Some(Node {
+            key,
+            handle,
+            degree: 0,
+            parent: None,
+            child: None,
+            sibling: None,
+        });
+        let i = match self.free_nodes.pop() {
+            Some(i) => {
+                self.nodes[i] = node;
+                i
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        self.node_of[handle] = Some(i);
+        self.head = self.union(self.head, Some(i));
+        self.len += 1;
+        Handle {
+            heap: self.id,
+            index: handle,
+            generation: self.generations[handle],
+        }
+    }
+
+    /// Root holding the smallest key, along with the root preceding it.
+    fn min_root(&self) -> Option<(Link, usize)> {
+        let mut best = (None, self.head?);
+        let mut current = best.1;
+        while let Some(next) = self.node(current).sibling {
+            if self.node(next).key < self.node(best.1).key {
+                best = (Some(current), next);
+            }
+            current = next;
+        }
+        Some(best)
+    }
+
+    /// Returns a reference to the smallest key in O(log(n)) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.min_root().map(|(_, i)| &self.node(i).key)
+    }
+
+    /// Removes and returns the smallest key in O(log(n)) time.
+    pub fn pop(&mut self) -> Option<T> {
+        let (prev, min) = self.min_root()?;
+        let sibling = self.node(min).sibling;
+        match prev {
+            None => self.head = sibling,
+            Some(prev) => self.node_mut(prev).sibling = sibling,
+        }
+
+        // The children of the root form a heap once their order is reversed
+        let mut children = None;
+        let mut child = self.node(min).child;
+        while let Some(c) = child {
+            let node = self.node_mut(c);
+            child = node.sibling;
+            node.sibling = children;
+            node.parent = None;
+            children = Some(c);
+        }
+        self.head = self.union(self.head, children);
+
+        let node = self.nodes[min].take().expect("linked binomial heap node");
+        self.node_of[node.handle] = None;
+        // Invalidate the handle of the extracted key
+        let generation = &mut self.generations[node.handle];
+        *generation = generation.wrapping_add(1);
+        self.free_nodes.push(min);
+        self.free_handles.push(node.handle);
+        self.len -= 1;
+        Some(node.key)
+    }
+
+    /// Replaces the key referred to by `handle` with `key`, provided it is
+    /// smaller, and restores the heap order in O(log(n)) time. Returns
+    /// whether the key was changed.
+    ///
+    /// Panics if the key of `handle` was already extracted, or if `handle`
+    /// belongs to another heap.
+    pub fn decrease_key(&mut self, handle: Handle, key: T) -> bool {
+        let mut i = self.index_of(handle).expect("key still in the heap");
+        if key.partial_cmp(&self.node(i).key) != Some(Ordering::Less) {
+            return false;
+        }
+        self.node_mut(i).key = key;
+
+        // Swap keys with the ancestors until the heap order holds
+        loop {
+            let parent = match self.node(i).parent {
+                Some(p) if self.node(i).key < self.node(p).key => p,
+                _ => break,
+            };
+            let (a, b) = (i.min(parent), i.max(parent));
+            let (left, right) = self.nodes.split_at_mut(b);
+            let (x, y) =
+                (left[a].as_mut().unwrap(), right[0].as_mut().unwrap());
+            core::mem::swap(&mut x.key, &mut y.key);
+            core::mem::swap(&mut x.handle, &mut y.handle);
+            self.node_of[x.handle] = Some(a);
+            self.node_of[y.handle] = Some(b);
+            i = parent;
+        }
+        true
+    }
+
+    /// Moves every key of `other` into this heap. Linking the trees takes
+    /// O(log(n)) time, on top of moving the nodes of `other` into the storage
+    /// of `self`. The handles of `other` are invalidated.
+    pub fn merge(&mut self, other: Self) {
+        let (offset, handle_offset) = (self.nodes.len(), self.node_of.len());
+        let shift = |link: Link| link.map(|i| i + offset);
+        self.nodes.extend(other.nodes.into_iter().map(|node| {
+            node.map(|node| Node {
+                handle: node.handle + handle_offset,
+                parent: shift(node.parent),
+                child: shift(node.child),
+                sibling: shift(node.sibling),
+                ..node
+            })
+        }));
+        self.node_of.extend(other.node_of.into_iter().map(shift));
+        self.generations.extend(other.generations);
+        self.free_nodes
+            .extend(other.free_nodes.iter().map(|i| i + offset));
+        self.free_handles
+            .extend(other.free_handles.iter().map(|i| i + handle_offset));
+        self.head = self.union(self.head, shift(other.head));
+        self.len += other.len;
+    }
+
+    /// Makes the root `child` a child of the root `parent`, both trees having
+    /// the same degree.
+    fn link(&mut self, child: usize, parent: usize) {
+        let first = self.node(parent).child;
+        let node = self.node_mut(child);
+        node.parent = Some(parent);
+        node.sibling = first;
+        let node = self.node_mut(parent);
+        node.child = Some(child);
+        node.degree += 1;
+    }
+
+    /// Merges two root lists into a single one by increasing degree.
+    fn merge_roots(&mut self, mut a: Link, mut b: Link) -> Link {
+        let mut head = None;
+        let mut tail: Link = None;
+        loop {
+            let next = match (a, b) {
+                (None, rest) | (rest, None) => {
+                    match tail {
+                        None => head = rest,
+                        Some(t) => self.node_mut(t).sibling = rest,
+                    }
+                    return head;
+                }
+                (Some(x), Some(y)) => {
+                    if self.node(x).degree <= self.node(y).degree {
+                        a = self.node(x).sibling;
+                        x
+                    } else {
+                        b = self.node(y).sibling;
+                        y
+                    }
+                }
+            };
+            match tail {
+                None => head = Some(next),
+                Some(t) => self.node_mut(t).sibling = Some(next),
+            }
+            tail = Some(next);
+        }
+    }
+
+    /// Union of two root lists, linking trees of equal degree until all
+    /// degrees are distinct.
+    fn union(&mut self, a: Link, b: Link) -> Link {
+        let mut head = self.merge_roots(a, b);
+        let mut prev: Link = None;
+        let mut x = head?;
+        while let Some(next) = self.node(x).sibling {
+            let degree = self.node(x).degree;
+            let next_sibling = self.node(next).sibling;
+            if degree != self.node(next).degree
+                || next_sibling.is_some_and(|s| self.node(s).degree == degree)
+            {
+                // Three trees of the same degree link the last two
+                prev = Some(x);
+                x = next;
+            } else if self.node(next).key < self.node(x).key {
+                match prev {
+                    None => head = Some(next),
+                    Some(p) => self.node_mut(p).sibling = Some(next),
+                }
+                self.link(x, next);
+                x = next;
+            } else {
+                self.node_mut(x).sibling = next_sibling;
+                self.link(next, x);
+            }
+        }
+        head
+    }
+}
+
+impl<T: PartialOrd> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd> FromIterator<T> for BinomialHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = BinomialHeap::new();
+        for key in iter {
+            heap.push(key);
+        }
+        heap
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::heap::{MinHeap, MinIndexedHeap};
+    use crate::random::Pcg32;
+
+    /// Checks the shape and order of the trees, returning the number of keys.
+    fn check<T: PartialOrd>(heap: &BinomialHeap<T>) -> usize {
+        fn tree<T: PartialOrd>(heap: &BinomialHeap<T>, i: usize) -> usize {
+            let node = heap.node(i);
+            assert_eq!(heap.node_of[node.handle], Some(i));
+            let mut size = 1;
+            let mut degree = node.degree;
+            let mut child = node.child;
+            while let Some(c) = child {
+                let child_node = heap.node(c);
+                assert_eq!(child_node.parent, Some(i));
+                assert!(child_node.key >= node.key);
+                degree -= 1;
+                assert_eq!(child_node.degree, degree);
+                size += tree(heap, c);
+                child = child_node.sibling;
+            }
+            assert_eq!(degree, 0);
+            assert_eq!(size, 1 << node.degree);
+            size
+        }
+
+        let mut len = 0;
+        let mut last_degree = None;
+        let mut root = heap.head;
+        while let Some(r) = root {
+            let node = heap.node(r);
+            assert!(node.parent.is_none());
+            assert!(last_degree < Some(node.degree));
+            last_degree = Some(node.degree);
+            len += tree(heap, r);
+            root = node.sibling;
+        }
+        assert_eq!(len, heap.len());
+        len
+    }
+
+    #[test]
+    fn push_n_pop() {
+        let mut heap = BinomialHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+        assert_eq!(heap.peek(), None);
+
+        let xs = [12, -3, 45, 0, 8, 8, 21, -17, 4, 4, 30];
+        let mut expected = MinHeap::new();
+        for x in xs {
+            heap.push(x);
+            expected.push(x);
+            check(&heap);
+        }
+        assert_eq!(heap.peek(), Some(&-17));
+        while let Some(x) = expected.pop() {
+            assert_eq!(heap.pop(), Some(x));
+            check(&heap);
+        }
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let mut a: BinomialHeap<i32> = (0..13).map(|x| 3 * x).collect();
+        let mut b: BinomialHeap<i32> = (0..7).map(|x| 5 * x + 1).collect();
+        b.pop();
+        let h = b.push(100);
+        let g = a.push(50);
+        a.merge(b);
+        check(&a);
+        assert_eq!(a.len(), 21);
+
+        // Handles of `other` are invalidated, those of `self` are not
+        assert_eq!(a.get(h), None);
+        assert_eq!(a.get(g), Some(&50));
+        assert!(!a.decrease_key(g, 100));
+        assert_eq!(a.get(g), Some(&50));
+
+        let mut expected: Vec<i32> = (0..13).map(|x| 3 * x).collect();
+        expected.extend((1..7).map(|x| 5 * x + 1));
+        expected.extend([50, 100]);
+        expected.sort();
+        let popped: Vec<_> = core::iter::from_fn(|| a.pop()).collect();
+        assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn decrease_key() {
+        let mut heap = BinomialHeap::new();
+        let handles: Vec<_> = (0..16).map(|x| heap.push(10 * x)).collect();
+        assert!(heap.decrease_key(handles[15], -1));
+        assert!(!heap.decrease_key(handles[3], 40));
+        assert_eq!(heap.get(handles[15]), Some(&-1));
+        check(&heap);
+        assert_eq!(heap.pop(), Some(-1));
+        assert_eq!(heap.get(handles[15]), None);
+        assert_eq!(heap.get(handles[7]), Some(&70));
+    }
+
+    #[test]
+    fn stale_handle() {
+        let mut heap = BinomialHeap::new();
+        let h = heap.push(5);
+        assert_eq!(heap.pop(), Some(5));
+        // The handle of the extracted key is reused
+        let g = heap.push(7);
+        assert_ne!(g, h);
+        assert_eq!(heap.get(h), None);
+        assert_eq!(heap.get(g), Some(&7));
+    }
+
+    #[test]
+    #[should_panic(expected = "key still in the heap")]
+    fn decrease_stale_key() {
+        let mut heap = BinomialHeap::new();
+        let h = heap.push(5);
+        heap.pop();
+        heap.push(7);
+        heap.decrease_key(h, 1);
+    }
+
+    #[test]
+    fn matches_binary_heap() {
+        let mut heap = BinomialHeap::new();
+        let mut expected = MinIndexedHeap::new();
+        let mut handles = Vec::new();
+        let mut rng = Pcg32::seed(0xda942042e4dd58b5);
+        for step in 0..10_000u64 {
+            // Keys are distinct so that both heaps pop the same handles
+            let key = rng.gen_range(0..10_000) << 14 | step;
+            match rng.gen_range(0..5) {
+                0 | 1 => {
+                    handles.push(Some(heap.push(key)));
+                    expected.push(handles.len() - 1, key);
+                }
+                2 => {
+                    let popped = expected.pop();
+                    assert_eq!(heap.pop(), popped.map(|(_, key)| key));
+                    if let Some((i, _)) = popped {
+                        handles[i] = None;
+                    }
+                }
+                _ if !handles.is_empty() => {
+                    let i = (key as usize) % handles.len();
+                    if let Some(h) = handles[i] {
+                        let new = (key >> 15) << 14 | step;
+                        assert_eq!(
+                            heap.decrease_key(h, new),
+                            expected.decrease_key(i, new)
+                        );
+                    }
+                }
+                _ => {}
+            }
+            assert_eq!(heap.len(), expected.len());
+            if step % 1000 == 0 {
+                check(&heap);
+            }
+        }
+        while let Some((_, key)) = expected.pop() {
+            assert_eq!(heap.pop(), Some(key));
+        }
+    }
+}
@@ -0,0 +1,533 @@
+use super::next_heap_id;
+use alloc::{vec, vec::Vec};
+use core::cmp::Ordering;
+
+/// Maximum degree of a node, as a tree whose root has degree `d` has at
+/// least `F(d + 2)` nodes, where `F` is the Fibonacci sequence.
+const MAX_DEGREE: usize = 92;
+
+/// Fibonacci min-heap: a circular list of heap-ordered trees which are only
+/// tidied up when the minimum is extracted. Inserting, merging and
+/// decreasing a key take O(1) amortized time, while extracting the minimum
+/// takes O(log(n)) amortized time.
+///
+/// The nodes are stored in a vector and linked by their indices. Keys are
+/// referred to by the `Handle` returned on insertion, which stays valid until
+/// the key is extracted. Slots of extracted keys are reused, but handles to
+/// them are told apart from the new ones by the generation of the slot.
+/// Handles of other heaps are rejected, even after merging them.
+///
+/// Example:
+/// ```
+/// use ralg::heap::fibonacci::FibHeap;
+///
+/// let mut heap = FibHeap::new();
+/// heap.push(5);
+/// let h = heap.push(8);
+/// heap.push(3);
+/// assert!(heap.decrease_key(h, 1));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), Some(3));
+/// ```
+pub struct FibHeap<T> {
+    /// Identifier carried by the handles of this heap.
+    id: usize,
+    nodes: Vec<Option<Node<T>>>,
+    /// Number of times each slot of `nodes` was freed.
+    generations: Vec<u32>,
+    free: Vec<usize>,
+    /// Root holding the smallest key.
+    min: Link,
+    len: usize,
+}
+
+/// Reference to a key of a heap, used to decrease it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Handle {
+    heap: usize,
+    index: usize,
+    generation: u32,
+}
+
+type Link = Option<usize>;
+
+struct Node<T> {
+    key: T,
+    parent: Link,
+    /// Any of the children.
+    child: Link,
+    /// Neighbours in the circular list of roots or of children.
+    left: usize,
+    right: usize,
+    degree: usize,
+    /// Whether the node lost a child since it last became a child itself.
+    mark: bool,
+}
+
+impl<T: PartialOrd> FibHeap<T> {
+    pub fn new() -> Self {
+        Self {
+            id: next_heap_id(),
+            nodes: Vec::new(),
+            generations: Vec::new(),
+            free: Vec::new(),
+            min: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn node(&self, i: usize) -> &Node<T> {
+        self.nodes[i].as_ref().expect("linked Fibonacci heap node")
+    }
+
+    fn node_mut(&mut self, i: usize) -> &mut Node<T> {
+        self.nodes[i].as_mut().expect("linked Fibonacci heap node")
+    }
+
+    fn less(&self, i: usize, j: usize) -> bool {
+        self.node(i).key < self.node(j).key
+    }
+
+    /// Node referred to by `handle`, if its key is still in the heap.
+    fn index_of(&self, handle: Handle) -> Option<usize> {
+        if handle.heap != self.id {
+            return None;
+        }
+        let generation = *self.generations.get(handle.index)?;
+        (generation == handle.generation).then_some(handle.index)
+    }
+
+    /// Key referred to by `handle`, if it is still in the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.index_of(handle).map(|i| &self.node(i).key)
+    }
+
+    /// Inserts `key` in O(1) time, returning a handle to it.
+    pub fn push(&mut self, key: T) -> Handle {
+        let i = self.free.pop().unwrap_or(self.nodes.len());
+        let node = Some(Node {
+            key,
+            parent: None,
+            child: None,
+            left: i,
+            right: i,
+            degree: 0,
+            mark: false,
+        });
+        if i == self.nodes.len() {
+            self.nodes.push(node);
+            self.generations.push(0);
+        } else {
+            self.nodes[i] = node;
+        }
+        self.add_root(i);
+        self.len += 1;
+        Handle {
+            heap: self.id,
+            index: i,
+            generation: self.generations[i],
+        }
+    }
+
+    /// Returns a reference to the smallest key in O(1) time.
+    pub fn peek(&self) -> Option<&T> {
+        self.min.map(|i| &self.node(i).key)
+    }
+
+    /// Removes and returns the smallest key in O(log(n)) amortized time.
+    pub fn pop(&mut self) -> Option<T> {
+        let z = self.min?;
+
+        // Every child of the minimum becomes a root
+        while let Some(c) = self.node(z).child {
+            self.remove_child(c, z);
+            self.splice(c, z);
+        }
+
+        let right = self.node(z).right;
+        self.unlink(z);
+        if right == z {
+            self.min = None;
+        } else {
+            self.min = Some(right);
+            self.consolidate();
+        }
+
+        let node = self.nodes[z].take().expect("linked Fibonacci heap node");
+        // Invalidate the handle of the extracted key
+        self.generations[z] = self.generations[z].wrapping_add(1);
+        self.free.push(z);
+        self.len -= 1;
+        Some(node.key)
+    }
+
+    /// Replaces the key referred to by `handle` with `key`, provided it is
+    /// smaller, in O(1) amortized time. Returns whether the key was changed.
+    ///
+    /// Panics if the key of `handle` was already extracted, or if `handle`
+    /// belongs to another heap.
+    pub fn decrease_key(&mut self, handle: Handle, key: T) -> bool {
+        let x = self.index_of(handle).expect("key still in the heap");
+        let node = self.node_mut(x);
+        if key.partial_cmp(&node.key) != Some(Ordering::Less) {
+            return false;
+        }
+        node.key = key;
+
+        if let Some(parent) = node.parent {
+            if self.less(x, parent) {
+                self.cut(x, parent);
+                self.cascading_cut(parent);
+            }
+        }
+        if self.min.is_some_and(|min| self.less(x, min)) {
+            self.min = Some(x);
+        }
+        true
+    }
+
+    /// Moves every key of `other` into this heap. Joining the root lists
+    /// takes O(1) time, on top of moving the nodes of `other` into the
+    /// storage of `self`. The handles of `other` are invalidated.
+    pub fn merge(&mut self, other: Self) {
+        let offset = self.nodes.len();
+        let shift = |link: Link| link.map(|i| i + offset);
+        self.nodes.extend(other.nodes.into_iter().map(|node| {
+            node.map(|node| Node {
+                parent: shift(node.parent),
+                child: shift(node.child),
+                left: node.left + offset,
+                right: node.right + offset,
+                ..node
+            })
+        }));
+        self.generations.extend(other.generations);
+        self.free.extend(other.free.iter().map(|i| i + offset));
+        match (self.min, shift(other.min)) {
+            (_, None) => {}
+            (None, min) => self.min = min,
+            (Some(a), Some(b)) => {
+                // Join the two circular lists
+                let (a_right, b_left) = (self.node(a).right, self.node(b).left);
+                self.node_mut(a).right = b;
+                self.node_mut(b).left = a;
+                self.node_mut(b_left).right = a_right;
+                self.node_mut(a_right).left = b_left;
+                if self.less(b, a) {
+                    self.min = Some(b);
+                }
+            }
+        }
+        self.len += other.len;
+    }
+
+    /// Inserts `x` to the right of `at` in its circular list.
+    fn splice(&mut self, x: usize, at: usize) {
+        let right = self.node(at).right;
+        self.node_mut(x).left = at;
+        self.node_mut(x).right = right;
+        self.node_mut(at).right = x;
+        self.node_mut(right).left = x;
+    }
+
+    /// Removes `x` from its circular list, leaving it on its own.
+    fn unlink(&mut self, x: usize) {
+        let (left, right) = (self.node(x).left, self.node(x).right);
+        self.node_mut(left).right = right;
+        self.node_mut(right).left = left;
+        self.node_mut(x).left = x;
+        self.node_mut(x).right = x;
+    }
+
+    /// Adds the lone node `x` to the root list.
+    fn add_root(&mut self, x: usize) {
+        self.node_mut(x).parent = None;
+        match self.min {
+            None => self.min = Some(x),
+            Some(min) => {
+                self.splice(x, min);
+                if self.less(x, min) {
+                    self.min = Some(x);
+                }
+            }
+        }
+    }
+
+    /// Detaches the child `x` from `parent`, leaving it on its own.
+    fn remove_child(&mut self, x: usize, parent: usize) {
+        let right = self.node(x).right;
+        let node = self.node_mut(parent);
+        node.degree -= 1;
+        if node.child == Some(x) {
+            node.child = (right != x).then_some(right);
+        }
+        self.unlink(x);
+        let node = self.node_mut(x);
+        node.parent = None;
+        node.mark = false;
+    }
+
+    /// Makes the root `y` a child of the root `x`.
+    fn link(&mut self, y: usize, x: usize) {
+        self.unlink(y);
+        match self.node(x).child {
+            None => self.node_mut(x).child = Some(y),
+            Some(c) => self.splice(y, c),
+        }
+        let node = self.node_mut(y);
+        node.parent = Some(x);
+        node.mark = false;
+        self.node_mut(x).degree += 1;
+    }
+
+    /// Links roots of equal degree until all degrees are distinct, then
+    /// finds the new minimum.
+    fn consolidate(&mut self) {
+        let start = self.min.expect("non-empty root list");
+        let mut roots = vec![start];
+        let mut r = self.node(start).right;
+        while r != start {
+            roots.push(r);
+            r = self.node(r).right;
+        }
+
+        let mut by_degree: [Link; MAX_DEGREE] = [None; MAX_DEGREE];
+        for mut x in roots {
+            let mut d = self.node(x).degree;
+            while let Some(mut y) = by_degree[d].take() {
+                if self.less(y, x) {
+                    core::mem::swap(&mut x, &mut y);
+                }
+                self.link(y, x);
+                d += 1;
+            }
+            by_degree[d] = Some(x);
+        }
+
+        self.min = None;
+        for x in by_degree.into_iter().flatten() {
+            if self.min.is_none_or(|min| self.less(x, min)) {
+                self.min = Some(x);
+            }
+        }
+    }
+
+    /// Moves the child `x` of `parent` to the root list.
+    fn cut(&mut self, x: usize, parent: usize) {
+        self.remove_child(x, parent);
+        self.add_root(x);
+    }
+
+    /// Cuts the ancestors of `y` that already lost a child, up to the first
+    /// unmarked one, which gets marked.
+    fn cascading_cut(&mut self, mut y: usize) {
+        while let Some(parent) = self.node(y).parent {
+            if !self.node(y).mark {
+                self.node_mut(y).mark = true;
+                return;
+            }
+            self.cut(y, parent);
+            y = parent;
+        }
+    }
+}
+
+impl<T: PartialOrd> Default for FibHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd> FromIterator<T> for FibHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = FibHeap::new();
+        for key in iter {
+            heap.push(key);
+        }
+        heap
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::heap::{MinHeap, MinIndexedHeap};
+    use crate::random::Pcg32;
+
+    /// Checks the links and order of the trees, returning the number of keys.
+    fn check<T: PartialOrd>(heap: &FibHeap<T>) -> usize {
+        /// Checks the circular list starting at `start`, whose nodes have
+        /// `parent` as parent, returning the number of nodes in their trees.
+        fn list<T: PartialOrd>(
+            heap: &FibHeap<T>,
+            start: usize,
+            parent: Link,
+        ) -> (usize, usize) {
+            let (mut count, mut size) = (0, 0);
+            let mut x = start;
+            loop {
+                let node = heap.node(x);
+                assert_eq!(node.parent, parent);
+                assert_eq!(heap.node(node.right).left, x);
+                if let Some(p) = parent {
+                    assert!(node.key >= heap.node(p).key);
+                }
+                let children = match node.child {
+                    None => (0, 0),
+                    Some(c) => list(heap, c, Some(x)),
+                };
+                assert_eq!(children.0, node.degree);
+                count += 1;
+                size += 1 + children.1;
+                x = node.right;
+                if x == start {
+                    return (count, size);
+                }
+            }
+        }
+
+        let size = heap.min.map_or(0, |min| list(heap, min, None).1);
+        assert_eq!(size, heap.len());
+        size
+    }
+
+    #[test]
+    fn push_n_pop() {
+        let mut heap = FibHeap::new();
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+        assert_eq!(heap.peek(), None);
+
+        let xs = [12, -3, 45, 0, 8, 8, 21, -17, 4, 4, 30];
+        let mut expected = MinHeap::new();
+        for x in xs {
+            heap.push(x);
+            expected.push(x);
+        }
+        check(&heap);
+        assert_eq!(heap.peek(), Some(&-17));
+        while let Some(x) = expected.pop() {
+            assert_eq!(heap.pop(), Some(x));
+            check(&heap);
+        }
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let mut a: FibHeap<i32> = (0..13).map(|x| 3 * x).collect();
+        let mut b: FibHeap<i32> = (0..7).map(|x| 5 * x + 1).collect();
+        b.pop();
+        let h = b.push(100);
+        a.pop();
+        let g = a.push(50);
+        a.merge(b);
+        check(&a);
+        assert_eq!(a.len(), 20);
+
+        // Handles of `other` are invalidated, those of `self` are not
+        assert_eq!(a.get(h), None);
+        assert_eq!(a.get(g), Some(&50));
+        assert!(!a.decrease_key(g, 100));
+        assert_eq!(a.get(g), Some(&50));
+
+        let mut expected: Vec<i32> = (1..13).map(|x| 3 * x).collect();
+        expected.extend((1..7).map(|x| 5 * x + 1));
+        expected.extend([50, 100]);
+        expected.sort();
+        assert_eq!(a.peek(), Some(&3));
+        let popped: Vec<_> = core::iter::from_fn(|| a.pop()).collect();
+        assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn decrease_key() {
+        let mut heap = FibHeap::new();
+        let handles: Vec<_> = (0..16).map(|x| heap.push(10 * x)).collect();
+        // Build deeper trees before cutting nodes out of them
+        assert_eq!(heap.pop(), Some(0));
+        assert!(heap.decrease_key(handles[15], -1));
+        assert!(heap.decrease_key(handles[14], -2));
+        assert!(heap.decrease_key(handles[13], -3));
+        assert!(!heap.decrease_key(handles[3], 40));
+        assert_eq!(heap.get(handles[15]), Some(&-1));
+        check(&heap);
+        assert_eq!(heap.pop(), Some(-3));
+        assert_eq!(heap.get(handles[13]), None);
+        assert_eq!(heap.get(handles[7]), Some(&70));
+        check(&heap);
+    }
+
+    #[test]
+    fn stale_handle() {
+        let mut heap = FibHeap::new();
+        let h = heap.push(5);
+        assert_eq!(heap.pop(), Some(5));
+        // The slot of the extracted key is reused
+        let g = heap.push(7);
+        assert_ne!(g, h);
+        assert_eq!(heap.get(h), None);
+        assert_eq!(heap.get(g), Some(&7));
+    }
+
+    #[test]
+    #[should_panic(expected = "key still in the heap")]
+    fn decrease_stale_key() {
+        let mut heap = FibHeap::new();
+        let h = heap.push(5);
+        heap.pop();
+        heap.push(7);
+        heap.decrease_key(h, 1);
+    }
+
+    #[test]
+    fn matches_binary_heap() {
+        let mut heap = FibHeap::new();
+        let mut expected = MinIndexedHeap::new();
+        let mut handles = Vec::new();
+        let mut rng = Pcg32::seed(0x1f83d9abfb41bd6b);
+        for step in 0..10_000u64 {
+            // Keys are distinct so that both heaps pop the same handles
+            let key = rng.gen_range(0..10_000) << 14 | step;
+            match rng.gen_range(0..5) {
+                0 | 1 => {
+                    handles.push(Some(heap.push(key)));
+                    expected.push(handles.len() - 1, key);
+                }
+                2 => {
+                    let popped = expected.pop();
+                    assert_eq!(heap.pop(), popped.map(|(_, key)| key));
+                    if let Some((i, _)) = popped {
+                        handles[i] = None;
+                    }
+                }
+                _ if !handles.is_empty() => {
+                    let i = (key as usize) % handles.len();
+                    if let Some(h) = handles[i] {
+                        let new = (key >> 15) << 14 | step;
+                        assert_eq!(
+                            heap.decrease_key(h, new),
+                            expected.decrease_key(i, new)
+                        );
+                    }
+                }
+                _ => {}
+            }
+            assert_eq!(heap.len(), expected.len());
+            if step % 1000 == 0 {
+                check(&heap);
+            }
+        }
+        while let Some((_, key)) = expected.pop() {
+            assert_eq!(heap.pop(), Some(key));
+        }
+    }
+}
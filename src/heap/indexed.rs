@@ -0,0 +1,297 @@
+use super::binary::{HeapOrder, Max, Min};
+use alloc::{vec, vec::Vec};
+use core::marker::PhantomData;
+
+/// Binary heap of keys `0, 1, 2, ...` ordered by their priorities, which also
+/// keeps track of the position of each key in the heap. This allows the
+/// priority of any key to be changed, or the key to be removed, in
+/// O(log(n)) time, as needed by Dijkstra's and Prim's algorithms.
+///
+/// The memory used is proportional to the greatest key ever inserted, so keys
+/// should be small indices, such as vertices of a graph.
+///
+/// Example:
+/// ```
+/// use ralg::heap::MinIndexedHeap;
+///
+/// let mut heap = MinIndexedHeap::new();
+/// heap.push(0, 10);
+/// heap.push(1, 20);
+/// heap.push(2, 30);
+/// assert!(heap.decrease_key(2, 5));
+/// assert!(!heap.decrease_key(1, 25));
+/// assert_eq!(heap.pop(), Some((2, 5)));
+/// assert_eq!(heap.pop(), Some((0, 10)));
+/// ```
+pub struct IndexedHeap<P, O: HeapOrder> {
+    /// Keys along with their priorities, as an implicit binary tree.
+    data: Vec<(usize, P)>,
+    /// Position in `data` of each key, if present.
+    pos: Vec<Option<usize>>,
+    order: PhantomData<O>,
+}
+
+/// Indexed heap whose root has the greatest priority.
+pub type MaxIndexedHeap<P> = IndexedHeap<P, Max>;
+
+/// Indexed heap whose root has the smallest priority.
+pub type MinIndexedHeap<P> = IndexedHeap<P, Min>;
+
+impl<P: PartialOrd, O: HeapOrder> IndexedHeap<P, O> {
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            pos: Vec::new(),
+            order: PhantomData,
+        }
+    }
+
+    /// Empty heap with room for the keys `0..n`.
+    pub fn with_capacity(n: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(n),
+            pos: vec![None; n],
+            order: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn contains(&self, key: usize) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Current priority of `key`, if present.
+    pub fn priority(&self, key: usize) -> Option<&P> {
+        self.position(key).map(|i| &self.data[i].1)
+    }
+
+    /// Returns the key at the root of the heap, along with its priority.
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        self.data.first().map(|(key, p)| (*key, p))
+    }
+
+    /// Inserts `key` with `priority` in O(log(n)) time. If `key` was already
+    /// present, its priority is replaced and the old one returned.
+    pub fn push(&mut self, key: usize, priority: P) -> Option<P> {
+        if self.contains(key) {
+            return self.change_priority(key, priority);
+        }
+        if key >= self.pos.len() {
+            self.pos.resize(key + 1, None);
+        }
+        self.data.push((key, priority));
+        let last = self.data.len() - 1;
+        self.pos[key] = Some(last);
+        self.sift_up(last);
+        None
+    }
+
+    /// Removes and returns the root of the heap, along with its priority, in
+    /// O(log(n)) time.
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(self.remove_at(0))
+    }
+
+    /// Removes `key` from the heap in O(log(n)) time, returning its priority
+    /// if it was present.
+    pub fn remove(&mut self, key: usize) -> Option<P> {
+        self.position(key).map(|i| self.remove_at(i).1)
+    }
+
+    /// Sets the priority of `key`, which may move in either direction, in
+    /// O(log(n)) time. Returns the old priority, or `None` without inserting
+    /// the key if it is not present.
+    pub fn change_priority(&mut self, key: usize, priority: P) -> Option<P> {
+        let i = self.position(key)?;
+        let old = core::mem::replace(&mut self.data[i].1, priority);
+        if O::precedes(&self.data[i].1, &old) {
+            self.sift_up(i);
+        } else {
+            self.sift_down(i);
+        }
+        Some(old)
+    }
+
+    /// Moves `key` closer to the root by setting its priority to `priority`,
+    /// provided it precedes the current one: a smaller priority for a
+    /// `MinIndexedHeap` and a greater one for a `MaxIndexedHeap`. Returns
+    /// whether the priority was changed, which is never the case for absent
+    /// keys. Takes O(log(n)) time.
+    pub fn decrease_key(&mut self, key: usize, priority: P) -> bool {
+        match self.position(key) {
+            Some(i) if O::precedes(&priority, &self.data[i].1) => {
+                self.data[i].1 = priority;
+                self.sift_up(i);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn position(&self, key: usize) -> Option<usize> {
+        self.pos.get(key).copied().flatten()
+    }
+
+    fn remove_at(&mut self, i: usize) -> (usize, P) {
+        let last = self.data.len() - 1;
+        self.swap(i, last);
+        let (key, priority) = self.data.pop().unwrap();
+        self.pos[key] = None;
+        if i < self.data.len() {
+            // The element moved from the end may go either way
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        (key, priority)
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.data.swap(i, j);
+        self.pos[self.data[i].0] = Some(i);
+        self.pos[self.data[j].0] = Some(j);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !O::precedes(&self.data[i].1, &self.data[parent].1) {
+                return;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        let n = self.data.len();
+        loop {
+            let (left, right) = (2 * i + 1, 2 * i + 2);
+            let mut top = i;
+            if left < n && O::precedes(&self.data[left].1, &self.data[top].1) {
+                top = left;
+            }
+            if right < n && O::precedes(&self.data[right].1, &self.data[top].1)
+            {
+                top = right;
+            }
+            if top == i {
+                return;
+            }
+            self.swap(i, top);
+            i = top;
+        }
+    }
+}
+
+impl<P: PartialOrd, O: HeapOrder> Default for IndexedHeap<P, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    fn check<P: PartialOrd, O: HeapOrder>(heap: &IndexedHeap<P, O>) {
+        for (i, (key, _)) in heap.data.iter().enumerate() {
+            assert_eq!(heap.pos[*key], Some(i));
+            if i > 0 {
+                let parent = (i - 1) / 2;
+                assert!(!O::precedes(&heap.data[i].1, &heap.data[parent].1));
+            }
+        }
+        let present = heap.pos.iter().filter(|p| p.is_some()).count();
+        assert_eq!(present, heap.len());
+    }
+
+    #[test]
+    fn push_n_pop() {
+        let mut heap = MaxIndexedHeap::with_capacity(4);
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+
+        for (key, p) in [3, 9, 1, 4, 7, 7, 0].into_iter().enumerate() {
+            assert_eq!(heap.push(key, p), None);
+        }
+        check(&heap);
+        assert_eq!(heap.peek(), Some((1, &9)));
+        assert_eq!(heap.push(1, 2), Some(9));
+        assert_eq!(heap.len(), 7);
+
+        let popped: Vec<_> = core::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped[..3], [(4, 7), (5, 7), (3, 4)]);
+        assert_eq!(popped[3..], [(0, 3), (1, 2), (2, 1), (6, 0)]);
+    }
+
+    #[test]
+    fn change_priority_and_remove() {
+        let mut heap = MinIndexedHeap::new();
+        for key in 0..10 {
+            heap.push(key, key as f64);
+        }
+        assert!(heap.decrease_key(7, -1.0));
+        assert!(!heap.decrease_key(7, 0.5));
+        assert!(!heap.decrease_key(12, 0.0));
+        assert_eq!(heap.priority(7), Some(&-1.0));
+
+        assert_eq!(heap.change_priority(0, 20.0), Some(0.0));
+        assert_eq!(heap.change_priority(12, 0.0), None);
+        assert!(!heap.contains(12));
+        check(&heap);
+
+        assert_eq!(heap.remove(5), Some(5.0));
+        assert_eq!(heap.remove(5), None);
+        check(&heap);
+
+        let keys: Vec<_> =
+            core::iter::from_fn(|| heap.pop().map(|(k, _)| k)).collect();
+        assert_eq!(keys, [7, 1, 2, 3, 4, 6, 8, 9, 0]);
+    }
+
+    #[test]
+    fn random_operations() {
+        let n = 200;
+        let mut heap = MinIndexedHeap::with_capacity(n);
+        let mut expected: Vec<Option<u64>> = vec![None; n];
+        let mut rng = Pcg32::seed(0x853c49e6748fea9b);
+        for _ in 0..10_000 {
+            let key = rng.gen_range(0..n);
+            let p = rng.gen_range(0..1000);
+            match rng.gen_range(0..4) {
+                0 => assert_eq!(heap.remove(key), expected[key].take()),
+                1 => {
+                    let min = expected.iter().flatten().min().copied();
+                    let popped = heap.pop();
+                    assert_eq!(popped.map(|(_, p)| p), min);
+                    if let Some((k, _)) = popped {
+                        expected[k] = None;
+                    }
+                }
+                2 => {
+                    let lower = expected[key].is_some_and(|q| p < q);
+                    assert_eq!(heap.decrease_key(key, p), lower);
+                    if lower {
+                        expected[key] = Some(p);
+                    }
+                }
+                _ => assert_eq!(heap.push(key, p), expected[key].replace(p)),
+            }
+        }
+        check(&heap);
+        for (key, p) in expected.iter().enumerate() {
+            assert_eq!(heap.priority(key), p.as_ref());
+        }
+    }
+}
@@ -0,0 +1,16 @@
+pub mod binary;
+pub mod binomial;
+pub mod fibonacci;
+pub mod indexed;
+
+pub use binary::{BinaryHeap, MaxHeap, MinHeap};
+pub use indexed::{IndexedHeap, MaxIndexedHeap, MinIndexedHeap};
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Distinct identifier for each new heap, carried by its handles so that the
+/// handles of another heap are rejected.
+pub(crate) fn next_heap_id() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
@@ -1,3 +1,22 @@
+//! Algorithms and data structures.
+//!
+//! Without the default `std` feature the crate is `no_std` and only needs
+//! `alloc`, leaving out what depends on the operating system, namely threads
+//! and entropy, and the floating point functions that `core` lacks, such as
+//! square roots and trigonometric functions.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod dp;
+pub mod ds;
+pub mod geometry;
+pub mod graph;
+pub mod heap;
 pub mod list;
 pub mod math;
+pub mod random;
 pub mod sorting;
+pub mod streaming;
+pub mod strings;
+pub mod tree;
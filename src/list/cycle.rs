@@ -0,0 +1,156 @@
+//! Cycle detection over linked structures
+//!
+//! The safe lists of this module own their nodes and thus can never form a
+//! cycle, but structures linked by raw pointers or indices can. These
+//! functions only need a way to step from a node to the next one.
+
+/// Floyd's tortoise and hare: walks the structure starting at `head` with two
+/// pointers, one moving twice as fast as the other. They meet if and only if
+/// the structure has a cycle, otherwise the fast one reaches the end. Runs in
+/// O(n) steps and O(1) space.
+///
+/// Nodes are compared through `PartialEq`, so they should be handles such as
+/// pointers or indices.
+///
+/// Example:
+/// ```
+/// use ralg::list::cycle::has_cycle;
+///
+/// // 0 -> 1 -> 2 -> 3 -> 1
+/// let next = [Some(1), Some(2), Some(3), Some(1)];
+/// assert!(has_cycle(Some(0), |i| next[i]));
+///
+/// // 0 -> 1 -> 2
+/// let next = [Some(1), Some(2), None];
+/// assert!(!has_cycle(Some(0), |i| next[i]));
+/// ```
+pub fn has_cycle<N, F>(head: Option<N>, next: F) -> bool
+where
+    N: Copy + PartialEq,
+    F: FnMut(N) -> Option<N>,
+{
+    meeting_point(head, next).is_some()
+}
+
+/// Position `(mu, lambda)` of the cycle reachable from `head`, if any: `mu`
+/// is the number of steps from `head` to the first node of the cycle, and
+/// `lambda` is the length of the cycle.
+///
+/// Example:
+/// ```
+/// use ralg::list::cycle::find_cycle;
+///
+/// // 0 -> 1 -> 2 -> 3 -> 4 -> 2
+/// let next = [Some(1), Some(2), Some(3), Some(4), Some(2)];
+/// assert_eq!(find_cycle(Some(0), |i| next[i]), Some((2, 3)));
+/// ```
+pub fn find_cycle<N, F>(head: Option<N>, mut next: F) -> Option<(usize, usize)>
+where
+    N: Copy + PartialEq,
+    F: FnMut(N) -> Option<N>,
+{
+    let meeting = meeting_point(head, &mut next)?;
+
+    // The distance from the head to the start of the cycle equals the
+    // distance from the meeting point to it, modulo the cycle length
+    let (mut slow, mut fast) = (head?, meeting);
+    let mut mu = 0;
+    while slow != fast {
+        slow = next(slow)?;
+        fast = next(fast)?;
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    let mut node = next(slow)?;
+    while node != slow {
+        node = next(node)?;
+        lambda += 1;
+    }
+    Some((mu, lambda))
+}
+
+/// Node where the tortoise and the hare meet, if the structure has a cycle.
+fn meeting_point<N, F>(head: Option<N>, mut next: F) -> Option<N>
+where
+    N: Copy + PartialEq,
+    F: FnMut(N) -> Option<N>,
+{
+    let (mut slow, mut fast) = (head?, head?);
+    loop {
+        slow = next(slow)?;
+        let hop = next(fast)?;
+        fast = next(hop)?;
+        if slow == fast {
+            return Some(slow);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::ptr;
+
+    struct Node {
+        next: *mut Node,
+    }
+
+    /// Raw pointer list of `n` nodes whose last node points back to the node
+    /// at `back_to`, if any.
+    fn raw_list(n: usize, back_to: Option<usize>) -> Vec<*mut Node> {
+        let nodes: Vec<*mut Node> = (0..n)
+            .map(|_| {
+                Box::into_raw(Box::new(Node {
+                    next: ptr::null_mut(),
+                }))
+            })
+            .collect();
+        unsafe {
+            for pair in nodes.windows(2) {
+                (*pair[0]).next = pair[1];
+            }
+            if let Some(j) = back_to {
+                let last = nodes[n - 1];
+                (*last).next = nodes[j];
+            }
+        }
+        nodes
+    }
+
+    fn free(nodes: Vec<*mut Node>) {
+        for node in nodes {
+            unsafe { drop(Box::from_raw(node)) };
+        }
+    }
+
+    fn step(node: *const Node) -> Option<*const Node> {
+        let next = unsafe { (*node).next };
+        (!next.is_null()).then_some(next as *const Node)
+    }
+
+    #[test]
+    fn raw_pointers() {
+        for n in 1..8 {
+            let nodes = raw_list(n, None);
+            assert!(!has_cycle(Some(nodes[0] as *const Node), step));
+            assert_eq!(find_cycle(Some(nodes[0] as *const Node), step), None);
+            free(nodes);
+
+            for j in 0..n {
+                let nodes = raw_list(n, Some(j));
+                let head = Some(nodes[0] as *const Node);
+                assert!(has_cycle(head, step));
+                assert_eq!(find_cycle(head, step), Some((j, n - j)));
+                free(nodes);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_and_self_loop() {
+        assert!(!has_cycle(None, |i: usize| Some(i)));
+        assert!(has_cycle(Some(0), Some));
+        assert_eq!(find_cycle(Some(0), Some), Some((0, 1)));
+    }
+}
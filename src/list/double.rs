@@ -1,89 +1,90 @@
-pub(crate) use std::cell::{Ref, RefCell, RefMut};
-use std::rc::Rc;
+use crate::sorting::partial_cmp;
+use alloc::boxed::Box;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
 
-// Each node should have exactly two pointers to it. Each node in the middle of
-// the list is pointed at by its predecessor and successor, while the nodes on
-// the ends are pointed to by the list itself.
+// Each node is pointed at by its predecessor and successor, while the nodes on
+// the ends are pointed to by the list itself. The list owns every node, which
+// are allocated as boxes and freed when popped or when the list is dropped.
+// Links are `NonNull` rather than `*mut` so that the list is covariant in `T`,
+// like `Box<T>`, and the marker tells the drop checker that dropping the list
+// drops keys of type `T`.
 //
-// `head`: has an empty prev and some next;
-// `tail`: has some next and an empty prev;
+// `head`: has no prev and, unless it is also the tail, some next;
+// `tail`: has no next and, unless it is also the head, some prev;
 pub struct DoubleLinked<T> {
     head: List<T>,
     tail: List<T>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
 }
 
+type List<T> = Option<NonNull<Node<T>>>;
+
+// The list owns its keys and hands out references to them only through `&self`
+// and `&mut self`, just like `Box<T>`, so it can be sent or shared across
+// threads whenever the keys can.
+unsafe impl<T: Send> Send for DoubleLinked<T> {}
+unsafe impl<T: Sync> Sync for DoubleLinked<T> {}
+
 impl<T> DoubleLinked<T> {
     pub fn new() -> Self {
         Self {
             head: None,
             tail: None,
+            len: 0,
+            marker: PhantomData,
         }
     }
 
     pub fn push_front(&mut self, key: T) {
-        let new_head = Node::new(key);
-        match self.head.take() {
-            Some(last_head) => {
-                // Cloning an Rc just creates a new pointer to the same object
-                last_head.borrow_mut().prev = Some(new_head.clone());
-                new_head.borrow_mut().next = Some(last_head);
-                self.head = Some(new_head);
-            }
-            None => {
-                self.head = Some(new_head.clone());
-                self.tail = Some(new_head);
-            }
+        let new_head = Node::new(key, None, self.head);
+        match self.head {
+            // The head is a valid node owned by the list, and no reference to
+            // it outlives a borrow of the list
+            Some(head) => unsafe { (*head.as_ptr()).prev = Some(new_head) },
+            None => self.tail = Some(new_head),
         }
+        self.head = Some(new_head);
+        self.len += 1;
     }
 
     pub fn pop_front(&mut self) -> Option<T> {
-        // Pop only when the list is nonempty
-        self.head.take().map(|last_head| {
-            // Check for the node head points to (next), the next node will
-            // become the new head of the list
-            match last_head.borrow_mut().next.take() {
-                Some(new_head) => {
-                    // Empty the previous node of the new head (which is the
-                    // last head) and update the head
-                    new_head.borrow_mut().prev.take();
-                    self.head = Some(new_head);
-                }
-                None => {
-                    // head was the only node, remove the last pointer to it
-                    self.tail.take();
-                }
+        self.head.map(|head| {
+            // We create a box in order to correctly deallocate `last_head`
+            let last_head = unsafe { Box::from_raw(head.as_ptr()) };
+            self.head = last_head.next;
+            match self.head {
+                Some(head) => unsafe { (*head.as_ptr()).prev = None },
+                // head was the only node
+                None => self.tail = None,
             }
-            Rc::try_unwrap(last_head).ok().unwrap().into_inner().key
+            self.len -= 1;
+            last_head.key
         })
     }
 
     pub fn push_back(&mut self, key: T) {
-        let new_tail = Node::new(key);
-        match self.tail.take() {
-            Some(last_tail) => {
-                last_tail.borrow_mut().next = Some(new_tail.clone());
-                new_tail.borrow_mut().prev = Some(last_tail);
-                self.tail = Some(new_tail);
-            }
-            None => {
-                self.tail = Some(new_tail.clone());
-                self.head = Some(new_tail);
-            }
+        let new_tail = Node::new(key, self.tail, None);
+        match self.tail {
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(new_tail) },
+            None => self.head = Some(new_tail),
         }
+        self.tail = Some(new_tail);
+        self.len += 1;
     }
 
     pub fn pop_back(&mut self) -> Option<T> {
-        self.tail.take().map(|last_tail| {
-            match last_tail.borrow_mut().prev.take() {
-                Some(new_tail) => {
-                    new_tail.borrow_mut().next = None;
-                    self.tail = Some(new_tail);
-                }
-                None => {
-                    self.head.take();
-                }
+        self.tail.map(|tail| {
+            let last_tail = unsafe { Box::from_raw(tail.as_ptr()) };
+            self.tail = last_tail.prev;
+            match self.tail {
+                Some(tail) => unsafe { (*tail.as_ptr()).next = None },
+                None => self.head = None,
             }
-            Rc::try_unwrap(last_tail).ok().unwrap().into_inner().key
+            self.len -= 1;
+            last_tail.key
         })
     }
 
@@ -91,43 +92,371 @@ impl<T> DoubleLinked<T> {
         self.head.is_none()
     }
 
-    // This is not a good peek because we return an option to a Ref<T>, which
-    // holds the key, but there is no turn around for that
-    pub fn peek_front(&self) -> Option<Ref<T>> {
-        self.head
-            .as_ref()
-            .map(|node| Ref::map(node.borrow(), |node| &node.key))
+    /// Number of keys in the list, tracked on every push and pop.
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    pub fn peek_back(&self) -> Option<Ref<T>> {
-        self.tail
-            .as_ref()
-            .map(|node| Ref::map(node.borrow(), |node| &node.key))
+    pub fn peek_front(&self) -> Option<&T> {
+        unsafe { self.head.map(|node| &(*node.as_ptr()).key) }
     }
 
-    pub fn peek_front_mut(&mut self) -> Option<RefMut<T>> {
-        self.head
-            .as_ref()
-            .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.key))
+    pub fn peek_back(&self) -> Option<&T> {
+        unsafe { self.tail.map(|node| &(*node.as_ptr()).key) }
     }
 
-    pub fn peek_back_mut(&mut self) -> Option<RefMut<T>> {
-        self.tail
-            .as_ref()
-            .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.key))
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.head.map(|node| &mut (*node.as_ptr()).key) }
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.tail.map(|node| &mut (*node.as_ptr()).key) }
+    }
+
+    /// Moves every node of `other` to the back of the list in O(1), leaving
+    /// `other` empty.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::list::double::DoubleLinked;
+    ///
+    /// let mut a: DoubleLinked<i32> = (0..2).collect();
+    /// let mut b: DoubleLinked<i32> = (2..4).collect();
+    /// a.append(&mut b);
+    /// assert!(b.is_empty());
+    /// assert_eq!(a.into_iter().collect::<Vec<_>>(), [0, 1, 2, 3]);
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        let (tail, other_head) = match (self.tail, other.head) {
+            (None, _) => return core::mem::swap(self, other),
+            (_, None) => return,
+            (Some(tail), Some(other_head)) => (tail, other_head),
+        };
+
+        unsafe {
+            (*tail.as_ptr()).next = Some(other_head);
+            (*other_head.as_ptr()).prev = Some(tail);
+        }
+        self.tail = other.tail;
+        self.len += other.len;
+
+        other.head = None;
+        other.tail = None;
+        other.len = 0;
+    }
+
+    /// Pushes `key` to the front, returning a handle to its node.
+    pub(crate) fn push_front_node(&mut self, key: T) -> NodeRef<T> {
+        self.push_front(key);
+        NodeRef(self.head.expect("pushed node"))
+    }
+
+    /// Handle to the back node, if any.
+    pub(crate) fn back_node(&self) -> Option<NodeRef<T>> {
+        self.tail.map(NodeRef)
+    }
+
+    /// Unlinks `node` from its neighbours and from the ends of the list.
+    ///
+    /// Safety: `node` must be a node of this list.
+    unsafe fn detach(&mut self, node: NonNull<Node<T>>) {
+        let (prev, next) = ((*node.as_ptr()).prev, (*node.as_ptr()).next);
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).prev = prev,
+            None => self.tail = prev,
+        }
+        self.len -= 1;
+    }
+
+    /// Removes the node of `node` in O(1), returning its key.
+    ///
+    /// Safety: `node` must be a node of this list.
+    pub(crate) unsafe fn remove_node(&mut self, node: NodeRef<T>) -> T {
+        self.detach(node.0);
+        Box::from_raw(node.0.as_ptr()).key
+    }
+
+    /// Moves the node of `node` to the front in O(1).
+    ///
+    /// Safety: `node` must be a node of this list.
+    pub(crate) unsafe fn move_to_front(&mut self, node: NodeRef<T>) {
+        let Some(head) = self.head.filter(|&head| head != node.0) else {
+            return;
+        };
+        // Not being the head, the node leaves a non-empty list behind
+        self.detach(node.0);
+        (*node.0.as_ptr()).prev = None;
+        (*node.0.as_ptr()).next = Some(head);
+        (*head.as_ptr()).prev = Some(node.0);
+        self.head = Some(node.0);
+        self.len += 1;
+    }
+
+    /// Splits the list at index `at`, returning the keys from `at` onwards
+    /// as a new list. The split node is reached from the closest end, so this
+    /// takes O(min(at, len - at)). Panics if `at > len`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::list::double::DoubleLinked;
+    ///
+    /// let mut list: DoubleLinked<i32> = (0..5).collect();
+    /// let suffix = list.split_off(3);
+    /// assert_eq!(list.into_iter().collect::<Vec<_>>(), [0, 1, 2]);
+    /// assert_eq!(suffix.into_iter().collect::<Vec<_>>(), [3, 4]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "cannot split off at a nonexistent index");
+        if at == 0 {
+            return core::mem::take(self);
+        }
+
+        // Split after the node at index `at - 1`
+        let mut cursor = if at - 1 < self.len / 2 {
+            let mut cursor = self.cursor_front_mut();
+            for _ in 0..at - 1 {
+                cursor.move_next();
+            }
+            cursor
+        } else {
+            let mut cursor = self.cursor_back_mut();
+            for _ in at..cursor.list.len {
+                cursor.move_prev();
+            }
+            cursor
+        };
+        cursor.split_after()
+    }
+
+    /// Sorts the list in ascending order, see `sort_by`.
+    pub fn sort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        self.sort_by(partial_cmp)
+    }
+
+    /// Sorts the list with respect to `cmp` by a stable bottom-up merge sort
+    /// acting directly on the links between nodes, in O(n log n) comparisons
+    /// and O(1) extra space. Runs are merged through their `next` links only,
+    /// the `prev` links being restored in a final pass.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::list::double::DoubleLinked;
+    ///
+    /// let mut list: DoubleLinked<i32> = vec![3, 1, 2].into_iter().collect();
+    /// list.sort();
+    /// assert_eq!(list.iter().rev().collect::<Vec<_>>(), [&3, &2, &1]);
+    /// ```
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut cmp: F) {
+        unsafe {
+            let mut width = 1;
+            while width < self.len {
+                let mut rest = self.head;
+                let mut tail: List<T> = None;
+                while let Some(a) = rest {
+                    let b = cut(a, width);
+                    rest = b.and_then(|b| cut(b, width));
+                    let (first, last) = merge(Some(a), b, &mut cmp);
+                    match tail {
+                        Some(tail) => (*tail.as_ptr()).next = first,
+                        None => self.head = first,
+                    }
+                    tail = last;
+                }
+                width *= 2;
+            }
+
+            let mut prev = None;
+            let mut node = self.head;
+            while let Some(current) = node {
+                (*current.as_ptr()).prev = prev;
+                prev = node;
+                node = (*current.as_ptr()).next;
+            }
+            self.tail = prev;
+        }
+    }
+
+    /// Cursor pointing at the front of the list, or at the "ghost" position if
+    /// the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let current = self.head;
+        CursorMut {
+            list: self,
+            current,
+            index: current.map(|_| 0),
+        }
+    }
+
+    /// Cursor pointing at the back of the list, or at the "ghost" position if
+    /// the list is empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let current = self.tail;
+        let index = self.len.checked_sub(1);
+        CursorMut {
+            list: self,
+            current,
+            index,
+        }
+    }
+
+    /// Iterates over references to the keys, from front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterates over mutable references to the keys, from front to back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
     }
 }
 
+/// Cuts the `next` links after the first `n` nodes starting at `list`,
+/// returning the remaining nodes.
+unsafe fn cut<T>(list: NonNull<Node<T>>, n: usize) -> List<T> {
+    let mut node = list;
+    for _ in 1..n {
+        match (*node.as_ptr()).next {
+            Some(next) => node = next,
+            None => return None,
+        }
+    }
+    (*node.as_ptr()).next.take()
+}
+
+/// Merges the sorted runs `a` and `b`, linked through `next` and ending at a
+/// `None` link, returning the first and last nodes of the result. Ties are
+/// taken from `a`, so that merging is stable.
+unsafe fn merge<T, F: FnMut(&T, &T) -> Ordering>(
+    mut a: List<T>,
+    mut b: List<T>,
+    cmp: &mut F,
+) -> (List<T>, List<T>) {
+    let mut first: List<T> = None;
+    let mut last: List<T> = None;
+    loop {
+        let node = match (a, b) {
+            (None, None) => break,
+            (Some(x), Some(y))
+                if cmp(&(*y.as_ptr()).key, &(*x.as_ptr()).key)
+                    == Ordering::Less =>
+            {
+                b = (*y.as_ptr()).next;
+                y
+            }
+            (Some(x), _) => {
+                a = (*x.as_ptr()).next;
+                x
+            }
+            (None, Some(y)) => {
+                b = (*y.as_ptr()).next;
+                y
+            }
+        };
+
+        match last {
+            Some(last) => (*last.as_ptr()).next = Some(node),
+            None => first = Some(node),
+        }
+        last = Some(node);
+    }
+    (first, last)
+}
+
 impl<T> Default for DoubleLinked<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl<T> Drop for DoubleLinked<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T> IntoIterator for DoubleLinked<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DoubleLinked<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DoubleLinked<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for DoubleLinked<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DoubleLinked::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for DoubleLinked<T> {
+    /// Pushes every key of `iter` to the back of the list.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for key in iter {
+            self.push_back(key);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for DoubleLinked<T> {
+    /// Serializes the keys as a sequence, from front to back.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for DoubleLinked<T>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        alloc::vec::Vec::deserialize(deserializer).map(Self::from_iter)
+    }
+}
+
 pub struct IntoIter<T>(DoubleLinked<T>);
 
 impl<T> Iterator for IntoIter<T> {
@@ -144,27 +473,320 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
     }
 }
 
-type List<T> = Option<Rc<RefCell<Node<T>>>>;
+/// Handle to a node of a `DoubleLinked` list, so that other structures can
+/// reach it in O(1). It is only valid while the node stays in its list, and
+/// compares equal to the handles of the same node.
+pub(crate) struct NodeRef<T>(NonNull<Node<T>>);
+
+impl<T> NodeRef<T> {
+    /// Safety: the node must still be in its list, which must outlive `'a`
+    /// without being mutated.
+    pub(crate) unsafe fn key<'a>(self) -> &'a T {
+        &(*self.0.as_ptr()).key
+    }
+
+    /// Safety: the node must still be in its list, which must outlive `'a`
+    /// without being otherwise accessed.
+    pub(crate) unsafe fn key_mut<'a>(self) -> &'a mut T {
+        &mut (*self.0.as_ptr()).key
+    }
+}
+
+impl<T> Clone for NodeRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeRef<T> {}
+
+impl<T> PartialEq for NodeRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for NodeRef<T> {}
+
+/// Cursor over a `DoubleLinked` list that can move in both directions and
+/// edit the list around its position in O(1).
+///
+/// Besides the nodes of the list, the cursor may point at a "ghost" position
+/// sitting between the back and the front of the list: moving next from the
+/// back, or previous from the front, reaches the ghost, and moving once more
+/// wraps around to the other end.
+///
+/// Example:
+/// ```
+/// use ralg::list::double::DoubleLinked;
+///
+/// let mut list: DoubleLinked<i32> = vec![1, 2, 4].into_iter().collect();
+/// let mut cursor = list.cursor_front_mut();
+/// cursor.move_next();
+/// cursor.insert_after(3);
+/// assert_eq!(cursor.remove_current(), Some(2));
+/// assert_eq!(cursor.current(), Some(&mut 3));
+/// assert_eq!(list.into_iter().collect::<Vec<_>>(), [1, 3, 4]);
+/// ```
+pub struct CursorMut<'a, T> {
+    list: &'a mut DoubleLinked<T>,
+    // `None` at the ghost position
+    current: List<T>,
+    index: Option<usize>,
+}
+
+// The cursor stands for a mutable borrow of the list.
+unsafe impl<T: Send> Send for CursorMut<'_, T> {}
+unsafe impl<T: Sync> Sync for CursorMut<'_, T> {}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Position of the cursor in the list, or `None` at the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Key the cursor points at, or `None` at the ghost position.
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.current.map(|node| &mut (*node.as_ptr()).key) }
+    }
+
+    /// Moves to the next node, going from the back to the ghost position and
+    /// from the ghost position to the front.
+    pub fn move_next(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.head;
+                self.index = self.current.map(|_| 0);
+            }
+            Some(current) => {
+                self.current = unsafe { (*current.as_ptr()).next };
+                self.index = match self.current {
+                    None => None,
+                    Some(_) => self.index.map(|i| i + 1),
+                };
+            }
+        }
+    }
+
+    /// Moves to the previous node, going from the front to the ghost position
+    /// and from the ghost position to the back.
+    pub fn move_prev(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.checked_sub(1);
+            }
+            Some(current) => {
+                self.current = unsafe { (*current.as_ptr()).prev };
+                self.index = match self.current {
+                    None => None,
+                    Some(_) => self.index.map(|i| i - 1),
+                };
+            }
+        }
+    }
+
+    /// Inserts `key` before the current node. At the ghost position, `key`
+    /// is pushed to the back of the list.
+    pub fn insert_before(&mut self, key: T) {
+        let Some(current) = self.current else {
+            return self.list.push_back(key);
+        };
+
+        unsafe {
+            let prev = (*current.as_ptr()).prev;
+            let new = Node::new(key, prev, Some(current));
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(new),
+                None => self.list.head = Some(new),
+            }
+            (*current.as_ptr()).prev = Some(new);
+        }
+        self.list.len += 1;
+        self.index = self.index.map(|i| i + 1);
+    }
+
+    /// Inserts `key` after the current node. At the ghost position, `key` is
+    /// pushed to the front of the list.
+    pub fn insert_after(&mut self, key: T) {
+        let Some(current) = self.current else {
+            return self.list.push_front(key);
+        };
+
+        unsafe {
+            let next = (*current.as_ptr()).next;
+            let new = Node::new(key, Some(current), next);
+            match next {
+                Some(next) => (*next.as_ptr()).prev = Some(new),
+                None => self.list.tail = Some(new),
+            }
+            (*current.as_ptr()).next = Some(new);
+        }
+        self.list.len += 1;
+    }
+
+    /// Removes the current node and returns its key, the cursor then points
+    /// at the following node. Nothing happens at the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let current = self.current?;
+
+        unsafe {
+            let node = Box::from_raw(current.as_ptr());
+            match node.prev {
+                Some(prev) => (*prev.as_ptr()).next = node.next,
+                None => self.list.head = node.next,
+            }
+            match node.next {
+                Some(next) => (*next.as_ptr()).prev = node.prev,
+                None => {
+                    self.list.tail = node.prev;
+                    self.index = None;
+                }
+            }
+
+            self.current = node.next;
+            self.list.len -= 1;
+            Some(node.key)
+        }
+    }
+
+    /// Splits the list after the current node, returning the nodes that
+    /// follow it as a new list. At the ghost position, the whole list is
+    /// moved out.
+    pub fn split_after(&mut self) -> DoubleLinked<T> {
+        let (Some(current), Some(index)) = (self.current, self.index) else {
+            return core::mem::take(self.list);
+        };
+
+        let mut suffix = DoubleLinked::new();
+        unsafe {
+            if let Some(next) = (*current.as_ptr()).next.take() {
+                (*next.as_ptr()).prev = None;
+                suffix.head = Some(next);
+                suffix.tail = self.list.tail;
+                suffix.len = self.list.len - index - 1;
+                self.list.tail = Some(current);
+                self.list.len = index + 1;
+            }
+        }
+        suffix
+    }
+}
+
+// Both ends of the borrowing iterators walk towards each other, `len` counts
+// the keys left between them so that they stop once they meet.
+pub struct Iter<'a, T> {
+    head: List<T>,
+    tail: List<T>,
+    len: usize,
+    marker: PhantomData<&'a T>,
+}
+
+// The iterators stand for `&'a T` and `&'a mut T`, and are sent and shared
+// whenever those are.
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            self.len -= 1;
+            self.head = node.next;
+            &node.key
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| unsafe {
+            let node = &*node.as_ptr();
+            self.len -= 1;
+            self.tail = node.prev;
+            &node.key
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    head: List<T>,
+    tail: List<T>,
+    len: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            self.len -= 1;
+            self.head = node.next;
+            &mut node.key
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| unsafe {
+            let node = &mut *node.as_ptr();
+            self.len -= 1;
+            self.tail = node.prev;
+            &mut node.key
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
 
 struct Node<T> {
     key: T,
-    next: List<T>,
     prev: List<T>,
+    next: List<T>,
 }
 
 impl<T> Node<T> {
-    fn new(key: T) -> Rc<RefCell<Self>> {
-        Rc::new(RefCell::new(Self {
-            key,
-            next: None,
-            prev: None,
-        }))
+    /// Allocates a new node linked to `prev` and `next`, the caller is
+    /// responsible for linking them back to it.
+    fn new(key: T, prev: List<T>, next: List<T>) -> NonNull<Node<T>> {
+        NonNull::from(Box::leak(Box::new(Self { key, prev, next })))
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::list::tracked::DropCounter;
 
     #[test]
     fn front_push_n_pop() {
@@ -211,22 +833,17 @@ mod test {
     fn peek_front() {
         let mut list = DoubleLinked::new();
 
-        // We cant test the Option<Ref<'_, T>> for equality directly
-        let check_empty = |node: Option<Ref<'_, i32>>| match node {
-            Some(_) => assert!(false),
-            None => assert!(true),
-        };
-        check_empty(list.peek_front());
+        assert_eq!(list.peek_front(), None);
 
         for x in 0..3 {
             list.push_front(x);
         }
         for x in (0..3).rev() {
-            assert_eq!(&*list.peek_front().unwrap(), &x);
+            assert_eq!(list.peek_front(), Some(&x));
             list.pop_front();
         }
 
-        check_empty(list.peek_front());
+        assert_eq!(list.peek_front(), None);
     }
 
     #[test]
@@ -238,7 +855,7 @@ mod test {
             list.push_back(x);
         }
         for x in (0..3).rev() {
-            assert_eq!(&*list.peek_back().unwrap(), &x);
+            assert_eq!(list.peek_back(), Some(&x));
             list.pop_back();
         }
 
@@ -254,19 +871,16 @@ mod test {
             list.push_front(x);
         }
         for mut x in (1..4).rev() {
-            assert_eq!(&*list.peek_front_mut().unwrap(), &mut x);
+            assert_eq!(list.peek_front_mut(), Some(&mut x));
             list.pop_front();
         }
-        assert_eq!(&*list.peek_front_mut().unwrap(), &mut 0);
+        assert_eq!(list.peek_front_mut(), Some(&mut 0));
 
-        {
-            // Check for the mutability of the peek method
-            let bm = list.peek_front_mut().unwrap();
-            let mut node_key = RefMut::map(bm, |key| key);
-            assert_eq!(*node_key, 0);
-            *node_key = 5;
-        }
-        assert_eq!(&*list.peek_front_mut().unwrap(), &mut 5);
+        // Check for the mutability of the peek method
+        let key = list.peek_front_mut().unwrap();
+        assert_eq!(*key, 0);
+        *key = 5;
+        assert_eq!(list.peek_front_mut(), Some(&mut 5));
     }
 
     #[test]
@@ -278,19 +892,16 @@ mod test {
             list.push_back(x);
         }
         for mut x in (1..4).rev() {
-            assert_eq!(&*list.peek_back_mut().unwrap(), &mut x);
+            assert_eq!(list.peek_back_mut(), Some(&mut x));
             list.pop_back();
         }
-        assert_eq!(&*list.peek_back_mut().unwrap(), &mut 0);
+        assert_eq!(list.peek_back_mut(), Some(&mut 0));
 
-        {
-            // Check for the mutability of the peek method
-            let bm = list.peek_back_mut().unwrap();
-            let mut node_key = RefMut::map(bm, |key| key);
-            assert_eq!(*node_key, 0);
-            *node_key = 5;
-        }
-        assert_eq!(&*list.peek_back_mut().unwrap(), &mut 5);
+        // Check for the mutability of the peek method
+        let key = list.peek_back_mut().unwrap();
+        assert_eq!(*key, 0);
+        *key = 5;
+        assert_eq!(list.peek_back_mut(), Some(&mut 5));
     }
 
     #[test]
@@ -317,4 +928,378 @@ mod test {
         }
         assert!(iter.next().is_none());
     }
+
+    #[test]
+    fn len() {
+        let mut list = DoubleLinked::new();
+        assert_eq!(list.len(), 0);
+        list.push_back(1);
+        list.push_front(0);
+        list.push_back(2);
+        assert_eq!(list.len(), 3);
+        list.pop_back();
+        list.pop_front();
+        assert_eq!(list.len(), 1);
+        list.pop_front();
+        list.pop_front();
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn iter() {
+        let list: DoubleLinked<i32> = (0..5).collect();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            [4, 3, 2, 1, 0]
+        );
+        assert_eq!(list.iter().len(), 5);
+
+        // Both ends meet in the middle
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+
+        // The list is left untouched
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.peek_front(), Some(&0));
+
+        let empty: DoubleLinked<i32> = DoubleLinked::new();
+        assert_eq!(empty.iter().next(), None);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut list: DoubleLinked<i32> = (0..4).collect();
+        for key in list.iter_mut() {
+            *key *= 10;
+        }
+        if let Some(key) = list.iter_mut().next_back() {
+            *key += 1;
+        }
+        for key in &mut list {
+            *key += 1;
+        }
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), [1, 11, 21, 32]);
+    }
+
+    #[test]
+    fn extend() {
+        let mut list = DoubleLinked::new();
+        list.push_front(0);
+        list.extend(vec![1, 2]);
+        list.extend(3..5);
+        assert_eq!(list.len(), 5);
+        assert_eq!((&list).into_iter().sum::<i32>(), 10);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn cursor_moves() {
+        let mut list: DoubleLinked<i32> = (0..3).collect();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 0)));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 2)));
+
+        // Wrap around through the ghost position
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 2)));
+
+        *cursor.current().unwrap() = 7;
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 1)));
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), [0, 1, 7]);
+
+        let mut empty: DoubleLinked<i32> = DoubleLinked::new();
+        let mut cursor = empty.cursor_front_mut();
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn cursor_insert() {
+        let mut list: DoubleLinked<i32> = vec![1, 3].into_iter().collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(0);
+        cursor.insert_after(2);
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_after(4);
+
+        // Ghost position inserts at the ends
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_before(5);
+        cursor.insert_after(-1);
+        assert_eq!(cursor.index(), None);
+
+        assert_eq!(list.len(), 7);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            [-1, 0, 1, 2, 3, 4, 5]
+        );
+        assert_eq!(list.iter().rev().count(), 7);
+        assert_eq!(list.peek_back(), Some(&5));
+    }
+
+    #[test]
+    fn cursor_remove() {
+        let mut list: DoubleLinked<i32> = (0..5).collect();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(0));
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 1)));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(2));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), [3, 1]);
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(3));
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert!(list.is_empty());
+        assert!(list.peek_back().is_none());
+    }
+
+    #[test]
+    fn cursor_split_after() {
+        let mut list: DoubleLinked<i32> = (0..5).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let suffix = cursor.split_after();
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(suffix.len(), 3);
+        assert_eq!(suffix.iter().rev().copied().collect::<Vec<_>>(), [4, 3, 2]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.peek_back(), Some(&1));
+
+        // Splitting after the back yields an empty list
+        let mut cursor = list.cursor_back_mut();
+        assert!(cursor.split_after().is_empty());
+
+        // Splitting at the ghost position moves everything
+        cursor.move_next();
+        let all = cursor.split_after();
+        assert!(list.is_empty());
+        assert_eq!(all.into_iter().collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn sort() {
+        for n in 0..20 {
+            let xs: Vec<i32> = (0..n).map(|i| (i * 7 + 3) % 11).collect();
+            let mut list: DoubleLinked<i32> = xs.iter().copied().collect();
+            list.sort();
+
+            let mut expected = xs.clone();
+            expected.sort();
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+            expected.reverse();
+            assert_eq!(
+                list.iter().rev().copied().collect::<Vec<_>>(),
+                expected
+            );
+            assert_eq!(list.len(), n as usize);
+        }
+
+        // Stability, and the list remains usable from both ends
+        let pairs = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')];
+        let mut list: DoubleLinked<(i32, char)> = pairs.into_iter().collect();
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+        list.push_back((3, 'f'));
+        list.push_front((-1, 'g'));
+        let tags: String = list.iter().map(|p| p.1).collect();
+        assert_eq!(tags, "gebdacf");
+        assert_eq!(list.pop_back(), Some((3, 'f')));
+        assert_eq!(list.peek_back(), Some(&(2, 'c')));
+    }
+
+    #[test]
+    fn append() {
+        let mut a: DoubleLinked<i32> = (0..3).collect();
+        let mut b: DoubleLinked<i32> = (3..5).collect();
+        a.append(&mut b);
+        assert_eq!(a.len(), 5);
+        assert_eq!((b.len(), b.peek_front(), b.peek_back()), (0, None, None));
+        assert_eq!(
+            a.iter().rev().copied().collect::<Vec<_>>(),
+            [4, 3, 2, 1, 0]
+        );
+
+        // Appending to and from empty lists
+        a.append(&mut b);
+        assert_eq!(a.len(), 5);
+        b.append(&mut a);
+        assert!(a.is_empty());
+        assert_eq!(b.len(), 5);
+
+        // The appended nodes remain usable from both ends
+        b.push_back(5);
+        assert_eq!(b.pop_front(), Some(0));
+        assert_eq!(b.into_iter().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn split_off() {
+        for at in 0..=6 {
+            let mut list: DoubleLinked<usize> = (0..6).collect();
+            let suffix = list.split_off(at);
+            assert_eq!((list.len(), suffix.len()), (at, 6 - at));
+            assert_eq!(
+                list.iter().copied().collect::<Vec<_>>(),
+                Vec::from_iter(0..at)
+            );
+            assert_eq!(
+                suffix.iter().rev().copied().collect::<Vec<_>>(),
+                Vec::from_iter((at..6).rev())
+            );
+        }
+
+        let mut list: DoubleLinked<i32> = DoubleLinked::new();
+        assert!(list.split_off(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        let mut list: DoubleLinked<i32> = (0..3).collect();
+        list.split_off(4);
+    }
+
+    #[test]
+    fn drops_every_node() {
+        let counter = DropCounter::new();
+        let mut list = DoubleLinked::new();
+        for key in 0..10 {
+            list.push_back(counter.track(key));
+            list.push_front(counter.track(key + 10));
+        }
+        // Popped keys are owned by the caller
+        let front = list.pop_front();
+        drop(list.pop_back());
+        assert_eq!(counter.drops(), 1);
+        drop(list);
+        assert_eq!(counter.drops(), 19);
+        drop(front);
+        assert_eq!(counter.drops(), 20);
+
+        // Partially consumed iterators drop the remaining nodes
+        let list: DoubleLinked<_> = (0..10).map(|k| counter.track(k)).collect();
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next().map(|t| t.key), Some(0));
+        assert_eq!(iter.next_back().map(|t| t.key), Some(9));
+        drop(iter);
+        assert_eq!(counter.drops(), 30);
+    }
+
+    #[test]
+    fn no_leaks_when_relinking() {
+        let counter = DropCounter::new();
+        let mut a: DoubleLinked<_> = (0..8).map(|k| counter.track(k)).collect();
+        let mut b: DoubleLinked<_> =
+            (8..16).rev().map(|k| counter.track(k)).collect();
+        a.append(&mut b);
+        let mut suffix = a.split_off(5);
+        suffix.sort();
+        a.append(&mut suffix);
+        a.sort_by(|x, y| y.cmp(x));
+        assert_eq!(counter.drops(), 0);
+
+        let mut cursor = a.cursor_front_mut();
+        cursor.move_next();
+        assert!(cursor.remove_current().is_some());
+        cursor.insert_before(counter.track(16));
+        cursor.insert_after(counter.track(17));
+        let rest = cursor.split_after();
+        assert_eq!(counter.drops(), 1);
+        drop(rest);
+        drop(a);
+        drop(b);
+        assert_eq!(counter.drops(), 18);
+    }
+
+    #[test]
+    fn drops_long_lists() {
+        // Dropping node by node, without recursion that overflows the stack
+        let list: DoubleLinked<u32> = (0..1_000_000).collect();
+        assert_eq!(list.len(), 1_000_000);
+        drop(list);
+
+        let counter = DropCounter::new();
+        let list: DoubleLinked<_> =
+            (0..100_000).map(|k| counter.track(k)).collect();
+        drop(list);
+        assert_eq!(counter.drops(), 100_000);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<DoubleLinked<i32>>();
+        assert_send_sync::<Iter<'_, String>>();
+        assert_send_sync::<IterMut<'_, String>>();
+        assert_send_sync::<IntoIter<String>>();
+        assert_send_sync::<CursorMut<'_, String>>();
+
+        let mut list: DoubleLinked<String> =
+            ["b", "c"].into_iter().map(String::from).collect();
+        let list = std::thread::spawn(move || {
+            list.push_front(String::from("a"));
+            list
+        })
+        .join()
+        .unwrap();
+        // Shared between threads through references
+        std::thread::scope(|s| {
+            for _ in 0..2 {
+                s.spawn(|| assert_eq!(list.iter().rev().count(), 3));
+            }
+        });
+        assert_eq!(list.into_iter().collect::<String>(), "abc");
+    }
+
+    #[test]
+    fn variance() {
+        // Compiles only if the list and its shared iterator are covariant
+        fn shorten<'a>(
+            list: DoubleLinked<&'static str>,
+        ) -> DoubleLinked<&'a str> {
+            list
+        }
+        fn shorten_iter<'a, 'b>(
+            iter: Iter<'b, &'static str>,
+        ) -> Iter<'b, &'a str> {
+            iter
+        }
+
+        let statics: DoubleLinked<&'static str> =
+            ["x", "y"].into_iter().collect();
+        let local = String::from("z");
+        let mut list = shorten(statics.iter().copied().collect());
+        list.push_back(&local);
+        let keys = shorten_iter(statics.iter()).chain(list.iter().rev());
+        assert_eq!(keys.copied().collect::<String>(), "xyzyx");
+    }
 }
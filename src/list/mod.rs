@@ -1,4 +1,11 @@
+pub mod cycle;
 pub mod double;
 pub mod persistent;
 pub mod queue;
+pub mod ring;
 pub mod single;
+pub mod skiplist;
+pub mod stack;
+
+#[cfg(test)]
+mod tracked;
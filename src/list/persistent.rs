@@ -1,25 +1,37 @@
-use std::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
+use core::fmt;
 
-pub struct Persistant<T> {
+/// Immutable singly linked list whose nodes are shared between the lists
+/// built from one another, through atomically reference counted pointers.
+/// Hence the list is `Send` and `Sync` whenever `T` is, and cloning it is
+/// O(1).
+pub struct Persistent<T> {
     head: List<T>,
+    len: usize,
 }
 
+/// Former, misspelled, name of `Persistent`.
+#[deprecated(note = "renamed to `Persistent`")]
+pub type Persistant<T> = Persistent<T>;
+
 type List<T> = Option<Arc<Node<T>>>;
 
-impl<T> Persistant<T> {
+impl<T> Persistent<T> {
     pub fn new() -> Self {
-        Self { head: None }
+        Self { head: None, len: 0 }
     }
 
     pub fn prepend(&self, key: T) -> Self {
         Self {
             head: Some(Arc::new(Node::new(key, self.head.clone()))),
+            len: self.len + 1,
         }
     }
 
     pub fn tail(&self) -> Self {
         Self {
             head: self.head.as_ref().and_then(|node| node.next.clone()),
+            len: self.len.saturating_sub(1),
         }
     }
 
@@ -27,14 +39,180 @@ impl<T> Persistant<T> {
         self.head.as_ref().map(|node| &node.key)
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    /// Number of keys in the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             next: self.head.as_deref(),
         }
     }
+
+    /// List made of `keys`, in order, followed by the nodes of `rest`.
+    fn with_prefix(keys: Vec<T>, rest: Self) -> Self {
+        keys.into_iter()
+            .rev()
+            .fold(rest, |list, key| list.prepend(key))
+    }
+
+    /// List of the images of the keys under `f`, in the same order.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Persistent<U> {
+        Persistent::with_prefix(self.iter().map(f).collect(), Persistent::new())
+    }
+
+    /// List of the keys satisfying `keep`, in the same order. The longest
+    /// suffix of the list in which every key is kept is shared with `self`
+    /// rather than copied.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::list::persistent::Persistent;
+    ///
+    /// let list: Persistent<i32> = (1..=6).collect();
+    /// let odd = list.filter(|x| x % 2 == 1);
+    /// assert_eq!(odd.iter().collect::<Vec<_>>(), [&1, &3, &5]);
+    /// ```
+    pub fn filter<F: FnMut(&T) -> bool>(&self, mut keep: F) -> Self
+    where
+        T: Clone,
+    {
+        let keeps: Vec<bool> = self.iter().map(&mut keep).collect();
+        let cut = keeps.iter().rposition(|&k| !k).map_or(0, |i| i + 1);
+
+        let mut link = &self.head;
+        let mut prefix = Vec::new();
+        for &k in &keeps[..cut] {
+            let node = link.as_ref().unwrap();
+            if k {
+                prefix.push(node.key.clone());
+            }
+            link = &node.next;
+        }
+
+        let rest = Self {
+            head: link.clone(),
+            len: self.len - cut,
+        };
+        Self::with_prefix(prefix, rest)
+    }
+
+    /// Folds the keys from the head onwards into an accumulator.
+    pub fn fold<A, F: FnMut(A, &T) -> A>(&self, init: A, f: F) -> A {
+        self.iter().fold(init, f)
+    }
+
+    /// List with the keys in reverse order.
+    pub fn rev(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.fold(Self::new(), |list, key| list.prepend(key.clone()))
+    }
+
+    /// List with the keys of `self` followed by the keys of `other`. The
+    /// nodes of `other` are shared, only the keys of `self` are copied.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::list::persistent::Persistent;
+    ///
+    /// let a: Persistent<i32> = (0..2).collect();
+    /// let b: Persistent<i32> = (2..4).collect();
+    /// let c = a.concat(&b);
+    /// assert_eq!(c.iter().collect::<Vec<_>>(), [&0, &1, &2, &3]);
+    /// assert_eq!(c.len(), 4);
+    /// ```
+    pub fn concat(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        Self::with_prefix(self.iter().cloned().collect(), other.clone())
+    }
+}
+
+impl<T> Clone for Persistent<T> {
+    /// New list pointing to the same nodes, in O(1).
+    fn clone(&self) -> Self {
+        Self {
+            head: self.head.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Persistent<T> {
+    /// Compares the keys in order, stopping as soon as both lists reach a
+    /// shared node.
+    fn eq(&self, other: &Self) -> bool {
+        if self.len != other.len {
+            return false;
+        }
+        let (mut a, mut b) = (&self.head, &other.head);
+        loop {
+            match (a, b) {
+                (Some(x), Some(y)) => {
+                    if Arc::ptr_eq(x, y) {
+                        return true;
+                    }
+                    if x.key != y.key {
+                        return false;
+                    }
+                    a = &x.next;
+                    b = &y.next;
+                }
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl<T: Eq> Eq for Persistent<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Persistent<T> {
+    /// Formats the keys from the head onwards.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> FromIterator<T> for Persistent<T> {
+    /// Builds the list with the keys in iteration order, the first one being
+    /// the head.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::with_prefix(iter.into_iter().collect(), Self::new())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Persistent<T> {
+    /// Serializes the keys as a sequence, from the head.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for Persistent<T>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from_iter)
+    }
 }
 
-impl<T> Default for Persistant<T> {
+impl<T> Default for Persistent<T> {
     fn default() -> Self {
         Self::new()
     }
@@ -55,7 +233,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
-impl<T> Drop for Persistant<T> {
+impl<T> Drop for Persistent<T> {
     fn drop(&mut self) {
         let mut head = self.head.take();
         while let Some(node) = head {
@@ -81,10 +259,11 @@ impl<T> Node<T> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::list::tracked::DropCounter;
 
     #[test]
     fn basics() {
-        let list = Persistant::new();
+        let list = Persistent::new();
         // Check head and tail in empty case
         assert_eq!(list.head(), None);
         assert_eq!(list.tail().head(), None);
@@ -105,7 +284,7 @@ mod test {
 
     #[test]
     fn iter() {
-        let list = Persistant::new().prepend(0).prepend(1).prepend(2);
+        let list = Persistent::new().prepend(0).prepend(1).prepend(2);
 
         let mut iter = list.iter();
         for x in (0..3).rev() {
@@ -113,4 +292,166 @@ mod test {
         }
         assert_eq!(iter.next(), None);
     }
+
+    /// Whether `a` and `b` point to the same node.
+    fn same_node<T>(a: &Persistent<T>, b: &Persistent<T>) -> bool {
+        match (&a.head, &b.head) {
+            (Some(x), Some(y)) => Arc::ptr_eq(x, y),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn keys(list: &Persistent<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn len() {
+        let list: Persistent<i32> = (0..3).collect();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.prepend(5).len(), 4);
+        assert_eq!(list.tail().tail().len(), 1);
+        assert_eq!(list.tail().tail().tail().tail().len(), 0);
+        assert!(list.tail().tail().tail().is_empty());
+        assert!(!list.is_empty());
+    }
+
+    #[test]
+    fn map_and_fold() {
+        let list: Persistent<i32> = (1..5).collect();
+        let squares = list.map(|x| x * x);
+        assert_eq!(keys(&squares), [1, 4, 9, 16]);
+        assert_eq!(squares.len(), 4);
+        let strings = list.map(|x| x.to_string());
+        assert_eq!(strings.head(), Some(&"1".to_string()));
+
+        assert_eq!(list.fold(0, |acc, x| acc + x), 10);
+        assert_eq!(
+            list.fold(String::new(), |acc, x| acc + &x.to_string()),
+            "1234"
+        );
+    }
+
+    #[test]
+    fn filter() {
+        let list: Persistent<i32> = vec![1, 2, 3, 5, 7].into_iter().collect();
+        let odd = list.filter(|x| x % 2 == 1);
+        assert_eq!(keys(&odd), [1, 3, 5, 7]);
+        assert_eq!(odd.len(), 4);
+
+        // Everything after the rejected key is shared
+        assert!(same_node(&odd.tail(), &list.tail().tail()));
+
+        let all = list.filter(|_| true);
+        assert!(same_node(&all, &list));
+        let none = list.filter(|_| false);
+        assert!(none.is_empty());
+        assert_eq!(none.len(), 0);
+    }
+
+    #[test]
+    fn rev_and_concat() {
+        let a: Persistent<i32> = (0..3).collect();
+        assert_eq!(keys(&a.rev()), [2, 1, 0]);
+        assert_eq!(a.rev().len(), 3);
+        assert!(Persistent::<i32>::new().rev().is_empty());
+
+        let b: Persistent<i32> = (3..5).collect();
+        let c = a.concat(&b);
+        assert_eq!(keys(&c), [0, 1, 2, 3, 4]);
+        assert_eq!(c.len(), 5);
+        assert!(same_node(&c.tail().tail().tail(), &b));
+
+        // The originals are left untouched
+        assert_eq!(keys(&a), [0, 1, 2]);
+        assert_eq!(keys(&b), [3, 4]);
+        assert_eq!(keys(&Persistent::new().concat(&b)), [3, 4]);
+    }
+
+    #[test]
+    fn clone_and_eq() {
+        let a: Persistent<i32> = (0..3).collect();
+        let b = a.clone();
+        assert!(same_node(&a, &b));
+        assert_eq!(a, b);
+
+        // Equal keys in distinct nodes
+        let c: Persistent<i32> = (0..3).collect();
+        assert!(!same_node(&a, &c));
+        assert_eq!(a, c);
+        assert_eq!(a.prepend(9), c.prepend(9));
+
+        assert_ne!(a, a.tail());
+        assert_ne!(a, a.map(|x| x + 1));
+        assert_eq!(Persistent::<i32>::new(), Persistent::new());
+        assert_eq!(format!("{:?}", a), "[0, 1, 2]");
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Persistent<i32>>();
+        assert_send_sync::<Persistent<String>>();
+
+        // Lists built on different threads share their common tail
+        let base: Persistent<i32> = (0..100).collect();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let base = base.clone();
+                std::thread::spawn(move || base.prepend(i))
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            let list = handle.join().unwrap();
+            assert_eq!(list.head(), Some(&(i as i32)));
+            assert!(same_node(&list.tail(), &base));
+        }
+    }
+
+    #[test]
+    fn no_leaks() {
+        let counter = DropCounter::new();
+        let base: Persistent<_> = (0..10).map(|k| counter.track(k)).collect();
+        let a = base.prepend(counter.track(10));
+        let b = base.tail().prepend(counter.track(11));
+        // Shared nodes live as long as some list points to them
+        drop(base);
+        assert_eq!(counter.drops(), 0);
+        drop(a);
+        assert_eq!(counter.drops(), 2);
+        drop(b);
+        assert_eq!(counter.drops(), 12);
+
+        // Long lists are dropped node by node, without recursion
+        let list: Persistent<u32> = (0..1_000_000).collect();
+        let shared = list.tail().tail();
+        drop(list);
+        assert_eq!(shared.len(), 999_998);
+        drop(shared);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_alias() {
+        let list: Persistant<i32> = Persistant::new().prepend(1);
+        assert_eq!(list.head(), Some(&1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde_test::{assert_tokens, Token};
+
+        let list: Persistent<char> = "ab".chars().collect();
+        assert_tokens(
+            &list,
+            &[
+                Token::Seq { len: None },
+                Token::Char('a'),
+                Token::Char('b'),
+                Token::SeqEnd,
+            ],
+        );
+    }
 }
@@ -1,85 +1,178 @@
-use std::ptr;
+use alloc::boxed::Box;
+use core::{fmt, marker::PhantomData, ptr::NonNull};
 
 // Push to the end of the queue
 // Pop from the head of the queue
-struct Queue<T> {
+//
+// The queue owns its nodes, which are allocated as boxes and freed when popped
+// or when the queue is dropped. Links are `NonNull` rather than `*mut` so that
+// the queue is covariant in `T`, like `Box<T>`, and the marker tells the drop
+// checker that dropping the queue drops keys of type `T`.
+pub struct Queue<T> {
     head: List<T>,
-    tail: *mut Node<T>,
+    tail: List<T>,
+    len: usize,
+    marker: PhantomData<Box<Node<T>>>,
 }
 
-type List<T> = *mut Node<T>;
+type List<T> = Option<NonNull<Node<T>>>;
+
+// The queue owns its keys and hands out references to them only through
+// `&self` and `&mut self`, just like `Box<T>`, so it can be sent or shared
+// across threads whenever the keys can.
+unsafe impl<T: Send> Send for Queue<T> {}
+unsafe impl<T: Sync> Sync for Queue<T> {}
 
 impl<T> Queue<T> {
     pub fn new() -> Self {
         Self {
-            head: ptr::null_mut(),
-            tail: ptr::null_mut(),
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
         }
     }
 
     pub fn push(&mut self, key: T) {
-        unsafe {
-            let new_tail =
-                Box::into_raw(Box::new(Node::new(key, ptr::null_mut())));
-
-            if self.tail.is_null() {
-                self.head = new_tail;
-            } else {
-                (*(self.tail)).next = new_tail;
-            }
-
-            self.tail = new_tail;
+        let new_tail = NonNull::from(Box::leak(Box::new(Node::new(key, None))));
+        match self.tail {
+            // The tail is a valid node owned by the queue, and no reference
+            // to it outlives a borrow of the queue
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(new_tail) },
+            None => self.head = Some(new_tail),
         }
+        self.tail = Some(new_tail);
+        self.len += 1;
     }
 
     pub fn pop(&mut self) -> Option<T> {
-        unsafe {
-            if self.head.is_null() {
-                None
-            } else {
-                // We create a box in order to correctly deallocate `last_head`
-                let last_head = Box::from_raw(self.head);
-                self.head = (*last_head).next;
-
-                // The head was the only node
-                if self.head.is_null() {
-                    self.tail = ptr::null_mut();
-                }
-
-                Some(last_head.key)
+        self.head.map(|head| {
+            // We create a box in order to correctly deallocate `last_head`
+            let last_head = unsafe { Box::from_raw(head.as_ptr()) };
+            self.head = last_head.next;
+
+            // The head was the only node
+            if self.head.is_none() {
+                self.tail = None;
             }
-        }
+
+            self.len -= 1;
+            last_head.key
+        })
     }
 
-    pub fn peek(&self) -> Option<&T> {
-        unsafe { self.head.as_ref().map(|head| &head.key) }
+    /// Number of keys in the queue.
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    pub fn peek_mut(&mut self) -> Option<&mut T> {
-        unsafe { self.head.as_mut().map(|head| &mut head.key) }
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+    /// Removes every key of the queue.
+    pub fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        unsafe { self.head.map(|head| &(*head.as_ptr()).key) }
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.head.map(|head| &mut (*head.as_ptr()).key) }
     }
 
     pub fn iter(&self) -> Iter<'_, T> {
-        unsafe {
-            Iter {
-                next: self.head.as_ref(),
-            }
+        Iter {
+            next: self.head.map(|head| unsafe { &*head.as_ptr() }),
         }
     }
 
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
-        unsafe {
-            IterMut {
-                next: self.head.as_mut(),
-            }
+        IterMut {
+            next: self.head.map(|head| unsafe { &mut *head.as_ptr() }),
+        }
+    }
+}
+
+impl<T> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Queue<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Queue<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut queue = Queue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T> Extend<T> for Queue<T> {
+    /// Pushes every key of `iter` to the end of the queue.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for key in iter {
+            self.push(key);
         }
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Queue<T> {
+    /// Serializes the keys as a sequence, from the head of the queue.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Queue<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        alloc::vec::Vec::deserialize(deserializer).map(Self::from_iter)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Queue<T> {
+    /// Formats the keys from the head to the end of the queue.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct IntoIter<T>(Queue<T>);
 
 impl<T> Iterator for IntoIter<T> {
@@ -98,12 +191,10 @@ impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        unsafe {
-            self.next.take().map(|node| {
-                self.next = node.next.as_ref();
-                &node.key
-            })
-        }
+        self.next.take().map(|node| {
+            self.next = node.next.map(|next| unsafe { &*next.as_ptr() });
+            &node.key
+        })
     }
 }
 
@@ -111,22 +202,27 @@ pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
 }
 
+// Iterators only hand out `&T` and `&mut T` respectively, as the borrows of
+// the queue they come from would
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
 impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        unsafe {
-            self.next.take().map(|node| {
-                self.next = node.next.as_mut();
-                &mut node.key
-            })
-        }
+        self.next.take().map(|node| {
+            self.next = node.next.map(|next| unsafe { &mut *next.as_ptr() });
+            &mut node.key
+        })
     }
 }
 
 impl<T> Drop for Queue<T> {
     fn drop(&mut self) {
-        while self.pop().is_some() {}
+        self.clear();
     }
 }
 
@@ -141,9 +237,13 @@ impl<T> Node<T> {
     }
 }
 
+// These tests exercise every pointer access of the queue, and are meant to be
+// run under Miri as well, with `cargo +nightly miri test list::queue`, which
+// checks them for leaks and aliasing violations. Sizes shrink under Miri.
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::list::tracked::DropCounter;
 
     #[test]
     fn push_n_pop() {
@@ -259,4 +359,180 @@ mod test {
         assert_eq!(queue.pop(), Some(6));
         assert!(queue.peek().is_none());
     }
+
+    #[test]
+    fn len() {
+        let mut queue = Queue::new();
+        assert_eq!(queue.len(), 0);
+        assert!(queue.is_empty());
+
+        queue.extend(0..3);
+        assert_eq!(queue.len(), 3);
+        assert!(!queue.is_empty());
+        queue.pop();
+        assert_eq!(queue.len(), 2);
+
+        queue.clear();
+        assert_eq!(queue.len(), 0);
+        assert!(queue.is_empty());
+        assert!(queue.peek().is_none());
+
+        // The queue is still usable after being cleared
+        queue.push(7);
+        assert_eq!(queue.pop(), Some(7));
+    }
+
+    #[test]
+    fn from_iter() {
+        let mut queue: Queue<i32> = (0..3).collect();
+        queue.extend(vec![3, 4]);
+        for key in &mut queue {
+            *key *= 2;
+        }
+        assert_eq!((&queue).into_iter().sum::<i32>(), 20);
+        assert_eq!(format!("{:?}", queue), "[0, 2, 4, 6, 8]");
+        assert_eq!(queue.into_iter().collect::<Vec<_>>(), [0, 2, 4, 6, 8]);
+
+        let empty: Queue<i32> = Queue::new();
+        assert_eq!(format!("{:?}", empty), "[]");
+    }
+
+    #[test]
+    fn no_leaks() {
+        let counter = DropCounter::new();
+        let mut queue: Queue<_> = (0..10).map(|k| counter.track(k)).collect();
+        drop(queue.pop());
+        queue.push(counter.track(10));
+        assert_eq!(counter.drops(), 1);
+        queue.clear();
+        assert_eq!(counter.drops(), 11);
+        assert!(queue.is_empty());
+
+        // Nodes left in the queue or in its iterator are dropped with them
+        queue.extend((0..10).map(|k| counter.track(k)));
+        drop(queue);
+        assert_eq!(counter.drops(), 21);
+        let mut iter: IntoIter<_> = (0..10)
+            .map(|k| counter.track(k))
+            .collect::<Queue<_>>()
+            .into_iter();
+        drop(iter.next());
+        drop(iter);
+        assert_eq!(counter.drops(), 31);
+
+        let n = if cfg!(miri) { 1000 } else { 1_000_000 };
+        let queue: Queue<u32> = (0..n).collect();
+        drop(queue);
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Queue<i32>>();
+        assert_send_sync::<Iter<'_, String>>();
+        assert_send_sync::<IterMut<'_, String>>();
+        assert_send_sync::<IntoIter<String>>();
+
+        let mut queue: Queue<String> =
+            ["a", "b"].into_iter().map(String::from).collect();
+        let queue = std::thread::spawn(move || {
+            queue.push(String::from("c"));
+            queue
+        })
+        .join()
+        .unwrap();
+        // Shared between threads through references
+        std::thread::scope(|s| {
+            for _ in 0..2 {
+                s.spawn(|| assert_eq!(queue.iter().count(), 3));
+            }
+        });
+        assert_eq!(queue.into_iter().collect::<String>(), "abc");
+    }
+
+    #[test]
+    fn variance() {
+        // Compiles only if the queue and its shared iterator are covariant
+        fn shorten<'a>(queue: Queue<&'static str>) -> Queue<&'a str> {
+            queue
+        }
+        fn shorten_iter<'a, 'b>(
+            iter: Iter<'b, &'static str>,
+        ) -> Iter<'b, &'a str> {
+            iter
+        }
+
+        let statics: Queue<&'static str> = ["x", "y"].into_iter().collect();
+        let local = String::from("z");
+        let mut queue = shorten(statics.iter().copied().collect());
+        queue.push(&local);
+        let keys = shorten_iter(statics.iter()).chain(queue.iter());
+        assert_eq!(keys.copied().collect::<String>(), "xyxyz");
+    }
+
+    #[test]
+    fn interleaved_mutation() {
+        let mut queue: Queue<i32> = (0..4).collect();
+        for key in queue.iter_mut() {
+            *key *= 10;
+        }
+        // The tail stays valid after mutating every node
+        queue.push(40);
+        *queue.peek_mut().unwrap() += 1;
+        assert_eq!(
+            queue.iter().copied().collect::<Vec<_>>(),
+            [1, 10, 20, 30, 40]
+        );
+
+        // Emptying the queue resets both ends
+        while queue.pop().is_some() {
+            assert_eq!(queue.iter().count(), queue.len());
+        }
+        queue.push(7);
+        assert_eq!((queue.peek(), queue.len()), (Some(&7), 1));
+        for key in &mut queue {
+            *key += 1;
+        }
+        queue.extend([9, 10]);
+        let mut iter = queue.into_iter();
+        assert_eq!(iter.next(), Some(8));
+        assert_eq!(iter.collect::<Vec<_>>(), [9, 10]);
+    }
+
+    #[test]
+    fn against_vec_deque() {
+        use crate::random::Pcg32;
+        use alloc::collections::VecDeque;
+
+        let mut rng = Pcg32::seed(0x9e37);
+        let mut queue = Queue::new();
+        let mut model = VecDeque::new();
+        let steps = if cfg!(miri) { 200 } else { 5000 };
+        for step in 0..steps {
+            match rng.gen_range(0..5) {
+                0 | 1 => {
+                    queue.push(step);
+                    model.push_back(step);
+                }
+                2 => assert_eq!(queue.pop(), model.pop_front()),
+                3 => {
+                    if let Some(key) = queue.peek_mut() {
+                        *key += 1;
+                        *model.front_mut().unwrap() += 1;
+                    }
+                }
+                _ => {
+                    for key in queue.iter_mut().step_by(2) {
+                        *key -= 1;
+                    }
+                    for key in model.iter_mut().step_by(2) {
+                        *key -= 1;
+                    }
+                }
+            }
+            assert_eq!(queue.len(), model.len());
+            assert_eq!(queue.peek(), model.front());
+            assert!(queue.iter().eq(model.iter()));
+        }
+    }
 }
@@ -0,0 +1,402 @@
+//! Fixed capacity double-ended queue over a circular buffer
+use alloc::vec::Vec;
+use core::{fmt, ops::Index};
+
+/// What a full `RingBuffer` does with a new key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Overflow {
+    /// The new key is handed back and the buffer is left untouched.
+    Reject,
+
+    /// The key at the opposite end is evicted to make room for the new one.
+    Overwrite,
+}
+
+/// Double-ended queue with a fixed capacity, storing its keys contiguously.
+/// The keys occupy the slots `head, head + 1, ..., head + len - 1`, wrapping
+/// around the end of the buffer, so that pushes and pops at both ends are
+/// O(1) and never reallocate.
+///
+/// Example:
+/// ```
+/// use ralg::list::ring::{Overflow, RingBuffer};
+///
+/// let mut ring = RingBuffer::with_policy(3, Overflow::Overwrite);
+/// for x in 0..5 {
+///     ring.push_back(x);
+/// }
+/// assert_eq!(ring.iter().collect::<Vec<_>>(), [&2, &3, &4]);
+/// assert_eq!(ring[0], 2);
+/// ```
+///
+/// With the `serde` feature, a buffer is (de)serialized as its capacity,
+/// policy and keys from front to back, and deserialization fails if there are
+/// more keys than the capacity.
+pub struct RingBuffer<T> {
+    buf: Vec<Option<T>>,
+    head: usize,
+    len: usize,
+    policy: Overflow,
+}
+
+/// Fields of a ring buffer, before checking that they agree.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename = "RingBuffer")]
+struct RawRingBuffer<T> {
+    capacity: usize,
+    policy: Overflow,
+    keys: Vec<T>,
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty buffer holding up to `capacity` keys, which rejects
+    /// new keys when full. Panics if `capacity == 0`.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_policy(capacity, Overflow::Reject)
+    }
+
+    /// Creates an empty buffer holding up to `capacity` keys, treating new
+    /// keys according to `policy` when full. Panics if `capacity == 0`.
+    pub fn with_policy(capacity: usize, policy: Overflow) -> Self {
+        assert!(capacity > 0, "ring buffer with zero capacity");
+        Self {
+            buf: (0..capacity).map(|_| None).collect(),
+            head: 0,
+            len: 0,
+            policy,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    pub fn policy(&self) -> Overflow {
+        self.policy
+    }
+
+    /// Slot of the buffer holding the key at position `i`.
+    fn slot(&self, i: usize) -> usize {
+        (self.head + i) % self.capacity()
+    }
+
+    /// Pushes `key` to the back. If the buffer is full, returns the key that
+    /// was left out: `key` itself under `Overflow::Reject`, or the evicted
+    /// front key under `Overflow::Overwrite`.
+    pub fn push_back(&mut self, key: T) -> Option<T> {
+        let mut out = None;
+        if self.is_full() {
+            match self.policy {
+                Overflow::Reject => return Some(key),
+                Overflow::Overwrite => out = self.pop_front(),
+            }
+        }
+        let slot = self.slot(self.len);
+        self.buf[slot] = Some(key);
+        self.len += 1;
+        out
+    }
+
+    /// Pushes `key` to the front. If the buffer is full, returns the key that
+    /// was left out: `key` itself under `Overflow::Reject`, or the evicted
+    /// back key under `Overflow::Overwrite`.
+    pub fn push_front(&mut self, key: T) -> Option<T> {
+        let mut out = None;
+        if self.is_full() {
+            match self.policy {
+                Overflow::Reject => return Some(key),
+                Overflow::Overwrite => out = self.pop_back(),
+            }
+        }
+        self.head = self.slot(self.capacity() - 1);
+        self.buf[self.head] = Some(key);
+        self.len += 1;
+        out
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let key = self.buf[self.head].take();
+        self.head = self.slot(1);
+        self.len -= 1;
+        key
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let slot = self.slot(self.len - 1);
+        self.len -= 1;
+        self.buf[slot].take()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Key at position `i` counting from the front, if any.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        match i < self.len {
+            true => self.buf[self.slot(i)].as_ref(),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        match i < self.len {
+            true => {
+                let slot = self.slot(i);
+                self.buf[slot].as_mut()
+            }
+            false => None,
+        }
+    }
+
+    /// Removes every key, keeping the capacity.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+        self.head = 0;
+    }
+
+    /// Iterates over the keys from front to back.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        (0..self.len).map(move |i| {
+            self.buf[self.slot(i)]
+                .as_ref()
+                .expect("occupied slot of a ring buffer")
+        })
+    }
+}
+
+impl<T> Index<usize> for RingBuffer<T> {
+    type Output = T;
+
+    /// Panics if `i` is out of bounds.
+    fn index(&self, i: usize) -> &T {
+        self.get(i).expect("ring buffer index out of bounds")
+    }
+}
+
+impl<T> Extend<T> for RingBuffer<T> {
+    /// Pushes every key of `iter` to the back, following the buffer's
+    /// overflow policy.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for key in iter {
+            self.push_back(key);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for RingBuffer<T> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let raw = RawRingBuffer {
+            capacity: self.capacity(),
+            policy: self.policy,
+            keys: self.iter().collect(),
+        };
+        raw.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for RingBuffer<T>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let raw = RawRingBuffer::deserialize(deserializer)?;
+        if raw.capacity == 0 {
+            return Err(D::Error::custom("ring buffer with zero capacity"));
+        }
+        if raw.keys.len() > raw.capacity {
+            return Err(D::Error::custom("more keys than the capacity"));
+        }
+        let mut ring = RingBuffer::with_policy(raw.capacity, raw.policy);
+        ring.extend(raw.keys);
+        Ok(ring)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RingBuffer<T> {
+    /// Formats the keys from front to back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn keys(ring: &RingBuffer<i32>) -> Vec<i32> {
+        ring.iter().copied().collect()
+    }
+
+    #[test]
+    fn push_n_pop() {
+        let mut ring = RingBuffer::new(4);
+        assert_eq!(ring.pop_front(), None);
+        assert_eq!(ring.pop_back(), None);
+
+        assert_eq!(ring.push_back(1), None);
+        assert_eq!(ring.push_back(2), None);
+        assert_eq!(ring.push_front(0), None);
+        assert_eq!(keys(&ring), [0, 1, 2]);
+        assert_eq!((ring.front(), ring.back()), (Some(&0), Some(&2)));
+
+        assert_eq!(ring.pop_back(), Some(2));
+        assert_eq!(ring.pop_front(), Some(0));
+        assert_eq!(ring.pop_front(), Some(1));
+        assert!(ring.is_empty());
+        assert_eq!((ring.front(), ring.back()), (None, None));
+    }
+
+    #[test]
+    fn wraparound() {
+        let mut ring = RingBuffer::new(3);
+        for round in 0..10 {
+            ring.push_back(round);
+            ring.push_back(round + 1);
+            assert_eq!(ring.pop_front(), Some(round));
+            assert_eq!(ring.pop_front(), Some(round + 1));
+        }
+
+        ring.push_front(1);
+        ring.push_front(0);
+        ring.push_back(2);
+        assert_eq!(keys(&ring), [0, 1, 2]);
+        assert_eq!(ring.iter().rev().copied().collect::<Vec<_>>(), [2, 1, 0]);
+        assert_eq!((ring[0], ring[2]), (0, 2));
+
+        *ring.get_mut(1).unwrap() = 5;
+        assert_eq!(format!("{:?}", ring), "[0, 5, 2]");
+        assert_eq!(ring.get(3), None);
+    }
+
+    #[test]
+    fn reject() {
+        let mut ring = RingBuffer::new(2);
+        ring.extend(0..2);
+        assert!(ring.is_full());
+        assert_eq!(ring.push_back(2), Some(2));
+        assert_eq!(ring.push_front(-1), Some(-1));
+        assert_eq!(keys(&ring), [0, 1]);
+    }
+
+    #[test]
+    fn overwrite() {
+        let mut ring = RingBuffer::with_policy(3, Overflow::Overwrite);
+        ring.extend(0..3);
+        assert_eq!(ring.push_back(3), Some(0));
+        assert_eq!(keys(&ring), [1, 2, 3]);
+        assert_eq!(ring.push_front(0), Some(3));
+        assert_eq!(keys(&ring), [0, 1, 2]);
+        assert_eq!(ring.len(), ring.capacity());
+
+        ring.clear();
+        assert!(ring.is_empty());
+        assert_eq!(ring.capacity(), 3);
+        assert_eq!(ring.policy(), Overflow::Overwrite);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_capacity() {
+        RingBuffer::<i32>::new(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let mut ring = RingBuffer::new(3);
+        ring.push_back(0);
+        let _ = ring[1];
+    }
+
+    /// Buffer compared by its capacity, policy and keys.
+    #[cfg(feature = "serde")]
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
+    #[serde(transparent)]
+    struct Ring(RingBuffer<i32>);
+
+    #[cfg(feature = "serde")]
+    impl PartialEq for Ring {
+        fn eq(&self, other: &Self) -> bool {
+            let (a, b) = (&self.0, &other.0);
+            a.capacity() == b.capacity()
+                && a.policy() == b.policy()
+                && a.iter().eq(b.iter())
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+        let ring = |capacity, keys: &[i32]| {
+            [
+                Token::Struct {
+                    name: "RingBuffer",
+                    len: 3,
+                },
+                Token::Str("capacity"),
+                Token::U64(capacity),
+                Token::Str("policy"),
+                Token::UnitVariant {
+                    name: "Overflow",
+                    variant: "Overwrite",
+                },
+                Token::Str("keys"),
+                Token::Seq {
+                    len: Some(keys.len()),
+                },
+            ]
+            .into_iter()
+            .chain(keys.iter().map(|&k| Token::I32(k)))
+            .chain([Token::SeqEnd, Token::StructEnd])
+            .collect::<Vec<_>>()
+        };
+        // The keys wrap around the end of the buffer
+        let mut wrapped = RingBuffer::with_policy(3, Overflow::Overwrite);
+        wrapped.extend(0..5);
+        assert_tokens(&Ring(wrapped), &ring(3, &[2, 3, 4]));
+
+        assert_de_tokens_error::<RingBuffer<i32>>(
+            &ring(2, &[0, 1, 2]),
+            "more keys than the capacity",
+        );
+        assert_de_tokens_error::<RingBuffer<i32>>(
+            &ring(0, &[]),
+            "ring buffer with zero capacity",
+        );
+    }
+}
@@ -1,23 +1,30 @@
+use crate::sorting::partial_cmp;
+use alloc::boxed::Box;
+use core::cmp::Ordering;
+
 pub struct SingleLinked<T> {
     head: List<T>,
+    len: usize,
 }
 
 type List<T> = Option<Box<Node<T>>>;
 
 impl<T> SingleLinked<T> {
     pub fn new() -> Self {
-        Self { head: None }
+        Self { head: None, len: 0 }
     }
 
     pub fn push(&mut self, key: T) {
         let node = Box::new(Node::new(key, self.head.take()));
 
         self.head = Some(node);
+        self.len += 1;
     }
 
     pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
             self.head = node.next;
+            self.len -= 1;
             node.key
         })
     }
@@ -30,29 +37,242 @@ impl<T> SingleLinked<T> {
         self.head.as_mut().map(|node| &mut node.key)
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+    /// Number of keys in the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Key at position `i`, counting from the head, in O(i).
+    pub fn nth(&self, i: usize) -> Option<&T> {
+        self.iter().nth(i)
+    }
+
+    /// Middle key of the list, found in a single pass by moving one pointer
+    /// twice as fast as another. For even lengths, the second of the two
+    /// middle keys is returned.
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.iter();
+        let mut fast = self.iter();
+        while fast.next().is_some() && fast.next().is_some() {
+            slow.next();
+        }
+        slow.next()
+    }
+
+    /// Key `k` positions away from the last one, so that `kth_from_end(0)`
+    /// is the last key, found in a single pass by keeping a pointer `k` nodes
+    /// ahead of another.
+    pub fn kth_from_end(&self, k: usize) -> Option<&T> {
+        let mut ahead = self.iter();
+        ahead.nth(k)?;
+        let mut behind = self.iter();
+        while ahead.next().is_some() {
+            behind.next();
+        }
+        behind.next()
+    }
+
+    /// Inserts `key` so that it ends up at position `i`, in O(i). Panics if
+    /// `i > len`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::list::single::SingleLinked;
+    ///
+    /// let mut list = SingleLinked::new();
+    /// list.push(2);
+    /// list.push(0);
+    /// list.insert_at(1, 1);
+    /// list.insert_at(3, 3);
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), [&0, &1, &2, &3]);
+    /// ```
+    pub fn insert_at(&mut self, i: usize, key: T) {
+        assert!(i <= self.len, "cannot insert at a nonexistent position");
+        let mut link = &mut self.head;
+        for _ in 0..i {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        *link = Some(Box::new(Node::new(key, link.take())));
+        self.len += 1;
+    }
+
+    /// Keeps only the keys satisfying `keep`, preserving their order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        // Relink the kept nodes one after the other, `tail` being the link
+        // the next kept node should be attached to
+        let mut rest = self.head.take();
+        let mut tail = &mut self.head;
+        while let Some(mut node) = rest {
+            rest = node.next.take();
+            if keep(&node.key) {
+                tail = &mut tail.insert(node).next;
+            } else {
+                self.len -= 1;
+            }
+        }
+    }
+
+    /// Removes and returns the first key satisfying `pred`, if any.
+    pub fn remove_first<F: FnMut(&T) -> bool>(
+        &mut self,
+        mut pred: F,
+    ) -> Option<T> {
+        let mut link = &mut self.head;
+        while link.as_ref().is_some_and(|node| !pred(&node.key)) {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let node = link.take()?;
+        *link = node.next;
+        self.len -= 1;
+        Some(node.key)
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    /// Reverses the list in place, in O(n).
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut rest = self.head.take();
+        while let Some(mut node) = rest {
+            rest = core::mem::replace(&mut node.next, reversed);
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    /// Sorts the list in ascending order, see `sort_by`.
+    pub fn sort(&mut self)
+    where
+        T: PartialOrd,
+    {
+        self.sort_by(partial_cmp)
+    }
+
+    /// Sorts the list with respect to `cmp` by a stable bottom-up merge sort
+    /// acting directly on the links between nodes: sorted runs of width 1, 2,
+    /// 4, ... are merged pairwise until a single run is left. This takes
+    /// O(n log n) comparisons and O(1) extra space.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::list::single::SingleLinked;
+    ///
+    /// let mut list = SingleLinked::new();
+    /// for x in [3, 1, 2] {
+    ///     list.push(x);
+    /// }
+    /// list.sort();
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), [&1, &2, &3]);
+    /// ```
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut cmp: F) {
+        let mut width = 1;
+        while width < self.len {
+            let mut rest = self.head.take();
+            let mut tail = &mut self.head;
+            while rest.is_some() {
+                let a = take_prefix(&mut rest, width);
+                let b = take_prefix(&mut rest, width);
+                tail = merge(a, b, tail, &mut cmp);
+            }
+            width *= 2;
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             next: self.head.as_deref(),
         }
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             next: self.head.as_deref_mut(),
         }
     }
 }
 
+/// Detaches the first `n` nodes of `list`, leaving the remaining ones there.
+fn take_prefix<T>(list: &mut List<T>, n: usize) -> List<T> {
+    let mut link = &mut *list;
+    for _ in 0..n {
+        if link.is_none() {
+            break;
+        }
+        link = &mut link.as_mut().unwrap().next;
+    }
+    let suffix = link.take();
+    core::mem::replace(list, suffix)
+}
+
+/// Merges the sorted lists `a` and `b` into `tail`, returning the link after
+/// the last merged node. Ties are taken from `a`, so that merging is stable.
+fn merge<'a, T, F: FnMut(&T, &T) -> Ordering>(
+    mut a: List<T>,
+    mut b: List<T>,
+    mut tail: &'a mut List<T>,
+    cmp: &mut F,
+) -> &'a mut List<T> {
+    loop {
+        let from_a = match (&a, &b) {
+            (Some(x), Some(y)) => cmp(&y.key, &x.key) != Ordering::Less,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => return tail,
+        };
+        let source = if from_a { &mut a } else { &mut b };
+        let mut node = source.take().unwrap();
+        *source = node.next.take();
+        tail = &mut tail.insert(node).next;
+    }
+}
+
 impl<T> Default for SingleLinked<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for SingleLinked<T> {
+    /// Serializes the keys as a sequence, from the head.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de>
+    for SingleLinked<T>
+{
+    /// Pushes the keys of a sequence from the last one, so that the first one
+    /// ends up at the head.
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let keys: alloc::vec::Vec<T> =
+            serde::Deserialize::deserialize(deserializer)?;
+        let mut list = SingleLinked::new();
+        for key in keys.into_iter().rev() {
+            list.push(key);
+        }
+        Ok(list)
+    }
+}
+
+impl<T> IntoIterator for SingleLinked<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
 pub struct IntoIter<T>(SingleLinked<T>);
 
 impl<T> Iterator for IntoIter<T> {
@@ -116,6 +336,7 @@ impl<T> Node<T> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::list::tracked::DropCounter;
 
     #[test]
     fn push_n_pop() {
@@ -144,9 +365,9 @@ mod test {
         list.push(1);
         assert_eq!(list.peek(), Some(&1));
 
-        list.peek_mut().map(|key| {
+        if let Some(key) = list.peek_mut() {
             *key += 2;
-        });
+        }
         assert_eq!(list.peek_mut(), Some(&mut 3));
         assert_eq!(list.peek(), Some(&3));
     }
@@ -189,4 +410,213 @@ mod test {
         }
         assert_eq!(iter.next(), None);
     }
+
+    /// List with keys `xs`, in this order from the head.
+    fn from_slice(xs: &[i32]) -> SingleLinked<i32> {
+        let mut list = SingleLinked::new();
+        for &x in xs.iter().rev() {
+            list.push(x);
+        }
+        list
+    }
+
+    fn keys(list: &SingleLinked<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn len() {
+        let mut list = from_slice(&[0, 1, 2]);
+        assert_eq!(list.len(), 3);
+        assert!(!list.is_empty());
+        list.pop();
+        assert_eq!(list.len(), 2);
+        list.pop();
+        list.pop();
+        list.pop();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn nth() {
+        let list = from_slice(&[4, 5, 6]);
+        assert_eq!(list.nth(0), Some(&4));
+        assert_eq!(list.nth(2), Some(&6));
+        assert_eq!(list.nth(3), None);
+    }
+
+    #[test]
+    fn middle() {
+        assert_eq!(from_slice(&[]).middle(), None);
+        assert_eq!(from_slice(&[1]).middle(), Some(&1));
+        assert_eq!(from_slice(&[1, 2]).middle(), Some(&2));
+        assert_eq!(from_slice(&[1, 2, 3]).middle(), Some(&2));
+        assert_eq!(from_slice(&[1, 2, 3, 4]).middle(), Some(&3));
+        assert_eq!(from_slice(&[1, 2, 3, 4, 5]).middle(), Some(&3));
+    }
+
+    #[test]
+    fn kth_from_end() {
+        // Odd and even lengths
+        for n in [5, 6] {
+            let xs: Vec<i32> = (0..n).collect();
+            let list = from_slice(&xs);
+            for k in 0..n as usize {
+                assert_eq!(list.kth_from_end(k), Some(&xs[n as usize - 1 - k]));
+            }
+            assert_eq!(list.kth_from_end(n as usize), None);
+        }
+        assert_eq!(from_slice(&[]).kth_from_end(0), None);
+    }
+
+    #[test]
+    fn sort() {
+        for n in 0..20 {
+            // Deterministic shuffle of 0..n with repeated keys
+            let xs: Vec<i32> = (0..n).map(|i| (i * 7 + 3) % 11).collect();
+            let mut list = from_slice(&xs);
+            list.sort();
+
+            let mut expected = xs.clone();
+            expected.sort();
+            assert_eq!(keys(&list), expected);
+            assert_eq!(list.len(), n as usize);
+        }
+
+        let mut list = from_slice(&[1, 5, 2, 4, 3]);
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(keys(&list), [5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn sort_stable() {
+        let pairs =
+            [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e'), (2, 'f')];
+        let mut list = SingleLinked::new();
+        for &p in pairs.iter().rev() {
+            list.push(p);
+        }
+        list.sort_by(|a, b| a.0.cmp(&b.0));
+        let tags: String = list.iter().map(|p| p.1).collect();
+        assert_eq!(tags, "ebdacf");
+    }
+
+    #[test]
+    fn insert_at() {
+        let mut list = SingleLinked::new();
+        list.insert_at(0, 1);
+        list.insert_at(0, 0);
+        list.insert_at(2, 3);
+        list.insert_at(2, 2);
+        assert_eq!(keys(&list), [0, 1, 2, 3]);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut list = from_slice(&[0, 1]);
+        list.insert_at(3, 2);
+    }
+
+    #[test]
+    fn remove_first() {
+        let mut list = from_slice(&[0, 1, 2, 1, 3]);
+        assert_eq!(list.remove_first(|&x| x == 1), Some(1));
+        assert_eq!(keys(&list), [0, 2, 1, 3]);
+        assert_eq!(list.remove_first(|&x| x == 0), Some(0));
+        assert_eq!(list.remove_first(|&x| x == 3), Some(3));
+        assert_eq!(list.remove_first(|&x| x > 5), None);
+        assert_eq!(keys(&list), [2, 1]);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn retain() {
+        let mut list = from_slice(&[0, 1, 2, 3, 4, 5, 6]);
+        list.retain(|x| x % 3 != 0);
+        assert_eq!(keys(&list), [1, 2, 4, 5]);
+        assert_eq!(list.len(), 4);
+
+        list.retain(|&x| x > 4);
+        assert_eq!(keys(&list), [5]);
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.len(), 0);
+
+        // The list is still usable afterwards
+        list.push(1);
+        assert_eq!(keys(&list), [1]);
+    }
+
+    #[test]
+    fn reverse() {
+        let mut list = from_slice(&[0, 1, 2, 3]);
+        list.reverse();
+        assert_eq!(keys(&list), [3, 2, 1, 0]);
+        assert_eq!(list.len(), 4);
+
+        let mut list = from_slice(&[]);
+        list.reverse();
+        assert!(list.is_empty());
+        let mut list = from_slice(&[7]);
+        list.reverse();
+        assert_eq!(keys(&list), [7]);
+    }
+
+    #[test]
+    fn no_leaks() {
+        let counter = DropCounter::new();
+        let mut list = SingleLinked::new();
+        for key in 0..10 {
+            list.push(counter.track(key));
+        }
+        list.reverse();
+        list.sort_by(|a, b| b.cmp(a));
+        list.insert_at(3, counter.track(10));
+        assert_eq!(counter.drops(), 0);
+        // Removed keys are dropped at once, unless returned
+        list.retain(|t| t.key % 2 == 0);
+        assert_eq!(counter.drops(), 5);
+        let first = list.remove_first(|t| t.key == 4);
+        drop(list.pop());
+        assert_eq!(counter.drops(), 6);
+        let mut iter = list.into_iter();
+        drop(iter.next());
+        drop(iter);
+        drop(first);
+        assert_eq!(counter.drops(), 11);
+
+        // Long lists are dropped node by node, without recursion
+        let mut list = SingleLinked::new();
+        for key in 0..1_000_000 {
+            list.push(key);
+        }
+        drop(list);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde::{de::value::SeqDeserializer, Deserialize};
+        use serde_test::{assert_ser_tokens, Token};
+
+        let list = from_slice(&[1, 2, 3]);
+        assert_ser_tokens(
+            &list,
+            &[
+                Token::Seq { len: None },
+                Token::I32(1),
+                Token::I32(2),
+                Token::I32(3),
+                Token::SeqEnd,
+            ],
+        );
+        // The keys come back in order, despite being pushed to the head
+        let seq = SeqDeserializer::<_, serde::de::value::Error>::new(
+            keys(&list).into_iter(),
+        );
+        assert_eq!(keys(&SingleLinked::deserialize(seq).unwrap()), [1, 2, 3]);
+    }
 }
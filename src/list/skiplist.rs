@@ -0,0 +1,370 @@
+//! Ordered map over a skip list
+use crate::random::Pcg32;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Maximum number of levels of a skip list, enough for about `2^32` keys.
+const MAX_LEVEL: usize = 32;
+
+/// Ordered map built as a hierarchy of sorted linked lists: every node
+/// belongs to the bottom list, and each node of a list is promoted to the one
+/// above it with probability 1/2. Searches start at the sparse top list and
+/// drop one level whenever the next key overshoots, which takes expected
+/// O(log n) steps.
+///
+/// The nodes are stored in a vector and linked by their indices, the slots
+/// of removed nodes being reused by later insertions.
+///
+/// Example:
+/// ```
+/// use ralg::list::skiplist::SkipList;
+///
+/// let mut map = SkipList::new();
+/// map.insert(3, "c");
+/// map.insert(1, "a");
+/// map.insert(2, "b");
+/// assert_eq!(map.get(&2), Some(&"b"));
+/// assert_eq!(map.remove(&1), Some("a"));
+/// assert_eq!(map.iter().collect::<Vec<_>>(), [(&2, &"b"), (&3, &"c")]);
+/// ```
+pub struct SkipList<K: Ord, V> {
+    nodes: Vec<Option<Node<K, V>>>,
+    /// Slots of `nodes` left empty by removals.
+    free: Vec<usize>,
+    /// First node of each level.
+    head: [Link; MAX_LEVEL],
+    /// Number of levels in use.
+    level: usize,
+    len: usize,
+    rng: Pcg32,
+}
+
+/// Index of the next node in `nodes`, if any.
+type Link = Option<usize>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    /// Next node on each of the levels the node belongs to.
+    next: Vec<Link>,
+}
+
+impl<K: Ord, V> SkipList<K, V> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            head: [None; MAX_LEVEL],
+            level: 0,
+            len: 0,
+            rng: Pcg32::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn node(&self, i: usize) -> &Node<K, V> {
+        self.nodes[i].as_ref().expect("linked skip list node")
+    }
+
+    fn node_mut(&mut self, i: usize) -> &mut Node<K, V> {
+        self.nodes[i].as_mut().expect("linked skip list node")
+    }
+
+    /// Node following `from` on level `lvl`, where `None` stands for the
+    /// head of the list.
+    fn next(&self, from: Link, lvl: usize) -> Link {
+        match from {
+            None => self.head[lvl],
+            Some(i) => self.node(i).next[lvl],
+        }
+    }
+
+    fn set_next(&mut self, from: Link, lvl: usize, to: Link) {
+        match from {
+            None => self.head[lvl] = to,
+            Some(i) => self.node_mut(i).next[lvl] = to,
+        }
+    }
+
+    /// Last node with a key less than `key` on each level in use, `None`
+    /// standing for the head of the list.
+    fn predecessors(&self, key: &K) -> [Link; MAX_LEVEL] {
+        let mut update = [None; MAX_LEVEL];
+        let mut current = None;
+        for lvl in (0..self.level).rev() {
+            while let Some(next) = self.next(current, lvl) {
+                if self.node(next).key >= *key {
+                    break;
+                }
+                current = Some(next);
+            }
+            update[lvl] = current;
+        }
+        update
+    }
+
+    /// Index of the node holding `key`, if any.
+    fn find(&self, key: &K) -> Link {
+        let prev = self.predecessors(key)[0];
+        self.next(prev, 0).filter(|&i| self.node(i).key == *key)
+    }
+
+    /// Number of levels of a new node: each extra level is kept with
+    /// probability 1/2.
+    fn random_level(&mut self) -> usize {
+        (self.rng.next_u64().trailing_zeros() as usize + 1).min(MAX_LEVEL)
+    }
+
+    /// Inserts `value` under `key`, returning the value previously stored
+    /// under it, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let mut update = self.predecessors(&key);
+        if let Some(i) = self.next(update[0], 0) {
+            if self.node(i).key == key {
+                return Some(core::mem::replace(
+                    &mut self.node_mut(i).value,
+                    value,
+                ));
+            }
+        }
+
+        let level = self.random_level();
+        if level > self.level {
+            // The new levels start at the head
+            for link in &mut update[self.level..level] {
+                *link = None;
+            }
+            self.level = level;
+        }
+
+        let next = (0..level).map(|lvl| self.next(update[lvl], lvl)).collect();
+        let node = Some(Node { key, value, next });
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx] = node;
+                idx
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for (lvl, &prev) in update.iter().enumerate().take(level) {
+            self.set_next(prev, lvl, Some(idx));
+        }
+        self.len += 1;
+        None
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let update = self.predecessors(key);
+        let idx = self.next(update[0], 0)?;
+        if self.node(idx).key != *key {
+            return None;
+        }
+
+        let node = self.nodes[idx].take().expect("linked skip list node");
+        for (lvl, &next) in node.next.iter().enumerate() {
+            self.set_next(update[lvl], lvl, next);
+        }
+        while self.level > 0 && self.head[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+        self.free.push(idx);
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|i| &self.node(i).value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.find(key).map(move |i| &mut self.node_mut(i).value)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Entry with the smallest key.
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    /// Iterates over the entries in increasing order of keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            list: self,
+            next: self.head[0],
+        }
+    }
+}
+
+impl<K: Ord, V> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SkipList<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = SkipList::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K: Ord + serde::Serialize, V: serde::Serialize> serde::Serialize
+    for SkipList<K, V>
+{
+    /// Serializes the entries as a map, in increasing order of keys.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::Deserialize<'de> for SkipList<K, V>
+where
+    K: Ord + serde::Deserialize<'de>,
+    V: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        alloc::collections::BTreeMap::deserialize(deserializer)
+            .map(Self::from_iter)
+    }
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for SkipList<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+pub struct Iter<'a, K: Ord, V> {
+    list: &'a SkipList<K, V>,
+    next: Link,
+}
+
+impl<'a, K: Ord, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|i| {
+            let node = self.list.node(i);
+            self.next = node.next[0];
+            (&node.key, &node.value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn insert_get_remove() {
+        let mut map = SkipList::new();
+        assert!(map.is_empty());
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.first(), None);
+
+        assert_eq!(map.insert(2, 'b'), None);
+        assert_eq!(map.insert(1, 'a'), None);
+        assert_eq!(map.insert(2, 'c'), Some('b'));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&2), Some(&'c'));
+        assert!(map.contains_key(&1));
+        assert!(!map.contains_key(&3));
+        assert_eq!(map.first(), Some((&1, &'a')));
+
+        *map.get_mut(&1).unwrap() = 'z';
+        assert_eq!(format!("{:?}", map), "{1: 'z', 2: 'c'}");
+
+        assert_eq!(map.remove(&1), Some('z'));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.remove(&2), Some('c'));
+        assert!(map.is_empty());
+        assert_eq!(map.level, 0);
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        let mut map = SkipList::new();
+        let mut expected = BTreeMap::new();
+        let mut rng = Pcg32::seed(0x2545f4914f6cdd1d);
+        for step in 0..5000 {
+            let key = rng.gen_range(0..500_u64);
+            if rng.gen_range(0..3) == 0 {
+                assert_eq!(map.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(map.insert(key, step), expected.insert(key, step));
+            }
+            assert_eq!(map.len(), expected.len());
+        }
+
+        let entries: Vec<_> = map.iter().collect();
+        let expected_entries: Vec<_> = expected.iter().collect();
+        assert_eq!(entries, expected_entries);
+
+        // Removed slots are reused
+        assert!(map.nodes.len() < 5000);
+    }
+
+    #[test]
+    fn from_iter() {
+        let map: SkipList<&str, usize> = ["pear", "apple", "fig"]
+            .into_iter()
+            .map(|s| (s, s.len()))
+            .collect();
+        let keys: Vec<_> = map.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, ["apple", "fig", "pear"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde::{de::value::MapDeserializer, Deserialize};
+        use serde_test::{assert_ser_tokens, Token};
+
+        let map: SkipList<i32, char> =
+            [(2, 'b'), (1, 'a')].into_iter().collect();
+        assert_ser_tokens(
+            &map,
+            &[
+                Token::Map { len: None },
+                Token::I32(1),
+                Token::Char('a'),
+                Token::I32(2),
+                Token::Char('b'),
+                Token::MapEnd,
+            ],
+        );
+        let entries = [(3, 'c'), (1, 'a'), (2, 'b')];
+        let de = MapDeserializer::<_, serde::de::value::Error>::new(
+            entries.into_iter(),
+        );
+        let back = SkipList::<i32, char>::deserialize(de).unwrap();
+        assert!(back.iter().eq([(&1, &'a'), (&2, &'b'), (&3, &'c')]));
+    }
+}
@@ -0,0 +1,267 @@
+//! Last-in first-out stacks backed by vectors
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Stack whose keys are stored contiguously in a vector, the top of the stack
+/// being the end of the vector.
+pub struct Stack<T> {
+    keys: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Self { keys: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            keys: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, key: T) {
+        self.keys.push(key);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.keys.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.keys.last()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.keys.last_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+
+    /// Iterates over the keys from the top to the bottom of the stack.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        self.keys.iter().rev()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for Stack<T> {
+    /// Pushes the keys in order, so that the last one ends up on top.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            keys: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.keys.extend(iter);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Stack<T> {
+    /// Serializes the keys as a sequence, from the bottom to the top of the
+    /// stack, in the order `from_iter` pushes them.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.keys)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Stack<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Self::from_iter)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Stack<T> {
+    /// Formats the keys from the top to the bottom of the stack.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Stack that also keeps track of its minimum and maximum keys, which are
+/// available in O(1).
+///
+/// Along with each key, we store the positions of the minimum and maximum of
+/// the keys up to it, so that popping restores the previous extrema for free.
+///
+/// Example:
+/// ```
+/// use ralg::list::stack::MinStack;
+///
+/// let mut stack = MinStack::new();
+/// for x in [3, 1, 4, 1, 5] {
+///     stack.push(x);
+/// }
+/// assert_eq!((stack.min(), stack.max()), (Some(&1), Some(&5)));
+/// stack.pop();
+/// stack.pop();
+/// stack.pop();
+/// assert_eq!((stack.min(), stack.max()), (Some(&1), Some(&3)));
+/// ```
+pub struct MinStack<T: PartialOrd> {
+    // Each entry is a key followed by the positions of the minimum and of the
+    // maximum among the keys below it, inclusive
+    entries: Vec<(T, usize, usize)>,
+}
+
+impl<T: PartialOrd> MinStack<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, key: T) {
+        let top = self.entries.len();
+        let (min, max) = match self.entries.last() {
+            None => (top, top),
+            Some(&(_, min, max)) => (
+                if key < self.entries[min].0 { top } else { min },
+                if key > self.entries[max].0 { top } else { max },
+            ),
+        };
+        self.entries.push((key, min, max));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.entries.pop().map(|(key, _, _)| key)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.entries.last().map(|(key, _, _)| key)
+    }
+
+    /// Smallest key in the stack. Among equal keys, the deepest one is
+    /// returned.
+    pub fn min(&self) -> Option<&T> {
+        self.entries.last().map(|&(_, min, _)| &self.entries[min].0)
+    }
+
+    /// Greatest key in the stack. Among equal keys, the deepest one is
+    /// returned.
+    pub fn max(&self) -> Option<&T> {
+        self.entries.last().map(|&(_, _, max)| &self.entries[max].0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<T: PartialOrd> Default for MinStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stack() {
+        let mut stack = Stack::new();
+        assert!(stack.pop().is_none());
+        assert!(stack.peek().is_none());
+
+        for x in 0..3 {
+            stack.push(x);
+        }
+        assert_eq!(stack.len(), 3);
+        assert_eq!(stack.peek(), Some(&2));
+        *stack.peek_mut().unwrap() = 5;
+        assert_eq!(format!("{:?}", stack), "[5, 1, 0]");
+
+        assert_eq!(stack.pop(), Some(5));
+        assert_eq!(stack.pop(), Some(1));
+        stack.clear();
+        assert!(stack.is_empty());
+
+        let mut stack: Stack<i32> = (0..3).collect();
+        stack.extend(vec![3, 4]);
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), [4, 3, 2, 1, 0]);
+        assert_eq!(stack.pop(), Some(4));
+    }
+
+    #[test]
+    fn min_stack() {
+        let mut stack = MinStack::new();
+        assert_eq!((stack.min(), stack.max()), (None, None));
+
+        let keys = [5, 3, 8, 3, 1, 9, 2];
+        for (i, &x) in keys.iter().enumerate() {
+            stack.push(x);
+            assert_eq!(stack.min(), keys[..=i].iter().min());
+            assert_eq!(stack.max(), keys[..=i].iter().max());
+        }
+        for i in (0..keys.len()).rev() {
+            assert_eq!(stack.min(), keys[..=i].iter().min());
+            assert_eq!(stack.max(), keys[..=i].iter().max());
+            assert_eq!(stack.peek(), Some(&keys[i]));
+            assert_eq!(stack.pop(), Some(keys[i]));
+        }
+        assert!(stack.is_empty());
+        assert_eq!(stack.min(), None);
+    }
+
+    #[test]
+    fn min_stack_floats() {
+        let mut stack = MinStack::default();
+        stack.push(0.5);
+        stack.push(-1.5);
+        stack.push(2.0);
+        assert_eq!((stack.min(), stack.max()), (Some(&-1.5), Some(&2.0)));
+        assert_eq!(stack.len(), 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde::{de::value::SeqDeserializer, Deserialize};
+        use serde_test::{assert_ser_tokens, Token};
+
+        let stack: Stack<i32> = (1..=3).collect();
+        assert_ser_tokens(
+            &stack,
+            &[
+                Token::Seq { len: Some(3) },
+                Token::I32(1),
+                Token::I32(2),
+                Token::I32(3),
+                Token::SeqEnd,
+            ],
+        );
+        let seq = SeqDeserializer::<_, serde::de::value::Error>::new(1..=3);
+        let back = Stack::<i32>::deserialize(seq).unwrap();
+        assert_eq!(back.peek(), Some(&3));
+        assert!(back.iter().eq(stack.iter()));
+    }
+}
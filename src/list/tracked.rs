@@ -0,0 +1,59 @@
+//! Values that count their drops, for the leak tests of the lists
+use alloc::rc::Rc;
+use core::cell::Cell;
+use core::cmp::Ordering;
+
+/// Shared counter of the drops of the values it tracks.
+#[derive(Default)]
+pub(crate) struct DropCounter(Rc<Cell<usize>>);
+
+impl DropCounter {
+    pub(crate) fn new() -> Self {
+        DropCounter::default()
+    }
+
+    /// Value with the key `key`, which is counted once it is dropped.
+    pub(crate) fn track(&self, key: usize) -> Tracked {
+        Tracked {
+            key,
+            drops: Rc::clone(&self.0),
+        }
+    }
+
+    pub(crate) fn drops(&self) -> usize {
+        self.0.get()
+    }
+}
+
+/// Value compared by its key alone, whose drop increments its counter.
+#[derive(Debug)]
+pub(crate) struct Tracked {
+    pub(crate) key: usize,
+    drops: Rc<Cell<usize>>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+impl PartialEq for Tracked {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Tracked {}
+
+impl PartialOrd for Tracked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tracked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
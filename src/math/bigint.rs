@@ -0,0 +1,597 @@
+//! Arbitrary-precision integers
+use crate::math::num::{Num, One, Signed, Zero};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cmp::Ordering;
+use core::ops::{Add, Mul, Neg, Sub};
+use core::{error::Error, fmt, str::FromStr};
+
+/// Operands with fewer limbs than this are multiplied by the schoolbook
+/// algorithm, which is faster than Karatsuba for small inputs.
+const KARATSUBA_THRESHOLD: usize = 32;
+
+/// Largest power of 10 fitting in a limb, used for decimal conversions.
+const DECIMAL_BASE: u32 = 1_000_000_000;
+const DECIMAL_DIGITS: usize = 9;
+
+/// Arbitrary-precision integer in sign-magnitude representation.
+///
+/// The magnitude is stored as a vector of base `2^32` limbs, least significant
+/// first, without leading (most significant) zero limbs. Zero has no limbs and
+/// is never negative, so that every integer has a unique representation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    mag: Vec<u32>,
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, mut mag: Vec<u32>) -> Self {
+        trim(&mut mag);
+        BigInt {
+            negative: negative && !mag.is_empty(),
+            mag,
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Absolute value.
+    pub fn abs(&self) -> Self {
+        BigInt::from_parts(false, self.mag.clone())
+    }
+
+    /// Number of bits of the absolute value, which is zero for zero.
+    pub fn bits(&self) -> u64 {
+        match self.mag.last() {
+            None => 0,
+            Some(&top) => {
+                32 * self.mag.len() as u64 - top.leading_zeros() as u64
+            }
+        }
+    }
+}
+
+/// Removes the leading zero limbs of `v`.
+fn trim(v: &mut Vec<u32>) {
+    while v.last() == Some(&0) {
+        v.pop();
+    }
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let s = x as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push(s as u32);
+        carry = s >> 32;
+    }
+    if carry > 0 {
+        sum.push(carry as u32);
+    }
+    sum
+}
+
+/// Computes `a - b`, assuming `a >= b`.
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut diff = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let d = x as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = (d < 0) as i64;
+        diff.push((d + (borrow << 32)) as u32);
+    }
+    debug_assert_eq!(borrow, 0, "subtracted a larger magnitude");
+    trim(&mut diff);
+    diff
+}
+
+/// Adds `x * 2^(32 * shift)` to `acc`, growing it as needed.
+fn add_shifted(acc: &mut Vec<u32>, x: &[u32], shift: usize) {
+    if acc.len() < x.len() + shift {
+        acc.resize(x.len() + shift, 0);
+    }
+    let mut carry = 0;
+    let mut i = shift;
+    for &limb in x {
+        let s = acc[i] as u64 + limb as u64 + carry;
+        acc[i] = s as u32;
+        carry = s >> 32;
+        i += 1;
+    }
+    while carry > 0 {
+        if i == acc.len() {
+            acc.push(0);
+        }
+        let s = acc[i] as u64 + carry;
+        acc[i] = s as u32;
+        carry = s >> 32;
+        i += 1;
+    }
+}
+
+/// Schoolbook multiplication, taking time `O(n m)`.
+fn mul_schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut prod = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + prod[i + j] as u64 + carry;
+            prod[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        prod[i + b.len()] = carry as u32;
+    }
+    trim(&mut prod);
+    prod
+}
+
+/// Karatsuba multiplication: splitting `a = a1 B^m + a0` and
+/// `b = b1 B^m + b0`, the product is
+/// `z2 B^(2m) + (z1 - z2 - z0) B^m + z0` where `z0 = a0 b0`, `z2 = a1 b1` and
+/// `z1 = (a0 + a1)(b0 + b1)`. Using three half-sized products instead of
+/// four gives time `O(n^log2(3))`.
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+
+    let m = a.len().max(b.len()) / 2;
+    let split = |x: &[u32]| {
+        let (lo, hi) = x.split_at(m.min(x.len()));
+        let mut lo = lo.to_vec();
+        trim(&mut lo);
+        (lo, hi.to_vec())
+    };
+    let (a0, a1) = split(a);
+    let (b0, b1) = split(b);
+
+    let z0 = mul_mag(&a0, &b0);
+    let z2 = mul_mag(&a1, &b1);
+    let z1 = mul_mag(&add_mag(&a0, &a1), &add_mag(&b0, &b1));
+    let z1 = sub_mag(&sub_mag(&z1, &z0), &z2);
+
+    let mut prod = z0;
+    add_shifted(&mut prod, &z1, m);
+    add_shifted(&mut prod, &z2, 2 * m);
+    trim(&mut prod);
+    prod
+}
+
+/// Divides `mag` in place by the non-zero `d`, returning the remainder.
+fn div_rem_small(mag: &mut Vec<u32>, d: u32) -> u32 {
+    let mut rem = 0_u64;
+    for limb in mag.iter_mut().rev() {
+        let cur = (rem << 32) | *limb as u64;
+        *limb = (cur / d as u64) as u32;
+        rem = cur % d as u64;
+    }
+    trim(mag);
+    rem as u32
+}
+
+/// Computes `mag * m + a` in place.
+fn mul_add_small(mag: &mut Vec<u32>, m: u32, a: u32) {
+    let mut carry = a as u64;
+    for limb in mag.iter_mut() {
+        let t = *limb as u64 * m as u64 + carry;
+        *limb = t as u32;
+        carry = t >> 32;
+    }
+    if carry > 0 {
+        mag.push(carry as u32);
+    }
+}
+
+impl From<u64> for BigInt {
+    fn from(n: u64) -> Self {
+        BigInt::from_parts(false, vec![n as u32, (n >> 32) as u32])
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> Self {
+        let mut x = BigInt::from(n.unsigned_abs());
+        x.negative = n < 0;
+        x
+    }
+}
+
+impl<'a> Add<&'a BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &'a BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(
+                self.negative,
+                add_mag(&self.mag, &rhs.mag),
+            );
+        }
+
+        // Different signs: subtract the smaller magnitude from the larger
+        match cmp_mag(&self.mag, &rhs.mag) {
+            Ordering::Less => {
+                BigInt::from_parts(rhs.negative, sub_mag(&rhs.mag, &self.mag))
+            }
+            _ => {
+                BigInt::from_parts(self.negative, sub_mag(&self.mag, &rhs.mag))
+            }
+        }
+    }
+}
+
+impl<'a> Sub<&'a BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &'a BigInt) -> BigInt {
+        self + &(-rhs)
+    }
+}
+
+impl<'a> Mul<&'a BigInt> for &BigInt {
+    type Output = BigInt;
+
+    /// Multiplication of big integers, using Karatsuba's algorithm for large
+    /// operands.
+    fn mul(self, rhs: &'a BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != rhs.negative,
+            mul_mag(&self.mag, &rhs.mag),
+        )
+    }
+}
+
+/// Implements the by-value variants of a binary operator in terms of the one
+/// taking both operands by reference.
+macro_rules! forward_val_binop {
+    ($imp:ident, $method:ident) => {
+        impl $imp for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: BigInt) -> BigInt {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl $imp<&BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: &BigInt) -> BigInt {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $imp<BigInt> for &BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: BigInt) -> BigInt {
+                self.$method(&rhs)
+            }
+        }
+    };
+}
+
+forward_val_binop!(Add, add);
+forward_val_binop!(Sub, sub);
+forward_val_binop!(Mul, mul);
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.mag.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.mag)
+    }
+}
+
+impl Zero for BigInt {
+    fn zero() -> Self {
+        BigInt::default()
+    }
+}
+
+impl One for BigInt {
+    fn one() -> Self {
+        BigInt::from(1_u64)
+    }
+}
+
+impl Num for BigInt {}
+
+impl Signed for BigInt {
+    fn abs(&self) -> Self {
+        BigInt::abs(self)
+    }
+
+    fn signum(&self) -> Self {
+        match (self.negative, self.mag.is_empty()) {
+            (_, true) => BigInt::zero(),
+            (true, false) => -BigInt::one(),
+            (false, false) => BigInt::one(),
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    fn is_positive(&self) -> bool {
+        !self.negative && !self.mag.is_empty()
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    /// Formats the integer in decimal notation.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Peel off chunks of 9 decimal digits, least significant first
+        let mut mag = self.mag.clone();
+        let mut chunks = Vec::new();
+        while !mag.is_empty() {
+            chunks.push(div_rem_small(&mut mag, DECIMAL_BASE));
+        }
+
+        let mut s = String::new();
+        match chunks.split_last() {
+            None => s.push('0'),
+            Some((top, rest)) => {
+                s.push_str(&top.to_string());
+                for chunk in rest.iter().rev() {
+                    s.push_str(&format!(
+                        "{:0width$}",
+                        chunk,
+                        width = DECIMAL_DIGITS
+                    ));
+                }
+            }
+        }
+        f.pad_integral(!self.negative, "", &s)
+    }
+}
+
+/// Error returned when parsing a big integer fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    /// The input contains no digits.
+    Empty,
+
+    /// The input contains a character that is not a decimal digit.
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse integer from empty string"),
+            Self::InvalidDigit(c) => write!(f, "invalid digit `{}`", c),
+        }
+    }
+}
+
+impl Error for ParseBigIntError {}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    /// Parses an optionally signed decimal integer, such as `-1234`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::bigint::BigInt;
+    ///
+    /// let a: BigInt = "123456789012345678901234567890".parse().unwrap();
+    /// let b: BigInt = "-987654321098765432109876543210".parse().unwrap();
+    /// assert_eq!((&a + &b).to_string(), "-864197532086419753208641975320");
+    /// assert_eq!(
+    ///     (&a * &b).to_string(),
+    ///     "-121932631137021795226185032733622923332237463801111263526900"
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'+') => (false, &s[1..]),
+            Some(b'-') => (true, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(ParseBigIntError::InvalidDigit(c));
+        }
+
+        // Accumulate chunks of 9 digits, most significant first, so that the
+        // first chunk may be shorter
+        let mut mag = Vec::new();
+        let first = match digits.len() % DECIMAL_DIGITS {
+            0 => DECIMAL_DIGITS,
+            r => r,
+        };
+        let mut start = 0;
+        let mut end = first;
+        while start < digits.len() {
+            let chunk: u32 = digits[start..end].parse().unwrap();
+            let scale = 10_u32.pow((end - start) as u32);
+            mul_add_small(&mut mag, scale, chunk);
+            start = end;
+            end += DECIMAL_DIGITS;
+        }
+        Ok(BigInt::from_parts(negative, mag))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    /// Deterministic pseudo-random magnitude with `n` limbs.
+    fn limbs(n: usize, seed: u64) -> Vec<u32> {
+        let mut rng = Pcg32::seed(seed);
+        (0..n).map(|_| rng.next_u32()).collect()
+    }
+
+    #[test]
+    fn arithmetic_matches_i128() {
+        let values: [i64; 9] = [
+            0,
+            1,
+            -1,
+            42,
+            -1000,
+            i64::MAX,
+            i64::MIN,
+            1 << 40,
+            -(1 << 33) + 7,
+        ];
+        for &a in &values {
+            for &b in &values {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                let (a, b) = (a as i128, b as i128);
+                assert_eq!((&x + &y).to_string(), (a + b).to_string());
+                assert_eq!((&x - &y).to_string(), (a - b).to_string());
+                assert_eq!((&x * &y).to_string(), (a * b).to_string());
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn karatsuba() {
+        for (n, m) in [(32, 32), (40, 100), (150, 149), (300, 33), (257, 31)] {
+            let (a, b) = (limbs(n, 3 * n as u64), limbs(m, 7 * m as u64));
+            assert_eq!(mul_mag(&a, &b), mul_schoolbook(&a, &b));
+        }
+
+        // (10^k - 1)^2 == 10^(2k) - 2 * 10^k + 1
+        let k = 700;
+        let x = big(&"9".repeat(k));
+        let expected = format!("{}8{}1", "9".repeat(k - 1), "0".repeat(k - 1));
+        assert_eq!((&x * &x).to_string(), expected);
+    }
+
+    #[test]
+    fn factorial() {
+        let f = (1..=30_u64).fold(BigInt::one(), |f, k| f * BigInt::from(k));
+        assert_eq!(f.to_string(), "265252859812191058636308480000000");
+        assert_eq!(f.bits(), 108);
+    }
+
+    #[test]
+    fn parse_and_format() {
+        for s in [
+            "0",
+            "7",
+            "-7",
+            "1000000000",
+            "-999999999999999999",
+            "18446744073709551616",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("+00012").to_string(), "12");
+        assert_eq!(big("-0"), BigInt::zero());
+        assert!(!big("-0").is_negative());
+        assert_eq!(format!("{:>6}", big("-42")), "   -42");
+        assert_eq!(format!("{:+}", big("42")), "+42");
+
+        assert_eq!("".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+        assert_eq!("-".parse::<BigInt>(), Err(ParseBigIntError::Empty));
+        assert_eq!(
+            "12a".parse::<BigInt>(),
+            Err(ParseBigIntError::InvalidDigit('a'))
+        );
+        assert_eq!(
+            "1 2".parse::<BigInt>(),
+            Err(ParseBigIntError::InvalidDigit(' '))
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let mut xs: Vec<BigInt> = [
+            "5",
+            "-100000000000000000000",
+            "0",
+            "-3",
+            "99999999999999999999",
+        ]
+        .iter()
+        .map(|s| big(s))
+        .collect();
+        xs.sort();
+        let sorted: Vec<String> = xs.iter().map(|x| x.to_string()).collect();
+        assert_eq!(
+            sorted,
+            [
+                "-100000000000000000000",
+                "-3",
+                "0",
+                "5",
+                "99999999999999999999"
+            ]
+        );
+        assert_eq!(big("-12").abs(), big("12"));
+    }
+
+    #[test]
+    fn signed() {
+        assert_eq!(Signed::signum(&big("-98765432109876543210")), big("-1"));
+        assert_eq!(Signed::signum(&big("-0")), BigInt::zero());
+        assert_eq!(Signed::signum(&big("5")), BigInt::one());
+        assert!(Signed::is_positive(&big("5")));
+        assert!(!Signed::is_positive(&BigInt::zero()));
+        assert!(Signed::is_negative(&big("-5")));
+    }
+
+    #[test]
+    fn identities() {
+        let x = big("-123456789123456789123456789");
+        assert_eq!(&x + &BigInt::zero(), x);
+        assert_eq!(&x * &BigInt::one(), x);
+        assert_eq!(&x - &x, BigInt::zero());
+        assert_eq!(-(-x.clone()), x);
+        assert_eq!(&x * &BigInt::zero(), BigInt::zero());
+    }
+}
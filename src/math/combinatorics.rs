@@ -0,0 +1,401 @@
+//! Counting and enumerating arrangements: factorials, binomial coefficients,
+//! permutations and combinations
+use crate::math::num::{
+    CheckedAdd, CheckedMul, Num, SaturatingAdd, WrappingAdd,
+};
+use alloc::{vec, vec::Vec};
+use core::iter::FusedIterator;
+
+/// Computes `n!`, returning `None` if it overflows a `u64` (that is, for
+/// `n > 20`).
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::factorial;
+///
+/// assert_eq!(factorial(5), Some(120));
+/// assert_eq!(factorial(21), None);
+/// ```
+pub fn factorial(n: u64) -> Option<u64> {
+    (2..=n).try_fold(1_u64, |acc, k| acc.checked_mul(k))
+}
+
+/// Computes `n!` in any integer type, returning `None` on overflow.
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::checked_factorial;
+///
+/// assert_eq!(checked_factorial::<u8>(5), Some(120));
+/// assert_eq!(checked_factorial::<i8>(6), None);
+/// assert_eq!(checked_factorial::<u128>(34), Some(295232799039604140847618609643520000000));
+/// ```
+pub fn checked_factorial<T>(n: u64) -> Option<T>
+where
+    T: Num + CheckedAdd + CheckedMul + Copy,
+{
+    let (mut acc, mut k) = (T::one(), T::one());
+    for _ in 1..n {
+        k = k.checked_add(T::one())?;
+        acc = acc.checked_mul(k)?;
+    }
+    Some(acc)
+}
+
+/// Binomial coefficient `C(n, k)` by Pascal's rule, where `add` adds two
+/// entries of the triangle, or returns `None` to give up.
+///
+/// Only the entries `C(i, j)` for `j <= min(k, n - k)` are computed, which
+/// never exceed the result.
+fn pascal<T, F>(n: usize, k: usize, add: F) -> Option<T>
+where
+    T: Num + Copy,
+    F: Fn(T, T) -> Option<T>,
+{
+    if k > n {
+        return Some(T::zero());
+    }
+    let k = k.min(n - k);
+    let mut row = vec![T::zero(); k + 1];
+    row[0] = T::one();
+    for i in 1..=n {
+        for j in (1..=k.min(i)).rev() {
+            row[j] = add(row[j], row[j - 1])?;
+        }
+    }
+    Some(row[k])
+}
+
+/// Binomial coefficient `C(n, k)` in any integer type, returning `None` on
+/// overflow. Built from additions only by Pascal's rule, in O(n min(k, n -
+/// k)) time, so that `binomial` is faster for `u64`.
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::checked_binomial;
+///
+/// assert_eq!(checked_binomial::<u8>(10, 3), Some(120));
+/// assert_eq!(checked_binomial::<u8>(12, 6), None);
+/// ```
+pub fn checked_binomial<T: Num + CheckedAdd + Copy>(
+    n: usize,
+    k: usize,
+) -> Option<T> {
+    pascal(n, k, T::checked_add)
+}
+
+/// Binomial coefficient `C(n, k)` clamped to the greatest value of the type,
+/// in O(n min(k, n - k)) time. Since the entries of Pascal's triangle only
+/// grow, clamping every sum gives the clamped result.
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::saturating_binomial;
+///
+/// assert_eq!(saturating_binomial::<u8>(10, 3), 120);
+/// assert_eq!(saturating_binomial::<u8>(12, 6), 255);
+/// ```
+pub fn saturating_binomial<T: Num + SaturatingAdd + Copy>(
+    n: usize,
+    k: usize,
+) -> T {
+    pascal(n, k, |a, b| Some(T::saturating_add(a, b))).unwrap()
+}
+
+/// Binomial coefficient `C(n, k)` wrapped around the bounds of the type, that
+/// is, modulo `2^bits` for integers of `bits` bits, in O(n min(k, n - k))
+/// time.
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::wrapping_binomial;
+///
+/// // C(10, 5) = 252 and C(20, 10) = 184756 = 2 * 2^16 + 53684
+/// assert_eq!(wrapping_binomial::<i8>(10, 5), 252_u8 as i8);
+/// assert_eq!(wrapping_binomial::<u16>(20, 10), 53684);
+/// ```
+pub fn wrapping_binomial<T: Num + WrappingAdd + Copy>(n: usize, k: usize) -> T {
+    pascal(n, k, |a, b| Some(T::wrapping_add(a, b))).unwrap()
+}
+
+/// Binomial coefficient `C(n, k)`, the number of `k`-element subsets of an
+/// `n`-element set, which is zero for `k > n`. Returns `None` on overflow.
+///
+/// Uses the multiplicative formula `C(n, i + 1) == C(n, i) (n - i) / (i + 1)`,
+/// whose intermediate values never exceed the result, so it succeeds whenever
+/// the result fits in a `u64`.
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::binomial;
+///
+/// assert_eq!(binomial(5, 2), Some(10));
+/// assert_eq!(binomial(66, 33), Some(7_219_428_434_016_265_740));
+/// assert_eq!(binomial(68, 34), None);
+/// ```
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+
+    let k = k.min(n - k);
+    let mut c: u128 = 1;
+    for i in 0..k {
+        // The product is exactly divisible by `i + 1`
+        c = c * (n - i) as u128 / (i + 1) as u128;
+        if c > u64::MAX as u128 {
+            return None;
+        }
+    }
+    Some(c as u64)
+}
+
+/// Multinomial coefficient `(k1 + ... + km)! / (k1! ... km!)`, the number of
+/// ways of splitting a set into groups of sizes `ks`. Returns `None` on
+/// overflow.
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::multinomial;
+///
+/// // Distinct arrangements of the letters of "MISSISSIPPI"
+/// assert_eq!(multinomial(&[1, 4, 4, 2]), Some(34_650));
+/// ```
+pub fn multinomial(ks: &[u64]) -> Option<u64> {
+    // Product of `C(k1 + ... + kj, kj)` over all `j`
+    let mut total: u64 = 0;
+    let mut acc: u64 = 1;
+    for &k in ks {
+        total = total.checked_add(k)?;
+        acc = acc.checked_mul(binomial(total, k)?)?;
+    }
+    Some(acc)
+}
+
+/// Iterator over the permutations of a slice, in lexicographic order of the
+/// positions of its elements. Created by `permutations`.
+#[derive(Clone, Debug)]
+pub struct Permutations<'a, T> {
+    xs: &'a [T],
+    idx: Vec<usize>,
+    done: bool,
+}
+
+/// Returns an iterator over all `n!` permutations of `xs`, each one as a new
+/// vector. Elements are treated as distinct by their positions, so repeated
+/// elements give repeated permutations.
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::permutations;
+///
+/// let perms: Vec<Vec<char>> = permutations(&['a', 'b', 'c']).collect();
+/// assert_eq!(perms.len(), 6);
+/// assert_eq!(perms[0], vec!['a', 'b', 'c']);
+/// assert_eq!(perms[1], vec!['a', 'c', 'b']);
+/// assert_eq!(perms[5], vec!['c', 'b', 'a']);
+/// ```
+pub fn permutations<T: Clone>(xs: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        xs,
+        idx: (0..xs.len()).collect(),
+        done: false,
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let item = self.idx.iter().map(|&i| self.xs[i].clone()).collect();
+
+        // Advance to the next permutation of the indices: find the longest
+        // decreasing suffix, swap its predecessor with the smallest larger
+        // element of the suffix and reverse the suffix
+        let idx = &mut self.idx;
+        match (1..idx.len()).rev().find(|&i| idx[i - 1] < idx[i]) {
+            None => self.done = true,
+            Some(i) => {
+                let j = (i..idx.len())
+                    .rev()
+                    .find(|&j| idx[j] > idx[i - 1])
+                    .unwrap();
+                idx.swap(i - 1, j);
+                idx[i..].reverse();
+            }
+        }
+        Some(item)
+    }
+}
+
+impl<T: Clone> FusedIterator for Permutations<'_, T> {}
+
+/// Iterator over the `k`-combinations of a slice, in lexicographic order of
+/// the positions of its elements. Created by `combinations`.
+#[derive(Clone, Debug)]
+pub struct Combinations<'a, T> {
+    xs: &'a [T],
+    idx: Vec<usize>,
+    done: bool,
+}
+
+/// Returns an iterator over all `C(n, k)` subsets of `k` elements of `xs`,
+/// each one as a new vector preserving the order of `xs`.
+///
+/// Example:
+/// ```
+/// use ralg::math::combinatorics::combinations;
+///
+/// let combs: Vec<Vec<i32>> = combinations(&[1, 2, 3, 4], 2).collect();
+/// assert_eq!(combs, vec![
+///     vec![1, 2], vec![1, 3], vec![1, 4],
+///     vec![2, 3], vec![2, 4], vec![3, 4],
+/// ]);
+/// ```
+pub fn combinations<T: Clone>(xs: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        xs,
+        idx: (0..k).collect(),
+        done: k > xs.len(),
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let item = self.idx.iter().map(|&i| self.xs[i].clone()).collect();
+
+        // Increment the rightmost index that can still move to the right and
+        // reset every index after it to the smallest possible values
+        let (n, k) = (self.xs.len(), self.idx.len());
+        match (0..k).rev().find(|&i| self.idx[i] < n - k + i) {
+            None => self.done = true,
+            Some(i) => {
+                self.idx[i] += 1;
+                for j in (i + 1)..k {
+                    self.idx[j] = self.idx[j - 1] + 1;
+                }
+            }
+        }
+        Some(item)
+    }
+}
+
+impl<T: Clone> FusedIterator for Combinations<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn factorials() {
+        assert_eq!(factorial(0), Some(1));
+        assert_eq!(factorial(1), Some(1));
+        assert_eq!(factorial(10), Some(3_628_800));
+        assert_eq!(factorial(20), Some(2_432_902_008_176_640_000));
+        assert_eq!(factorial(21), None);
+    }
+
+    #[test]
+    fn binomials() {
+        // Pascal's rule
+        for n in 1..60 {
+            for k in 1..n {
+                assert_eq!(
+                    binomial(n, k),
+                    Some(
+                        binomial(n - 1, k - 1).unwrap()
+                            + binomial(n - 1, k).unwrap()
+                    )
+                );
+            }
+        }
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(binomial(3, 5), Some(0));
+        assert_eq!(binomial(u64::MAX, 1), Some(u64::MAX));
+        assert_eq!(binomial(u64::MAX, u64::MAX - 1), Some(u64::MAX));
+        assert_eq!(binomial(u64::MAX, 2), None);
+    }
+
+    #[test]
+    fn generic_variants() {
+        for n in 0..=34 {
+            let expected = (2..=n as u128).product::<u128>();
+            assert_eq!(checked_factorial::<u128>(n), Some(expected));
+        }
+        assert_eq!(checked_factorial::<u128>(35), None);
+        assert_eq!(
+            checked_factorial::<i64>(20),
+            factorial(20).map(|f| f as i64)
+        );
+        assert_eq!(checked_factorial::<i64>(21), None);
+
+        for n in 0..70_u64 {
+            for k in 0..=n + 1 {
+                let (n_, k_) = (n as usize, k as usize);
+                assert_eq!(checked_binomial::<u64>(n_, k_), binomial(n, k));
+                let exact = checked_binomial::<u128>(n_, k_).unwrap();
+                assert_eq!(
+                    saturating_binomial::<u32>(n_, k_) as u128,
+                    exact.min(u32::MAX as u128)
+                );
+                assert_eq!(
+                    wrapping_binomial::<u16>(n_, k_) as u128,
+                    exact % (1 << 16)
+                );
+            }
+        }
+        assert_eq!(saturating_binomial::<i8>(8, 4), 70);
+        assert_eq!(saturating_binomial::<i8>(10, 4), i8::MAX);
+        assert_eq!(checked_binomial::<i8>(3, 5), Some(0));
+    }
+
+    #[test]
+    fn multinomials() {
+        assert_eq!(multinomial(&[]), Some(1));
+        assert_eq!(multinomial(&[5]), Some(1));
+        assert_eq!(multinomial(&[2, 3]), binomial(5, 2));
+        assert_eq!(multinomial(&[1, 1, 1, 1]), factorial(4));
+        assert_eq!(multinomial(&[20, 20, 20]), None);
+        assert_eq!(multinomial(&[u64::MAX, 1]), None);
+    }
+
+    #[test]
+    fn permutation_iterator() {
+        assert_eq!(permutations::<i32>(&[]).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(permutations(&[7]).collect::<Vec<_>>(), vec![vec![7]]);
+
+        let xs = [1, 2, 3, 4, 5];
+        let perms: Vec<Vec<i32>> = permutations(&xs).collect();
+        assert_eq!(perms.len() as u64, factorial(5).unwrap());
+        assert!(perms.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(perms.last(), Some(&vec![5, 4, 3, 2, 1]));
+
+        // Repeated elements are distinguished by position
+        assert_eq!(permutations(&[0, 0, 0]).count(), 6);
+    }
+
+    #[test]
+    fn combination_iterator() {
+        let xs = ['a', 'b', 'c', 'd', 'e', 'f'];
+        for k in 0..=xs.len() {
+            let combs: Vec<Vec<char>> = combinations(&xs, k).collect();
+            assert_eq!(combs.len() as u64, binomial(6, k as u64).unwrap());
+            assert!(combs.windows(2).all(|w| w[0] < w[1]));
+        }
+        assert_eq!(combinations(&xs, 0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(combinations(&xs, 7).next(), None);
+
+        let mut it = combinations(&[1, 2], 2);
+        assert_eq!(it.next(), Some(vec![1, 2]));
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next(), None);
+    }
+}
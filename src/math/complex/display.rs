@@ -0,0 +1,251 @@
+//! Formatting and parsing of complex numbers
+use super::Complex;
+use crate::math::{num::Num, poly::display::split_terms};
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{error::Error, fmt, str::FromStr};
+
+/// Formats `x` respecting the precision of `f`, if any.
+fn with_precision<T: fmt::Display>(x: &T, f: &fmt::Formatter<'_>) -> String {
+    match f.precision() {
+        Some(p) => format!("{:.*}", p, x),
+        None => x.to_string(),
+    }
+}
+
+impl<T: Clone + Num + fmt::Display> fmt::Display for Complex<T> {
+    /// Formats the complex number in cartesian form, as in `3+4i` or `3-4i`.
+    /// A precision, if given, is applied to both the real and imaginary
+    /// parts.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// assert_eq!(Complex::new(3, -4).to_string(), "3-4i");
+    /// assert_eq!(format!("{:.2}", Complex::new(0.5, 1.0)), "0.50+1.00i");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let re = with_precision(&self.re, f);
+        let im = with_precision(&self.im, f);
+
+        // As for polynomials, the sign is detected through the formatted
+        // imaginary part so that no ordering is required on `T`
+        match im.strip_prefix('-') {
+            Some(abs) => write!(f, "{}-{}i", re, abs),
+            None => write!(f, "{}+{}i", re, im),
+        }
+    }
+}
+
+/// Polar form `r e^(theta i)` of a complex number, used for formatting.
+///
+/// Example:
+/// ```
+/// use ralg::math::complex::{display::Polar, Complex};
+///
+/// # #[cfg(feature = "std")] {
+/// let z = Complex::new(0.0_f64, -2.0);
+/// assert_eq!(format!("{:.4}", Polar::from(z)), "2.0000e^(-1.5708i)");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Polar<T> {
+    /// Modulus
+    pub r: T,
+
+    /// Argument, in radians
+    pub theta: T,
+}
+
+#[cfg(feature = "std")]
+impl From<Complex<f32>> for Polar<f32> {
+    fn from(z: Complex<f32>) -> Self {
+        let (r, theta) = z.to_polar();
+        Polar { r, theta }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Complex<f64>> for Polar<f64> {
+    fn from(z: Complex<f64>) -> Self {
+        let (r, theta) = z.to_polar_f64();
+        Polar { r, theta }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Polar<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let r = with_precision(&self.r, f);
+        let theta = with_precision(&self.theta, f);
+        write!(f, "{}e^({}i)", r, theta)
+    }
+}
+
+/// Error returned when parsing a complex number fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseComplexError {
+    /// The input contains no terms.
+    Empty,
+
+    /// A term is malformed, e.g. a lone sign.
+    InvalidTerm(String),
+
+    /// A real or imaginary part could not be parsed as the part type.
+    InvalidPart(String),
+}
+
+impl fmt::Display for ParseComplexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => {
+                write!(f, "cannot parse complex number from empty string")
+            }
+            Self::InvalidTerm(t) => write!(f, "invalid term `{}`", t),
+            Self::InvalidPart(p) => write!(f, "invalid part `{}`", p),
+        }
+    }
+}
+
+impl Error for ParseComplexError {}
+
+impl<T: Clone + Num + FromStr> FromStr for Complex<T> {
+    type Err = ParseComplexError;
+
+    /// Parses a complex number in cartesian form, such as `3+4i`, `-2.5i`,
+    /// `7` or `1 - i`. Imaginary terms are suffixed by `i` (or `*i`), terms
+    /// may appear in any order and repeated terms are added up, so that the
+    /// output of `Display` is always accepted.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z: Complex<f64> = "1.5 - 2i".parse().unwrap();
+    /// assert_eq!(z, Complex::new(1.5, -2.0));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if s.is_empty() {
+            return Err(ParseComplexError::Empty);
+        }
+
+        let mut z = Complex::new(T::zero(), T::zero());
+        for term in split_terms(&s) {
+            let invalid = || ParseComplexError::InvalidTerm(term.to_string());
+            let (sign, body) = match term.as_bytes().first() {
+                Some(b'+') => ("", &term[1..]),
+                Some(b'-') => ("-", &term[1..]),
+                _ => ("", term),
+            };
+            if body.is_empty() {
+                return Err(invalid());
+            }
+
+            let (part, imaginary) = match body.strip_suffix('i') {
+                Some(c) => {
+                    let c = c.strip_suffix('*').unwrap_or(c);
+                    (if c.is_empty() { "1" } else { c }, true)
+                }
+                None => (body, false),
+            };
+
+            // The sign is parsed together with the part, so that unsigned
+            // types reject negative parts instead of underflowing
+            let part = format!("{}{}", sign, part);
+            let x: T = part
+                .parse()
+                .map_err(|_| ParseComplexError::InvalidPart(part.clone()))?;
+            if imaginary {
+                z.im = z.im + x;
+            } else {
+                z.re = z.re + x;
+            }
+        }
+        Ok(z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Complex::new(3, 4).to_string(), "3+4i");
+        assert_eq!(Complex::new(-3, -4).to_string(), "-3-4i");
+        assert_eq!(Complex::new(0, 0).to_string(), "0+0i");
+        assert_eq!(Complex::new(1.5, -0.25).to_string(), "1.5-0.25i");
+        assert_eq!(Complex::new(-0.0, -0.0).to_string(), "-0-0i");
+        assert_eq!(format!("{:.1}", Complex::new(1.0, -2.0)), "1.0-2.0i");
+        assert_eq!(Complex::new(7_u8, 1).to_string(), "7+1i");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn polar() {
+        let z: Complex<f32> = Complex::new(-1.0, 0.0);
+        assert_eq!(
+            Polar::from(z),
+            Polar {
+                r: 1.0,
+                theta: core::f32::consts::PI
+            }
+        );
+        assert_eq!(format!("{:.3}", Polar::from(z)), "1.000e^(3.142i)");
+
+        let z: Complex<f64> = Complex::new(0.0, 0.0);
+        assert_eq!(Polar::from(z).to_string(), "0e^(0i)");
+    }
+
+    #[test]
+    fn parse() {
+        let z: Complex<i32> = "3+4i".parse().unwrap();
+        assert_eq!(z, Complex::new(3, 4));
+
+        let z: Complex<i32> = " -3 - 4 * i ".parse().unwrap();
+        assert_eq!(z, Complex::new(-3, -4));
+
+        let z: Complex<f64> = "-i".parse().unwrap();
+        assert_eq!(z, Complex::new(0.0, -1.0));
+
+        let z: Complex<f64> = "2.5e-1i + 1E+2".parse().unwrap();
+        assert_eq!(z, Complex::new(100.0, 0.25));
+
+        let z: Complex<f64> = "7".parse().unwrap();
+        assert_eq!(z, Complex::new(7.0, 0.0));
+
+        let z: Complex<u32> = "1 + i + 2".parse().unwrap();
+        assert_eq!(z, Complex::new(3, 1));
+    }
+
+    #[test]
+    fn parse_errors() {
+        use ParseComplexError::*;
+
+        let err = "  ".parse::<Complex<i32>>();
+        assert_eq!(err, Err(Empty));
+        let err = "1 +".parse::<Complex<i32>>();
+        assert_eq!(err, Err(InvalidTerm("+".to_string())));
+        let err = "1 + 2j".parse::<Complex<i32>>();
+        assert_eq!(err, Err(InvalidPart("2j".to_string())));
+        let err = "1.5 + i".parse::<Complex<i32>>();
+        assert_eq!(err, Err(InvalidPart("1.5".to_string())));
+        let err = "1 - i".parse::<Complex<u32>>();
+        assert_eq!(err, Err(InvalidPart("-1".to_string())));
+    }
+
+    #[test]
+    fn round_trip() {
+        let z = Complex::new(-3, 12);
+        assert_eq!(z.to_string().parse::<Complex<i32>>(), Ok(z));
+
+        let z = Complex::new(0.125, -2.5e-7);
+        assert_eq!(z.to_string().parse::<Complex<f64>>(), Ok(z));
+
+        let z = Complex::new(f32::MAX, f32::MIN_POSITIVE);
+        assert_eq!(z.to_string().parse::<Complex<f32>>(), Ok(z));
+    }
+}
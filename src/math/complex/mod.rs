@@ -0,0 +1,698 @@
+//! An implementation of complex numbers
+use crate::math::{
+    misc::pow_by_squaring,
+    num::{Num, One, Zero},
+};
+use alloc::vec::Vec;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+pub mod display;
+
+/// Complex number
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Complex<T: Clone + Num> {
+    /// Real part
+    pub re: T,
+
+    /// Imaginary part
+    pub im: T,
+}
+
+impl<T: Clone + Num> Complex<T> {
+    /// Create a new complex number
+    pub fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
+
+    /// Imaginary unit
+    pub fn i() -> Self {
+        Complex::new(T::zero(), T::one())
+    }
+
+    /// Norm. Given a complex number `x == a + bi`, returns `a * a + b * b`
+    pub fn norm(x: Self) -> T {
+        x.re.clone() * x.re.clone() + x.im.clone() * x.im
+    }
+
+    /// Given a real number `re`, returns the complex number `re + 0 * i`
+    pub fn from_real(re: T) -> Self {
+        Complex::new(re, T::zero())
+    }
+
+    /// Given a vector of real numbers transforms into a vector of complex
+    /// number with zero imaginary part.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let vec_re = vec![0.1, 4.0, -6.0];
+    /// let vec_cpx = Complex::from_real_vec(vec_re);
+    /// assert_eq!(vec_cpx, vec![
+    ///     Complex::new(0.1, 0.0),
+    ///     Complex::new(4.0, 0.0),
+    ///     Complex::new(-6.0, 0.0)]
+    /// );
+    /// ```
+    pub fn from_real_vec(vec_re: Vec<T>) -> Vec<Complex<T>> {
+        let mut cpx_vec = Vec::new();
+        for re in vec_re {
+            cpx_vec.push(Complex::from_real(re));
+        }
+        cpx_vec
+    }
+}
+
+/// Unfortunately, we still split the cases for `f32` and `f64`, in the future
+/// I'll maybe add a `Float` type that will generalize this. I'm aware of the
+/// `num` crate, but my goal is to build everything from scratch.
+///
+/// These need the `std` feature, which provides the floating point functions.
+#[cfg(feature = "std")]
+impl Complex<f32> {
+    /// Given polar coordinates `r` (radius) and `theta` (angle in radians),
+    /// returns the corresponding complex number.
+    pub fn from_polar(r: f32, theta: f32) -> Self {
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// Compute the exponential function of `self`, returning the corresponding
+    /// complex number.
+    pub fn exp(self) -> Self {
+        Complex::from_polar(self.re.exp(), self.im)
+    }
+
+    /// Modulus of the complex number `self == a + bi`, that is,
+    /// `sqrt(a * a + b * b)`.
+    pub fn abs(self) -> f32 {
+        self.re.hypot(self.im)
+    }
+
+    /// Argument of `self`, that is, the angle in radians between the
+    /// positive real axis and `self`, in the interval `(-pi, pi]`.
+    pub fn arg(self) -> f32 {
+        self.im.atan2(self.re)
+    }
+
+    /// Returns the polar coordinates `(r, theta)` of `self`, the inverse of
+    /// `from_polar`.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let (r, theta) = Complex::new(0.0_f32, 2.0).to_polar();
+    /// assert_eq!(r, 2.0);
+    /// assert_eq!(theta, core::f32::consts::FRAC_PI_2);
+    /// ```
+    pub fn to_polar(self) -> (f32, f32) {
+        (self.abs(), self.arg())
+    }
+
+    /// Principal branch of the natural logarithm of `self`, that is,
+    /// `ln(r) + theta * i` where `(r, theta)` are the polar coordinates of
+    /// `self`.
+    pub fn ln(self) -> Self {
+        let (r, theta) = self.to_polar();
+        Complex::new(r.ln(), theta)
+    }
+
+    /// Raises `self` to the real power `x` using De Moivre's formula
+    /// `(r e^(theta i))^x == r^x e^(x theta i)`, taking the principal value.
+    pub fn powf(self, x: f32) -> Self {
+        let (r, theta) = self.to_polar();
+        Complex::from_polar(r.powf(x), x * theta)
+    }
+
+    /// Raises `self` to the complex power `w`, taking the principal value of
+    /// `e^(w ln(self))`. By convention, `0^0 == 1` and `0^w == 0` otherwise.
+    pub fn powc(self, w: Self) -> Self {
+        if self.re == 0.0 && self.im == 0.0 {
+            return if w.re == 0.0 && w.im == 0.0 {
+                Complex::one()
+            } else {
+                Complex::zero()
+            };
+        }
+        (w * self.ln()).exp()
+    }
+
+    /// Returns the `n` distinct `n`th roots of `self`, starting at the
+    /// principal root and going counterclockwise.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let roots = Complex::new(-8.0_f32, 0.0).nth_roots(3);
+    /// assert_eq!(roots.len(), 3);
+    /// assert!((roots[1].re + 2.0).abs() < 1.0e-6);
+    /// assert!(roots[1].im.abs() < 1.0e-6);
+    /// ```
+    ///
+    /// Panics if `n == 0`.
+    pub fn nth_roots(self, n: usize) -> Vec<Self> {
+        assert!(n > 0, "there are no 0th roots of a complex number");
+        let (r, theta) = self.to_polar();
+        let r = r.powf(1.0 / n as f32);
+        let tau = 2.0 * core::f32::consts::PI;
+        (0..n)
+            .map(|k| {
+                Complex::from_polar(r, (theta + tau * k as f32) / n as f32)
+            })
+            .collect()
+    }
+
+    /// Returns the `n`th root of unity.
+    pub fn root_of_unity(n: i32) -> Self {
+        // e^{theta i} = cos(theta) + sin(theta) * i
+        let theta: f32 = -2.0 * core::f32::consts::PI / n as f32;
+        Complex::new(theta.cos(), theta.sin())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Complex<f64> {
+    /// Given polar coordinates `r` (radius) and `theta` (angle in radians),
+    /// returns the corresponding complex number.
+    pub fn from_polar_f64(r: f64, theta: f64) -> Self {
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// Compute the exponential function of `self`, returning the corresponding
+    /// complex number.
+    pub fn exp_f64(self) -> Self {
+        Complex::from_polar_f64(self.re.exp(), self.im)
+    }
+
+    /// Modulus of the complex number `self == a + bi`, that is,
+    /// `sqrt(a * a + b * b)`.
+    pub fn abs_f64(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// Argument of `self`, that is, the angle in radians between the
+    /// positive real axis and `self`, in the interval `(-pi, pi]`.
+    pub fn arg_f64(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// Returns the polar coordinates `(r, theta)` of `self`, the inverse of
+    /// `from_polar_f64`.
+    pub fn to_polar_f64(self) -> (f64, f64) {
+        (self.abs_f64(), self.arg_f64())
+    }
+
+    /// Principal branch of the natural logarithm of `self`, that is,
+    /// `ln(r) + theta * i` where `(r, theta)` are the polar coordinates of
+    /// `self`.
+    pub fn ln_f64(self) -> Self {
+        let (r, theta) = self.to_polar_f64();
+        Complex::new(r.ln(), theta)
+    }
+
+    /// Raises `self` to the real power `x` using De Moivre's formula
+    /// `(r e^(theta i))^x == r^x e^(x theta i)`, taking the principal value.
+    pub fn powf_f64(self, x: f64) -> Self {
+        let (r, theta) = self.to_polar_f64();
+        Complex::from_polar_f64(r.powf(x), x * theta)
+    }
+
+    /// Raises `self` to the complex power `w`, taking the principal value of
+    /// `e^(w ln(self))`. By convention, `0^0 == 1` and `0^w == 0` otherwise.
+    pub fn powc_f64(self, w: Self) -> Self {
+        if self.re == 0.0 && self.im == 0.0 {
+            return if w.re == 0.0 && w.im == 0.0 {
+                Complex::one()
+            } else {
+                Complex::zero()
+            };
+        }
+        (w * self.ln_f64()).exp_f64()
+    }
+
+    /// Returns the `n` distinct `n`th roots of `self`, starting at the
+    /// principal root and going counterclockwise.
+    ///
+    /// Panics if `n == 0`.
+    pub fn nth_roots_f64(self, n: usize) -> Vec<Self> {
+        assert!(n > 0, "there are no 0th roots of a complex number");
+        let (r, theta) = self.to_polar_f64();
+        let r = r.powf(1.0 / n as f64);
+        let tau = 2.0 * core::f64::consts::PI;
+        (0..n)
+            .map(|k| {
+                Complex::from_polar_f64(r, (theta + tau * k as f64) / n as f64)
+            })
+            .collect()
+    }
+
+    /// Returns the `n`th root of unity.
+    pub fn root_of_unity_f64(n: usize) -> Self {
+        // e^{theta i} = cos(theta) + sin(theta) * i
+        let theta: f64 = 2.0 * core::f64::consts::PI / n as f64;
+        Complex::new(theta.cos(), theta.sin())
+    }
+}
+
+impl<T: Num + Copy + Clone + Div<Output = T>> Complex<T> {
+    pub fn divide(self, rhs: T) -> Self {
+        Complex::new(self.re / rhs, self.im / rhs)
+    }
+}
+
+impl<T: Copy + Num + Div<Output = T> + Neg<Output = T>> Complex<T> {
+    /// Multiplicative inverse of `self`, that is, `conj(self) / norm(self)`.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z = Complex::new(0.0, 2.0);
+    /// assert_eq!(z.inv(), Complex::new(0.0, -0.5));
+    /// ```
+    pub fn inv(self) -> Self {
+        self.conj().divide(Complex::norm(self))
+    }
+
+    /// Raises `self` to the integer power `n` by repeated squaring, which
+    /// takes `O(log(n))` multiplications. Negative powers are computed as
+    /// powers of `self.inv()`.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z = Complex::new(1.0, 1.0);
+    /// assert_eq!(z.powi(4), Complex::new(-4.0, 0.0));
+    /// assert_eq!(z.powi(-2), Complex::new(0.0, -0.5));
+    /// ```
+    pub fn powi(self, n: i32) -> Self {
+        let base = if n < 0 { self.inv() } else { self };
+        pow_by_squaring(base, n.unsigned_abs() as u64)
+    }
+}
+
+impl<T: Clone + Num + Neg<Output = T>> Complex<T> {
+    /// Complex conjugate of a given complex number.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z = Complex::new(4, 5);
+    /// assert_eq!(z.conj(), Complex::new(4, -5));
+    /// ```
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+impl<T: Clone + Num> PartialEq for Complex<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.re == other.re && self.im == other.im
+    }
+}
+
+impl<T: Clone + Num> Add for Complex<T> {
+    type Output = Self;
+
+    /// Addition of complex numbers.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z1 = Complex::new(3.3, 20.0);
+    /// let z2 = Complex::new(4.4, -5.0);
+    /// assert_eq!(z1 + z2, Complex::new(7.7, 15.0));
+    /// ```
+    fn add(self, rhs: Self) -> Self {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: Clone + Num> Add<T> for Complex<T> {
+    type Output = Self;
+
+    /// Addition of a complex number and real number.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z = Complex::new(3.3, 20.0);
+    /// assert_eq!(z + 5.0, Complex::new(8.3, 20.0));
+    /// ```
+    fn add(self, rhs: T) -> Self {
+        Complex::new(self.re + rhs, self.im)
+    }
+}
+
+impl<T: Num + Clone> Zero for Complex<T> {
+    fn zero() -> Self {
+        Complex::new(T::zero(), T::zero())
+    }
+}
+
+impl<T: Num + Clone + Copy> One for Complex<T> {
+    fn one() -> Self {
+        Complex::new(T::one(), T::zero())
+    }
+}
+
+impl<T: Copy + Num + Sub<T, Output = T>> Sub for Complex<T> {
+    type Output = Self;
+
+    /// Subtraction between complex numbers.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z1 = Complex::new(3, 4);
+    /// let z2 = Complex::new(7, 5);
+    /// assert_eq!(z1 - z2, Complex::new(-4, -1));
+    /// ```
+    fn sub(self, rhs: Self) -> Self {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: Copy + Num + Sub<T, Output = T>> Sub<T> for Complex<T> {
+    type Output = Self;
+
+    /// Subtraction of complex numbers by real numbers.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z = Complex::new(3.0, 4.3);
+    /// assert_eq!(z - 2.5, Complex::new(0.5, 4.3));
+    /// ```
+    fn sub(self, rhs: T) -> Self {
+        Complex::new(self.re - rhs, self.im)
+    }
+}
+
+impl<T: Copy + Num + Neg<Output = T>> Neg for Complex<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl<T: Copy + Num + Sub<T, Output = T>> Mul for Complex<T> {
+    type Output = Self;
+
+    /// Multiplication of complex numbers.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z1 = Complex::new(3, 5);
+    /// let z2 = Complex::new(6, 2);
+    /// assert_eq!(z1 * z2, Complex::new(8, 36));
+    ///
+    /// let z = Complex::new(6, 7);
+    /// assert_eq!(z.clone() * z, Complex::new(-13, 84));
+    /// ```
+    fn mul(self, rhs: Self) -> Self {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl<T: Copy + Num> Mul<T> for Complex<T> {
+    type Output = Self;
+
+    /// Multiplication of a complex number by a real number.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z = Complex::new(6, -2);
+    /// assert_eq!(z * -4, Complex::new(-24, 8));
+    /// ```
+    fn mul(self, rhs: T) -> Self {
+        Complex::new(self.re * rhs, self.im * rhs)
+    }
+}
+
+impl<T: Copy + Num + Div<Output = T>> Div for Complex<T> {
+    type Output = Self;
+
+    /// Division of complex numbers, given by
+    /// `(a + bi) / (c + di) == ((ac + bd) + (bc - ad)i) / (c * c + d * d)`.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z1 = Complex::new(8.0, 36.0);
+    /// let z2 = Complex::new(6.0, 2.0);
+    /// assert_eq!(z1 / z2, Complex::new(3.0, 5.0));
+    /// ```
+    fn div(self, rhs: Self) -> Self {
+        let n = Complex::norm(rhs);
+        Complex::new(
+            (self.re * rhs.re + self.im * rhs.im) / n,
+            (self.im * rhs.re - self.re * rhs.im) / n,
+        )
+    }
+}
+
+impl<T: Copy + Num + Div<Output = T>> Div<T> for Complex<T> {
+    type Output = Self;
+
+    /// Division of a complex number by a real number.
+    /// Example:
+    /// ```
+    /// use ralg::math::complex::Complex;
+    ///
+    /// let z = Complex::new(6.0, -2.0);
+    /// assert_eq!(z / 4.0, Complex::new(1.5, -0.5));
+    /// ```
+    fn div(self, rhs: T) -> Self {
+        self.divide(rhs)
+    }
+}
+
+/// Since `Complex<T>` is `Copy` for the numeric types we care about, every
+/// variant of a binary operator taking references, as well as its assignment
+/// versions, simply forwards to the by-value implementation.
+macro_rules! forward_ref_binop {
+    ($imp:ident, $method:ident, $imp_assign:ident, $method_assign:ident, $($bound:tt)+) => {
+        impl<T: $($bound)+> $imp<&Complex<T>> for Complex<T> {
+            type Output = Complex<T>;
+
+            fn $method(self, rhs: &Complex<T>) -> Complex<T> {
+                $imp::$method(self, *rhs)
+            }
+        }
+
+        impl<T: $($bound)+> $imp<Complex<T>> for &Complex<T> {
+            type Output = Complex<T>;
+
+            fn $method(self, rhs: Complex<T>) -> Complex<T> {
+                $imp::$method(*self, rhs)
+            }
+        }
+
+        impl<T: $($bound)+> $imp<&Complex<T>> for &Complex<T> {
+            type Output = Complex<T>;
+
+            fn $method(self, rhs: &Complex<T>) -> Complex<T> {
+                $imp::$method(*self, *rhs)
+            }
+        }
+
+        impl<T: $($bound)+> $imp_assign for Complex<T> {
+            fn $method_assign(&mut self, rhs: Complex<T>) {
+                *self = $imp::$method(*self, rhs);
+            }
+        }
+
+        impl<T: $($bound)+> $imp_assign<&Complex<T>> for Complex<T> {
+            fn $method_assign(&mut self, rhs: &Complex<T>) {
+                *self = $imp::$method(*self, *rhs);
+            }
+        }
+
+        impl<T: $($bound)+> $imp_assign<T> for Complex<T> {
+            fn $method_assign(&mut self, rhs: T) {
+                *self = $imp::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+forward_ref_binop!(Add, add, AddAssign, add_assign, Copy + Num);
+forward_ref_binop!(Sub, sub, SubAssign, sub_assign, Copy + Num);
+forward_ref_binop!(Mul, mul, MulAssign, mul_assign, Copy + Num);
+forward_ref_binop!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    Copy + Num + Div<Output = T>
+);
+
+impl<T: Copy + Num> Num for Complex<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn div() {
+        let z1 = Complex::new(3.0, 4.0);
+        let z2 = Complex::new(1.0, -2.0);
+        assert_eq!(z1 / z2, Complex::new(-1.0, 2.0));
+        assert_eq!((z1 / z2) * z2, z1);
+        assert_eq!(z1 / 2.0, Complex::new(1.5, 2.0));
+
+        // Division by zero yields non-finite parts for floats
+        let z: Complex<f64> = z1 / Complex::new(0.0, 0.0);
+        assert!(!z.re.is_finite() && !z.im.is_finite());
+    }
+
+    #[test]
+    fn inv() {
+        let z = Complex::new(3.0, 4.0);
+        assert_eq!(z.inv(), Complex::new(0.12, -0.16));
+        assert_eq!(z * z.inv(), Complex::new(1.0, 0.0));
+        assert_eq!(Complex::new(-2.0, 0.0).inv(), Complex::new(-0.5, 0.0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn polar() {
+        let eps = 1.0e-6;
+        let z: Complex<f32> = Complex::new(3.0, 4.0);
+        assert_eq!(z.abs(), 5.0);
+        assert!((z.arg() - 0.927_295_2).abs() < eps);
+
+        // Round trip through polar coordinates
+        let (r, theta) = z.to_polar();
+        let w = Complex::from_polar(r, theta);
+        assert!((w.re - z.re).abs() < eps && (w.im - z.im).abs() < eps);
+
+        let z: Complex<f64> = Complex::new(-1.0, 0.0);
+        assert_eq!(z.to_polar_f64(), (1.0, core::f64::consts::PI));
+        let z: Complex<f64> = Complex::new(-1.0, -1.0);
+        assert_eq!(z.abs_f64(), core::f64::consts::SQRT_2);
+        assert_eq!(z.arg_f64(), -3.0 * core::f64::consts::FRAC_PI_4);
+        let (r, theta) = z.to_polar_f64();
+        let w = Complex::from_polar_f64(r, theta);
+        assert!((w.re - z.re).abs() < 1.0e-12 && (w.im - z.im).abs() < 1.0e-12);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn ref_ops() {
+        let z1 = Complex::new(3, 5);
+        let z2 = Complex::new(6, 2);
+        assert_eq!(&z1 + &z2, Complex::new(9, 7));
+        assert_eq!(z1 - &z2, Complex::new(-3, 3));
+        assert_eq!(&z1 * z2, Complex::new(8, 36));
+
+        let z1 = Complex::new(8.0, 36.0);
+        let z2 = Complex::new(6.0, 2.0);
+        assert_eq!(&z1 / &z2, Complex::new(3.0, 5.0));
+    }
+
+    #[test]
+    fn assign_ops() {
+        let mut z = Complex::new(1.0, 2.0);
+        z += Complex::new(1.0, -1.0);
+        assert_eq!(z, Complex::new(2.0, 1.0));
+        z -= &Complex::new(0.5, 0.5);
+        assert_eq!(z, Complex::new(1.5, 0.5));
+        z *= Complex::new(2.0, 0.0);
+        assert_eq!(z, Complex::new(3.0, 1.0));
+        z /= &Complex::new(3.0, 1.0);
+        assert_eq!(z, Complex::new(1.0, 0.0));
+
+        z += 1.0;
+        z *= 3.0;
+        z -= 0.5;
+        z /= 2.0;
+        assert_eq!(z, Complex::new(2.75, 0.0));
+    }
+
+    fn close(z: Complex<f64>, w: Complex<f64>) -> bool {
+        (z.re - w.re).abs() < 1.0e-12 && (z.im - w.im).abs() < 1.0e-12
+    }
+
+    #[test]
+    fn powi() {
+        let z = Complex::new(2.0, -1.0);
+        assert_eq!(z.powi(0), Complex::new(1.0, 0.0));
+        assert_eq!(z.powi(1), z);
+        assert_eq!(z.powi(3), z * z * z);
+        assert!(close(z.powi(-3) * z.powi(3), Complex::new(1.0, 0.0)));
+        assert_eq!(Complex::new(0, 1).powi(6), Complex::new(-1, 0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn powf() {
+        let z: Complex<f64> = Complex::new(0.0, 4.0);
+        assert!(close(
+            z.powf_f64(0.5),
+            Complex::new(2.0_f64.sqrt(), 2.0_f64.sqrt())
+        ));
+        assert!(close(z.powf_f64(3.0), z.powi(3)));
+        assert!(close(
+            Complex::new(0.0, 0.0).powf_f64(2.0),
+            Complex::new(0.0, 0.0)
+        ));
+
+        let z: Complex<f32> = Complex::new(-4.0, 0.0);
+        let w = z.powf(0.5);
+        assert!(w.re.abs() < 1.0e-6 && (w.im - 2.0).abs() < 1.0e-6);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn powc() {
+        // i^i == e^(-pi / 2)
+        let i: Complex<f64> = Complex::i();
+        let expected = (-core::f64::consts::FRAC_PI_2).exp();
+        assert!(close(i.powc_f64(i), Complex::new(expected, 0.0)));
+
+        let z: Complex<f64> = Complex::new(1.5, -0.5);
+        assert!(close(z.powc_f64(Complex::new(2.0, 0.0)), z * z));
+
+        let zero: Complex<f64> = Complex::zero();
+        assert_eq!(zero.powc_f64(zero), Complex::one());
+        assert_eq!(zero.powc_f64(i), zero);
+        assert_eq!(
+            Complex::new(0.0_f32, 0.0).powc(Complex::i()),
+            Complex::zero()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn nth_roots() {
+        let z: Complex<f64> = Complex::new(3.0, -7.0);
+        for n in 1..8 {
+            let roots = z.nth_roots_f64(n);
+            assert_eq!(roots.len(), n);
+            assert!(roots.iter().all(|w| close(w.powi(n as i32), z)));
+        }
+
+        // The roots of unity
+        let one: Complex<f64> = Complex::one();
+        let roots = one.nth_roots_f64(4);
+        let expected = [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)];
+        for (w, (re, im)) in roots.into_iter().zip(expected) {
+            assert!(close(w, Complex::new(re, im)));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn zeroth_roots() {
+        Complex::new(1.0_f32, 0.0).nth_roots(0);
+    }
+}
@@ -0,0 +1,225 @@
+//! Numerical differentiation
+//!
+//! Finite differences of step `h` make a truncation error in O(h), for the
+//! forward difference, or O(h^2), for the central one, and a rounding error
+//! in O(eps / h). The default steps balance both, relative to the magnitude
+//! of the point, while Richardson extrapolation cancels the truncation error
+//! of successive central differences.
+use alloc::vec::Vec;
+
+/// Step of `forward_derivative` relative to the point, the square root of
+/// `f64::EPSILON`.
+pub const FORWARD_STEP: f64 = 1.4901161193847656e-8;
+
+/// Step of `derivative` relative to the point, about the cube root of
+/// `f64::EPSILON`.
+pub const CENTRAL_STEP: f64 = 6.055454452393343e-6;
+
+/// Approximation of a derivative.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Derivative {
+    pub value: f64,
+    /// Estimate of the absolute error of `value`.
+    pub error: f64,
+}
+
+/// Forward difference `(f(x + h) - f(x)) / h`.
+pub fn forward<F: Fn(f64) -> f64>(f: F, x: f64, h: f64) -> f64 {
+    (f(x + h) - f(x)) / h
+}
+
+/// Central difference `(f(x + h) - f(x - h)) / 2h`.
+pub fn central<F: Fn(f64) -> f64>(f: F, x: f64, h: f64) -> f64 {
+    (f(x + h) - f(x - h)) / (2.0 * h)
+}
+
+/// Step relative to `x`, rounded so that `x + h` and `x - h` are exactly
+/// `h` away from `x`.
+fn step(x: f64, relative: f64) -> f64 {
+    let h = relative * x.abs().max(1.0);
+    (x + h) - x
+}
+
+/// Derivative of `f` at `x` by a forward difference, for functions that are
+/// only defined on the right of `x`, or too costly to evaluate twice more.
+pub fn forward_derivative<F: Fn(f64) -> f64>(f: F, x: f64) -> f64 {
+    forward(f, x, step(x, FORWARD_STEP))
+}
+
+/// Derivative of `f` at `x` by a central difference, accurate to about
+/// two thirds of the significant digits.
+///
+/// Example:
+/// ```
+/// use ralg::math::diff::derivative;
+///
+/// let d = derivative(|x| x * x * x, 2.0);
+/// assert!((d - 12.0).abs() < 1e-9);
+/// ```
+pub fn derivative<F: Fn(f64) -> f64>(f: F, x: f64) -> f64 {
+    central(f, x, step(x, CENTRAL_STEP))
+}
+
+/// Greatest number of halvings of the step in `richardson`.
+const RICHARDSON_ROWS: usize = 10;
+
+/// Derivative of `f` at `x` by Richardson extrapolation of central
+/// differences, starting from the step `h` and halving it, so that each
+/// column of the tableau cancels the next even power of the step in the
+/// error. Stops when the extrapolations stop improving, returning the one
+/// with the least estimated error. Accurate almost to the last digits for
+/// a smooth `f` and a starting step over which `f` varies smoothly.
+///
+/// Example:
+/// ```
+/// use ralg::math::diff::richardson;
+///
+/// let d = richardson(|x| 1.0 / x, 0.5, 0.1);
+/// assert!((d.value + 4.0).abs() < 1e-12);
+/// assert!(d.error < 1e-10);
+/// ```
+pub fn richardson<F: Fn(f64) -> f64>(f: F, x: f64, h: f64) -> Derivative {
+    let mut table = [[0.0; RICHARDSON_ROWS]; RICHARDSON_ROWS];
+    let mut h = h;
+    table[0][0] = central(&f, x, h);
+    let mut best = Derivative {
+        value: table[0][0],
+        error: f64::INFINITY,
+    };
+    for i in 1..RICHARDSON_ROWS {
+        h /= 2.0;
+        table[i][0] = central(&f, x, h);
+        let mut factor = 1.0;
+        for j in 1..=i {
+            factor *= 4.0;
+            table[i][j] = (factor * table[i][j - 1] - table[i - 1][j - 1])
+                / (factor - 1.0);
+            let error = (table[i][j] - table[i][j - 1])
+                .abs()
+                .max((table[i][j] - table[i - 1][j - 1]).abs());
+            if error <= best.error {
+                best = Derivative {
+                    value: table[i][j],
+                    error,
+                };
+            }
+        }
+        // Rounding errors took over
+        if (table[i][i] - table[i - 1][i - 1]).abs() >= 2.0 * best.error {
+            break;
+        }
+    }
+    best
+}
+
+/// Forward differences `(ys[i + 1] - ys[i]) / h` of samples of a function
+/// taken with a spacing `h`, one fewer than the samples.
+pub fn forward_differences(ys: &[f64], h: f64) -> Vec<f64> {
+    ys.windows(2).map(|w| (w[1] - w[0]) / h).collect()
+}
+
+/// Derivative of a function at each of its samples `ys`, taken with a
+/// spacing `h`: central differences inside, and one-sided differences of
+/// the same second order at the ends, from three samples. With only two
+/// samples, both ends get their forward difference.
+///
+/// Panics if there are fewer than two samples.
+///
+/// Example:
+/// ```
+/// use ralg::math::diff::gradient;
+///
+/// // Samples of x^2 at 0, 0.5, 1, 1.5
+/// let d = gradient(&[0.0, 0.25, 1.0, 2.25], 0.5);
+/// assert_eq!(d, [0.0, 1.0, 2.0, 3.0]);
+/// ```
+pub fn gradient(ys: &[f64], h: f64) -> Vec<f64> {
+    let n = ys.len();
+    assert!(n >= 2, "fewer than two samples");
+    if n == 2 {
+        let d = (ys[1] - ys[0]) / h;
+        return Vec::from([d, d]);
+    }
+    let mut d = Vec::with_capacity(n);
+    d.push((-3.0 * ys[0] + 4.0 * ys[1] - ys[2]) / (2.0 * h));
+    d.extend(ys.windows(3).map(|w| (w[2] - w[0]) / (2.0 * h)));
+    d.push((3.0 * ys[n - 1] - 4.0 * ys[n - 2] + ys[n - 3]) / (2.0 * h));
+    d
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::poly::Polynomial;
+    use alloc::vec;
+
+    #[test]
+    fn orders_of_the_differences() {
+        let f = |x: f64| 1.0 / (1.0 + x * x);
+        let exact = -0.16;
+        let (mut previous_forward, mut previous_central) = (1.0, 1.0);
+        for k in 1..6 {
+            let h = 0.1 / (1 << k) as f64;
+            let forward = (forward(f, 2.0, h) - exact).abs();
+            let central = (central(f, 2.0, h) - exact).abs();
+            assert!(forward < previous_forward / 1.8);
+            assert!(central < previous_central / 3.5);
+            (previous_forward, previous_central) = (forward, central);
+        }
+        assert!((forward_derivative(f, 2.0) - exact).abs() < 1e-7);
+        assert!((derivative(f, 2.0) - exact).abs() < 1e-10);
+        // The step scales with the point
+        let g = |x: f64| x * x;
+        assert!((derivative(g, 1e8) - 2e8).abs() < 1e-2);
+    }
+
+    #[test]
+    fn richardson_on_polynomials() {
+        let p = Polynomial::new(vec![3.0, -1.0, 0.5, 2.0, -0.25, 0.125]);
+        let dp = p.derivative();
+        for x in [-2.0, -0.3, 0.0, 1.0, 4.0] {
+            let d = richardson(|x| p.eval(x), x, 0.5);
+            let exact = dp.eval(x);
+            assert!((d.value - exact).abs() <= 1e-11 * exact.abs().max(1.0));
+            assert!((d.value - exact).abs() <= d.error.max(1e-12));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn richardson_on_transcendental() {
+        for x in [0.1, 1.0, 2.5] {
+            let d = richardson(f64::exp, x, 0.2);
+            assert!((d.value - x.exp()).abs() < 1e-12 * x.exp());
+            let d = richardson(f64::sin, x, 0.2);
+            assert!((d.value - x.cos()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn sampled() {
+        let p = Polynomial::new(vec![1.0, -2.0, 3.0]);
+        let h = 0.25;
+        let ys: Vec<f64> = (0..9).map(|i| p.eval(i as f64 * h)).collect();
+        // Second order differences are exact on quadratics
+        let d = gradient(&ys, h);
+        let dp = p.derivative();
+        for (i, &di) in d.iter().enumerate() {
+            assert!((di - dp.eval(i as f64 * h)).abs() < 1e-12);
+        }
+        let fd = forward_differences(&ys, h);
+        assert_eq!(fd.len(), 8);
+        for (i, &di) in fd.iter().enumerate() {
+            let midpoint = (i as f64 + 0.5) * h;
+            assert!((di - dp.eval(midpoint)).abs() < 1e-12);
+        }
+        assert_eq!(gradient(&[1.0, 2.0], 0.5), [2.0, 2.0]);
+        assert!(forward_differences(&[1.0], 1.0).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn gradient_of_one_sample() {
+        gradient(&[1.0], 1.0);
+    }
+}
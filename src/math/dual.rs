@@ -0,0 +1,355 @@
+//! Dual numbers and forward-mode automatic differentiation
+//!
+//! A dual number `a + b e` satisfies `e * e == 0`, so that for a polynomial
+//! `f`, `f(a + b e) == f(a) + b f'(a) e` by Taylor's formula. Evaluating a
+//! function built from arithmetic operations at `x + e` thus yields its
+//! value and its exact derivative at `x`, without any truncation error.
+use crate::math::{
+    num::{Num, One, Zero},
+    poly::Polynomial,
+};
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+/// Dual number `re + eps * e`, where `e * e == 0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dual<T: Copy + Num> {
+    /// Real part, the value of a function
+    pub re: T,
+
+    /// Dual part, the derivative of a function
+    pub eps: T,
+}
+
+impl<T: Copy + Num> Dual<T> {
+    pub fn new(re: T, eps: T) -> Self {
+        Dual { re, eps }
+    }
+
+    /// Constant `c`, whose derivative vanishes.
+    pub fn constant(c: T) -> Self {
+        Dual::new(c, T::zero())
+    }
+
+    /// Variable of differentiation at the point `x`, of derivative one.
+    pub fn variable(x: T) -> Self {
+        Dual::new(x, T::one())
+    }
+}
+
+/// Value and derivative of `f` at `x`, evaluating it at the dual number
+/// `x + e`.
+///
+/// Example:
+/// ```
+/// use ralg::math::dual::{derivative, Dual};
+///
+/// // d/dx x^2 / (1 + x) = (x^2 + 2x) / (1 + x)^2
+/// let f = |x: Dual<f64>| x * x / (x + 1.0);
+/// assert_eq!(derivative(f, 1.0), (0.5, 0.75));
+/// ```
+pub fn derivative<T, F>(f: F, x: T) -> (T, T)
+where
+    T: Copy + Num,
+    F: Fn(Dual<T>) -> Dual<T>,
+{
+    let y = f(Dual::variable(x));
+    (y.re, y.eps)
+}
+
+impl<T: Copy + Num> Polynomial<T> {
+    /// Evaluates the polynomial at a dual number by Horner's method, which
+    /// gives both `p(x)` and `p'(x)` for `x + e`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::{dual::Dual, poly::Polynomial};
+    ///
+    /// // p = 1 + 2x + 3x^2, p' = 2 + 6x
+    /// let p = Polynomial::new(vec![1, 2, 3]);
+    /// assert_eq!(p.eval_dual(Dual::variable(2)), Dual::new(17, 14));
+    /// ```
+    pub fn eval_dual(&self, x: Dual<T>) -> Dual<T> {
+        self.coeff
+            .iter()
+            .rev()
+            .fold(Dual::zero(), |acc, &c| acc * x + c)
+    }
+}
+
+/// Elementary functions, following the chain rule `f(a + b e) = f(a) +
+/// b f'(a) e`. These need the `std` feature, which provides the floating
+/// point functions.
+#[cfg(feature = "std")]
+impl Dual<f64> {
+    pub fn exp(self) -> Self {
+        let e = self.re.exp();
+        Dual::new(e, self.eps * e)
+    }
+
+    /// Natural logarithm, defined for a positive real part.
+    pub fn ln(self) -> Self {
+        Dual::new(self.re.ln(), self.eps / self.re)
+    }
+
+    pub fn sin(self) -> Self {
+        Dual::new(self.re.sin(), self.eps * self.re.cos())
+    }
+
+    pub fn cos(self) -> Self {
+        Dual::new(self.re.cos(), -self.eps * self.re.sin())
+    }
+
+    /// Square root, differentiable for a positive real part.
+    pub fn sqrt(self) -> Self {
+        let s = self.re.sqrt();
+        Dual::new(s, self.eps / (2.0 * s))
+    }
+
+    pub fn powi(self, n: i32) -> Self {
+        let p = self.re.powi(n - 1);
+        Dual::new(p * self.re, self.eps * n as f64 * p)
+    }
+
+    pub fn powf(self, x: f64) -> Self {
+        let p = self.re.powf(x - 1.0);
+        Dual::new(p * self.re, self.eps * x * p)
+    }
+}
+
+impl<T: Copy + Num> From<T> for Dual<T> {
+    fn from(c: T) -> Self {
+        Dual::constant(c)
+    }
+}
+
+impl<T: Copy + Num> Zero for Dual<T> {
+    fn zero() -> Self {
+        Dual::new(T::zero(), T::zero())
+    }
+}
+
+impl<T: Copy + Num> One for Dual<T> {
+    fn one() -> Self {
+        Dual::new(T::one(), T::zero())
+    }
+}
+
+impl<T: Copy + Num> Add for Dual<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Dual::new(self.re + rhs.re, self.eps + rhs.eps)
+    }
+}
+
+impl<T: Copy + Num> Add<T> for Dual<T> {
+    type Output = Self;
+
+    fn add(self, rhs: T) -> Self {
+        Dual::new(self.re + rhs, self.eps)
+    }
+}
+
+impl<T: Copy + Num> Sub for Dual<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Dual::new(self.re - rhs.re, self.eps - rhs.eps)
+    }
+}
+
+impl<T: Copy + Num> Sub<T> for Dual<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self {
+        Dual::new(self.re - rhs, self.eps)
+    }
+}
+
+impl<T: Copy + Num + Neg<Output = T>> Neg for Dual<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Dual::new(-self.re, -self.eps)
+    }
+}
+
+impl<T: Copy + Num> Mul for Dual<T> {
+    type Output = Self;
+
+    /// Product, following the product rule `(uv)' = u'v + uv'`.
+    fn mul(self, rhs: Self) -> Self {
+        Dual::new(self.re * rhs.re, self.eps * rhs.re + self.re * rhs.eps)
+    }
+}
+
+impl<T: Copy + Num> Mul<T> for Dual<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Dual::new(self.re * rhs, self.eps * rhs)
+    }
+}
+
+impl<T: Copy + Num + Div<Output = T>> Div for Dual<T> {
+    type Output = Self;
+
+    /// Quotient, following the quotient rule `(u/v)' = (u'v - uv') / v^2`.
+    fn div(self, rhs: Self) -> Self {
+        Dual::new(
+            self.re / rhs.re,
+            (self.eps * rhs.re - self.re * rhs.eps) / (rhs.re * rhs.re),
+        )
+    }
+}
+
+impl<T: Copy + Num + Div<Output = T>> Div<T> for Dual<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        Dual::new(self.re / rhs, self.eps / rhs)
+    }
+}
+
+/// Every variant of a binary operator taking references, as well as its
+/// assignment versions, forwards to the by-value implementation.
+macro_rules! forward_ref_binop {
+    ($imp:ident, $method:ident, $imp_assign:ident, $method_assign:ident, $($bound:tt)+) => {
+        impl<T: $($bound)+> $imp<&Dual<T>> for Dual<T> {
+            type Output = Dual<T>;
+
+            fn $method(self, rhs: &Dual<T>) -> Dual<T> {
+                $imp::$method(self, *rhs)
+            }
+        }
+
+        impl<T: $($bound)+> $imp<Dual<T>> for &Dual<T> {
+            type Output = Dual<T>;
+
+            fn $method(self, rhs: Dual<T>) -> Dual<T> {
+                $imp::$method(*self, rhs)
+            }
+        }
+
+        impl<T: $($bound)+> $imp<&Dual<T>> for &Dual<T> {
+            type Output = Dual<T>;
+
+            fn $method(self, rhs: &Dual<T>) -> Dual<T> {
+                $imp::$method(*self, *rhs)
+            }
+        }
+
+        impl<T: $($bound)+> $imp_assign for Dual<T> {
+            fn $method_assign(&mut self, rhs: Dual<T>) {
+                *self = $imp::$method(*self, rhs);
+            }
+        }
+
+        impl<T: $($bound)+> $imp_assign<&Dual<T>> for Dual<T> {
+            fn $method_assign(&mut self, rhs: &Dual<T>) {
+                *self = $imp::$method(*self, *rhs);
+            }
+        }
+
+        impl<T: $($bound)+> $imp_assign<T> for Dual<T> {
+            fn $method_assign(&mut self, rhs: T) {
+                *self = $imp::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+forward_ref_binop!(Add, add, AddAssign, add_assign, Copy + Num);
+forward_ref_binop!(Sub, sub, SubAssign, sub_assign, Copy + Num);
+forward_ref_binop!(Mul, mul, MulAssign, mul_assign, Copy + Num);
+forward_ref_binop!(
+    Div,
+    div,
+    DivAssign,
+    div_assign,
+    Copy + Num + Div<Output = T>
+);
+
+impl<T: Copy + Num> Num for Dual<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::{num::Rational, roots::newton};
+    use crate::random::Pcg32;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn polynomials() {
+        let mut rng = Pcg32::seed(0xd0a1);
+        for _ in 0..50 {
+            let coeff: Vec<i64> = (0..rng.gen_range(0..10))
+                .map(|_| rng.gen_range(-9..10))
+                .collect();
+            let p = Polynomial::new(coeff);
+            let dp = p.derivative();
+            let x = rng.gen_range(-5..6);
+            let y = p.eval_dual(Dual::variable(x));
+            assert_eq!(y, Dual::new(p.eval(x), dp.eval(x)));
+
+            // Same through a polynomial of dual numbers
+            let q =
+                Polynomial::new(p.coeff.iter().map(|&c| c.into()).collect());
+            assert_eq!(q.eval(Dual::variable(x)), y);
+        }
+    }
+
+    #[test]
+    fn rational_functions() {
+        // f = (x^2 - 1) / (x^2 + 1), f' = 4x / (x^2 + 1)^2
+        let f = |x: Dual<Rational>| {
+            let one = Rational::from(1);
+            (x * x - one) / (x * x + one)
+        };
+        let x = Rational::new(1, 2);
+        let (y, dy) = derivative(f, x);
+        assert_eq!(y, Rational::new(-3, 5));
+        assert_eq!(dy, Rational::new(32, 25));
+
+        let mut z = Dual::variable(3.0);
+        z *= 2.0;
+        z -= Dual::new(1.0, 0.5);
+        z /= &Dual::constant(2.0);
+        assert_eq!(z, Dual::new(2.5, 0.75));
+        assert_eq!(-z, Dual::new(-2.5, -0.75));
+    }
+
+    #[test]
+    fn exact_newton_steps() {
+        let f = |x: Dual<f64>| x * x * x - x * 2.0 - 5.0;
+        let root = newton(
+            |x| f(Dual::constant(x)).re,
+            |x| derivative(f, x).1,
+            2.0,
+            1e-15,
+            50,
+        );
+        assert!((root.unwrap() - 2.0945514815423265).abs() < 1e-15);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn elementary_functions() {
+        for x in [0.3, 1.0, 2.7] {
+            // d/dx exp(sin(x)) = cos(x) exp(sin(x))
+            let (y, dy) = derivative(|x| x.sin().exp(), x);
+            assert!((y - x.sin().exp()).abs() < 1e-15);
+            assert!((dy - x.cos() * x.sin().exp()).abs() < 1e-14);
+            // d/dx ln(x) sqrt(x) = (2 + ln(x)) / (2 sqrt(x))
+            let (_, dy) = derivative(|x| x.ln() * x.sqrt(), x);
+            assert!((dy - (2.0 + x.ln()) / (2.0 * x.sqrt())).abs() < 1e-14);
+            let (_, dy) = derivative(|x| x.cos().powi(3), x);
+            assert!((dy + 3.0 * x.cos().powi(2) * x.sin()).abs() < 1e-14);
+            let (_, dy) = derivative(|x| x.powf(1.5), x);
+            assert!((dy - 1.5 * x.sqrt()).abs() < 1e-14);
+        }
+    }
+}
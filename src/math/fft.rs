@@ -1,4 +1,9 @@
-use super::{complex::Complex, misc::next_power_of_2, poly::Polynomial};
+use super::{
+    complex::Complex,
+    misc::{bit_reverse, next_power_of_2},
+    poly::Polynomial,
+};
+use alloc::{vec, vec::Vec};
 
 /// Fast Fourier Transform (FFT): we use the Cooley-Tukey algorithm, which in
 /// this implementation will require zero-padding of the polynomial coefficients
@@ -9,13 +14,365 @@ use super::{complex::Complex, misc::next_power_of_2, poly::Polynomial};
 ///
 /// `fft` returns a vector consisting of the evaluation of `p` at each
 /// root-of-unity.
-pub fn fft(mut p: Polynomial<f32>) -> Vec<Complex<f32>> {
+pub fn fft(p: Polynomial<f32>) -> Vec<Complex<f32>> {
+    fft_observed(p, &mut ())
+}
+
+/// Instrumentation hook for `fft_observed`, which is called with each output
+/// coefficient of the transform. The library never prints anything by
+/// itself: instrumentation is opt-in by passing an observer.
+///
+/// The unit type `()` is the observer that does nothing, and any closure
+/// `FnMut(usize, Complex<f32>)` is an observer.
+pub trait FftObserver {
+    /// Called with the index `k` and value `y` of each output coefficient,
+    /// in increasing order of `k`.
+    fn observe(&mut self, k: usize, y: Complex<f32>);
+}
+
+impl FftObserver for () {
+    fn observe(&mut self, _k: usize, _y: Complex<f32>) {}
+}
+
+impl<F: FnMut(usize, Complex<f32>)> FftObserver for F {
+    fn observe(&mut self, k: usize, y: Complex<f32>) {
+        self(k, y)
+    }
+}
+
+/// Same as `fft`, but reports each output coefficient to `observer`.
+///
+/// Example:
+/// ```
+/// use ralg::math::{fft::fft_observed, poly::Polynomial};
+///
+/// let mut trace = Vec::new();
+/// let p = Polynomial::new(vec![1.0, 2.0]);
+/// let y = fft_observed(p, &mut |k, y| trace.push(format!("y[{}] = {}", k, y)));
+/// assert_eq!(y.len(), 2);
+/// assert_eq!(trace, ["y[0] = 3+0i", "y[1] = -1+0i"]);
+/// ```
+pub fn fft_observed<O: FftObserver + ?Sized>(
+    mut p: Polynomial<f32>,
+    observer: &mut O,
+) -> Vec<Complex<f32>> {
     // Zero padding, if necessary
-    let n2 = next_power_of_2(p.coeff.len());
+    let n2 = next_power_of_2(p.coeff.len())
+        .expect("transform length overflows usize");
     if n2 != p.coeff.len() {
         p.set_degree_bound(n2 - 1);
     }
-    fft_recursive(Complex::from_real_vec(p.coeff))
+    let y = fft_recursive(Complex::from_real_vec(p.coeff));
+    for (k, &z) in y.iter().enumerate() {
+        observer.observe(k, z);
+    }
+    y
+}
+
+/// Inverse Fast Fourier Transform: given the evaluations `y` of a polynomial
+/// at the roots of unity (as returned by `fft`), recovers its coefficients.
+/// Panics unless the length of `y` is a power of 2.
+///
+/// We use the fact that the inverse DFT can be computed by the forward DFT
+/// of the conjugate: `ifft(y) = conj(fft(conj(y))) / n`.
+pub fn ifft(y: Vec<Complex<f32>>) -> Vec<Complex<f32>> {
+    let n = y.len();
+    if n == 0 {
+        return y;
+    }
+    assert!(n.is_power_of_two(), "FFT length {} is not a power of 2", n);
+
+    fft_recursive(y.into_iter().map(|z| z.conj()).collect())
+        .into_iter()
+        .map(|z| z.conj().divide(n as f32))
+        .collect()
+}
+
+/// Exact Discrete Fourier Transform of `v` for any length `n`, without
+/// zero-padding: returns `y` with `y[k] == sum(v[j] * w^(jk))` where `w` is
+/// the primitive `n`th root of unity `e^(-2 pi i / n)`. This agrees with `fft`
+/// whenever `n` is a power of 2.
+///
+/// Other lengths are handled by Bluestein's chirp-z algorithm: using the
+/// identity `jk == (j^2 + k^2 - (k - j)^2) / 2`, the transform is rewritten as
+/// the convolution of `v[j] * c[j]` with `conj(c)`, where
+/// `c[j] = e^(-pi i j^2 / n)` is the "chirp". The convolution is then
+/// computed by power of 2 FFTs of length at least `2n - 1`, so the whole
+/// transform still takes `O(n log(n))` time.
+///
+/// Example:
+/// ```
+/// use ralg::{assert_approx_eq, math::{complex::Complex, fft::dft_exact}};
+///
+/// let y = dft_exact(Complex::from_real_vec(vec![1.0, 2.0, 3.0]));
+/// let expected = vec![
+///     Complex::new(6.0, 0.0),
+///     Complex::new(-1.5, 0.866_025_4),
+///     Complex::new(-1.5, -0.866_025_4),
+/// ];
+/// assert_approx_eq!(y, expected, 1.0e-5);
+/// ```
+pub fn dft_exact(v: Vec<Complex<f32>>) -> Vec<Complex<f32>> {
+    let n = v.len();
+    if n == 0 {
+        return v;
+    }
+    if n.is_power_of_two() {
+        return fft_recursive(v);
+    }
+
+    // Reducing `j^2` modulo `2n` keeps the angle of the chirp small, which
+    // avoids losing precision for large indices
+    let chirp: Vec<Complex<f32>> = (0..n)
+        .map(|j| {
+            let j2 = (j * j) % (2 * n);
+            let theta = -core::f32::consts::PI * j2 as f32 / n as f32;
+            Complex::from_polar(1.0, theta)
+        })
+        .collect();
+
+    let m =
+        next_power_of_2(2 * n - 1).expect("transform length overflows usize");
+    let zero = Complex::new(0.0, 0.0);
+    let mut a = vec![zero; m];
+    for (j, (x, c)) in v.into_iter().zip(&chirp).enumerate() {
+        a[j] = x * c;
+    }
+
+    // Kernel `conj(c[j])` for `-n < j < n`, wrapped around cyclically
+    let mut b = vec![zero; m];
+    b[0] = chirp[0].conj();
+    for j in 1..n {
+        b[j] = chirp[j].conj();
+        b[m - j] = chirp[j].conj();
+    }
+
+    let ya = fft_recursive(a);
+    let yb = fft_recursive(b);
+    let conv = ifft(ya.into_iter().zip(yb).map(|(za, zb)| za * zb).collect());
+    conv.into_iter().zip(chirp).map(|(z, c)| z * c).collect()
+}
+
+/// Real Fast Fourier Transform: computes the same transform as `fft` for a
+/// real-valued input `x`, zero-padded to a length `n` that is a power of 2,
+/// but returns only the first `n / 2 + 1` bins. The remaining ones are
+/// determined by the conjugate symmetry `y[n - k] == conj(y[k])` of the
+/// transform of real sequences.
+///
+/// The even and odd indexed samples of `x` are packed as the real and
+/// imaginary parts of a complex sequence of length `n / 2`, whose transform is
+/// then unpacked into the transform of `x`, so that only half of the work of
+/// `fft` is done.
+///
+/// Example:
+/// ```
+/// use ralg::{assert_approx_eq, math::{complex::Complex, fft::rfft}};
+///
+/// let y = rfft(vec![0.0, 1.0, 3.0, 7.0]);
+/// let expected = vec![
+///     Complex::new(11.0, 0.0),
+///     Complex::new(-3.0, 6.0),
+///     Complex::new(-5.0, 0.0),
+/// ];
+/// assert_approx_eq!(y, expected, 1.0e-5);
+/// ```
+pub fn rfft(mut x: Vec<f32>) -> Vec<Complex<f32>> {
+    if x.len() < 2 {
+        return Complex::from_real_vec(x);
+    }
+
+    let n = next_power_of_2(x.len()).expect("transform length overflows usize");
+    x.resize(n, 0.0);
+    let m = n / 2;
+    let z = fft_recursive(
+        x.chunks(2)
+            .map(|pair| Complex::new(pair[0], pair[1]))
+            .collect(),
+    );
+
+    (0..=m)
+        .map(|k| {
+            let zk = z[k % m];
+            let zc = z[(m - k) % m].conj();
+            let even = (zk + zc).divide(2.0);
+            let odd = (zk - zc) * Complex::new(0.0, -0.5);
+            even + twiddle(k, n) * odd
+        })
+        .collect()
+}
+
+/// Inverse of `rfft`: given the first `n / 2 + 1` bins `y` of the transform
+/// of a real sequence of length `n`, recovers that sequence. Panics unless `n`
+/// is a power of 2.
+pub fn irfft(y: Vec<Complex<f32>>) -> Vec<f32> {
+    if y.len() < 2 {
+        return y.into_iter().map(|z| z.re).collect();
+    }
+
+    let m = y.len() - 1;
+    let n = 2 * m;
+    assert!(n.is_power_of_two(), "FFT length {} is not a power of 2", n);
+    let z = (0..m)
+        .map(|k| {
+            let yc = y[m - k].conj();
+            let even = (y[k] + yc).divide(2.0);
+            let odd = (y[k] - yc).divide(2.0) * twiddle(k, n).conj();
+            even + odd * Complex::i()
+        })
+        .collect();
+
+    ifft(z).into_iter().flat_map(|z| [z.re, z.im]).collect()
+}
+
+/// Two-dimensional Fast Fourier Transform of the matrix `a`, given as a
+/// vector of rows: the one-dimensional transform is applied to each row and
+/// then to each column of the result.
+///
+/// As with `fft`, both dimensions are zero-padded to the next power of 2, and
+/// rows of different lengths are padded to a common length.
+///
+/// Example:
+/// ```
+/// use ralg::math::{complex::Complex, fft::fft2};
+///
+/// let a = vec![
+///     vec![Complex::new(1.0, 0.0), Complex::new(2.0, 0.0)],
+///     vec![Complex::new(3.0, 0.0), Complex::new(4.0, 0.0)],
+/// ];
+/// let y = fft2(a);
+/// assert_eq!(y[0][0], Complex::new(10.0, 0.0));
+/// assert_eq!(y[0][1], Complex::new(-2.0, 0.0));
+/// assert_eq!(y[1][0], Complex::new(-4.0, 0.0));
+/// assert_eq!(y[1][1], Complex::new(0.0, 0.0));
+/// ```
+pub fn fft2(mut a: Vec<Vec<Complex<f32>>>) -> Vec<Vec<Complex<f32>>> {
+    if a.is_empty() {
+        return a;
+    }
+
+    let cols = next_power_of_2(a.iter().map(Vec::len).max().unwrap_or(0))
+        .expect("transform length overflows usize");
+    let rows =
+        next_power_of_2(a.len()).expect("transform length overflows usize");
+    a.resize(rows, Vec::new());
+    for row in a.iter_mut() {
+        row.resize(cols, Complex::new(0.0, 0.0));
+    }
+    transform_2d(a, fft_recursive)
+}
+
+/// Inverse of `fft2`. Both dimensions of `y` should be powers of 2 and all of
+/// its rows should have the same length.
+pub fn ifft2(y: Vec<Vec<Complex<f32>>>) -> Vec<Vec<Complex<f32>>> {
+    transform_2d(y, ifft)
+}
+
+/// Applies the one-dimensional `transform` to the rows and then to the
+/// columns of the rectangular matrix `a`.
+fn transform_2d<F>(
+    a: Vec<Vec<Complex<f32>>>,
+    transform: F,
+) -> Vec<Vec<Complex<f32>>>
+where
+    F: Fn(Vec<Complex<f32>>) -> Vec<Complex<f32>>,
+{
+    let a = a.into_iter().map(&transform).collect();
+    let a = transpose(a).into_iter().map(&transform).collect();
+    transpose(a)
+}
+
+fn transpose(a: Vec<Vec<Complex<f32>>>) -> Vec<Vec<Complex<f32>>> {
+    let cols = a.first().map_or(0, Vec::len);
+    (0..cols)
+        .map(|j| a.iter().map(|row| row[j]).collect())
+        .collect()
+}
+
+/// Plan for repeated in-place transforms of buffers of a fixed length `n`,
+/// which must be a power of 2. The twiddle factors and the bit-reversal
+/// permutation are computed once when creating the plan, and each transform
+/// is then an iterative Cooley-Tukey FFT using no extra memory.
+///
+/// Example:
+/// ```
+/// use ralg::{assert_approx_eq, math::{complex::Complex, fft::FftPlanner}};
+///
+/// let plan = FftPlanner::new(4);
+/// let mut buf = Complex::from_real_vec(vec![0.0, 1.0, 3.0, 7.0]);
+/// plan.forward(&mut buf);
+/// assert_eq!(buf[0], Complex::new(11.0, 0.0));
+///
+/// plan.inverse(&mut buf);
+/// assert_approx_eq!(buf, Complex::from_real_vec(vec![0.0, 1.0, 3.0, 7.0]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct FftPlanner {
+    /// Powers `w^k` for `0 <= k < n / 2` of the `n`th root of unity `w`.
+    twiddles: Vec<Complex<f32>>,
+
+    /// Bit-reversal permutation of the indices `0..n`.
+    rev: Vec<usize>,
+}
+
+impl FftPlanner {
+    /// Creates a plan for transforms of length `n`. Panics if `n` is not a
+    /// power of 2.
+    pub fn new(n: usize) -> Self {
+        assert!(n.is_power_of_two(), "FFT length {} is not a power of 2", n);
+        let bits = n.trailing_zeros();
+        let rev = (0..n).map(|i| bit_reverse(i, bits)).collect();
+        FftPlanner {
+            twiddles: (0..n / 2).map(|k| twiddle(k, n)).collect(),
+            rev,
+        }
+    }
+
+    /// Length of the buffers transformed by this plan.
+    pub fn size(&self) -> usize {
+        self.rev.len()
+    }
+
+    /// Replaces `buf` by its transform, as computed by `fft`. Panics if the
+    /// length of `buf` differs from the size of the plan.
+    pub fn forward(&self, buf: &mut [Complex<f32>]) {
+        let n = self.size();
+        assert_eq!(buf.len(), n, "buffer length differs from the plan size");
+
+        for (i, &j) in self.rev.iter().enumerate() {
+            if i < j {
+                buf.swap(i, j);
+            }
+        }
+
+        let mut len = 2;
+        while len <= n {
+            let step = n / len;
+            for block in buf.chunks_mut(len) {
+                let (lo, hi) = block.split_at_mut(len / 2);
+                for (j, (a, b)) in lo.iter_mut().zip(hi).enumerate() {
+                    let t = self.twiddles[j * step] * *b;
+                    *b = *a - t;
+                    *a += t;
+                }
+            }
+            len *= 2;
+        }
+    }
+
+    /// Replaces `buf` by its inverse transform, as computed by `ifft`. Panics
+    /// if the length of `buf` differs from the size of the plan.
+    pub fn inverse(&self, buf: &mut [Complex<f32>]) {
+        buf.iter_mut().for_each(|z| *z = z.conj());
+        self.forward(buf);
+        let n = buf.len() as f32;
+        buf.iter_mut().for_each(|z| *z = z.conj().divide(n));
+    }
+}
+
+/// Returns `w^k`, where `w` is the `n`th root of unity used by `fft`.
+fn twiddle(k: usize, n: usize) -> Complex<f32> {
+    let theta = -2.0 * core::f32::consts::PI * k as f32 / n as f32;
+    Complex::from_polar(1.0, theta)
 }
 
 fn fft_recursive(mut v: Vec<Complex<f32>>) -> Vec<Complex<f32>> {
@@ -42,11 +399,11 @@ fn fft_recursive(mut v: Vec<Complex<f32>>) -> Vec<Complex<f32>> {
     let y_even = fft_recursive(v_even);
     let y_odd = fft_recursive(v_odd);
 
-    for j in 0..n/2 {
+    for j in 0..n / 2 {
         let t = omega * y_odd[j];
         v[j] = y_even[j] + t;
-        v[j + n/2] = y_even[j] - t;
-        omega = root_n * omega;
+        v[j + n / 2] = y_even[j] - t;
+        omega *= root_n;
     }
     v
 }
@@ -54,15 +411,7 @@ fn fft_recursive(mut v: Vec<Complex<f32>>) -> Vec<Complex<f32>> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::math::poly::Polynomial;
-
-    fn check_result(result: Vec<Complex<f32>>, expected: Vec<Complex<f32>>) {
-        let eps = 1.0e-6;
-        for (r, e) in result.iter().zip(expected) {
-            assert!((r.re - e.re).abs() < eps);
-            assert!((r.im - e.im).abs() < eps);
-        }
-    }
+    use crate::{assert_approx_eq, math::poly::Polynomial};
 
     #[test]
     fn _fft() {
@@ -73,7 +422,7 @@ mod test {
             Complex::new(-5.0, 0.0),
             Complex::new(-3.0, -6.0),
         ];
-        check_result(fft(p), expected);
+        assert_approx_eq!(fft(p), expected);
 
         let p = Polynomial::new(vec![1.0, 3.0, 4.0, 6.0, 7.0, 8.0, 0.0, 0.0]);
         let expected = vec![
@@ -86,7 +435,7 @@ mod test {
             Complex::new(4.0, 5.0),
             Complex::new(-13.778_174, 4.707_106_7),
         ];
-        check_result(fft(p), expected);
+        assert_approx_eq!(fft(p), expected);
 
         let p = Polynomial::new(vec![0.0, 1.0, 3.0, 7.0, 8.0]);
         let expected = vec![
@@ -99,6 +448,214 @@ mod test {
             Complex::new(5.0, -6.0),
             Complex::new(-12.242_64, 8.656_854),
         ];
-        check_result(fft(p), expected);
+        assert_approx_eq!(fft(p), expected);
+    }
+
+    #[test]
+    fn observer() {
+        struct Count(usize);
+
+        impl FftObserver for Count {
+            fn observe(&mut self, k: usize, _y: Complex<f32>) {
+                assert_eq!(k, self.0);
+                self.0 += 1;
+            }
+        }
+
+        let p = Polynomial::new(vec![0.0, 1.0, 3.0, 7.0, 8.0]);
+        let mut count = Count(0);
+        let y = fft_observed(p.clone(), &mut count);
+        assert_eq!(count.0, 8);
+        assert_approx_eq!(y, fft(p.clone()));
+
+        let mut seen = Vec::new();
+        let y = fft_observed(p, &mut |_, z| seen.push(z));
+        assert_eq!(seen, y);
+    }
+
+    #[test]
+    fn _ifft() {
+        let y = vec![
+            Complex::new(11.0, 0.0),
+            Complex::new(-3.0, 6.0),
+            Complex::new(-5.0, 0.0),
+            Complex::new(-3.0, -6.0),
+        ];
+        let expected = Complex::from_real_vec(vec![0.0, 1.0, 3.0, 7.0]);
+        assert_approx_eq!(ifft(y), expected);
+
+        // Round trip
+        let coeff = vec![1.0, 3.0, 4.0, 6.0, 7.0, 8.0, 0.0, 0.0];
+        let p = Polynomial::new(coeff.clone());
+        assert_approx_eq!(ifft(fft(p)), Complex::from_real_vec(coeff));
+
+        assert!(ifft(vec![]).is_empty());
+    }
+
+    /// Direct O(n^2) evaluation of the DFT, computed in double precision.
+    fn naive_dft(v: &[Complex<f32>]) -> Vec<Complex<f32>> {
+        let n = v.len();
+        (0..n)
+            .map(|k| {
+                let (mut re, mut im) = (0.0_f64, 0.0_f64);
+                for (j, x) in v.iter().enumerate() {
+                    let theta =
+                        -2.0 * core::f64::consts::PI * ((j * k) % n) as f64
+                            / n as f64;
+                    let (xr, xi) = (x.re as f64, x.im as f64);
+                    re += xr * theta.cos() - xi * theta.sin();
+                    im += xr * theta.sin() + xi * theta.cos();
+                }
+                Complex::new(re as f32, im as f32)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn _dft_exact() {
+        for n in [1, 2, 3, 5, 6, 7, 8, 12, 17, 100] {
+            let v: Vec<Complex<f32>> = (0..n)
+                .map(|j| Complex::new((j % 7) as f32 - 3.0, (j % 3) as f32))
+                .collect();
+            let expected = naive_dft(&v);
+            assert_approx_eq!(dft_exact(v), expected, 1.0e-3);
+        }
+
+        // Agrees with `fft` for powers of 2
+        let coeff = vec![0.0, 1.0, 3.0, 7.0];
+        assert_approx_eq!(
+            dft_exact(Complex::from_real_vec(coeff.clone())),
+            fft(Polynomial::new(coeff)),
+        );
+
+        assert!(dft_exact(vec![]).is_empty());
+    }
+
+    #[test]
+    fn planner() {
+        for n in [1, 2, 4, 8, 64] {
+            let plan = FftPlanner::new(n);
+            assert_eq!(plan.size(), n);
+
+            // The same plan is reused for several buffers
+            for shift in 0..3 {
+                let coeff: Vec<f32> =
+                    (0..n).map(|j| ((j + shift) % 5) as f32 - 2.0).collect();
+                let mut buf = Complex::from_real_vec(coeff.clone());
+                plan.forward(&mut buf);
+                let expected = fft(Polynomial::new(coeff.clone()));
+                assert_approx_eq!(buf.clone(), expected, 1.0e-4);
+
+                plan.inverse(&mut buf);
+                assert_approx_eq!(buf, Complex::from_real_vec(coeff), 1.0e-5);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn ifft_not_power_of_2() {
+        ifft(vec![Complex::new(1.0, 0.0); 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn irfft_not_power_of_2() {
+        // The bins of a sequence of length 6
+        irfft(vec![Complex::new(1.0, 0.0); 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn planner_not_power_of_2() {
+        FftPlanner::new(6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn planner_wrong_length() {
+        let plan = FftPlanner::new(4);
+        plan.forward(&mut [Complex::new(1.0, 0.0); 8]);
+    }
+
+    #[test]
+    fn _rfft() {
+        let x = vec![1.0, 3.0, 4.0, 6.0, 7.0, 8.0, 0.0, 0.0];
+        let mut expected = fft(Polynomial::new(x.clone()));
+        expected.truncate(5);
+        assert_approx_eq!(rfft(x), expected, 1.0e-5);
+
+        // Zero-padding to the next power of 2
+        let x = vec![0.0, 1.0, 3.0, 7.0, 8.0];
+        let mut expected = fft(Polynomial::new(x.clone()));
+        expected.truncate(5);
+        assert_approx_eq!(rfft(x), expected, 1.0e-5);
+
+        assert_eq!(rfft(vec![2.5]), vec![Complex::new(2.5, 0.0)]);
+        assert!(rfft(vec![]).is_empty());
+    }
+
+    #[test]
+    fn _irfft() {
+        let y = vec![
+            Complex::new(11.0, 0.0),
+            Complex::new(-3.0, 6.0),
+            Complex::new(-5.0, 0.0),
+        ];
+        assert_approx_eq!(irfft(y), vec![0.0, 1.0, 3.0, 7.0]);
+
+        // Round trip
+        let x: Vec<f32> =
+            (0..64).map(|k| ((k * 37) % 11) as f32 - 5.0).collect();
+        assert_approx_eq!(irfft(rfft(x.clone())), x, 1.0e-4);
+
+        assert_eq!(irfft(vec![Complex::new(2.5, 0.0)]), vec![2.5]);
+        assert!(irfft(vec![]).is_empty());
+    }
+
+    #[test]
+    fn _fft2() {
+        let real = |rows: Vec<Vec<f32>>| -> Vec<Vec<Complex<f32>>> {
+            rows.into_iter().map(Complex::from_real_vec).collect()
+        };
+
+        // A single row is the one dimensional transform, padded
+        let y = fft2(real(vec![vec![0.0, 1.0, 3.0, 7.0]]));
+        assert_eq!(y.len(), 1);
+        assert_approx_eq!(
+            y[0].clone(),
+            fft(Polynomial::new(vec![0.0, 1.0, 3.0, 7.0])),
+        );
+
+        // A single column is also the one dimensional transform
+        let y = fft2(real(vec![vec![0.0], vec![1.0], vec![3.0], vec![7.0]]));
+        let expected = fft(Polynomial::new(vec![0.0, 1.0, 3.0, 7.0]));
+        assert_approx_eq!(y.into_iter().map(|row| row[0]).collect(), expected);
+
+        // Ragged rows are zero-padded to a 4x4 matrix
+        let y =
+            fft2(real(vec![vec![1.0, 2.0, 3.0], vec![4.0], vec![5.0, 6.0]]));
+        assert_eq!(y.len(), 4);
+        assert!(y.iter().all(|row| row.len() == 4));
+        assert_approx_eq!(vec![y[0][0]], vec![Complex::new(21.0, 0.0)]);
+
+        assert!(fft2(vec![]).is_empty());
+    }
+
+    #[test]
+    fn _ifft2() {
+        let a: Vec<Vec<Complex<f32>>> = (0..4)
+            .map(|i| {
+                (0..8)
+                    .map(|j| Complex::new((i * j % 5) as f32, i as f32))
+                    .collect()
+            })
+            .collect();
+        let b = ifft2(fft2(a.clone()));
+        for (r, e) in b.into_iter().zip(a) {
+            assert_approx_eq!(r, e, 1.0e-5);
+        }
+
+        assert!(ifft2(vec![]).is_empty());
     }
 }
@@ -0,0 +1,228 @@
+//! Least-squares fitting of lines and polynomials to points
+//!
+//! A fit minimizes the sum of the squared residuals `y - f(x)` over the
+//! points `(x, y)`, and keeps these residuals along with the coefficients.
+use crate::math::{
+    matrix::{Matrix, MatrixError},
+    poly::Polynomial,
+};
+use alloc::{vec, vec::Vec};
+
+/// Least-squares line `y = intercept + slope * x`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearFit {
+    pub intercept: f64,
+    pub slope: f64,
+    /// Residual `y - intercept - slope * x` of every point, in order.
+    pub residuals: Vec<f64>,
+}
+
+/// Least-squares polynomial.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolynomialFit {
+    pub polynomial: Polynomial<f64>,
+    /// Residual `y - polynomial(x)` of every point, in order.
+    pub residuals: Vec<f64>,
+}
+
+/// Sum of the squares of `residuals`.
+fn sum_of_squares(residuals: &[f64]) -> f64 {
+    residuals.iter().map(|r| r * r).sum()
+}
+
+impl LinearFit {
+    /// Sum of the squared residuals.
+    pub fn residual_sum_of_squares(&self) -> f64 {
+        sum_of_squares(&self.residuals)
+    }
+}
+
+impl PolynomialFit {
+    /// Sum of the squared residuals.
+    pub fn residual_sum_of_squares(&self) -> f64 {
+        sum_of_squares(&self.residuals)
+    }
+}
+
+/// Ordinary least-squares line through `points`, computed from the sums of
+/// the deviations from the means, which avoids the cancellations of the raw
+/// normal equations.
+///
+/// Returns `MatrixError::Singular` unless there are two distinct abscissas,
+/// since the line is not determined otherwise.
+///
+/// Example:
+/// ```
+/// use ralg::math::fit::linear;
+///
+/// let fit = linear(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0), (3.0, 7.5)]).unwrap();
+/// assert!((fit.slope - 2.15).abs() < 1e-12);
+/// assert!((fit.intercept - 0.9).abs() < 1e-12);
+/// assert_eq!(fit.residuals.len(), 4);
+/// ```
+pub fn linear(points: &[(f64, f64)]) -> Result<LinearFit, MatrixError> {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let (mut sxx, mut sxy) = (0.0, 0.0);
+    for &(x, y) in points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+    }
+    if points.is_empty() || sxx == 0.0 {
+        return Err(MatrixError::Singular);
+    }
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+    let residuals = points
+        .iter()
+        .map(|&(x, y)| y - intercept - slope * x)
+        .collect();
+    Ok(LinearFit {
+        intercept,
+        slope,
+        residuals,
+    })
+}
+
+impl Polynomial<f64> {
+    /// Least-squares polynomial of degree at most `degree` through `points`,
+    /// found by solving the normal equations `(A^T A) c = A^T y` with the LU
+    /// decomposition, where `A` is the Vandermonde matrix of the abscissas.
+    /// Since `A^T A` is badly conditioned for high degrees or abscissas far
+    /// from zero, those are better shifted and scaled into `[-1, 1]` first.
+    ///
+    /// Returns `MatrixError::Singular` unless there are more distinct
+    /// abscissas than `degree`, since the polynomial is not determined
+    /// otherwise.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // Points of 1 - x + 2x^2
+    /// let points = [(-1.0, 4.0), (0.0, 1.0), (1.0, 2.0), (2.0, 7.0)];
+    /// let fit = Polynomial::fit(&points, 2).unwrap();
+    /// let expected = [1.0, -1.0, 2.0];
+    /// assert!(fit.polynomial.coeff.iter().zip(&expected).all(|(a, b)| (a - b).abs() < 1e-12));
+    /// assert!(fit.residual_sum_of_squares() < 1e-20);
+    /// ```
+    pub fn fit(
+        points: &[(f64, f64)],
+        degree: usize,
+    ) -> Result<PolynomialFit, MatrixError> {
+        let mut xs: Vec<f64> = points.iter().map(|p| p.0).collect();
+        xs.sort_by(|a, b| a.total_cmp(b));
+        xs.dedup();
+        if xs.len() <= degree {
+            return Err(MatrixError::Singular);
+        }
+
+        // Entry (i, j) of A^T A is the sum of x^(i + j), and entry i of A^T y
+        // the sum of y x^i
+        let m = degree + 1;
+        let mut power_sums = vec![0.0; 2 * m - 1];
+        let mut rhs = vec![0.0; m];
+        for &(x, y) in points {
+            let mut power = 1.0;
+            for (k, sum) in power_sums.iter_mut().enumerate() {
+                *sum += power;
+                if k < m {
+                    rhs[k] += y * power;
+                }
+                power *= x;
+            }
+        }
+        let normal = Matrix::from_vec(
+            m,
+            m,
+            (0..m * m).map(|k| power_sums[k / m + k % m]).collect(),
+        );
+        let polynomial = Polynomial::new(normal.solve(&rhs)?);
+        let residuals = points
+            .iter()
+            .map(|&(x, y)| y - polynomial.eval(x))
+            .collect();
+        Ok(PolynomialFit {
+            polynomial,
+            residuals,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    #[test]
+    fn exact_polynomials() {
+        let p = Polynomial::new(vec![0.5, -1.0, 0.25, 2.0]);
+        let points: Vec<(f64, f64)> = (0..12)
+            .map(|i| {
+                let x = i as f64 / 6.0 - 1.0;
+                (x, p.eval(x))
+            })
+            .collect();
+        // Higher degrees find the same polynomial
+        for degree in 3..6 {
+            let fit = Polynomial::fit(&points, degree).unwrap();
+            assert_eq!(fit.polynomial.coeff.len(), degree + 1);
+            for (i, c) in fit.polynomial.coeff.iter().enumerate() {
+                let expected = p.coeff.get(i).copied().unwrap_or(0.0);
+                assert!((c - expected).abs() < 1e-9);
+            }
+            assert!(fit.residuals.iter().all(|r| r.abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn noisy_points() {
+        let mut rng = Pcg32::seed(0xf17);
+        let points: Vec<(f64, f64)> = (0..200)
+            .map(|_| {
+                let x = rng.gen_range(-3.0..3.0);
+                (x, 1.5 - 0.5 * x + rng.gen_range(-0.1..0.1))
+            })
+            .collect();
+        let line = linear(&points).unwrap();
+        assert!((line.slope + 0.5).abs() < 0.01);
+        assert!((line.intercept - 1.5).abs() < 0.01);
+        // Both methods agree on the same problem
+        let fit = Polynomial::fit(&points, 1).unwrap();
+        assert!((fit.polynomial.coeff[0] - line.intercept).abs() < 1e-12);
+        assert!((fit.polynomial.coeff[1] - line.slope).abs() < 1e-12);
+
+        // Residuals are orthogonal to the columns of the Vandermonde matrix
+        let fit = Polynomial::fit(&points, 3).unwrap();
+        for k in 0..4 {
+            let dot: f64 = points
+                .iter()
+                .zip(&fit.residuals)
+                .map(|(&(x, _), r)| r * (0..k).fold(1.0, |p, _| p * x))
+                .sum();
+            assert!(dot.abs() < 1e-9);
+        }
+        // Higher degrees never increase the residuals
+        assert!(
+            fit.residual_sum_of_squares()
+                <= line.residual_sum_of_squares() + 1e-12
+        );
+    }
+
+    #[test]
+    fn underdetermined() {
+        let points = [(1.0, 2.0), (1.0, 3.0), (2.0, 0.0)];
+        assert_eq!(
+            linear(&points[..2]).map(|f| f.slope),
+            Err(MatrixError::Singular)
+        );
+        assert_eq!(linear(&[]).map(|f| f.slope), Err(MatrixError::Singular));
+        assert!(linear(&points).is_ok());
+        assert!(Polynomial::fit(&points, 1).is_ok());
+        assert_eq!(
+            Polynomial::fit(&points, 2).map(|f| f.polynomial),
+            Err(MatrixError::Singular)
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Binary fixed-point numbers
+use crate::math::num::{Bounded, Num, One, Signed, Zero};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Fixed-point number in the Q format, stored as an `i64` counting units of
+/// `2^-FRAC`, so that it has `63 - FRAC` integer bits besides the sign and
+/// `FRAC` fractional bits. `FRAC` is at most 62.
+///
+/// Addition and subtraction are exact, while products and quotients are
+/// computed with 128-bit integers and then rounded, to nearest for products
+/// and towards zero for quotients. Every operation panics if its result
+/// doesn't fit, like the integer types in debug builds.
+///
+/// Example:
+/// ```
+/// use ralg::math::fixed::Fixed;
+///
+/// type Q16 = Fixed<16>;
+/// let x = Q16::from_f64(1.5);
+/// let y = Q16::from_int(3);
+/// assert_eq!(x * y, Q16::from_f64(4.5));
+/// assert_eq!((y / x).to_f64(), 2.0);
+/// assert_eq!(Q16::EPSILON.to_f64(), 1.0 / 65536.0);
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fixed<const FRAC: u32> {
+    bits: i64,
+}
+
+impl<const FRAC: u32> Fixed<FRAC> {
+    /// Raw representation of one.
+    const ONE_BITS: i64 = 1 << FRAC;
+
+    /// Smallest positive number, `2^-FRAC`.
+    pub const EPSILON: Self = Fixed { bits: 1 };
+
+    pub const MIN: Self = Fixed { bits: i64::MIN };
+
+    pub const MAX: Self = Fixed { bits: i64::MAX };
+
+    /// Number whose representation is `bits`, that is, `bits * 2^-FRAC`.
+    pub const fn from_bits(bits: i64) -> Self {
+        Fixed { bits }
+    }
+
+    pub const fn to_bits(self) -> i64 {
+        self.bits
+    }
+
+    /// The integer `n`. Panics if it doesn't fit.
+    pub fn from_int(n: i64) -> Self {
+        let bits = n.checked_mul(Self::ONE_BITS);
+        Fixed {
+            bits: bits.expect("fixed-point overflow"),
+        }
+    }
+
+    /// Nearest number to `x`, rounding halfway cases away from zero. Panics
+    /// if `x` is not finite or out of range.
+    pub fn from_f64(x: f64) -> Self {
+        let scaled = x * Self::ONE_BITS as f64;
+        // i64::MAX rounds up to 2^63 as a float, which is out of range
+        assert!(
+            scaled.is_finite()
+                && scaled >= i64::MIN as f64
+                && scaled < i64::MAX as f64,
+            "fixed-point overflow"
+        );
+        // Truncate, then look at the exact fractional part, since adding 0.5
+        // first could round up numbers just below one half
+        let truncated = scaled as i64;
+        let fraction = scaled - truncated as f64;
+        let bits = if fraction >= 0.5 {
+            truncated + 1
+        } else if fraction <= -0.5 {
+            truncated - 1
+        } else {
+            truncated
+        };
+        Fixed { bits }
+    }
+
+    /// Nearest number to `x`, see `from_f64`.
+    pub fn from_f32(x: f32) -> Self {
+        Self::from_f64(x as f64)
+    }
+
+    /// Float nearest to the number, which is exact when it has at most 53
+    /// significant bits.
+    pub fn to_f64(self) -> f64 {
+        self.bits as f64 / Self::ONE_BITS as f64
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Integer part, rounding towards negative infinity.
+    pub fn floor(self) -> i64 {
+        self.bits >> FRAC
+    }
+
+    /// Fractional part, `self - floor(self)`, in `[0, 1)`.
+    pub fn fract(self) -> Self {
+        Fixed {
+            bits: self.bits & (Self::ONE_BITS - 1),
+        }
+    }
+
+    pub fn abs(self) -> Self {
+        Fixed {
+            bits: self.bits.checked_abs().expect("fixed-point overflow"),
+        }
+    }
+
+    /// Narrows a 128-bit representation, panicking if it doesn't fit.
+    fn narrow(bits: i128) -> Self {
+        Fixed {
+            bits: i64::try_from(bits).expect("fixed-point overflow"),
+        }
+    }
+}
+
+impl<const FRAC: u32> From<i32> for Fixed<FRAC> {
+    fn from(n: i32) -> Self {
+        Fixed::from_int(n as i64)
+    }
+}
+
+impl<const FRAC: u32> Add for Fixed<FRAC> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let bits = self.bits.checked_add(rhs.bits);
+        Fixed {
+            bits: bits.expect("fixed-point overflow"),
+        }
+    }
+}
+
+impl<const FRAC: u32> Sub for Fixed<FRAC> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let bits = self.bits.checked_sub(rhs.bits);
+        Fixed {
+            bits: bits.expect("fixed-point overflow"),
+        }
+    }
+}
+
+impl<const FRAC: u32> Mul for Fixed<FRAC> {
+    type Output = Self;
+
+    /// Product, rounded to nearest with halfway cases going up.
+    fn mul(self, rhs: Self) -> Self {
+        let product = self.bits as i128 * rhs.bits as i128;
+        if FRAC == 0 {
+            return Fixed::narrow(product);
+        }
+        Fixed::narrow((product + (1 << (FRAC - 1))) >> FRAC)
+    }
+}
+
+impl<const FRAC: u32> Div for Fixed<FRAC> {
+    type Output = Self;
+
+    /// Quotient, rounded towards zero. Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs.bits != 0, "fixed-point division by zero");
+        Fixed::narrow(((self.bits as i128) << FRAC) / rhs.bits as i128)
+    }
+}
+
+impl<const FRAC: u32> Neg for Fixed<FRAC> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Fixed {
+            bits: self.bits.checked_neg().expect("fixed-point overflow"),
+        }
+    }
+}
+
+impl<const FRAC: u32> Zero for Fixed<FRAC> {
+    fn zero() -> Self {
+        Fixed { bits: 0 }
+    }
+}
+
+impl<const FRAC: u32> One for Fixed<FRAC> {
+    fn one() -> Self {
+        Fixed {
+            bits: Self::ONE_BITS,
+        }
+    }
+}
+
+impl<const FRAC: u32> Num for Fixed<FRAC> {}
+
+impl<const FRAC: u32> Signed for Fixed<FRAC> {
+    fn abs(&self) -> Self {
+        Fixed::abs(*self)
+    }
+
+    fn signum(&self) -> Self {
+        Fixed::from_int(self.bits.signum())
+    }
+
+    fn is_negative(&self) -> bool {
+        self.bits < 0
+    }
+
+    fn is_positive(&self) -> bool {
+        self.bits > 0
+    }
+}
+
+impl<const FRAC: u32> Bounded for Fixed<FRAC> {
+    fn min_value() -> Self {
+        Self::MIN
+    }
+
+    fn max_value() -> Self {
+        Self::MAX
+    }
+}
+
+impl<const FRAC: u32> fmt::Display for Fixed<FRAC> {
+    /// Formats as the nearest float.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_f64(), f)
+    }
+}
+
+impl<const FRAC: u32> fmt::Debug for Fixed<FRAC> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fixed<{}>({})", FRAC, self.to_f64())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::poly::Polynomial;
+    use crate::random::Pcg32;
+    use alloc::{string::ToString, vec::Vec};
+
+    type Q16 = Fixed<16>;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Q16::from_int(-3).to_bits(), -3 << 16);
+        assert_eq!(Q16::from_f64(0.75).to_bits(), 3 << 14);
+        // Rounding to the nearest representable number
+        assert_eq!(Q16::from_f64(1e-5).to_bits(), 1);
+        assert_eq!(Q16::from_f64(-1e-5).to_bits(), -1);
+        let below_half = 0.5 - f64::EPSILON / 4.0;
+        assert_eq!(Fixed::<0>::from_f64(below_half), Fixed::zero());
+        assert_eq!(Q16::from_f32(-2.5).to_f32(), -2.5);
+        assert_eq!(Q16::from(7).floor(), 7);
+        assert_eq!(Q16::from_f64(-1.25).floor(), -2);
+        assert_eq!(Q16::from_f64(-1.25).fract(), Q16::from_f64(0.75));
+        assert_eq!(Fixed::<0>::from_f64(2.5), Fixed::<0>::from_int(3));
+        assert_eq!(Fixed::<62>::one().to_f64(), 1.0);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Q16::from_f64(2.25), Q16::from_f64(-0.5));
+        assert_eq!(a + b, Q16::from_f64(1.75));
+        assert_eq!(a - b, Q16::from_f64(2.75));
+        assert_eq!(a * b, Q16::from_f64(-1.125));
+        assert_eq!(a / b, Q16::from_f64(-4.5));
+        assert_eq!(-a, Q16::from_f64(-2.25));
+        assert_eq!(Signed::signum(&b), -Q16::one());
+        assert!(Signed::is_positive(&a));
+        assert!(b < Q16::zero() && Q16::zero() < a);
+        // Products round to nearest, quotients towards zero
+        assert_eq!(Q16::EPSILON * Q16::from_f64(0.5), Q16::EPSILON);
+        assert_eq!(Q16::EPSILON * Q16::from_f64(0.25), Q16::zero());
+        let third = Q16::one() / Q16::from_int(3);
+        assert_eq!(third.to_bits(), 21845);
+        assert_eq!((-Q16::one() / Q16::from_int(3)).to_bits(), -21845);
+    }
+
+    #[test]
+    fn against_floats() {
+        let mut rng = Pcg32::seed(0xf1c5);
+        for _ in 0..1000 {
+            let (x, y) =
+                (rng.gen_range(-100.0..100.0), rng.gen_range(-100.0..100.0));
+            let (a, b) = (Q16::from_f64(x), Q16::from_f64(y));
+            let (x, y) = (a.to_f64(), b.to_f64());
+            // Sums are exact and products within half a unit
+            assert_eq!((a + b).to_f64(), x + y);
+            assert!(((a * b).to_f64() - x * y).abs() <= 0.5 / 65536.0);
+            if y.abs() > 1.0 {
+                assert!(((a / b).to_f64() - x / y).abs() < 1.0 / 65536.0);
+            }
+        }
+    }
+
+    #[test]
+    fn polynomials() {
+        let coeff = [0.5, -1.25, 0.375, 2.0];
+        let p = Polynomial::new(coeff.to_vec());
+        let q: Polynomial<Fixed<32>> = Polynomial::new(
+            coeff.iter().map(|&c| Fixed::from_f64(c)).collect(),
+        );
+        for i in -10..=10 {
+            let x = i as f64 / 8.0;
+            let fixed = q.eval(Fixed::from_f64(x)).to_f64();
+            assert!((fixed - p.eval(x)).abs() < 1e-8);
+        }
+        let xs: Vec<Q16> = (1..=4).map(Q16::from).collect();
+        assert_eq!(crate::math::num::max_of(&xs), Q16::from(4));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Q16::from_f64(-2.5).to_string(), "-2.5");
+        assert_eq!(format!("{:?}", Q16::one()), "Fixed<16>(1)");
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let _ = Q16::MAX + Q16::EPSILON;
+    }
+
+    #[test]
+    #[should_panic]
+    fn division_by_zero() {
+        let _ = Q16::one() / Q16::zero();
+    }
+}
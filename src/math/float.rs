@@ -0,0 +1,241 @@
+//! Approximate comparisons of floating point numbers
+use crate::math::complex::Complex;
+use alloc::vec::Vec;
+
+/// Default absolute tolerance used by `assert_approx_eq!`.
+pub const DEFAULT_EPSILON: f64 = 1.0e-6;
+
+/// Types whose values can be compared up to an absolute tolerance.
+pub trait ApproxEq {
+    /// Returns `true` if `self` and `other` differ by at most `eps`. For
+    /// compound types, every component must be within `eps`.
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        // Equality also covers infinities of the same sign
+        self == other || (self - other).abs() <= eps
+    }
+}
+
+impl ApproxEq for f32 {
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        (*self as f64).approx_eq(&(*other as f64), eps)
+    }
+}
+
+impl<T: ApproxEq + Clone + crate::math::num::Num> ApproxEq for Complex<T> {
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        self.re.approx_eq(&other.re, eps) && self.im.approx_eq(&other.im, eps)
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    /// Slices are approximately equal if they have the same length and are
+    /// approximately equal element by element.
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        self.len() == other.len()
+            && self.iter().zip(other).all(|(a, b)| a.approx_eq(b, eps))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    fn approx_eq(&self, other: &Self, eps: f64) -> bool {
+        self.as_slice().approx_eq(other.as_slice(), eps)
+    }
+}
+
+/// Returns `true` if `a` and `b` differ by at most `eps`.
+///
+/// Example:
+/// ```
+/// use ralg::math::{complex::Complex, float::approx_eq};
+///
+/// assert!(approx_eq(&0.1, &(0.3 - 0.2), 1.0e-12));
+/// let zs = vec![Complex::new(1.0, 0.0), Complex::new(0.0, 1.0)];
+/// let ws = vec![Complex::new(1.0, 1.0e-9), Complex::new(-1.0e-9, 1.0)];
+/// assert!(approx_eq(&zs, &ws, 1.0e-6));
+/// ```
+pub fn approx_eq<T: ApproxEq + ?Sized>(a: &T, b: &T, eps: f64) -> bool {
+    a.approx_eq(b, eps)
+}
+
+/// Returns `true` if `a` and `b` differ by at most `max_rel` times the larger
+/// of their magnitudes. Unlike an absolute tolerance, this is meaningful
+/// regardless of the scale of the numbers, but it is too strict near zero.
+///
+/// Example:
+/// ```
+/// use ralg::math::float::relative_eq;
+///
+/// assert!(relative_eq(1.0e20, 1.0e20 + 1.0e5, 1.0e-12));
+/// assert!(!relative_eq(1.0e-20, 2.0e-20, 1.0e-12));
+/// ```
+pub fn relative_eq<T: Into<f64>>(a: T, b: T, max_rel: f64) -> bool {
+    let (a, b) = (a.into(), b.into());
+    a == b || (a - b).abs() <= max_rel * a.abs().max(b.abs())
+}
+
+/// Maps the bits of a float to an integer whose order agrees with the order
+/// of the floats, so that adjacent floats map to adjacent integers.
+fn ordered_bits_f64(x: f64) -> i64 {
+    let bits = x.to_bits() as i64;
+    if bits < 0 {
+        i64::MIN - bits
+    } else {
+        bits
+    }
+}
+
+fn ordered_bits(x: f32) -> i32 {
+    let bits = x.to_bits() as i32;
+    if bits < 0 {
+        i32::MIN - bits
+    } else {
+        bits
+    }
+}
+
+/// Number of representable `f32` values between `a` and `b`, that is, their
+/// distance in units in the last place (ULPs). Returns `None` if either is
+/// NaN. Both zeros are at distance zero.
+pub fn ulp_distance(a: f32, b: f32) -> Option<u32> {
+    if a.is_nan() || b.is_nan() {
+        return None;
+    }
+    Some(ordered_bits(a).abs_diff(ordered_bits(b)))
+}
+
+/// Number of representable `f64` values between `a` and `b`. Returns `None` if
+/// either is NaN.
+pub fn ulp_distance_f64(a: f64, b: f64) -> Option<u64> {
+    if a.is_nan() || b.is_nan() {
+        return None;
+    }
+    Some(ordered_bits_f64(a).abs_diff(ordered_bits_f64(b)))
+}
+
+/// Returns `true` if `a` and `b` are at most `max_ulps` representable `f32`
+/// values apart. NaN is never equal to anything.
+///
+/// Example:
+/// ```
+/// use ralg::math::float::ulps_eq;
+///
+/// let x = 1.0_f32;
+/// let next = f32::from_bits(x.to_bits() + 1);
+/// assert!(ulps_eq(x, next, 1));
+/// assert!(!ulps_eq(x, next, 0));
+/// ```
+pub fn ulps_eq(a: f32, b: f32, max_ulps: u32) -> bool {
+    ulp_distance(a, b).is_some_and(|d| d <= max_ulps)
+}
+
+/// Returns `true` if `a` and `b` are at most `max_ulps` representable `f64`
+/// values apart. NaN is never equal to anything.
+pub fn ulps_eq_f64(a: f64, b: f64, max_ulps: u64) -> bool {
+    ulp_distance_f64(a, b).is_some_and(|d| d <= max_ulps)
+}
+
+/// Asserts that two values are approximately equal, as given by `ApproxEq`,
+/// up to an absolute tolerance (by default `DEFAULT_EPSILON`). On failure,
+/// both values are printed.
+///
+/// Example:
+/// ```
+/// use ralg::assert_approx_eq;
+///
+/// assert_approx_eq!(0.1 + 0.2, 0.3);
+/// assert_approx_eq!(vec![1.0, 2.0], vec![1.001, 1.999], 1.0e-2);
+/// ```
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left: expr, $right: expr $(,)?) => {
+        $crate::assert_approx_eq!(
+            $left,
+            $right,
+            $crate::math::float::DEFAULT_EPSILON
+        )
+    };
+    ($left: expr, $right: expr, $eps: expr $(,)?) => {
+        match (&$left, &$right, $eps) {
+            (left, right, eps) => {
+                if !$crate::math::float::ApproxEq::approx_eq(left, right, eps) {
+                    panic!(
+                        "assertion `left approx_eq right` failed (eps = {:e})\n  left: {:?}\n right: {:?}",
+                        eps, left, right
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn absolute() {
+        assert!(approx_eq(&1.0, &1.0000001, 1.0e-6));
+        assert!(!approx_eq(&1.0, &1.00001, 1.0e-6));
+        assert!(approx_eq(&f64::INFINITY, &f64::INFINITY, 0.0));
+        assert!(!approx_eq(&f64::INFINITY, &f64::NEG_INFINITY, 1.0));
+        assert!(!approx_eq(&f64::NAN, &f64::NAN, 1.0));
+        assert!(approx_eq(&1.0_f32, &1.000_000_1, 1.0e-6));
+
+        let zs = [Complex::new(1.0_f32, 2.0), Complex::new(3.0, 4.0)];
+        assert!(approx_eq(
+            &zs[..],
+            &[Complex::new(1.0, 2.0), Complex::new(3.0, 4.0)][..],
+            0.0
+        ));
+        assert!(!approx_eq(&zs[..], &zs[..1], 1.0));
+        assert!(!approx_eq(&zs[0], &Complex::new(1.0, 2.1), 1.0e-2));
+    }
+
+    #[test]
+    fn relative() {
+        assert!(relative_eq(100.0, 100.0001, 1.0e-5));
+        assert!(!relative_eq(100.0, 100.01, 1.0e-5));
+        assert!(relative_eq(-3.0_f32, -3.0, 0.0));
+        assert!(relative_eq(0.0, 0.0, 0.0));
+        assert!(!relative_eq(0.0, 1.0e-300, 0.5));
+    }
+
+    #[test]
+    fn ulps() {
+        let x = 1.0_f64;
+        let next = f64::from_bits(x.to_bits() + 1);
+        assert_eq!(ulp_distance_f64(x, next), Some(1));
+        assert_eq!(ulp_distance_f64(0.0, -0.0), Some(0));
+        assert_eq!(ulp_distance_f64(f64::NAN, 1.0), None);
+
+        // Distances are measured across zero
+        let tiny = f32::from_bits(1);
+        assert_eq!(ulp_distance(tiny, -tiny), Some(2));
+        assert_eq!(ulp_distance(f32::MAX, f32::INFINITY), Some(1));
+
+        assert!(ulps_eq_f64(0.1 + 0.2, 0.3, 1));
+        assert!(!ulps_eq_f64(0.1 + 0.2, 0.3, 0));
+        assert!(!ulps_eq(f32::NAN, f32::NAN, u32::MAX));
+    }
+
+    #[test]
+    fn macro_assertions() {
+        assert_approx_eq!(1.0, 1.0 + 1.0e-7);
+        assert_approx_eq!(vec![1.0_f32, 2.0], vec![1.0, 2.0], 0.0);
+        assert_approx_eq!(
+            Complex::new(0.5, -0.5),
+            Complex::new(0.5, -0.49),
+            0.1,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "left approx_eq right")]
+    fn macro_failure() {
+        assert_approx_eq!(1.0, 1.1);
+    }
+}
@@ -0,0 +1,146 @@
+//! Gaussian elimination
+use super::{pivot_row, Matrix};
+use crate::math::num::Num;
+use core::ops::Div;
+
+impl<T: Num + Copy + PartialOrd + Div<Output = T>> Matrix<T> {
+    /// Reduced row echelon form of the matrix, computed by Gauss-Jordan
+    /// elimination with partial pivoting in time `O(n m min(n, m))`.
+    ///
+    /// Over an exact type such as `Rational`, the result is exact. This makes
+    /// it possible to solve small linear systems, even singular or
+    /// non-square ones, by reducing the augmented matrix `[A | b]`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::{matrix::Matrix, num::Rational};
+    ///
+    /// // x + 2y = 1, 3x + 4y = 1
+    /// let q = |n| Rational::from_integer(n);
+    /// let a = Matrix::from_rows(vec![
+    ///     vec![q(1), q(2), q(1)],
+    ///     vec![q(3), q(4), q(1)],
+    /// ]);
+    /// let r = a.rref();
+    /// assert_eq!(r.row(0), &[q(1), q(0), q(-1)]);
+    /// assert_eq!(r.row(1), &[q(0), q(1), q(1)]);
+    /// ```
+    pub fn rref(&self) -> Self {
+        self.eliminate().0
+    }
+
+    /// Rank of the matrix, that is, the number of pivots found by Gaussian
+    /// elimination. Only reliable for exact types, since rounding errors may
+    /// turn zero entries into small non-zero ones.
+    pub fn rank(&self) -> usize {
+        self.eliminate().1
+    }
+
+    /// Gauss-Jordan elimination, returning the reduced row echelon form and
+    /// the number of pivots.
+    fn eliminate(&self) -> (Self, usize) {
+        let mut a = self.clone();
+        let mut row = 0;
+        for col in 0..a.cols() {
+            if row == a.rows() {
+                break;
+            }
+
+            let p = pivot_row(&a, row, col);
+            if a[(p, col)] == T::zero() {
+                continue;
+            }
+            a.swap_rows(p, row);
+
+            let pivot = a[(row, col)];
+            for j in col..a.cols() {
+                a[(row, j)] = a[(row, j)] / pivot;
+            }
+            for i in (0..a.rows()).filter(|&i| i != row) {
+                let factor = a[(i, col)];
+                if factor == T::zero() {
+                    continue;
+                }
+                for j in col..a.cols() {
+                    a[(i, j)] = a[(i, j)] - factor * a[(row, j)];
+                }
+            }
+            row += 1;
+        }
+        (a, row)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::num::{Rational, Zero};
+
+    fn q(num: i128, den: i128) -> Rational {
+        Rational::new(num, den)
+    }
+
+    /// The `n x n` Hilbert matrix, with entries `1 / (i + j + 1)`, a classic
+    /// example of an ill-conditioned matrix.
+    fn hilbert(n: usize) -> Matrix<Rational> {
+        Matrix::from_rows(
+            (0..n)
+                .map(|i| (0..n).map(|j| q(1, (i + j + 1) as i128)).collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn rref() {
+        let a = Matrix::from_rows(vec![
+            vec![q(2, 1), q(4, 1), q(-2, 1), q(2, 1)],
+            vec![q(4, 1), q(9, 1), q(-3, 1), q(8, 1)],
+            vec![q(-2, 1), q(-3, 1), q(7, 1), q(10, 1)],
+        ]);
+        let expected = Matrix::from_rows(vec![
+            vec![q(1, 1), q(0, 1), q(0, 1), q(-1, 1)],
+            vec![q(0, 1), q(1, 1), q(0, 1), q(2, 1)],
+            vec![q(0, 1), q(0, 1), q(1, 1), q(2, 1)],
+        ]);
+        assert_eq!(a.rref(), expected);
+        assert_eq!(a.rank(), 3);
+
+        // Rank deficient and with a zero column
+        let a = Matrix::from_rows(vec![
+            vec![q(0, 1), q(1, 1), q(2, 1)],
+            vec![q(0, 1), q(2, 1), q(4, 1)],
+            vec![q(0, 1), q(1, 2), q(1, 1)],
+        ]);
+        assert_eq!(a.rank(), 1);
+        assert_eq!(a.rref().row(0), &[q(0, 1), q(1, 1), q(2, 1)]);
+        assert_eq!(a.rref().row(2), &[q(0, 1); 3]);
+
+        let zero: Matrix<Rational> = Matrix::zeros(2, 3);
+        assert_eq!(zero.rank(), 0);
+        assert_eq!(zero.rref(), zero);
+    }
+
+    #[test]
+    fn exact_solve() {
+        // Solving Hilbert systems in floating point loses most digits, but
+        // over the rationals the solution is exact
+        let n = 6;
+        let h = hilbert(n);
+        let x: Vec<Rational> =
+            (1..=n as i128).map(Rational::from_integer).collect();
+        let b: Vec<Rational> = (0..n)
+            .map(|i| {
+                h.row(i)
+                    .iter()
+                    .zip(&x)
+                    .fold(Rational::zero(), |s, (&hij, &xj)| s + hij * xj)
+            })
+            .collect();
+        assert_eq!(h.solve(&b), Ok(x));
+
+        let inv = h.inverse().unwrap();
+        assert_eq!(&h * &inv, Matrix::identity(n));
+        assert_eq!(inv[(0, 0)], q(36, 1));
+        assert_eq!(hilbert(3).det(), Ok(q(1, 2160)));
+    }
+}
@@ -0,0 +1,302 @@
+//! LU decomposition and the linear algebra built on top of it
+use super::{pivot_row, Matrix, MatrixError};
+use crate::math::num::Num;
+use alloc::{vec, vec::Vec};
+use core::ops::Div;
+
+/// LU decomposition with partial pivoting `PA = LU` of a square matrix `A`,
+/// where `P` is a permutation matrix, `L` is lower triangular with ones on the
+/// diagonal and `U` is upper triangular.
+///
+/// Both `L` and `U` are stored in a single matrix: the strictly lower part
+/// holds `L` (whose diagonal is implicit) and the upper part holds `U`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lu<T: Num + Copy> {
+    lu: Matrix<T>,
+    perm: Vec<usize>,
+    even: bool,
+}
+
+impl<T: Num + Copy + PartialOrd + Div<Output = T>> Matrix<T> {
+    /// Computes the LU decomposition of the matrix using Gaussian elimination
+    /// with partial pivoting: at each step, the row with the entry of largest
+    /// absolute value in the current column is chosen as the pivot, which
+    /// keeps the decomposition numerically stable for floating point types.
+    /// Takes time `O(n^3)`.
+    ///
+    /// Since only field operations and comparisons are needed, the
+    /// decomposition is exact for exact types.
+    ///
+    /// Returns `MatrixError::NotSquare` for non-square matrices and
+    /// `MatrixError::Singular` if some pivot is exactly zero.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_rows(vec![vec![1.0, 2.0], vec![4.0, 4.0]]);
+    /// let lu = a.lu().unwrap();
+    /// assert_eq!(lu.perm(), &[1, 0]);
+    /// assert_eq!(lu.l(), Matrix::from_rows(vec![vec![1.0, 0.0], vec![0.25, 1.0]]));
+    /// assert_eq!(lu.u(), Matrix::from_rows(vec![vec![4.0, 4.0], vec![0.0, 1.0]]));
+    /// ```
+    pub fn lu(&self) -> Result<Lu<T>, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare);
+        }
+
+        let n = self.rows();
+        let mut lu = self.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut even = true;
+        for k in 0..n {
+            let p = pivot_row(&lu, k, k);
+            if lu[(p, k)] == T::zero() {
+                return Err(MatrixError::Singular);
+            }
+            if p != k {
+                lu.swap_rows(p, k);
+                perm.swap(p, k);
+                even = !even;
+            }
+
+            let pivot = lu[(k, k)];
+            for i in (k + 1)..n {
+                let factor = lu[(i, k)] / pivot;
+                lu[(i, k)] = factor;
+                for j in (k + 1)..n {
+                    lu[(i, j)] = lu[(i, j)] - factor * lu[(k, j)];
+                }
+            }
+        }
+        Ok(Lu { lu, perm, even })
+    }
+
+    /// Solves the linear system `self * x == b` for `x`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_rows(vec![vec![2.0, 1.0], vec![1.0, 3.0]]);
+    /// assert_eq!(a.solve(&[3.0, 5.0]), Ok(vec![0.8, 1.4]));
+    /// ```
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        self.lu()?.solve(b)
+    }
+
+    /// Determinant of the matrix, which is zero for singular matrices.
+    /// Returns `MatrixError::NotSquare` for non-square matrices.
+    pub fn det(&self) -> Result<T, MatrixError> {
+        match self.lu() {
+            Ok(lu) => Ok(lu.det()),
+            Err(MatrixError::Singular) => Ok(T::zero()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Inverse of the matrix, obtained by solving `self * x == e_j` for each
+    /// column `e_j` of the identity matrix.
+    pub fn inverse(&self) -> Result<Self, MatrixError> {
+        let lu = self.lu()?;
+        let n = self.rows();
+        let mut inv = Matrix::zeros(n, n);
+        let mut e = vec![T::zero(); n];
+        for j in 0..n {
+            e[j] = T::one();
+            for (i, x) in lu.solve(&e)?.into_iter().enumerate() {
+                inv[(i, j)] = x;
+            }
+            e[j] = T::zero();
+        }
+        Ok(inv)
+    }
+}
+
+impl<T: Num + Copy + PartialOrd + Div<Output = T>> Lu<T> {
+    /// Unit lower triangular factor `L`.
+    pub fn l(&self) -> Matrix<T> {
+        let n = self.lu.rows();
+        let mut l = Matrix::identity(n);
+        for i in 0..n {
+            for j in 0..i {
+                l[(i, j)] = self.lu[(i, j)];
+            }
+        }
+        l
+    }
+
+    /// Upper triangular factor `U`.
+    pub fn u(&self) -> Matrix<T> {
+        let n = self.lu.rows();
+        let mut u = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in i..n {
+                u[(i, j)] = self.lu[(i, j)];
+            }
+        }
+        u
+    }
+
+    /// Row permutation of the decomposition: the `i`th row of `PA` is the
+    /// row `perm()[i]` of `A`.
+    pub fn perm(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Determinant of the decomposed matrix, given by the product of the
+    /// diagonal of `U` and the sign of the permutation.
+    pub fn det(&self) -> T {
+        let n = self.lu.rows();
+        let d = (0..n).fold(T::one(), |d, i| d * self.lu[(i, i)]);
+        if self.even {
+            d
+        } else {
+            T::zero() - d
+        }
+    }
+
+    /// Solves `A x == b` by forward substitution on `L y == P b` followed by
+    /// back substitution on `U x == y`, taking time `O(n^2)`.
+    ///
+    /// Returns `MatrixError::DimensionMismatch` if the length of `b` differs
+    /// from the dimension of the matrix.
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        let n = self.lu.rows();
+        if b.len() != n {
+            return Err(MatrixError::DimensionMismatch);
+        }
+
+        let mut x: Vec<T> = self.perm.iter().map(|&i| b[i]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] = x[i] - self.lu[(i, j)] * x[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in (i + 1)..n {
+                x[i] = x[i] - self.lu[(i, j)] * x[j];
+            }
+            x[i] = x[i] / self.lu[(i, i)];
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(a: &Matrix<f64>, b: &Matrix<f64>) -> bool {
+        let (a, b) = (a.clone().into_rows(), b.clone().into_rows());
+        a.len() == b.len()
+            && a.iter()
+                .flatten()
+                .zip(b.iter().flatten())
+                .all(|(x, y)| (x - y).abs() < 1.0e-9)
+    }
+
+    #[test]
+    fn lu() {
+        let a: Matrix<f64> = Matrix::from_rows(vec![
+            vec![2.0, -1.0, 0.0, 4.0],
+            vec![4.0, 1.0, 3.0, -2.0],
+            vec![-6.0, 2.0, 5.0, 1.0],
+            vec![0.0, 3.0, -1.0, 7.0],
+        ]);
+        let lu = a.lu().unwrap();
+
+        // Rebuild `PA` from the permutation
+        let pa = Matrix::from_rows(
+            lu.perm().iter().map(|&i| a.row(i).to_vec()).collect(),
+        );
+        assert!(close(&(&lu.l() * &lu.u()), &pa));
+        assert_eq!(lu.perm()[0], 2);
+
+        let l = lu.l();
+        let u = lu.u();
+        for i in 0..4 {
+            assert_eq!(l[(i, i)], 1.0);
+            for j in (i + 1)..4 {
+                assert_eq!(l[(i, j)], 0.0);
+                assert_eq!(u[(j, i)], 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let a: Matrix<f64> = Matrix::zeros(2, 3);
+        assert_eq!(a.lu(), Err(MatrixError::NotSquare));
+        assert_eq!(a.det(), Err(MatrixError::NotSquare));
+        assert_eq!(a.inverse(), Err(MatrixError::NotSquare));
+
+        let a = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+        assert_eq!(a.lu(), Err(MatrixError::Singular));
+        assert_eq!(a.solve(&[1.0, 2.0]), Err(MatrixError::Singular));
+        assert_eq!(a.inverse(), Err(MatrixError::Singular));
+        assert_eq!(a.det(), Ok(0.0));
+
+        let a: Matrix<f64> = Matrix::identity(2);
+        assert_eq!(a.solve(&[1.0]), Err(MatrixError::DimensionMismatch));
+    }
+
+    #[test]
+    fn solve() {
+        let a: Matrix<f64> = Matrix::from_rows(vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, -2.0, -3.0],
+            vec![-1.0, 1.0, 2.0],
+        ]);
+        let x = a.solve(&[-8.0, 0.0, 3.0]).unwrap();
+        for (xi, e) in x.iter().zip([-4.0, -5.0, 2.0]) {
+            assert!((xi - e).abs() < 1.0e-12);
+        }
+
+        let lu = a.lu().unwrap();
+        let y = lu.solve(&[1.0, 0.0, 0.0]).unwrap();
+        let ay: Vec<f64> = (0..3)
+            .map(|i| a.row(i).iter().zip(&y).map(|(p, q)| p * q).sum())
+            .collect();
+        assert!(ay
+            .iter()
+            .zip([1.0, 0.0, 0.0])
+            .all(|(p, q)| (p - q).abs() < 1.0e-12));
+
+        let empty: Matrix<f64> = Matrix::zeros(0, 0);
+        assert_eq!(empty.solve(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn det() {
+        let a: Matrix<f64> =
+            Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        assert!((a.det().unwrap() + 2.0).abs() < 1.0e-12);
+
+        let a: Matrix<f64> = Matrix::from_rows(vec![
+            vec![6.0, 1.0, 1.0],
+            vec![4.0, -2.0, 5.0],
+            vec![2.0, 8.0, 7.0],
+        ]);
+        assert!((a.det().unwrap() + 306.0).abs() < 1.0e-9);
+        assert!((a.transpose().det().unwrap() + 306.0).abs() < 1.0e-9);
+
+        let id: Matrix<f32> = Matrix::identity(5);
+        assert_eq!(id.det(), Ok(1.0));
+        assert_eq!(Matrix::<f64>::zeros(0, 0).det(), Ok(1.0));
+    }
+
+    #[test]
+    fn inverse() {
+        let a: Matrix<f64> = Matrix::from_rows(vec![
+            vec![4.0, 7.0, 2.0],
+            vec![3.0, 6.0, 1.0],
+            vec![2.0, 5.0, 3.0],
+        ]);
+        let inv = a.inverse().unwrap();
+        let id = Matrix::identity(3);
+        assert!(close(&(&a * &inv), &id));
+        assert!(close(&(&inv * &a), &id));
+        assert!(close(&inv.inverse().unwrap(), &a));
+    }
+}
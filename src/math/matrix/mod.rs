@@ -0,0 +1,485 @@
+//! Dense matrices and basic linear algebra
+use crate::math::{misc::pow_with, num::Num};
+use alloc::{vec, vec::Vec};
+use core::ops::{Add, Index, IndexMut, Mul, Sub};
+use core::{error::Error, fmt};
+
+mod gauss;
+pub mod lu;
+
+/// Error returned by linear algebra routines that cannot be carried out for
+/// the given matrices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixError {
+    /// The operation requires a square matrix.
+    NotSquare,
+
+    /// The matrix is singular, that is, not invertible.
+    Singular,
+
+    /// The dimensions of the operands are incompatible.
+    DimensionMismatch,
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotSquare => write!(f, "matrix is not square"),
+            Self::Singular => write!(f, "matrix is singular"),
+            Self::DimensionMismatch => write!(f, "incompatible dimensions"),
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+/// Dense matrix with `rows` rows and `cols` columns, whose entries are stored
+/// contiguously in row-major order.
+///
+/// With the `serde` feature, a matrix is (de)serialized as its dimensions and
+/// entries, and deserialization fails if their numbers don't agree.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawMatrix<T>"))]
+pub struct Matrix<T: Num + Copy> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+/// Fields of a matrix, before checking that they agree.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Matrix")]
+struct RawMatrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Num + Copy> TryFrom<RawMatrix<T>> for Matrix<T> {
+    type Error = MatrixError;
+
+    fn try_from(raw: RawMatrix<T>) -> Result<Self, MatrixError> {
+        if raw.rows.checked_mul(raw.cols) != Some(raw.data.len()) {
+            return Err(MatrixError::DimensionMismatch);
+        }
+        Ok(Matrix {
+            rows: raw.rows,
+            cols: raw.cols,
+            data: raw.data,
+        })
+    }
+}
+
+impl<T: Num + Copy> Matrix<T> {
+    /// Creates a `rows x cols` matrix from its entries `data` in row-major
+    /// order. Panics if `data.len() != rows * cols`.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(
+            data.len(),
+            rows * cols,
+            "a {}x{} matrix needs {} entries",
+            rows,
+            cols,
+            rows * cols
+        );
+        Matrix { rows, cols, data }
+    }
+
+    /// Creates a matrix from a vector of rows. Panics if the rows don't all
+    /// have the same length.
+    /// Example:
+    /// ```
+    /// use ralg::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    /// assert_eq!((a.rows(), a.cols()), (2, 3));
+    /// assert_eq!(a[(1, 0)], 4);
+    /// ```
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "all rows of a matrix must have the same length"
+        );
+        Matrix::from_vec(rows.len(), cols, rows.concat())
+    }
+
+    /// The `rows x cols` matrix whose entries are all zero.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix::from_vec(rows, cols, vec![T::zero(); rows * cols])
+    }
+
+    /// The `n x n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        let mut id = Matrix::zeros(n, n);
+        for i in 0..n {
+            id[(i, i)] = T::one();
+        }
+        id
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Returns the `i`th row of the matrix as a slice.
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Consumes the matrix, returning its vector of rows.
+    pub fn into_rows(self) -> Vec<Vec<T>> {
+        if self.cols == 0 {
+            return vec![Vec::new(); self.rows];
+        }
+        self.data.chunks(self.cols).map(<[T]>::to_vec).collect()
+    }
+
+    /// Transpose of the matrix, whose entry `(i, j)` is the entry `(j, i)` of
+    /// `self`.
+    /// Example:
+    /// ```
+    /// use ralg::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    /// let t = Matrix::from_rows(vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+    /// assert_eq!(a.transpose(), t);
+    /// ```
+    pub fn transpose(&self) -> Self {
+        let mut t = Matrix::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                t[(j, i)] = self[(i, j)];
+            }
+        }
+        t
+    }
+
+    /// Swaps the rows `i` and `j` of the matrix.
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        if i == j {
+            return;
+        }
+        let (lo, hi) = (i.min(j), i.max(j));
+        let (top, bottom) = self.data.split_at_mut(hi * self.cols);
+        top[lo * self.cols..(lo + 1) * self.cols]
+            .swap_with_slice(&mut bottom[..self.cols]);
+    }
+
+    /// Raises a square matrix to the power `exp` by repeated squaring, taking
+    /// `O(n^3 log(exp))` time. Panics if the matrix is not square.
+    /// Example:
+    /// ```
+    /// use ralg::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_rows(vec![vec![1, 1], vec![0, 1]]);
+    /// assert_eq!(a.pow(5), Matrix::from_rows(vec![vec![1, 5], vec![0, 1]]));
+    /// ```
+    pub fn pow(&self, exp: u64) -> Self {
+        assert!(self.is_square(), "only square matrices have powers");
+        let identity = Matrix::identity(self.rows);
+        pow_with(self.clone(), exp, identity, |a, b| a * b)
+    }
+
+    /// Multiplies every entry of the matrix by the scalar `k`.
+    pub fn scale(mut self, k: T) -> Self {
+        self.data.iter_mut().for_each(|a| *a = *a * k);
+        self
+    }
+
+    fn assert_same_shape(&self, rhs: &Self) {
+        assert_eq!(
+            (self.rows, self.cols),
+            (rhs.rows, rhs.cols),
+            "matrices must have the same shape"
+        );
+    }
+}
+
+/// Index of the row, at or below `row`, whose entry in column `col` has the
+/// largest absolute value. This is the pivot choice of partial pivoting.
+fn pivot_row<T: Num + Copy + PartialOrd>(
+    a: &Matrix<T>,
+    row: usize,
+    col: usize,
+) -> usize {
+    // Absolute value over ordered types, without requiring `Neg`
+    let abs = |x: T| if x < T::zero() { T::zero() - x } else { x };
+    (row + 1..a.rows()).fold(row, |p, i| {
+        if abs(a[(i, col)]) > abs(a[(p, col)]) {
+            i
+        } else {
+            p
+        }
+    })
+}
+
+impl<T: Num + Copy> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    /// Entry at row `i` and column `j`.
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T: Num + Copy> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<T: Num + Copy> Add<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    /// Entrywise addition. Panics if the matrices have different shapes.
+    fn add(self, rhs: &Matrix<T>) -> Matrix<T> {
+        self.assert_same_shape(rhs);
+        let data = self.data.iter().zip(&rhs.data).map(|(&a, &b)| a + b);
+        Matrix::from_vec(self.rows, self.cols, data.collect())
+    }
+}
+
+impl<T: Num + Copy> Add for Matrix<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        &self + &rhs
+    }
+}
+
+impl<T: Num + Copy> Sub<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    /// Entrywise subtraction. Panics if the matrices have different shapes.
+    fn sub(self, rhs: &Matrix<T>) -> Matrix<T> {
+        self.assert_same_shape(rhs);
+        let data = self.data.iter().zip(&rhs.data).map(|(&a, &b)| a - b);
+        Matrix::from_vec(self.rows, self.cols, data.collect())
+    }
+}
+
+impl<T: Num + Copy> Sub for Matrix<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        &self - &rhs
+    }
+}
+
+/// Naive matrix multiplication, taking `O(n m p)` time for an `n x m` matrix
+/// times an `m x p` matrix.
+impl<T: Num + Copy> Mul<&Matrix<T>> for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    /// Panics if the number of columns of `self` differs from the number of
+    /// rows of `rhs`.
+    /// Example:
+    /// ```
+    /// use ralg::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]);
+    /// let b = Matrix::from_rows(vec![vec![0, 1], vec![1, 0]]);
+    /// assert_eq!(&a * &b, Matrix::from_rows(vec![vec![2, 1], vec![4, 3]]));
+    /// ```
+    fn mul(self, rhs: &Matrix<T>) -> Matrix<T> {
+        assert_eq!(
+            self.cols, rhs.rows,
+            "cannot multiply a {}x{} matrix by a {}x{} matrix",
+            self.rows, self.cols, rhs.rows, rhs.cols
+        );
+
+        let mut prod = Matrix::zeros(self.rows, rhs.cols);
+        for i in 0..self.rows {
+            // The `i-k-j` loop order walks both operands row by row
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..rhs.cols {
+                    prod[(i, j)] = prod[(i, j)] + a * rhs[(k, j)];
+                }
+            }
+        }
+        prod
+    }
+}
+
+impl<T: Num + Copy> Mul for Matrix<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn constructors() {
+        let z: Matrix<i32> = Matrix::zeros(2, 3);
+        assert_eq!(z.into_rows(), vec![vec![0; 3]; 2]);
+
+        let id: Matrix<f64> = Matrix::identity(3);
+        assert_eq!(
+            id,
+            Matrix::from_rows(vec![
+                vec![1.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0],
+            ])
+        );
+        assert!(id.is_square());
+
+        let a = Matrix::from_vec(2, 2, vec![1, 2, 3, 4]);
+        assert_eq!(a.row(1), &[3, 4]);
+        assert_eq!(a, Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]));
+
+        let empty: Matrix<i32> = Matrix::from_rows(vec![]);
+        assert_eq!((empty.rows(), empty.cols()), (0, 0));
+        let a: Matrix<i32> = Matrix::zeros(3, 0);
+        assert_eq!(a.into_rows(), vec![Vec::<i32>::new(); 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ragged_rows() {
+        Matrix::from_rows(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn index() {
+        let mut a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(a[(0, 2)], 3);
+        a[(1, 1)] = -5;
+        assert_eq!(a.row(1), &[4, -5, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let a: Matrix<i32> = Matrix::zeros(2, 3);
+        let _ = a[(0, 3)];
+    }
+
+    #[test]
+    fn swap_rows() {
+        let mut a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+        a.swap_rows(2, 0);
+        assert_eq!(
+            a,
+            Matrix::from_rows(vec![vec![5, 6], vec![3, 4], vec![1, 2]])
+        );
+        a.swap_rows(1, 1);
+        assert_eq!(a.row(1), &[3, 4]);
+    }
+
+    #[test]
+    fn transpose() {
+        let a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(a.transpose().row(2), &[3, 6]);
+    }
+
+    #[test]
+    fn add_sub() {
+        let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        let b = Matrix::from_rows(vec![vec![5, -2], vec![0, 1]]);
+        assert_eq!(&a + &b, Matrix::from_rows(vec![vec![6, 0], vec![3, 5]]));
+        assert_eq!(
+            a.clone() - b,
+            Matrix::from_rows(vec![vec![-4, 4], vec![3, 3]])
+        );
+        assert_eq!(a.clone().scale(2), &a + &a);
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_different_shapes() {
+        let _ = Matrix::<i32>::zeros(2, 3) + Matrix::zeros(3, 2);
+    }
+
+    #[test]
+    fn mul() {
+        let a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = Matrix::from_rows(vec![vec![7, 8], vec![9, 10], vec![11, 12]]);
+        assert_eq!(
+            &a * &b,
+            Matrix::from_rows(vec![vec![58, 64], vec![139, 154]])
+        );
+        assert_eq!((&a * &b).transpose(), &b.transpose() * &a.transpose());
+
+        let id = Matrix::identity(3);
+        assert_eq!(&a * &id, a);
+        assert_eq!(Matrix::identity(2) * a.clone(), a);
+
+        let c: Matrix<i32> = Matrix::zeros(2, 0);
+        assert_eq!(&c * &Matrix::zeros(0, 4), Matrix::zeros(2, 4));
+    }
+
+    #[test]
+    fn pow() {
+        let a = Matrix::from_rows(vec![vec![2, 1], vec![1, 1]]);
+        assert_eq!(a.pow(0), Matrix::identity(2));
+        assert_eq!(a.pow(1), a);
+        assert_eq!(a.pow(3), &(&a * &a) * &a);
+
+        let z: Matrix<i32> = Matrix::zeros(0, 0);
+        assert_eq!(z.pow(10), z);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_incompatible() {
+        let a: Matrix<i32> = Matrix::zeros(2, 3);
+        let _ = &a * &a;
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+        let fields = |rows, cols| {
+            [
+                Token::Str("rows"),
+                Token::U64(rows),
+                Token::Str("cols"),
+                Token::U64(cols),
+                Token::Str("data"),
+            ]
+        };
+        let start = Token::Struct {
+            name: "Matrix",
+            len: 3,
+        };
+        let a = Matrix::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        let mut tokens = vec![start];
+        tokens.extend(fields(2, 2));
+        tokens.extend([Token::Seq { len: Some(4) }, Token::I32(1)]);
+        tokens.extend([Token::I32(2), Token::I32(3), Token::I32(4)]);
+        tokens.extend([Token::SeqEnd, Token::StructEnd]);
+        assert_tokens(&a, &tokens);
+
+        let mut bad = vec![start];
+        bad.extend(fields(1, 2));
+        bad.extend([Token::Seq { len: Some(1) }, Token::I32(1)]);
+        bad.extend([Token::SeqEnd, Token::StructEnd]);
+        assert_de_tokens_error::<Matrix<i32>>(&bad, "incompatible dimensions");
+    }
+}
@@ -1,31 +1,273 @@
-///! Miscellaneous functions
+//! Miscellaneous functions
+use crate::math::num::Num;
 
 /// Given a number `n`, computes and returns the smallest power of 2 greater
-/// than or equal to `n`. Assumes 0 as a power of 2.
-pub fn next_power_of_2(n: usize) -> usize {
-    // The zero case needs to be treated separately because we may cause usize overflow
-    if n == 0 {
-        1
-    } else if (n & (n - 1)) == 0 {
-        n
-    } else {
-        0x8000000000000000 >> (n.leading_zeros() - 1)
+/// than or equal to `n`, which is 1 for zero. Returns `None` if that power
+/// doesn't fit in a `usize`, whatever its width on the target.
+///
+/// Example:
+/// ```
+/// use ralg::math::misc::next_power_of_2;
+///
+/// assert_eq!(next_power_of_2(0), Some(1));
+/// assert_eq!(next_power_of_2(33), Some(64));
+/// assert_eq!(next_power_of_2(usize::MAX), None);
+/// ```
+pub fn next_power_of_2(n: usize) -> Option<usize> {
+    if n <= 1 {
+        return Some(1);
     }
+    // Shifting by the width of the type overflows
+    let shift = usize::BITS - (n - 1).leading_zeros();
+    1_usize.checked_shl(shift)
+}
+
+/// Floor of the base 2 logarithm of `n`, that is, the position of its
+/// highest set bit. Returns `None` for zero.
+///
+/// Example:
+/// ```
+/// use ralg::math::misc::ilog2;
+///
+/// assert_eq!(ilog2(1), Some(0));
+/// assert_eq!(ilog2(1000), Some(9));
+/// assert_eq!(ilog2(usize::MAX), Some(usize::BITS - 1));
+/// assert_eq!(ilog2(0), None);
+/// ```
+pub fn ilog2(n: usize) -> Option<u32> {
+    match n {
+        0 => None,
+        _ => Some(usize::BITS - 1 - n.leading_zeros()),
+    }
+}
+
+/// Whether `n` is a power of 2, that is, has a single set bit. Zero is not a
+/// power of 2.
+pub fn is_power_of_two(n: usize) -> bool {
+    n != 0 && n & (n - 1) == 0
+}
+
+/// Largest power of 2 less than or equal to `n`, or `None` for zero.
+///
+/// Example:
+/// ```
+/// use ralg::math::misc::prev_power_of_2;
+///
+/// assert_eq!(prev_power_of_2(1), Some(1));
+/// assert_eq!(prev_power_of_2(1000), Some(512));
+/// assert_eq!(prev_power_of_2(0), None);
+/// ```
+pub fn prev_power_of_2(n: usize) -> Option<usize> {
+    ilog2(n).map(|k| 1 << k)
+}
+
+/// Reverses the order of the lowest `bits` bits of `x`, discarding the
+/// higher ones, as in the bit-reversal permutation of the FFT on `2^bits`
+/// points. Panics if `bits` exceeds the width of `usize`.
+///
+/// Example:
+/// ```
+/// use ralg::math::misc::bit_reverse;
+///
+/// assert_eq!(bit_reverse(0b0011, 4), 0b1100);
+/// assert_eq!(bit_reverse(0b1_0110, 3), 0b011);
+/// assert_eq!(bit_reverse(7, 0), 0);
+/// ```
+pub fn bit_reverse(x: usize, bits: u32) -> usize {
+    assert!(bits <= usize::BITS, "cannot reverse {} bits of usize", bits);
+    match bits {
+        0 => 0,
+        _ => x.reverse_bits() >> (usize::BITS - bits),
+    }
+}
+
+/// Number of set bits of `x`.
+///
+/// Example:
+/// ```
+/// use ralg::math::misc::hamming_weight;
+///
+/// assert_eq!(hamming_weight(0b1011_0001), 4);
+/// assert_eq!(hamming_weight(usize::MAX), usize::BITS);
+/// ```
+pub fn hamming_weight(x: usize) -> u32 {
+    x.count_ones()
+}
+
+/// Computes `base^exp` by repeated squaring, where `one` is the identity of
+/// the product `mul`, taking `O(log(exp))` products. This serves types that
+/// are not `Copy` or whose identity depends on the value, such as matrices.
+///
+/// Example:
+/// ```
+/// use ralg::math::misc::pow_with;
+///
+/// // Concatenating strings: the identity is the empty string
+/// let s = pow_with(String::from("ab"), 3, String::new(), |a, b| a.clone() + b);
+/// assert_eq!(s, "ababab");
+/// ```
+pub fn pow_with<T, F>(mut base: T, mut exp: u64, one: T, mul: F) -> T
+where
+    F: Fn(&T, &T) -> T,
+{
+    let mut acc = one;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul(&acc, &base);
+        }
+        exp >>= 1;
+        // Skip the last squaring, which is never used
+        if exp > 0 {
+            base = mul(&base, &base);
+        }
+    }
+    acc
+}
+
+/// Computes `base^exp` by repeated squaring, taking `O(log(exp))`
+/// multiplications. The zeroth power is always one.
+///
+/// Example:
+/// ```
+/// use ralg::math::{misc::pow_by_squaring, num::Rational};
+///
+/// assert_eq!(pow_by_squaring(3_u64, 13), 1594323);
+/// assert_eq!(pow_by_squaring(Rational::new(-2, 3), 3), Rational::new(-8, 27));
+/// assert_eq!(pow_by_squaring(0, 0), 1);
+/// ```
+pub fn pow_by_squaring<T: Num + Copy>(base: T, exp: u64) -> T {
+    pow_with(base, exp, T::one(), |&a, &b| a * b)
+}
+
+/// Computes `base^exp mod m` by repeated squaring, with products carried
+/// out in 128-bit integers so that every modulus up to `u64::MAX` works.
+/// Panics if `m` is zero.
+///
+/// Example:
+/// ```
+/// use ralg::math::misc::mod_pow;
+///
+/// // Fermat's little theorem
+/// assert_eq!(mod_pow(3, 1_000_000_006, 1_000_000_007), 1);
+/// assert_eq!(mod_pow(2, 10, 1000), 24);
+/// assert_eq!(mod_pow(5, 0, 1), 0);
+/// ```
+pub fn mod_pow(base: u64, exp: u64, m: u64) -> u64 {
+    assert!(m != 0, "modulus must be nonzero");
+    let mul = |&a: &u64, &b: &u64| (a as u128 * b as u128 % m as u128) as u64;
+    pow_with(base % m, exp, 1 % m, mul)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::math::num::Rational;
+    use crate::random::Pcg32;
+    use alloc::vec;
 
     #[test]
     fn next_power() {
-        assert_eq!(next_power_of_2(0), 1);
-        assert_eq!(next_power_of_2(1), 1);
-        assert_eq!(next_power_of_2(2), 2);
-        assert_eq!(next_power_of_2(3), 4);
-        assert_eq!(next_power_of_2(8), 8);
-        assert_eq!(next_power_of_2(33), 64);
-        assert_eq!(next_power_of_2(55), 64);
-        assert_eq!(next_power_of_2(1291), 2048);
+        assert_eq!(next_power_of_2(0), Some(1));
+        assert_eq!(next_power_of_2(1), Some(1));
+        assert_eq!(next_power_of_2(2), Some(2));
+        assert_eq!(next_power_of_2(3), Some(4));
+        assert_eq!(next_power_of_2(8), Some(8));
+        assert_eq!(next_power_of_2(33), Some(64));
+        assert_eq!(next_power_of_2(55), Some(64));
+        assert_eq!(next_power_of_2(1291), Some(2048));
+    }
+
+    #[test]
+    fn next_power_near_overflow() {
+        let top = 1_usize << (usize::BITS - 1);
+        assert_eq!(next_power_of_2(top - 1), Some(top));
+        assert_eq!(next_power_of_2(top), Some(top));
+        assert_eq!(next_power_of_2(top + 1), None);
+        assert_eq!(next_power_of_2(usize::MAX - 1), None);
+        assert_eq!(next_power_of_2(usize::MAX), None);
+        // Agrees with the standard library for every width
+        for n in (0..5000).chain(top - 5000..top + 5000) {
+            assert_eq!(next_power_of_2(n), n.checked_next_power_of_two());
+        }
+    }
+
+    #[test]
+    fn bits() {
+        for k in 0..usize::BITS {
+            let n = 1_usize << k;
+            assert!(is_power_of_two(n));
+            assert_eq!(ilog2(n), Some(k));
+            assert_eq!(prev_power_of_2(n), Some(n));
+            assert_eq!(hamming_weight(n), 1);
+            if k > 1 {
+                assert!(!is_power_of_two(n + 1));
+                assert_eq!(ilog2(n - 1), Some(k - 1));
+                assert_eq!(prev_power_of_2(n + 1), Some(n));
+                assert_eq!(prev_power_of_2(n - 1), Some(n / 2));
+                assert_eq!(hamming_weight(n - 1), k);
+            }
+        }
+        assert!(!is_power_of_two(0));
+        assert_eq!(prev_power_of_2(usize::MAX), Some(1 << (usize::BITS - 1)));
+    }
+
+    #[test]
+    fn bit_reversal() {
+        assert_eq!(bit_reverse(1, usize::BITS), 1 << (usize::BITS - 1));
+        assert_eq!(bit_reverse(0b110, 1), 0);
+        for bits in 1..12 {
+            let n = 1 << bits;
+            let mut seen = vec![false; n];
+            for i in 0..n {
+                let j = bit_reverse(i, bits);
+                // An involution permuting 0..n
+                assert_eq!(bit_reverse(j, bits), i);
+                assert!(!seen[j]);
+                seen[j] = true;
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_bits() {
+        bit_reverse(1, usize::BITS + 1);
+    }
+
+    #[test]
+    fn powers() {
+        for exp in 0..20 {
+            assert_eq!(pow_by_squaring(3_i64, exp), 3_i64.pow(exp as u32));
+            assert_eq!(pow_by_squaring(-2_i64, exp), (-2_i64).pow(exp as u32));
+        }
+        assert_eq!(pow_by_squaring(1_u8, u64::MAX), 1);
+        assert_eq!(pow_by_squaring(0.5, 10), 1.0 / 1024.0);
+        assert_eq!(
+            pow_by_squaring(Rational::new(3, 2), 5),
+            Rational::new(243, 32)
+        );
+    }
+
+    #[test]
+    fn modular_powers() {
+        let mut rng = Pcg32::seed(0x3c1a);
+        for _ in 0..200 {
+            let m = rng.next_u64() | 1;
+            let (a, e) = (rng.next_u64(), rng.gen_range(0..64));
+            // Compare with the naive product
+            let naive = (0..e).fold(1 % m as u128, |acc, _| {
+                acc * (a % m) as u128 % m as u128
+            });
+            assert_eq!(mod_pow(a, e, m), naive as u64);
+        }
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX), 0);
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(mod_pow(7, 0, 13), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_modulus() {
+        mod_pow(2, 3, 0);
     }
 }
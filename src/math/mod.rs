@@ -1,5 +1,21 @@
+pub mod bigint;
+pub mod combinatorics;
 pub mod complex;
+pub mod diff;
+pub mod dual;
+#[cfg(feature = "std")]
 pub mod fft;
+pub mod fit;
+pub mod fixed;
+pub mod float;
+pub mod matrix;
 pub mod misc;
+pub mod modular;
 pub mod num;
+pub mod numtheory;
+pub mod ode;
 pub mod poly;
+pub mod primes;
+pub mod quadrature;
+pub mod recurrence;
+pub mod roots;
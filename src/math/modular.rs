@@ -0,0 +1,214 @@
+//! Modular arithmetic
+use crate::math::{
+    misc::pow_by_squaring,
+    num::{Num, One, Zero},
+    numtheory::mod_inverse,
+};
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Integer modulo the compile-time constant `M`, that is, an element of the
+/// ring `Z/MZ`, represented by its least non-negative residue. When `M` is a
+/// prime, this is a finite field and every non-zero element is invertible.
+///
+/// Products are computed with 128-bit integers, so any modulus up to
+/// `u64::MAX` is supported.
+///
+/// Example:
+/// ```
+/// use ralg::math::modular::Zmod;
+///
+/// type F7 = Zmod<7>;
+/// let x = F7::new(3);
+/// assert_eq!(x * F7::new(5), F7::new(1));
+/// assert_eq!(x.inverse(), Some(F7::new(5)));
+/// assert_eq!(F7::from(-1_i64), F7::new(6));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Zmod<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> Zmod<M> {
+    /// The residue class of `x` modulo `M`. Panics if `M == 0`.
+    pub fn new(x: u64) -> Self {
+        assert!(M > 0, "the modulus must be positive");
+        Zmod { value: x % M }
+    }
+
+    /// Least non-negative residue, in the range `0..M`.
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    /// The modulus `M`.
+    pub fn modulus() -> u64 {
+        M
+    }
+
+    /// Computes `self^exp` by repeated squaring, taking `O(log(exp))`
+    /// multiplications.
+    pub fn pow_mod(self, exp: u64) -> Self {
+        pow_by_squaring(self, exp)
+    }
+
+    /// Multiplicative inverse of `self`, found by the extended Euclidean
+    /// algorithm. Returns `None` if `self` is not coprime to `M`.
+    pub fn inverse(self) -> Option<Self> {
+        let inv = mod_inverse(self.value as i128, M as i128).ok()?;
+        Some(Zmod::new(inv as u64))
+    }
+}
+
+impl<const M: u64> From<u64> for Zmod<M> {
+    fn from(x: u64) -> Self {
+        Zmod::new(x)
+    }
+}
+
+impl<const M: u64> From<i64> for Zmod<M> {
+    fn from(x: i64) -> Self {
+        Zmod::new((x as i128).rem_euclid(M as i128) as u64)
+    }
+}
+
+impl<const M: u64> Add for Zmod<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let s = self.value as u128 + rhs.value as u128;
+        Zmod {
+            value: (s % M as u128) as u64,
+        }
+    }
+}
+
+impl<const M: u64> Sub for Zmod<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<const M: u64> Neg for Zmod<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Zmod::new(M - self.value)
+    }
+}
+
+impl<const M: u64> Mul for Zmod<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let p = self.value as u128 * rhs.value as u128;
+        Zmod {
+            value: (p % M as u128) as u64,
+        }
+    }
+}
+
+impl<const M: u64> Div for Zmod<M> {
+    type Output = Self;
+
+    /// Multiplication by the inverse of `rhs`. Panics if `rhs` is not
+    /// invertible modulo `M`.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        match rhs.inverse() {
+            Some(inv) => self * inv,
+            None => panic!("{} is not invertible modulo {}", rhs.value, M),
+        }
+    }
+}
+
+impl<const M: u64> Zero for Zmod<M> {
+    fn zero() -> Self {
+        Zmod::new(0)
+    }
+}
+
+impl<const M: u64> One for Zmod<M> {
+    fn one() -> Self {
+        Zmod::new(1)
+    }
+}
+
+impl<const M: u64> Num for Zmod<M> {}
+
+impl<const M: u64> fmt::Display for Zmod<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::poly::Polynomial;
+
+    type F7 = Zmod<7>;
+    type Z12 = Zmod<12>;
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (F7::new(5), F7::new(4));
+        assert_eq!(a + b, F7::new(2));
+        assert_eq!(b - a, F7::new(6));
+        assert_eq!(a * b, F7::new(6));
+        assert_eq!(a / b, F7::new(3));
+        assert_eq!(-F7::zero(), F7::zero());
+        assert_eq!(F7::new(20).value(), 6);
+        assert_eq!(F7::from(-15_i64), F7::new(6));
+        assert_eq!(F7::modulus(), 7);
+        assert_eq!(a.to_string(), "5");
+
+        // Large moduli don't overflow
+        const P: u64 = 18_446_744_073_709_551_557;
+        let x = Zmod::<P>::new(P - 1);
+        assert_eq!(x * x, Zmod::one());
+        assert_eq!(x + x, Zmod::new(P - 2));
+    }
+
+    #[test]
+    fn pow_and_inverse() {
+        // Fermat's little theorem
+        for x in 1..7 {
+            assert_eq!(F7::new(x).pow_mod(6), F7::one());
+            let inv = F7::new(x).inverse().unwrap();
+            assert_eq!(inv * F7::new(x), F7::one());
+        }
+        assert_eq!(F7::new(3).pow_mod(0), F7::one());
+        assert_eq!(F7::zero().inverse(), None);
+
+        assert_eq!(Z12::new(5).inverse(), Some(Z12::new(5)));
+        assert_eq!(Z12::new(8).inverse(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_invertible_division() {
+        let _ = Z12::new(1) / Z12::new(3);
+    }
+
+    #[test]
+    fn polynomials() {
+        let p = |cs: &[u64]| {
+            Polynomial::new(cs.iter().map(|&c| F7::new(c)).collect())
+        };
+
+        // (x + 1)^7 == x^7 + 1 over the field with 7 elements
+        let x1 = p(&[1, 1]);
+        let mut pow = p(&[1]);
+        for _ in 0..7 {
+            pow *= &x1;
+        }
+        assert_eq!(pow, p(&[1, 0, 0, 0, 0, 0, 0, 1]));
+
+        let (q, r) = p(&[1, 0, 0, 0, 0, 0, 0, 1]).div_rem(&x1);
+        assert_eq!(q, p(&[1, 6, 1, 6, 1, 6, 1]));
+        assert_eq!(r, p(&[]));
+    }
+}
@@ -0,0 +1,377 @@
+//! Number types and such
+use core::ops::{Add, Mul, Neg, Sub};
+
+pub mod ordered;
+pub mod rational;
+pub use ordered::OrderedFloat;
+pub use rational::Rational;
+
+pub trait Zero: Sized + Add<Self, Output = Self> {
+    /// Returns the zero element of the structure
+    fn zero() -> Self;
+
+    /// Zero is always an additive identity, that is, given any element `x`, one
+    /// should have `x + 0 = x` and `0 + x = x`.
+    fn add_zero<T: Sized + Add<T, Output = T>>(x: T) -> T {
+        x
+    }
+}
+
+/// Used to implement boiler plate code for typical numerical types
+#[macro_export]
+macro_rules! zero_impl {
+    ($t: ty, $zero: expr) => {
+        impl Zero for $t {
+            fn zero() -> $t {
+                $zero
+            }
+
+            fn add_zero<T: Sized + Add<T, Output = T>>(x: T) -> T {
+                x
+            }
+        }
+    };
+}
+
+zero_impl!(usize, 0);
+zero_impl!(u8, 0);
+zero_impl!(u16, 0);
+zero_impl!(u32, 0);
+zero_impl!(u64, 0);
+zero_impl!(u128, 0);
+
+zero_impl!(isize, 0);
+zero_impl!(i8, 0);
+zero_impl!(i16, 0);
+zero_impl!(i32, 0);
+zero_impl!(i64, 0);
+zero_impl!(i128, 0);
+
+zero_impl!(f32, 0.0);
+zero_impl!(f64, 0.0);
+
+/// Multiplicative identity, given `x`, we have `x * 1 = x` and `1 * x = x`
+pub trait One: Sized + Mul<Self, Output = Self> {
+    /// Returns the one element of the structure
+    fn one() -> Self;
+
+    fn multiply_one<T: Sized + Mul<Self, Output = Self>>(x: T) -> T {
+        x
+    }
+}
+
+/// Used to implement boiler plate code for typical numerical types
+#[macro_export]
+macro_rules! one_impl {
+    ($t: ty, $one: expr) => {
+        impl One for $t {
+            fn one() -> $t {
+                $one
+            }
+
+            fn multiply_one<T: Sized + Mul<Self, Output = Self>>(x: T) -> T {
+                x
+            }
+        }
+    };
+}
+
+one_impl!(usize, 1);
+one_impl!(u8, 1);
+one_impl!(u16, 1);
+one_impl!(u32, 1);
+one_impl!(u64, 1);
+one_impl!(u128, 1);
+
+one_impl!(isize, 1);
+one_impl!(i8, 1);
+one_impl!(i16, 1);
+one_impl!(i32, 1);
+one_impl!(i64, 1);
+one_impl!(i128, 1);
+
+one_impl!(f32, 1.0);
+one_impl!(f64, 1.0);
+
+/// Typical numerical trait assemblying the most important traits
+pub trait Num:
+    PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Zero
+    + One
+{
+    // Empty trait
+}
+
+macro_rules! impl_num {
+    ($($t: ty)*) => ($(
+        impl Num for $t {}
+    )*)
+}
+
+impl_num!(usize u8 u16 u32 u64 u128);
+impl_num!(isize i8 i16 i32 i64 i128);
+impl_num!(f32 f64);
+
+/// Numbers carrying a sign, such as the signed integers and floats
+pub trait Signed: Num + Neg<Output = Self> {
+    /// Absolute value of the number
+    fn abs(&self) -> Self;
+
+    /// Returns `-1`, `0` or `1` according to the sign of the number. For
+    /// floats, both zeros map to zero and `NaN` maps to `NaN`.
+    fn signum(&self) -> Self;
+
+    /// Whether the number is strictly less than zero
+    fn is_negative(&self) -> bool;
+
+    /// Whether the number is strictly greater than zero
+    fn is_positive(&self) -> bool;
+}
+
+macro_rules! signed_int_impl {
+    ($($t: ty)*) => ($(
+        impl Signed for $t {
+            fn abs(&self) -> $t {
+                <$t>::abs(*self)
+            }
+
+            fn signum(&self) -> $t {
+                <$t>::signum(*self)
+            }
+
+            fn is_negative(&self) -> bool {
+                *self < 0
+            }
+
+            fn is_positive(&self) -> bool {
+                *self > 0
+            }
+        }
+    )*)
+}
+
+signed_int_impl!(isize i8 i16 i32 i64 i128);
+
+macro_rules! signed_float_impl {
+    ($($t: ty)*) => ($(
+        impl Signed for $t {
+            fn abs(&self) -> $t {
+                <$t>::abs(*self)
+            }
+
+            fn signum(&self) -> $t {
+                // The standard `signum` maps zeros to one
+                if *self == 0.0 {
+                    0.0
+                } else {
+                    <$t>::signum(*self)
+                }
+            }
+
+            fn is_negative(&self) -> bool {
+                *self < 0.0
+            }
+
+            fn is_positive(&self) -> bool {
+                *self > 0.0
+            }
+        }
+    )*)
+}
+
+signed_float_impl!(f32 f64);
+
+/// Numbers with a least and a greatest value
+pub trait Bounded {
+    /// Smallest value of the type
+    fn min_value() -> Self;
+
+    /// Largest value of the type
+    fn max_value() -> Self;
+}
+
+macro_rules! bounded_impl {
+    ($($t: ty)*) => ($(
+        impl Bounded for $t {
+            fn min_value() -> $t {
+                <$t>::MIN
+            }
+
+            fn max_value() -> $t {
+                <$t>::MAX
+            }
+        }
+    )*)
+}
+
+bounded_impl!(usize u8 u16 u32 u64 u128);
+bounded_impl!(isize i8 i16 i32 i64 i128);
+// Floats are bounded by their finite extremes, not by the infinities
+bounded_impl!(f32 f64);
+
+/// Addition returning `None` instead of overflowing
+pub trait CheckedAdd: Sized + Add<Output = Self> {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+/// Multiplication returning `None` instead of overflowing
+pub trait CheckedMul: Sized + Mul<Output = Self> {
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+/// Addition wrapping around at the bounds of the type, that is, modulo
+/// `2^bits` for integers of `bits` bits
+pub trait WrappingAdd: Sized + Add<Output = Self> {
+    fn wrapping_add(self, rhs: Self) -> Self;
+}
+
+/// Addition clamped to the bounds of the type instead of overflowing
+pub trait SaturatingAdd: Sized + Add<Output = Self> {
+    fn saturating_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! overflow_impl {
+    ($($t: ty)*) => ($(
+        impl CheckedAdd for $t {
+            fn checked_add(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_add(self, rhs)
+            }
+        }
+
+        impl CheckedMul for $t {
+            fn checked_mul(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_mul(self, rhs)
+            }
+        }
+
+        impl WrappingAdd for $t {
+            fn wrapping_add(self, rhs: $t) -> $t {
+                <$t>::wrapping_add(self, rhs)
+            }
+        }
+
+        impl SaturatingAdd for $t {
+            fn saturating_add(self, rhs: $t) -> $t {
+                <$t>::saturating_add(self, rhs)
+            }
+        }
+    )*)
+}
+
+overflow_impl!(usize u8 u16 u32 u64 u128);
+overflow_impl!(isize i8 i16 i32 i64 i128);
+
+macro_rules! float_checked_add_impl {
+    ($($t: ty)*) => ($(
+        // Floats never overflow, rounding to infinity instead
+        impl CheckedAdd for $t {
+            fn checked_add(self, rhs: $t) -> Option<$t> {
+                Some(self + rhs)
+            }
+        }
+    )*)
+}
+
+float_checked_add_impl!(f32 f64);
+
+/// Smallest element of `xs`, or `T::max_value()` if `xs` is empty, since the
+/// greatest value is the identity of the minimum.
+///
+/// Example:
+/// ```
+/// use ralg::math::num::min_of;
+///
+/// assert_eq!(min_of(&[3, -1, 4]), -1);
+/// assert_eq!(min_of::<u8>(&[]), u8::MAX);
+/// ```
+pub fn min_of<T: Bounded + PartialOrd + Copy>(xs: &[T]) -> T {
+    xs.iter()
+        .fold(T::max_value(), |m, &x| if x < m { x } else { m })
+}
+
+/// Greatest element of `xs`, or `T::min_value()` if `xs` is empty, since the
+/// smallest value is the identity of the maximum.
+///
+/// Example:
+/// ```
+/// use ralg::math::num::max_of;
+///
+/// assert_eq!(max_of(&[3.5, -1.0, 4.25]), 4.25);
+/// assert_eq!(max_of::<i32>(&[]), i32::MIN);
+/// ```
+pub fn max_of<T: Bounded + PartialOrd + Copy>(xs: &[T]) -> T {
+    xs.iter()
+        .fold(T::min_value(), |m, &x| if x > m { x } else { m })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn magnitudes<T: Signed + Copy>(xs: &[T]) -> Vec<T> {
+        xs.iter().map(Signed::abs).collect()
+    }
+
+    #[test]
+    fn signed_integers() {
+        assert_eq!(magnitudes(&[-3, 0, 5, i32::MIN + 1]), [3, 0, 5, i32::MAX]);
+        assert_eq!(Signed::signum(&-7_i64), -1);
+        assert_eq!(Signed::signum(&0_i8), 0);
+        assert_eq!(Signed::signum(&12_isize), 1);
+        assert!(Signed::is_negative(&-1_i128));
+        assert!(!Signed::is_negative(&0_i16));
+        assert!(Signed::is_positive(&1_i16));
+        assert!(!Signed::is_positive(&0_i16));
+    }
+
+    #[test]
+    fn signed_floats() {
+        assert_eq!(magnitudes(&[-1.5, 2.0, -0.0]), [1.5, 2.0, 0.0]);
+        assert_eq!(Signed::signum(&-2.5_f64), -1.0);
+        assert_eq!(Signed::signum(&0.0_f32), 0.0);
+        assert_eq!(Signed::signum(&-0.0_f64), 0.0);
+        assert_eq!(Signed::signum(&f64::INFINITY), 1.0);
+        assert!(Signed::signum(&f32::NAN).is_nan());
+        assert!(!Signed::is_negative(&-0.0_f64));
+        assert!(!Signed::is_positive(&f64::NAN));
+        assert!(Signed::is_negative(&f32::NEG_INFINITY));
+    }
+
+    fn checked_sum<T: CheckedAdd + Zero + Copy>(xs: &[T]) -> Option<T> {
+        xs.iter().try_fold(T::zero(), |acc, &x| acc.checked_add(x))
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(checked_sum(&[100_u8, 100, 55]), Some(255));
+        assert_eq!(checked_sum(&[100_u8, 100, 56]), None);
+        assert_eq!(checked_sum(&[i64::MIN, -1]), None);
+        assert_eq!(checked_sum(&[f64::MAX, f64::MAX]), Some(f64::INFINITY));
+        assert_eq!(
+            CheckedMul::checked_mul(1_u32 << 16, 1 << 15),
+            Some(1 << 31)
+        );
+        assert_eq!(CheckedMul::checked_mul(1_u32 << 16, 1 << 16), None);
+        assert_eq!(WrappingAdd::wrapping_add(250_u8, 10), 4);
+        assert_eq!(WrappingAdd::wrapping_add(i8::MAX, 1), i8::MIN);
+        assert_eq!(SaturatingAdd::saturating_add(250_u8, 10), u8::MAX);
+        assert_eq!(SaturatingAdd::saturating_add(-100_i8, -100), i8::MIN);
+    }
+
+    #[test]
+    fn bounded() {
+        assert_eq!(<u16 as Bounded>::min_value(), 0);
+        assert_eq!(<i8 as Bounded>::max_value(), 127);
+        assert_eq!(<f64 as Bounded>::max_value(), f64::MAX);
+        assert_eq!(<f32 as Bounded>::min_value(), f32::MIN);
+
+        assert_eq!(min_of(&[7_u64, 2, 9]), 2);
+        assert_eq!(max_of(&[7_u64, 2, 9]), 9);
+        assert_eq!(max_of(&[i128::MIN]), i128::MIN);
+        assert_eq!(min_of(&[-0.5, f64::MAX]), -0.5);
+        assert_eq!(max_of::<f32>(&[]), f32::MIN);
+    }
+}
@@ -0,0 +1,119 @@
+//! Totally ordered floating point numbers
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// Floating point number with a total order, so that it can be sorted or used
+/// as a key: NaN is greater than every other value and equal to itself, while
+/// the remaining values keep their usual order, `-0.0` being equal to `0.0`.
+///
+/// Example:
+/// ```
+/// use ralg::math::num::OrderedFloat;
+///
+/// let mut xs = [2.5, f64::NAN, -1.0, f64::INFINITY].map(OrderedFloat);
+/// xs.sort();
+/// assert_eq!(xs[..3], [-1.0, 2.5, f64::INFINITY].map(OrderedFloat));
+/// assert!(xs[3].into_inner().is_nan());
+/// assert_eq!(OrderedFloat(f64::NAN), OrderedFloat(-f64::NAN));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OrderedFloat<T>(pub T);
+
+impl<T> OrderedFloat<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for OrderedFloat<T> {
+    fn from(x: T) -> Self {
+        OrderedFloat(x)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for OrderedFloat<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! ordered_float_impl {
+    ($($t: ty)*) => ($(
+        impl Ord for OrderedFloat<$t> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                let (x, y) = (self.0, other.0);
+                match x.partial_cmp(&y) {
+                    Some(ord) => ord,
+                    // At least one of them is NaN
+                    None => x.is_nan().cmp(&y.is_nan()),
+                }
+            }
+        }
+
+        impl PartialOrd for OrderedFloat<$t> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl PartialEq for OrderedFloat<$t> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for OrderedFloat<$t> {}
+
+        impl Hash for OrderedFloat<$t> {
+            /// Hashes equal values alike, all NaNs as one and both zeros as
+            /// positive zero.
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                let x = if self.0.is_nan() {
+                    <$t>::NAN
+                } else if self.0 == 0.0 {
+                    0.0
+                } else {
+                    self.0
+                };
+                x.to_bits().hash(state);
+            }
+        }
+    )*)
+}
+
+ordered_float_impl!(f32 f64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash<T: Hash>(x: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        x.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn total_order() {
+        let nan = OrderedFloat(f32::NAN);
+        let inf = OrderedFloat(f32::INFINITY);
+        assert!(nan > inf);
+        assert!(OrderedFloat(f32::NEG_INFINITY) < OrderedFloat(f32::MIN));
+        assert_eq!(nan.cmp(&OrderedFloat(-f32::NAN)), Ordering::Equal);
+        assert_eq!(OrderedFloat(-0.0_f32), OrderedFloat(0.0));
+        assert!(OrderedFloat(1.5_f64).max(OrderedFloat(f64::NAN)).0.is_nan());
+        assert_eq!(OrderedFloat::from(2.0_f64).to_string(), "2");
+    }
+
+    #[test]
+    fn hash_agrees_with_eq() {
+        assert_eq!(hash(&OrderedFloat(0.0_f64)), hash(&OrderedFloat(-0.0_f64)));
+        assert_eq!(
+            hash(&OrderedFloat(f64::NAN)),
+            hash(&OrderedFloat(-f64::NAN))
+        );
+        assert_ne!(hash(&OrderedFloat(1.0_f32)), hash(&OrderedFloat(-1.0_f32)));
+    }
+}
@@ -0,0 +1,326 @@
+//! Exact rational numbers
+use super::{Num, One, Signed, Zero};
+use crate::math::numtheory::gcd;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Rational number `num / den` in lowest terms, with a positive denominator.
+///
+/// Both parts are 128-bit integers. Common factors are cancelled before
+/// multiplying, so an operation only panics if its reduced result doesn't fit
+/// in 128 bits, or for sums, if the numerator before the last reduction
+/// doesn't.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    /// Creates the rational number `num / den`, reduced to lowest terms.
+    /// Panics if `den == 0`.
+    /// Example:
+    /// ```
+    /// use ralg::math::num::Rational;
+    ///
+    /// let q = Rational::new(6, -4);
+    /// assert_eq!((q.numer(), q.denom()), (-3, 2));
+    /// ```
+    pub fn new(num: i128, den: i128) -> Self {
+        Rational::reduce(num, den)
+    }
+
+    /// The rational number `n / 1`.
+    pub fn from_integer(n: i128) -> Self {
+        Rational::reduce(n, 1)
+    }
+
+    /// Numerator in lowest terms, carrying the sign of the number.
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    /// Denominator in lowest terms, which is always positive.
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// Multiplicative inverse `den / num`. Panics if `self` is zero.
+    pub fn recip(self) -> Self {
+        Rational::reduce(self.den, self.num)
+    }
+
+    /// Absolute value.
+    pub fn abs(self) -> Self {
+        Rational {
+            num: self.num.abs(),
+            den: self.den,
+        }
+    }
+
+    /// Normalizes `num / den` so that the denominator is positive and
+    /// `gcd(num, den) == 1`. Neither part may end up as `i128::MIN`, so
+    /// that negating a rational never overflows.
+    fn reduce(num: i128, den: i128) -> Self {
+        assert!(den != 0, "rational number with zero denominator");
+        let g = gcd(num, den);
+        let (num, den) = (num / g, den / g);
+        let (num, den) = if den < 0 {
+            (num.checked_neg(), den.checked_neg())
+        } else {
+            (Some(num), Some(den))
+        };
+        match (num, den) {
+            (Some(num), Some(den)) if num != i128::MIN => Rational { num, den },
+            _ => overflow(),
+        }
+    }
+}
+
+/// Panics on a rational number whose parts don't fit in 128 bits.
+fn overflow() -> ! {
+    panic!("rational number overflow")
+}
+
+/// Checked product of two 128-bit integers.
+fn mul(a: i128, b: i128) -> i128 {
+    a.checked_mul(b).unwrap_or_else(|| overflow())
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Rational::from_integer(n.into())
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // With g = gcd(b, d), a/b + c/d = (a d/g + c b/g) / (b d/g), whose
+        // numerator only shares factors with g
+        let (a, b, c, d) = (self.num, self.den, rhs.num, rhs.den);
+        let g = gcd(b, d);
+        let num = mul(a, d / g)
+            .checked_add(mul(c, b / g))
+            .unwrap_or_else(|| overflow());
+        let h = gcd(num, g);
+        Rational::reduce(num / h, mul(b / g, d / h))
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // Cancel across the fractions, so the products are already reduced
+        let (a, b, c, d) = (self.num, self.den, rhs.num, rhs.den);
+        let (g, h) = (gcd(a, d), gcd(c, b));
+        Rational::reduce(mul(a / g, c / h), mul(b / h, d / g))
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    /// Division of rational numbers. Panics if `rhs` is zero.
+    fn div(self, rhs: Self) -> Self {
+        Mul::mul(self, rhs.recip())
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Zero for Rational {
+    fn zero() -> Self {
+        Rational::from_integer(0)
+    }
+}
+
+impl One for Rational {
+    fn one() -> Self {
+        Rational::from_integer(1)
+    }
+}
+
+impl Num for Rational {}
+
+impl Signed for Rational {
+    fn abs(&self) -> Self {
+        Rational::abs(*self)
+    }
+
+    fn signum(&self) -> Self {
+        Rational::from_integer(self.num.signum())
+    }
+
+    fn is_negative(&self) -> bool {
+        self.num < 0
+    }
+
+    fn is_positive(&self) -> bool {
+        self.num > 0
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive, so cross-multiplying keeps the order
+        let (a, b, c, d) = (self.num, self.den, other.num, other.den);
+        match (a.checked_mul(d), c.checked_mul(b)) {
+            (Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+            _ => cmp_continued(a, b, c, d),
+        }
+    }
+}
+
+/// Compares `a / b` and `c / d`, for positive `b` and `d`, through their
+/// continued fractions: the integer parts first, then the reciprocals of the
+/// fractional parts, whose order is reversed. Nothing overflows.
+fn cmp_continued(
+    mut a: i128,
+    mut b: i128,
+    mut c: i128,
+    mut d: i128,
+) -> Ordering {
+    loop {
+        let (p, q) = (a.div_euclid(b), c.div_euclid(d));
+        if p != q {
+            return p.cmp(&q);
+        }
+        let (r, s) = (a.rem_euclid(b), c.rem_euclid(d));
+        if r == 0 || s == 0 {
+            return r.cmp(&s);
+        }
+        // r/b < s/d exactly when d/s < b/r
+        (a, b, c, d) = (d, s, b, r);
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    /// Formats as `num/den`, or just `num` for integers.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn q(num: i128, den: i128) -> Rational {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!(q(2, 4), q(1, 2));
+        assert_eq!(q(-2, -4), q(1, 2));
+        assert_eq!((q(3, -9).numer(), q(3, -9).denom()), (-1, 3));
+        assert_eq!((q(0, -5).numer(), q(0, -5).denom()), (0, 1));
+        assert!(q(10, 5).is_integer());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_denominator() {
+        q(1, 0);
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(q(1, 2) + q(1, 3), q(5, 6));
+        assert_eq!(q(1, 2) - q(3, 4), q(-1, 4));
+        assert_eq!(q(2, 3) * q(9, 4), q(3, 2));
+        assert_eq!(q(2, 3) / q(-4, 9), q(-3, 2));
+        assert_eq!(-q(2, 3), q(-2, 3));
+        assert_eq!(q(-7, 3).recip(), q(-3, 7));
+        assert_eq!(q(-7, 3).abs(), q(7, 3));
+        assert_eq!(Signed::signum(&q(-7, 3)), q(-1, 1));
+        assert_eq!(Signed::signum(&q(0, 3)), Rational::zero());
+        assert!(Signed::is_positive(&q(1, 9)));
+        assert_eq!(Rational::zero() + Rational::one(), Rational::from(1));
+
+        // Common factors are cancelled before multiplying
+        let big = q(i128::MAX, 3);
+        assert_eq!(big * q(3, i128::MAX), Rational::one());
+        assert_eq!(big / big, Rational::one());
+        assert_eq!(q(1, i128::MAX) + q(1, i128::MAX), q(2, i128::MAX));
+        assert_eq!(big - big, Rational::zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "rational number overflow")]
+    fn overflow() {
+        let _ = q(i128::MAX, 1) + q(1, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "rational number overflow")]
+    fn min_numerator() {
+        q(i128::MIN, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn division_by_zero() {
+        let _ = q(1, 2) / Rational::zero();
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(q(1, 3) < q(1, 2));
+        assert!(q(-1, 2) < q(-1, 3));
+        assert!(q(7, 7) == Rational::one());
+        let mut xs = vec![q(3, 4), q(-1, 2), q(2, 3), q(0, 1)];
+        xs.sort();
+        assert_eq!(xs, vec![q(-1, 2), q(0, 1), q(2, 3), q(3, 4)]);
+
+        // Cross products that overflow 128 bits
+        let (m, n) = (i128::MAX, i128::MAX - 1);
+        assert!(q(n, m) < q(m, n));
+        assert!(q(n - 1, n) < q(n, m));
+        assert!(q(-m, n) < q(-n, m));
+        assert_eq!(q(m, n).cmp(&q(m, n)), Ordering::Equal);
+        assert!(q(m, 2) > q(m - 2, 2));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(q(6, -4).to_string(), "-3/2");
+        assert_eq!(q(8, 4).to_string(), "2");
+        assert_eq!(Rational::zero().to_string(), "0");
+    }
+}
@@ -0,0 +1,288 @@
+//! Elementary number theory: greatest common divisors, modular inverses and
+//! the Chinese Remainder Theorem
+use core::{error::Error, fmt};
+
+/// Error returned by number theoretic routines whose inputs admit no answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumTheoryError {
+    /// A modulus is not positive.
+    InvalidModulus(i128),
+
+    /// Two numbers that were required to be coprime share a common factor.
+    NotCoprime(i128, i128),
+
+    /// The result doesn't fit in 128 bits.
+    Overflow,
+}
+
+impl fmt::Display for NumTheoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidModulus(m) => write!(f, "invalid modulus `{}`", m),
+            Self::NotCoprime(a, b) => {
+                write!(f, "`{}` and `{}` are not coprime", a, b)
+            }
+            Self::Overflow => write!(f, "result overflows 128 bits"),
+        }
+    }
+}
+
+impl Error for NumTheoryError {}
+
+/// Greatest common divisor of `a` and `b`, which is always non-negative, and
+/// zero only if both `a` and `b` are zero. Uses the Euclidean algorithm.
+/// Panics if the gcd is `2^127`, which doesn't fit in `i128`: this only
+/// happens when both `a` and `b` are `0` or `i128::MIN`, see `gcd_u128`.
+///
+/// Example:
+/// ```
+/// use ralg::math::numtheory::gcd;
+///
+/// assert_eq!(gcd(84, -36), 12);
+/// assert_eq!(gcd(0, 0), 0);
+/// ```
+pub fn gcd(a: i128, b: i128) -> i128 {
+    gcd_u128(a.unsigned_abs(), b.unsigned_abs())
+        .try_into()
+        .expect("gcd overflows i128")
+}
+
+/// Greatest common divisor of unsigned integers.
+pub fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple of `a` and `b`, which is always non-negative, and
+/// zero if either of them is zero. Panics on overflow.
+pub fn lcm(a: i128, b: i128) -> i128 {
+    lcm_u128(a.unsigned_abs(), b.unsigned_abs())
+        .try_into()
+        .expect("lcm overflows i128")
+}
+
+/// Least common multiple of unsigned integers. Panics on overflow.
+pub fn lcm_u128(a: u128, b: u128) -> u128 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd_u128(a, b))
+        .checked_mul(b)
+        .expect("lcm overflows u128")
+}
+
+/// Extended Euclidean algorithm: returns `(g, x, y)` such that
+/// `a * x + b * y == g`, where `g == gcd(a, b)`. As with `gcd`, panics if
+/// `g` is `2^127`, which doesn't fit in `i128`.
+///
+/// Example:
+/// ```
+/// use ralg::math::numtheory::ext_gcd;
+///
+/// let (g, x, y) = ext_gcd(240, 46);
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    // A step dividing `i128::MIN` by `-1` would overflow
+    if a.unsigned_abs() == 1 {
+        return (1, a, 0);
+    }
+    if b.unsigned_abs() == 1 {
+        return (1, 0, b);
+    }
+
+    // Invariants: `r0 == a * x0 + b * y0` and `r1 == a * x1 + b * y1`
+    //
+    // The coefficients of the last step, `x1 == b / g` and `y1 == -a / g` up
+    // to sign, may be `2^127`. Since they are discarded, we compute modulo
+    // `2^128`, which is exact for the coefficients we return.
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1_i128, 0_i128);
+    let (mut y0, mut y1) = (0_i128, 1_i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0.wrapping_sub(q.wrapping_mul(x1)));
+        (y0, y1) = (y1, y0.wrapping_sub(q.wrapping_mul(y1)));
+    }
+    if r0 < 0 {
+        let g = r0.checked_neg().expect("gcd overflows i128");
+        (g, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// Inverse of `a` modulo `m`, that is, the unique `x` in `0..m` with
+/// `a * x == 1 (mod m)`.
+///
+/// Example:
+/// ```
+/// use ralg::math::numtheory::{mod_inverse, NumTheoryError};
+///
+/// assert_eq!(mod_inverse(3, 11), Ok(4));
+/// assert_eq!(mod_inverse(6, 9), Err(NumTheoryError::NotCoprime(6, 9)));
+/// ```
+pub fn mod_inverse(a: i128, m: i128) -> Result<i128, NumTheoryError> {
+    if m <= 0 {
+        return Err(NumTheoryError::InvalidModulus(m));
+    }
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return Err(NumTheoryError::NotCoprime(a, m));
+    }
+    Ok(x.rem_euclid(m))
+}
+
+/// Computes `a * b mod m` for `a, b` in `0..m` without overflowing, by
+/// falling back to multiplication by doubling when the product is too wide.
+fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    if let Some(p) = a.checked_mul(b) {
+        return p % m;
+    }
+
+    let (mut a, mut b, m) = (a as u128, b as u128, m as u128);
+    let mut acc = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            acc = (acc + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    acc as i128
+}
+
+/// Chinese Remainder Theorem: given congruences `x == r (mod m)` as pairs
+/// `(r, m)` with pairwise coprime moduli, returns `(x, M)` where `M` is the
+/// product of the moduli and `x` in `0..M` is the unique solution modulo `M`.
+/// An empty system has the solution `(0, 1)`.
+///
+/// Returns `NumTheoryError::NotCoprime` if two moduli share a factor,
+/// `NumTheoryError::InvalidModulus` if some modulus is not positive and
+/// `NumTheoryError::Overflow` if `M` doesn't fit in an `i128`.
+///
+/// Example:
+/// ```
+/// use ralg::math::numtheory::crt;
+///
+/// // x == 2 (mod 3), x == 3 (mod 5), x == 2 (mod 7)
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+/// ```
+pub fn crt(
+    congruences: &[(i128, i128)],
+) -> Result<(i128, i128), NumTheoryError> {
+    let (mut x, mut m) = (0, 1);
+    for &(r, n) in congruences {
+        if n <= 0 {
+            return Err(NumTheoryError::InvalidModulus(n));
+        }
+        let inv =
+            mod_inverse(m, n).map_err(|_| NumTheoryError::NotCoprime(m, n))?;
+
+        // Find `x + m * t == r (mod n)`, that is, `t == (r - x) / m (mod n)`
+        let diff = (r.rem_euclid(n) - x % n).rem_euclid(n);
+        let t = mul_mod(diff, inv, n);
+        let next = m.checked_mul(n).ok_or(NumTheoryError::Overflow)?;
+        x += m * t;
+        m = next;
+    }
+    Ok((x, m))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(-12, -18), 6);
+        assert_eq!(gcd(0, -5), 5);
+        assert_eq!(gcd(17, 31), 1);
+        assert_eq!(gcd(i128::MIN, 6), 2);
+        assert_eq!(gcd(i128::MIN, i128::MAX), 1);
+        assert_eq!(gcd_u128(i128::MIN.unsigned_abs(), 0), 1 << 127);
+        assert_eq!(gcd_u128(u128::MAX, 5), 5);
+
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm_u128(1 << 100, 3), 3 << 100);
+    }
+
+    #[test]
+    #[should_panic]
+    fn gcd_overflow() {
+        gcd(i128::MIN, 0);
+    }
+
+    #[test]
+    fn extended() {
+        for (a, b) in [
+            (240, 46),
+            (-240, 46),
+            (0, 7),
+            (7, 0),
+            (13, -8),
+            (0, 0),
+            (1, -1),
+            (i128::MIN, 1),
+            (i128::MIN, i128::MAX),
+            (i128::MIN, 6),
+            (i128::MAX, -2),
+        ] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a.wrapping_mul(x).wrapping_add(b.wrapping_mul(y)), g);
+        }
+        assert_eq!(ext_gcd(i128::MIN, -1), (1, 0, -1));
+        assert_eq!(ext_gcd(-1, i128::MIN), (1, -1, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn ext_gcd_overflow() {
+        ext_gcd(i128::MIN, 0);
+    }
+
+    #[test]
+    fn inverse() {
+        for a in 1..13 {
+            let inv = mod_inverse(a, 13).unwrap();
+            assert_eq!(a * inv % 13, 1);
+        }
+        assert_eq!(mod_inverse(-3, 11), Ok(7));
+        assert_eq!(mod_inverse(5, 1), Ok(0));
+        assert_eq!(mod_inverse(4, 0), Err(NumTheoryError::InvalidModulus(0)));
+        assert_eq!(mod_inverse(4, 8), Err(NumTheoryError::NotCoprime(4, 8)));
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt(&[]), Ok((0, 1)));
+        assert_eq!(crt(&[(-1, 4)]), Ok((3, 4)));
+        assert_eq!(crt(&[(1, 2), (2, 3), (3, 5), (4, 7)]), Ok((53, 210)));
+        assert_eq!(
+            crt(&[(1, 4), (3, 6)]),
+            Err(NumTheoryError::NotCoprime(4, 6))
+        );
+        assert_eq!(
+            crt(&[(1, 4), (3, -6)]),
+            Err(NumTheoryError::InvalidModulus(-6))
+        );
+
+        // Moduli whose products need the overflow-free multiplication
+        let (p, q) = (1_000_000_000_000_000_003, 1_000_000_000_000_000_009);
+        let (x, m) = crt(&[(5, p), (7, q)]).unwrap();
+        assert_eq!(m, p * q);
+        assert_eq!((x % p, x % q), (5, 7));
+
+        let big = 1 << 126;
+        assert_eq!(crt(&[(0, big), (0, 3)]), Err(NumTheoryError::Overflow));
+    }
+}
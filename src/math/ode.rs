@@ -0,0 +1,378 @@
+//! Initial value problems for ordinary differential equations
+//!
+//! Solvers integrate systems `dy/dt = f(t, y)`, where `f` returns the
+//! derivative of the state `y` at the time `t`, from the state `y0` at `t0`
+//! up to the time `t1`, which may come before `t0`. They return the
+//! trajectory: the times of every step paired with the states at these times,
+//! from `(t0, y0)` up to the state at `t1`.
+use alloc::{vec, vec::Vec};
+use core::{error::Error, fmt};
+
+/// States of a system at increasing (or decreasing) times.
+pub type Trajectory = Vec<(f64, Vec<f64>)>;
+
+/// Greatest number of attempted steps of `rk45`.
+pub const MAX_STEPS: usize = 100_000;
+
+/// Error returned when the adaptive solver cannot reach the final time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OdeError {
+    /// The step needed for the tolerance vanished in front of the time, which
+    /// happens near singularities of the solution.
+    StepTooSmall(f64),
+
+    /// The solver took `MAX_STEPS` steps without reaching the final time,
+    /// stopping at the given time.
+    TooManySteps(f64),
+
+    /// The derivative is NaN or infinite at the given time.
+    NotFinite(f64),
+}
+
+impl fmt::Display for OdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StepTooSmall(t) => {
+                write!(f, "step size vanished at t = {}", t)
+            }
+            Self::TooManySteps(t) => {
+                write!(f, "too many steps, stopped at t = {}", t)
+            }
+            Self::NotFinite(t) => {
+                write!(f, "derivative not finite at t = {}", t)
+            }
+        }
+    }
+}
+
+impl Error for OdeError {}
+
+/// `y + h (c_1 k_1 + c_2 k_2 + ...)` for the pairs `(c_i, k_i)` of `terms`.
+fn combine(y: &[f64], h: f64, terms: &[(f64, &[f64])]) -> Vec<f64> {
+    let mut z = y.to_vec();
+    for &(c, k) in terms {
+        if c != 0.0 {
+            for (zi, ki) in z.iter_mut().zip(k) {
+                *zi += h * c * ki;
+            }
+        }
+    }
+    z
+}
+
+/// Integrates with `steps` equal steps of `step`, which maps the time, the
+/// state and the step size to the next state.
+fn fixed_steps<S>(
+    t0: f64,
+    y0: &[f64],
+    t1: f64,
+    steps: usize,
+    step: S,
+) -> Trajectory
+where
+    S: Fn(f64, &[f64], f64) -> Vec<f64>,
+{
+    assert!(steps > 0, "no steps");
+    let h = (t1 - t0) / steps as f64;
+    let mut trajectory = Vec::with_capacity(steps + 1);
+    trajectory.push((t0, y0.to_vec()));
+    for i in 1..=steps {
+        let (t, y) = &trajectory[i - 1];
+        let next = step(*t, y, h);
+        // Compute the times afresh to avoid accumulating rounding errors
+        let t = if i == steps { t1 } else { t0 + i as f64 * h };
+        trajectory.push((t, next));
+    }
+    trajectory
+}
+
+/// Explicit Euler method with `steps` equal steps, whose global error is in
+/// O(h) for a step `h`.
+///
+/// Panics if `steps == 0`.
+///
+/// Example:
+/// ```
+/// use ralg::math::ode::euler;
+///
+/// // y' = 1 is solved exactly
+/// let trajectory = euler(|_, _| vec![1.0], 0.0, &[2.0], 3.0, 6);
+/// assert_eq!(trajectory.len(), 7);
+/// assert_eq!(trajectory[6], (3.0, vec![5.0]));
+/// ```
+pub fn euler<F>(f: F, t0: f64, y0: &[f64], t1: f64, steps: usize) -> Trajectory
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    fixed_steps(t0, y0, t1, steps, |t, y, h| {
+        combine(y, h, &[(1.0, &f(t, y))])
+    })
+}
+
+/// Classical fourth order Runge-Kutta method with `steps` equal steps, whose
+/// global error is in O(h^4) for a step `h`.
+///
+/// Panics if `steps == 0`.
+///
+/// Example:
+/// ```
+/// use ralg::math::ode::rk4;
+///
+/// // y' = y, so that y(1) = e
+/// let trajectory = rk4(|_, y| y.to_vec(), 0.0, &[1.0], 1.0, 100);
+/// let (t, y) = trajectory.last().unwrap();
+/// assert_eq!(*t, 1.0);
+/// assert!((y[0] - std::f64::consts::E).abs() < 1e-9);
+/// ```
+pub fn rk4<F>(f: F, t0: f64, y0: &[f64], t1: f64, steps: usize) -> Trajectory
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    fixed_steps(t0, y0, t1, steps, |t, y, h| {
+        let k1 = f(t, y);
+        let k2 = f(t + h / 2.0, &combine(y, h / 2.0, &[(1.0, &k1)]));
+        let k3 = f(t + h / 2.0, &combine(y, h / 2.0, &[(1.0, &k2)]));
+        let k4 = f(t + h, &combine(y, h, &[(1.0, &k3)]));
+        let sixth = 1.0 / 6.0;
+        let third = 1.0 / 3.0;
+        combine(
+            y,
+            h,
+            &[(sixth, &k1), (third, &k2), (third, &k3), (sixth, &k4)],
+        )
+    })
+}
+
+/// Nodes of the Dormand-Prince method.
+const DP_C: [f64; 6] = [1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+
+/// Runge-Kutta matrix of the Dormand-Prince method, whose last row gives the
+/// fifth order solution.
+const DP_A: [[f64; 6]; 6] = [
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+
+/// Weights of the embedded fourth order solution of the Dormand-Prince method.
+const DP_B4: [f64; 7] = [
+    5179.0 / 57600.0,
+    0.0,
+    7571.0 / 16695.0,
+    393.0 / 640.0,
+    -92097.0 / 339200.0,
+    187.0 / 2100.0,
+    1.0 / 40.0,
+];
+
+/// Adaptive Runge-Kutta method of orders 5 and 4 by Dormand and Prince: each
+/// step is taken with the fifth order solution, and accepted if it differs
+/// from the fourth order one by at most `tol` in every coordinate, relative
+/// to the coordinate when greater than one. The step is halved after a
+/// rejection, and doubled when the difference is small enough for a step
+/// twice as long to pass.
+///
+/// Example:
+/// ```
+/// use ralg::math::ode::rk45;
+/// use std::f64::consts::PI;
+///
+/// // The harmonic oscillator y'' = -y comes back after a period
+/// let f = |_, y: &[f64]| vec![y[1], -y[0]];
+/// let trajectory = rk45(f, 0.0, &[1.0, 0.0], 2.0 * PI, 1e-10).unwrap();
+/// let (_, y) = trajectory.last().unwrap();
+/// assert!((y[0] - 1.0).abs() < 1e-8 && y[1].abs() < 1e-8);
+/// ```
+pub fn rk45<F>(
+    f: F,
+    t0: f64,
+    y0: &[f64],
+    t1: f64,
+    tol: f64,
+) -> Result<Trajectory, OdeError>
+where
+    F: Fn(f64, &[f64]) -> Vec<f64>,
+{
+    let eval = |t: f64, y: &[f64]| {
+        let k = f(t, y);
+        if k.iter().all(|x| x.is_finite()) {
+            Ok(k)
+        } else {
+            Err(OdeError::NotFinite(t))
+        }
+    };
+
+    let mut trajectory = vec![(t0, y0.to_vec())];
+    let (mut t, mut y) = (t0, y0.to_vec());
+    let mut h = (t1 - t0) / 100.0;
+    // The last stage of an accepted step is the first of the next one
+    let mut k1 = eval(t, &y)?;
+    for _ in 0..MAX_STEPS {
+        if t == t1 {
+            return Ok(trajectory);
+        }
+        // Land exactly on t1
+        let last = (t + h - t1) * h.signum() >= 0.0;
+        if last {
+            h = t1 - t;
+        }
+        if t + h == t {
+            return Err(OdeError::StepTooSmall(t));
+        }
+
+        let mut k: Vec<Vec<f64>> = vec![k1.clone()];
+        for (row, &c) in DP_A.iter().zip(&DP_C) {
+            let terms: Vec<(f64, &[f64])> = row
+                .iter()
+                .zip(&k)
+                .map(|(&a, ki)| (a, ki.as_slice()))
+                .collect();
+            let stage = eval(t + c * h, &combine(&y, h, &terms))?;
+            k.push(stage);
+        }
+        // The fifth order solution, where the last stage was evaluated
+        let next: Vec<f64> = {
+            let terms: Vec<(f64, &[f64])> = DP_A[5]
+                .iter()
+                .zip(&k)
+                .map(|(&a, ki)| (a, ki.as_slice()))
+                .collect();
+            combine(&y, h, &terms)
+        };
+        let (fourth, fifth) = (&DP_B4, &DP_A[5]);
+        let mut error: f64 = 0.0;
+        for (i, (&yi, &zi)) in y.iter().zip(&next).enumerate() {
+            let mut diff = h * fourth[6] * k[6][i];
+            for j in 0..6 {
+                diff += h * (fourth[j] - fifth[j]) * k[j][i];
+            }
+            let scale = tol * yi.abs().max(zi.abs()).max(1.0);
+            error = error.max(diff.abs() / scale);
+        }
+
+        if error <= 1.0 {
+            t = if last { t1 } else { t + h };
+            y = next;
+            k1 = k.swap_remove(6);
+            trajectory.push((t, y.clone()));
+            // A step twice as long has an error about 32 times larger
+            if error <= 1.0 / 32.0 {
+                h *= 2.0;
+            }
+        } else {
+            h /= 2.0;
+        }
+    }
+    Err(OdeError::TooManySteps(t))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::f64::consts::{E, PI};
+
+    fn oscillator(_: f64, y: &[f64]) -> Vec<f64> {
+        vec![y[1], -y[0]]
+    }
+
+    /// Distance to the starting point of the oscillator after a period.
+    fn period_error(trajectory: &Trajectory) -> f64 {
+        let (t, y) = trajectory.last().unwrap();
+        assert_eq!(*t, 2.0 * PI);
+        (y[0] - 1.0).abs().max(y[1].abs())
+    }
+
+    #[test]
+    fn orders_of_fixed_steps() {
+        let mut previous = (f64::INFINITY, f64::INFINITY);
+        for steps in [100, 200, 400, 800] {
+            let y0 = [1.0, 0.0];
+            let e = period_error(&euler(oscillator, 0.0, &y0, 2.0 * PI, steps));
+            let r = period_error(&rk4(oscillator, 0.0, &y0, 2.0 * PI, steps));
+            // Halving the step divides the errors by about 2 and 16
+            assert!(e < previous.0 / 1.8);
+            assert!(r < previous.1 / 14.0);
+            previous = (e, r);
+        }
+        assert!(previous.1 < 1e-9);
+    }
+
+    #[test]
+    fn trajectories() {
+        let f = |t: f64, _: &[f64]| vec![2.0 * t];
+        let trajectory = rk4(f, 1.0, &[1.0], 0.0, 4);
+        let times: Vec<f64> = trajectory.iter().map(|(t, _)| *t).collect();
+        assert_eq!(times, [1.0, 0.75, 0.5, 0.25, 0.0]);
+        // Integrating backwards, y = t^2 is solved exactly
+        for (t, y) in &trajectory {
+            assert!((y[0] - t * t).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn adaptive() {
+        // y' = -2ty has the solution exp(-t^2)
+        let f = |t: f64, y: &[f64]| vec![-2.0 * t * y[0]];
+        let trajectory = rk45(f, 0.0, &[1.0], 1.0, 1e-12).unwrap();
+        let (t, y) = trajectory.last().unwrap();
+        assert_eq!(*t, 1.0);
+        assert!((y[0] - 1.0 / E).abs() < 1e-10);
+        assert!(trajectory.windows(2).all(|w| w[0].0 < w[1].0));
+
+        // Looser tolerances take fewer steps
+        let y0 = [1.0, 0.0];
+        let loose = rk45(oscillator, 0.0, &y0, 2.0 * PI, 1e-4).unwrap();
+        let tight = rk45(oscillator, 0.0, &y0, 2.0 * PI, 1e-10).unwrap();
+        assert!(loose.len() < tight.len());
+        assert!(period_error(&loose) < 1e-3);
+        assert!(period_error(&tight) < 1e-8);
+    }
+
+    #[test]
+    fn adaptive_errors() {
+        // y' = y^2 from y(0) = 1 blows up at t = 1
+        let f = |_: f64, y: &[f64]| vec![y[0] * y[0]];
+        match rk45(f, 0.0, &[1.0], 2.0, 1e-8) {
+            Err(OdeError::StepTooSmall(t) | OdeError::NotFinite(t)) => {
+                assert!((t - 1.0).abs() < 1e-3)
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        let nan =
+            |t: f64, _: &[f64]| vec![if t < 0.5 { 1.0 } else { f64::NAN }];
+        assert!(matches!(
+            rk45(nan, 0.0, &[0.0], 1.0, 1e-8),
+            Err(OdeError::NotFinite(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_steps() {
+        euler(oscillator, 0.0, &[1.0, 0.0], 1.0, 0);
+    }
+}
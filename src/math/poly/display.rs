@@ -0,0 +1,322 @@
+//! Formatting and parsing of polynomials in the variable `x`
+use super::Polynomial;
+use crate::math::num::Num;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{error::Error, fmt, str::FromStr};
+
+impl<T: Num + Copy + fmt::Display> fmt::Display for Polynomial<T> {
+    /// Formats the polynomial with increasing powers, omitting zero terms and
+    /// unit coefficients, as in `4 + 3x - x^2`. Coefficients which are sums
+    /// themselves, such as complex numbers, are wrapped in parentheses. The
+    /// zero polynomial is formatted as `0`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::{complex::Complex, poly::Polynomial};
+    ///
+    /// let p = Polynomial::new(vec![4, 3, 0, -1, 1]);
+    /// assert_eq!(p.to_string(), "4 + 3x - x^3 + x^4");
+    ///
+    /// let p = Polynomial::new(vec![Complex::new(1, 2), Complex::new(0, -1)]);
+    /// assert_eq!(p.to_string(), "(1+2i) + (0-1i)x");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (power, &a) in self.coeff.iter().enumerate() {
+            if a == T::zero() {
+                continue;
+            }
+
+            // Signs are detected through the formatted coefficient so that no
+            // ordering is required on `T`. Compound coefficients keep theirs
+            // within the parentheses.
+            let repr = a.to_string();
+            let compound = split_terms(&repr).len() > 1;
+            let (negative, abs) = match repr.strip_prefix('-') {
+                Some(abs) if !compound => (true, abs),
+                _ => (false, repr.as_str()),
+            };
+            match (first, negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+
+            let unit = abs == T::one().to_string();
+            match power {
+                _ if power > 0 && unit => {}
+                _ if compound => write!(f, "({})", abs)?,
+                _ => write!(f, "{}", abs)?,
+            }
+            match power {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{}", power)?,
+            }
+        }
+
+        if first {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a polynomial fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePolynomialError {
+    /// The input contains no terms.
+    Empty,
+
+    /// A term is malformed, e.g. `3x^` or `x2`.
+    InvalidTerm(String),
+
+    /// A coefficient could not be parsed as the coefficient type.
+    InvalidCoefficient(String),
+
+    /// An exponent is not a non-negative integer.
+    InvalidExponent(String),
+
+    /// An exponent is greater than `MAX_PARSED_DEGREE`.
+    ExponentTooLarge(usize),
+}
+
+/// Greatest exponent accepted when parsing a polynomial, which bounds the
+/// number of coefficients allocated for it.
+pub const MAX_PARSED_DEGREE: usize = 1 << 20;
+
+impl fmt::Display for ParsePolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => {
+                write!(f, "cannot parse polynomial from empty string")
+            }
+            Self::InvalidTerm(t) => write!(f, "invalid term `{}`", t),
+            Self::InvalidCoefficient(c) => {
+                write!(f, "invalid coefficient `{}`", c)
+            }
+            Self::InvalidExponent(e) => write!(f, "invalid exponent `{}`", e),
+            Self::ExponentTooLarge(e) => {
+                write!(f, "exponent `{}` is too large", e)
+            }
+        }
+    }
+}
+
+impl Error for ParsePolynomialError {}
+
+impl<T: Num + Copy + FromStr> FromStr for Polynomial<T> {
+    type Err = ParsePolynomialError;
+
+    /// Parses a sum of terms in the variable `x`, such as `3x^2 - 1 + x`.
+    /// Each term is an optional coefficient, optionally followed by `x` (or
+    /// `*x`) and an exponent `^n`. Terms may appear in any order and repeated
+    /// powers are added up. The result is reduced, so that `0` parses to the
+    /// zero polynomial. Exponents may not exceed `MAX_PARSED_DEGREE`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let p: Polynomial<i32> = "3x^2 - 1 + x".parse().unwrap();
+    /// assert_eq!(p, Polynomial::new(vec![-1, 1, 3]));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if s.is_empty() {
+            return Err(ParsePolynomialError::Empty);
+        }
+
+        let mut coeff: Vec<T> = Vec::new();
+        for term in split_terms(&s) {
+            let (c, power) = parse_term(term)?;
+            if coeff.len() <= power {
+                coeff.resize(power + 1, T::zero());
+            }
+            coeff[power] = coeff[power] + c;
+        }
+
+        let mut p = Polynomial::new(coeff);
+        p.reduce();
+        Ok(p)
+    }
+}
+
+/// Splits `s` before each `+` or `-` sign, except for the signs of exponents
+/// in scientific notation such as `1e-5`, and those within parentheses.
+pub(crate) fn split_terms(s: &str) -> Vec<&str> {
+    let bytes = s.as_bytes();
+    let mut terms = Vec::new();
+    let mut start = 0;
+    let mut depth = 0_usize;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        let is_sign = b == b'+' || b == b'-';
+        let after_exp = i > 0 && (bytes[i - 1] == b'e' || bytes[i - 1] == b'E');
+        if is_sign && depth == 0 && i > start && !after_exp {
+            terms.push(&s[start..i]);
+            start = i;
+        }
+    }
+    terms.push(&s[start..]);
+    terms
+}
+
+/// Parses a single signed term, returning its coefficient and power.
+fn parse_term<T: Num + FromStr>(
+    term: &str,
+) -> Result<(T, usize), ParsePolynomialError> {
+    let invalid = || ParsePolynomialError::InvalidTerm(term.to_string());
+    let (sign, body) = match term.as_bytes().first() {
+        Some(b'+') => ("", &term[1..]),
+        Some(b'-') => ("-", &term[1..]),
+        _ => ("", term),
+    };
+    if body.is_empty() {
+        return Err(invalid());
+    }
+
+    let (c, power) = match body.find('x') {
+        None => (body, 0),
+        Some(idx) => {
+            let c = &body[..idx];
+            let c = c.strip_suffix('*').unwrap_or(c);
+            let power = match &body[idx + 1..] {
+                "" => 1,
+                rest => {
+                    let exp = rest.strip_prefix('^').ok_or_else(invalid)?;
+                    exp.parse().map_err(|_| {
+                        ParsePolynomialError::InvalidExponent(exp.to_string())
+                    })?
+                }
+            };
+            if power > MAX_PARSED_DEGREE {
+                return Err(ParsePolynomialError::ExponentTooLarge(power));
+            }
+            (if c.is_empty() { "1" } else { c }, power)
+        }
+    };
+
+    // Coefficients in parentheses carry their own sign, as in `(1-2i)x`
+    if let Some(inner) = c.strip_prefix('(').and_then(|c| c.strip_suffix(')')) {
+        if !sign.is_empty() {
+            return Err(invalid());
+        }
+        let c = inner.parse().map_err(|_| {
+            ParsePolynomialError::InvalidCoefficient(inner.to_string())
+        })?;
+        return Ok((c, power));
+    }
+
+    // The sign is parsed together with the coefficient, so that unsigned
+    // coefficient types reject negative terms instead of underflowing
+    let c = format!("{}{}", sign, c);
+    let c = c
+        .parse()
+        .map_err(|_| ParsePolynomialError::InvalidCoefficient(c.clone()))?;
+    Ok((c, power))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::complex::Complex;
+
+    #[test]
+    fn display() {
+        let p: Polynomial<i32> = Polynomial::new(vec![]);
+        assert_eq!(p.to_string(), "0");
+
+        let p = Polynomial::new(vec![0, 0, 0]);
+        assert_eq!(p.to_string(), "0");
+
+        let p = Polynomial::new(vec![4, 3, 2]);
+        assert_eq!(p.to_string(), "4 + 3x + 2x^2");
+
+        let p = Polynomial::new(vec![0, -1, 0, 5, -7]);
+        assert_eq!(p.to_string(), "-x + 5x^3 - 7x^4");
+
+        let p = Polynomial::new(vec![-1, 1]);
+        assert_eq!(p.to_string(), "-1 + x");
+
+        let p = Polynomial::new(vec![0.5, 0.0, -2.25]);
+        assert_eq!(p.to_string(), "0.5 - 2.25x^2");
+
+        let p: Polynomial<u8> = Polynomial::new(vec![1, 1, 1]);
+        assert_eq!(p.to_string(), "1 + x + x^2");
+    }
+
+    #[test]
+    fn parse() {
+        let p: Polynomial<i32> = "3x^2 - 1 + x".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![-1, 1, 3]));
+
+        let p: Polynomial<i32> = "-x^3 + 2 * x + x - 4x^0".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![-4, 3, 0, -1]));
+
+        let p: Polynomial<f64> = "1.5e-1x + 2.5E+1".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![25.0, 0.15]));
+
+        let p: Polynomial<i32> = "0".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![]));
+
+        let p: Polynomial<u32> = "+2x^2 + 7".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![7, 0, 2]));
+    }
+
+    #[test]
+    fn parse_errors() {
+        use ParsePolynomialError::*;
+
+        let err = "".parse::<Polynomial<i32>>();
+        assert_eq!(err, Err(Empty));
+        let err = "3x^".parse::<Polynomial<i32>>();
+        assert_eq!(err, Err(InvalidExponent("".to_string())));
+        let err = "x^-2".parse::<Polynomial<i32>>();
+        assert_eq!(err, Err(InvalidExponent("".to_string())));
+        let err = "x^1000000000000".parse::<Polynomial<i32>>();
+        assert_eq!(err, Err(ExponentTooLarge(1_000_000_000_000)));
+        let p = format!("x^{}", MAX_PARSED_DEGREE).parse::<Polynomial<i32>>();
+        assert_eq!(p.map(|p| p.coeff.len()), Ok(MAX_PARSED_DEGREE + 1));
+        let err = "x2".parse::<Polynomial<i32>>();
+        assert_eq!(err, Err(InvalidTerm("x2".to_string())));
+        let err = "1 +".parse::<Polynomial<i32>>();
+        assert_eq!(err, Err(InvalidTerm("+".to_string())));
+        let err = "abc".parse::<Polynomial<i32>>();
+        assert_eq!(err, Err(InvalidCoefficient("abc".to_string())));
+        let err = "1 - x".parse::<Polynomial<u32>>();
+        assert_eq!(err, Err(InvalidCoefficient("-1".to_string())));
+        let err = "-(1+2i)x".parse::<Polynomial<Complex<i32>>>();
+        assert_eq!(err, Err(InvalidTerm("-(1+2i)x".to_string())));
+        let err = "(1+2i".parse::<Polynomial<Complex<i32>>>();
+        assert_eq!(err, Err(InvalidCoefficient("(1+2i".to_string())));
+    }
+
+    #[test]
+    fn round_trip() {
+        let p = Polynomial::new(vec![-3, 0, 1, 12, 0, -1]);
+        assert_eq!(p.to_string().parse::<Polynomial<i32>>(), Ok(p));
+
+        let p = Polynomial::new(vec![0.0, 2.5, -0.125]);
+        assert_eq!(p.to_string().parse::<Polynomial<f64>>(), Ok(p));
+
+        let z = Complex::new;
+        let p = Polynomial::new(vec![z(1.0, 0.0), z(-2.0, 3.0), z(1.0, 0.0)]);
+        assert_eq!(p.to_string(), "(1+0i) + (-2+3i)x + x^2");
+        assert_eq!(p.to_string().parse::<Polynomial<Complex<f64>>>(), Ok(p));
+
+        let p = Polynomial::new(vec![z(0.0, -1.0), z(-1.0, 0.0), z(0.5, -2.5)]);
+        assert_eq!(p.to_string(), "(0-1i) + (-1+0i)x + (0.5-2.5i)x^2");
+        assert_eq!(p.to_string().parse::<Polynomial<Complex<f64>>>(), Ok(p));
+    }
+}
@@ -0,0 +1,220 @@
+//! Greatest common divisors and resultants of polynomials over a field
+use super::Polynomial;
+use crate::math::{misc::pow_by_squaring, num::Num};
+use alloc::vec;
+use core::ops::Div;
+
+impl<T: Num + Copy + Div<Output = T>> Polynomial<T> {
+    /// Greatest common divisor of `a` and `b`, computed with the Euclidean
+    /// algorithm and normalized to be monic. The gcd of two zero polynomials
+    /// is the zero polynomial.
+    ///
+    /// The coefficients should belong to a field. Over floating point types,
+    /// rounding errors may keep the remainders from vanishing, in which case
+    /// the result is a constant.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // (x - 1)(x - 2) and (x - 1)(x + 3) share the factor x - 1
+    /// let a = Polynomial::new(vec![2.0, -3.0, 1.0]);
+    /// let b = Polynomial::new(vec![-3.0, 2.0, 1.0]);
+    /// assert_eq!(Polynomial::gcd(&a, &b), Polynomial::new(vec![-1.0, 1.0]));
+    /// ```
+    pub fn gcd(a: &Self, b: &Self) -> Self {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        a.reduce();
+        b.reduce();
+        while !b.coeff.is_empty() {
+            let (_, r) = a.div_rem(&b);
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// Resultant of `a` and `b`: the determinant of their Sylvester matrix,
+    /// which vanishes if and only if `a` and `b` have a common root (or both
+    /// leading coefficients vanish). The resultant with the zero polynomial
+    /// is zero.
+    ///
+    /// Computed along the Euclidean algorithm with the identity
+    /// `res(a, b) = (-1)^(m n) lc(b)^(m - k) res(b, a mod b)`, where `m`, `n`
+    /// and `k` are the degrees of `a`, `b` and `a mod b`, and
+    /// `res(a, b) = b^m` when `b` is a constant.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // x^2 - 1 and x - 1 share the root 1
+    /// let a = Polynomial::new(vec![-1.0, 0.0, 1.0]);
+    /// let b = Polynomial::new(vec![-1.0, 1.0]);
+    /// assert_eq!(Polynomial::resultant(&a, &b), 0.0);
+    /// ```
+    pub fn resultant(a: &Self, b: &Self) -> T {
+        let (mut a, mut b) = (a.clone(), b.clone());
+        a.reduce();
+        b.reduce();
+        if a.coeff.is_empty() || b.coeff.is_empty() {
+            return T::zero();
+        }
+
+        let mut res = T::one();
+        loop {
+            let (m, n) = (a.coeff.len() - 1, b.coeff.len() - 1);
+            let lead = b.coeff[n];
+            if n == 0 {
+                return res * pow_by_squaring(lead, m as u64);
+            }
+
+            let (_, r) = a.div_rem(&b);
+            if r.coeff.is_empty() {
+                return T::zero();
+            }
+
+            let k = r.coeff.len() - 1;
+            res = res * pow_by_squaring(lead, (m - k) as u64);
+            if m % 2 == 1 && n % 2 == 1 {
+                res = T::zero() - res;
+            }
+            a = b;
+            b = r;
+        }
+    }
+
+    /// Square-free part of the polynomial: the product of its distinct
+    /// irreducible factors, obtained as `p / gcd(p, p')`. The result is
+    /// monic. Requires a field of characteristic zero and is subject to the
+    /// same rounding caveats as `gcd` over floating point types.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // (x - 1)^2 has square-free part x - 1
+    /// let p = Polynomial::new(vec![1.0, -2.0, 1.0]);
+    /// assert_eq!(p.square_free(), Polynomial::new(vec![-1.0, 1.0]));
+    /// ```
+    pub fn square_free(&self) -> Self {
+        let g = Polynomial::gcd(self, &self.derivative());
+        if g.coeff.is_empty() {
+            return Polynomial::new(vec![]);
+        }
+        self.div_rem(&g).0.monic()
+    }
+
+    /// Divides the polynomial by its leading coefficient, returning a reduced
+    /// polynomial. The zero polynomial is left unchanged.
+    pub fn monic(mut self) -> Self {
+        self.reduce();
+        if let Some(&lead) = self.coeff.last() {
+            self.coeff.iter_mut().for_each(|c| *c = *c / lead);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gcd() {
+        let zero: Polynomial<f64> = Polynomial::new(vec![]);
+        assert_eq!(Polynomial::gcd(&zero, &zero), zero);
+
+        // gcd(p, 0) = p, normalized
+        let p = Polynomial::new(vec![2.0, 4.0]);
+        assert_eq!(Polynomial::gcd(&p, &zero), Polynomial::new(vec![0.5, 1.0]));
+        assert_eq!(Polynomial::gcd(&zero, &p), Polynomial::new(vec![0.5, 1.0]));
+
+        // Coprime polynomials
+        let a = Polynomial::new(vec![1.0, 0.0, 1.0]);
+        let b = Polynomial::new(vec![-1.0, 1.0]);
+        assert_eq!(Polynomial::gcd(&a, &b), Polynomial::new(vec![1.0]));
+
+        // (x - 1)^2 (x + 2) and (x - 1)(x + 2)(x - 4): gcd is x^2 + x - 2
+        let a = Polynomial::new(vec![2.0, -3.0, 0.0, 1.0]);
+        let b = Polynomial::new(vec![8.0, -6.0, -3.0, 1.0]);
+        assert_eq!(
+            Polynomial::gcd(&a, &b),
+            Polynomial::new(vec![-2.0, 1.0, 1.0])
+        );
+    }
+
+    /// Polynomials whose division leaves a rounding leftover in the leading
+    /// coefficient of the remainder, which used to stall the Euclidean loop.
+    fn rounding_pair() -> (Polynomial<f64>, Polynomial<f64>) {
+        let a = Polynomial::new(vec![
+            55.0,
+            -24.0,
+            -47.857142857142854,
+            -56.42857142857143,
+        ]);
+        let b = Polynomial::new(vec![-100.57142857142857, 91.28571428571429]);
+        (a, b)
+    }
+
+    #[test]
+    fn gcd_rounding() {
+        let (a, b) = rounding_pair();
+        // Coprime up to rounding, since b has a single root that is not a
+        // root of a
+        assert_eq!(Polynomial::gcd(&a, &b), Polynomial::new(vec![1.0]));
+        assert_eq!(Polynomial::gcd(&b, &a), Polynomial::new(vec![1.0]));
+    }
+
+    #[test]
+    fn resultant_rounding() {
+        // With b = b1 (x - r), res(a, b) = (-1)^3 b1^3 a(r)
+        let (a, b) = rounding_pair();
+        let root = -b.coeff[0] / b.coeff[1];
+        let expected = -b.coeff[1] * b.coeff[1] * b.coeff[1] * a.eval(root);
+        let res = Polynomial::resultant(&a, &b);
+        assert!((res - expected).abs() < 1e-9 * expected.abs());
+    }
+
+    #[test]
+    fn resultant() {
+        // The resultant of a0 + a1 x and b0 + b1 x is a1 b0 - a0 b1
+        let a = Polynomial::new(vec![1.0, 2.0]);
+        let b = Polynomial::new(vec![3.0, 5.0]);
+        let res: f64 = Polynomial::resultant(&a, &b);
+        assert!((res - (2.0 * 3.0 - 1.0 * 5.0)).abs() < 1.0e-12);
+
+        // res(x^2 + 1, x - 2) = (2)^2 + 1 = 5
+        let a = Polynomial::new(vec![1.0, 0.0, 1.0]);
+        let b = Polynomial::new(vec![-2.0, 1.0]);
+        assert_eq!(Polynomial::resultant(&a, &b), 5.0);
+        assert_eq!(Polynomial::resultant(&b, &a), 5.0);
+
+        // res(x^2 - 3x + 2, x^2 - 5x + 4) = 0, common root 1
+        let a = Polynomial::new(vec![2.0, -3.0, 1.0]);
+        let b = Polynomial::new(vec![4.0, -5.0, 1.0]);
+        assert_eq!(Polynomial::resultant(&a, &b), 0.0);
+
+        // res(x^2 - 1, x^2 - 4) = prod (a_i - b_j) = (1-2)(1+2)(-1-2)(-1+2) = 9
+        let a = Polynomial::new(vec![-1.0, 0.0, 1.0]);
+        let b = Polynomial::new(vec![-4.0, 0.0, 1.0]);
+        assert_eq!(Polynomial::resultant(&a, &b), 9.0);
+
+        // Constants and the zero polynomial
+        let c = Polynomial::new(vec![3.0]);
+        assert_eq!(Polynomial::resultant(&a, &c), 9.0);
+        let zero = Polynomial::new(vec![]);
+        assert_eq!(Polynomial::resultant(&a, &zero), 0.0);
+    }
+
+    #[test]
+    fn square_free() {
+        // (x + 1)^3 has square-free part x + 1
+        let p = Polynomial::new(vec![1.0, 3.0, 3.0, 1.0]);
+        assert_eq!(p.square_free(), Polynomial::new(vec![1.0, 1.0]));
+
+        // Already square-free
+        let p = Polynomial::new(vec![-2.0, 0.0, 2.0]);
+        assert_eq!(p.square_free(), Polynomial::new(vec![-1.0, 0.0, 1.0]));
+    }
+}
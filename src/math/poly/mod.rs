@@ -0,0 +1,1235 @@
+//! Polynomials in coefficient representation
+use crate::math::num::Num;
+#[cfg(feature = "std")]
+use crate::math::{
+    fft::{fft, ifft},
+    misc::next_power_of_2,
+};
+use alloc::{vec, vec::Vec};
+use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Sub, SubAssign};
+
+pub mod display;
+mod gcd;
+mod overflow;
+#[cfg(feature = "std")]
+pub mod roots;
+pub mod special;
+mod sturm;
+
+/// Polynomial representation using coefficients
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polynomial<T: Num + Copy> {
+    /// Vector containing the coefficients of a polynomial, the indices
+    /// correspond to the variable's power.
+    ///
+    /// For instance, the polynomial
+    /// `let p = Polynomial::new(vec![4, 3, 2, 9]);`
+    /// Corresponds to the polynomial
+    /// `p(x) = 4 + 3 x + 2 x^2 + 9 x^3`
+    /// in mathematical notation.
+    pub coeff: Vec<T>,
+}
+
+impl<T: Num + Copy> Polynomial<T> {
+    /// Create a new polynomial out ou a vector containing its coefficients.
+    /// Example: the vector `c = vec![0, 1, 5, 6]` generates the polynomial
+    /// `x^1 + 5 x^2 + 6 x^3`.
+    pub fn new(coeff: Vec<T>) -> Self {
+        Polynomial { coeff }
+    }
+
+    /// Degree-bound is always greater than or equal to the actuall degree of
+    /// the polynomial, we define it as the length - 1 of the coefficient
+    /// vector. Notice however that, if the coefficient vector is empty or
+    /// consists entirely of zeros, the degree is undefined, hence `None` is
+    /// returned.
+    pub fn degree_bound(&self) -> Option<usize> {
+        self.degree().map(|_| self.coeff.len() - 1)
+    }
+
+    /// Degree of the polynomial. Returns the index of the last non-zero
+    /// coefficient. If the vector of coefficients is empty or is entirely
+    /// composed of zeros, `None` is returned: the zero polynomial has no
+    /// degree.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// assert_eq!(Polynomial::new(vec![1, 2, 0]).degree(), Some(1));
+    /// assert_eq!(Polynomial::new(vec![0, 0]).degree(), None);
+    /// ```
+    pub fn degree(&self) -> Option<usize> {
+        self.coeff.iter().rposition(|&a| a != T::zero())
+    }
+
+    /// Evaluate the polynomial at a given point `x` of the domain.
+    ///
+    /// We use the Horner's method, which is O(n)
+    pub fn eval(&self, x: T) -> T {
+        let l = self.coeff.len();
+        if l == 0 {
+            // Empty polynomials are identically zero
+            return T::zero();
+        }
+
+        (0..self.coeff.len() - 1)
+            .rev()
+            .fold(self.coeff[l - 1], |acc, idx| self.coeff[idx] + x * acc)
+    }
+
+    /// Evaluates the polynomial at each of the points `xs`, by Horner's
+    /// method, which takes O(nm) time for `m` points.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let p = Polynomial::new(vec![1, 0, 2]);
+    /// assert_eq!(p.eval_many(&[0, 1, -3]), [1, 3, 19]);
+    /// ```
+    pub fn eval_many(&self, xs: &[T]) -> Vec<T> {
+        xs.iter().map(|&x| self.eval(x)).collect()
+    }
+
+    /// Reduces the coefficient representation of a given polynomial. That is,
+    /// we truncate the collection of zero valued coefficients from the end of
+    /// the `coeff` vector.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let mut p = Polynomial::new(vec![1, 0, 2, 4, 0, 0]);
+    /// p.reduce();
+    /// assert_eq!(p, Polynomial::new(vec![1, 0, 2, 4]));
+    ///
+    /// let mut q = Polynomial::new(vec![0, 0, 0]);
+    /// q.reduce();
+    /// assert_eq!(q, Polynomial::new(vec![]));
+    /// ```
+    pub fn reduce(&mut self) {
+        match self
+            .coeff
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, a)| a != &&T::zero())
+        {
+            // Truncate the vector of coefficients so that the last non-zero
+            // value is spared
+            Some((last_nonzero, _)) => {
+                self.coeff.truncate(last_nonzero + 1);
+            }
+
+            // If no index was found, the coefficients are either empty or all
+            // zeros, in both cases we should return a polynomial with an empty
+            // vector of coefficients
+            None => {
+                self.coeff = Vec::new();
+            }
+        }
+    }
+
+    /// Set degree-bound: Given a polynomial `p` and a degree bound `n`, we
+    /// append zeros to the tail of the coefficient vector `p.coeff` until it
+    /// has `n + 1` coefficients. If it is already that long, we do nothing (we
+    /// do not truncate coefficients to decrease the degree). In particular,
+    /// the zero polynomial is padded to `n + 1` zeros.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let mut p = Polynomial::new(vec![5, 2, 0, 1]);
+    /// assert_eq!(p.degree_bound(), Some(3));
+    ///
+    /// p.set_degree_bound(6);
+    /// assert_eq!(p, Polynomial::new(vec![5, 2, 0, 1, 0, 0, 0]));
+    /// assert_eq!(p.degree_bound(), Some(6));
+    /// ```
+    pub fn set_degree_bound(&mut self, n: usize) {
+        if self.coeff.len() <= n {
+            self.coeff.resize(n + 1, T::zero());
+        }
+    }
+
+    /// Derivative of the polynomial. The derivative of a constant polynomial
+    /// is the zero polynomial, represented by an empty coefficient vector.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let p = Polynomial::new(vec![5, 2, 0, 4]);
+    /// assert_eq!(p.derivative(), Polynomial::new(vec![2, 0, 12]));
+    /// ```
+    pub fn derivative(&self) -> Self {
+        // The powers are built up by successive additions of one, since `Num`
+        // provides no conversion from `usize`
+        let mut power = T::zero();
+        let coeff = self
+            .coeff
+            .iter()
+            .skip(1)
+            .map(|&a| {
+                power = power + T::one();
+                power * a
+            })
+            .collect();
+        Polynomial::new(coeff)
+    }
+
+    /// Taylor shift: the polynomial `p(x + c)`, whose coefficients are the
+    /// Taylor coefficients of `p` at `c`. Computed by repeated synthetic
+    /// division by `x - c`, as in Horner's method, with O(n^2) additions and
+    /// multiplications by `c` only, so that it is exact over the integers.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // (x + 1)^2 = x^2 + 2x + 1
+    /// let p = Polynomial::new(vec![0, 0, 1]);
+    /// assert_eq!(p.shift(1), Polynomial::new(vec![1, 2, 1]));
+    /// assert_eq!(p.shift(1).shift(-1), p);
+    /// ```
+    pub fn shift(&self, c: T) -> Self {
+        let mut coeff = self.coeff.clone();
+        let n = coeff.len();
+        // After round i, coeff[i] holds the i-th Taylor coefficient
+        for i in 0..n.saturating_sub(1) {
+            for j in (i..n - 1).rev() {
+                coeff[j] = coeff[j] + c * coeff[j + 1];
+            }
+        }
+        Polynomial::new(coeff)
+    }
+
+    /// The polynomial `p(kx)`, whose coefficient of `x^i` is multiplied by
+    /// `k^i`. Scaling maps the roots `r` of `p` to `r / k`, for instance into
+    /// the unit interval before isolating them.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let p = Polynomial::new(vec![1, 1, 1, 1]);
+    /// assert_eq!(p.scale(-2), Polynomial::new(vec![1, -2, 4, -8]));
+    /// ```
+    pub fn scale(&self, k: T) -> Self {
+        let mut power = T::one();
+        let coeff = self
+            .coeff
+            .iter()
+            .map(|&a| {
+                let scaled = a * power;
+                power = power * k;
+                scaled
+            })
+            .collect();
+        Polynomial::new(coeff)
+    }
+}
+
+impl<T: Num + Copy + Div<Output = T>> Polynomial<T> {
+    /// Antiderivative of the polynomial whose constant term is `constant`.
+    /// Since the coefficients get divided, this is meant for fields such as
+    /// `f32` and `f64`: over the integers, the divisions truncate.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let p = Polynomial::new(vec![2.0, 0.0, 12.0]);
+    /// assert_eq!(
+    ///     p.antiderivative(5.0),
+    ///     Polynomial::new(vec![5.0, 2.0, 0.0, 4.0])
+    /// );
+    /// ```
+    pub fn antiderivative(&self, constant: T) -> Self {
+        let mut power = T::zero();
+        let mut coeff = vec![constant];
+        coeff.extend(self.coeff.iter().map(|&a| {
+            power = power + T::one();
+            a / power
+        }));
+        Polynomial::new(coeff)
+    }
+
+    /// Definite integral of the polynomial over the interval `[a, b]`.
+    pub fn integrate(&self, a: T, b: T) -> T {
+        let anti = self.antiderivative(T::zero());
+        anti.eval(b) - anti.eval(a)
+    }
+
+    /// Evaluates the polynomial at each of the points `xs` with a subproduct
+    /// tree: the products of `x - xs[i]` over halves of the points, halves of
+    /// the halves, and so on. Since `p(a)` is the remainder of `p` divided by
+    /// `x - a`, the polynomial is reduced modulo the root of the tree and each
+    /// remainder in turn modulo the children of its node, down to the leaves.
+    ///
+    /// With fast multiplication and division this takes O(n log^2(n)) time
+    /// for `n` points and degree. The products and remainders here are
+    /// computed by the schoolbook algorithms, so that it is meant for fields
+    /// such as floats, complex numbers and rationals as a basis to plug faster
+    /// arithmetic into, `eval_many` being the better choice for few points.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let p = Polynomial::new(vec![-1.0, 0.0, 1.0]);
+    /// let xs = [0.0, 1.0, 2.0, 3.0, 4.0];
+    /// assert_eq!(p.eval_many_subproduct(&xs), p.eval_many(&xs));
+    /// ```
+    pub fn eval_many_subproduct(&self, xs: &[T]) -> Vec<T> {
+        if xs.is_empty() {
+            return Vec::new();
+        }
+
+        // Level 0 holds the factors x - xs[i], and each node of level k + 1
+        // the product of two consecutive nodes of level k, or the last one
+        let mut tree = vec![xs
+            .iter()
+            .map(|&a| Polynomial::new(vec![T::zero() - a, T::one()]))
+            .collect::<Vec<_>>()];
+        while let Some(level) = tree.last().filter(|level| level.len() > 1) {
+            let up = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => a * b,
+                    [a] => a.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            tree.push(up);
+        }
+
+        let mut remainders = vec![self.div_rem(&tree[tree.len() - 1][0]).1];
+        for level in tree.iter().rev().skip(1) {
+            remainders = level
+                .iter()
+                .enumerate()
+                .map(|(i, m)| remainders[i / 2].div_rem(m).1)
+                .collect();
+        }
+        // Remainders modulo linear factors are constants
+        remainders.iter().map(|r| r.eval(T::zero())).collect()
+    }
+
+    /// Polynomial long division: returns the quotient `q` and remainder `r`
+    /// such that `self = q * rhs + r`, where the degree of `r` is less than
+    /// the degree of `rhs`. Both results are reduced. Panics if `rhs` is the
+    /// zero polynomial.
+    ///
+    /// Takes time O(m n), where `m` and `n` are the lengths of the quotient
+    /// and of `rhs`, respectively.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // x^3 - 1 = (x^2 + x + 1)(x - 1)
+    /// let p = Polynomial::new(vec![-1.0, 0.0, 0.0, 1.0]);
+    /// let (q, r) = p.div_rem(&Polynomial::new(vec![-1.0, 1.0]));
+    /// assert_eq!(q, Polynomial::new(vec![1.0, 1.0, 1.0]));
+    /// assert_eq!(r, Polynomial::new(vec![]));
+    /// ```
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let mut d = rhs.clone();
+        d.reduce();
+        assert!(!d.coeff.is_empty(), "division by the zero polynomial");
+
+        let mut r = self.clone();
+        r.reduce();
+        let n = d.coeff.len();
+        if r.coeff.len() < n {
+            return (Polynomial::new(vec![]), r);
+        }
+
+        let lead = d.coeff[n - 1];
+        let mut q = vec![T::zero(); r.coeff.len() - n + 1];
+        for k in (0..q.len()).rev() {
+            let c = r.coeff[k + n - 1] / lead;
+            q[k] = c;
+            for (j, &dj) in d.coeff.iter().enumerate() {
+                r.coeff[k + j] = r.coeff[k + j] - c * dj;
+            }
+            // The leading term cancels exactly, although rounding may leave
+            // a tiny leftover that would keep the degree from dropping
+            r.coeff[k + n - 1] = T::zero();
+        }
+
+        let mut q = Polynomial::new(q);
+        q.reduce();
+        r.coeff.truncate(n - 1);
+        r.reduce();
+        (q, r)
+    }
+}
+
+impl<T: Num + Copy> AddAssign<&Polynomial<T>> for Polynomial<T> {
+    /// In-place addition, reusing the coefficient buffer of `self`.
+    fn add_assign(&mut self, rhs: &Self) {
+        if self.coeff.len() < rhs.coeff.len() {
+            self.coeff.resize(rhs.coeff.len(), T::zero());
+        }
+        for (a, b) in self.coeff.iter_mut().zip(&rhs.coeff) {
+            *a = *a + *b;
+        }
+    }
+}
+
+impl<T: Num + Copy> SubAssign<&Polynomial<T>> for Polynomial<T> {
+    /// In-place subtraction, reusing the coefficient buffer of `self`.
+    fn sub_assign(&mut self, rhs: &Self) {
+        if self.coeff.len() < rhs.coeff.len() {
+            self.coeff.resize(rhs.coeff.len(), T::zero());
+        }
+        for (a, b) in self.coeff.iter_mut().zip(&rhs.coeff) {
+            *a = *a - *b;
+        }
+    }
+}
+
+/// Implements the by-value and by-reference variants of a binary operator, as
+/// well as its assignment by value, in terms of the in-place assignment by
+/// reference.
+macro_rules! forward_binop_to_assign {
+    ($imp:ident, $method:ident, $imp_assign:ident, $method_assign:ident) => {
+        impl<T: Num + Copy> $imp_assign for Polynomial<T> {
+            fn $method_assign(&mut self, rhs: Self) {
+                self.$method_assign(&rhs);
+            }
+        }
+
+        impl<T: Num + Copy> $imp for Polynomial<T> {
+            type Output = Self;
+
+            fn $method(mut self, rhs: Self) -> Self {
+                self.$method_assign(&rhs);
+                self
+            }
+        }
+
+        impl<T: Num + Copy> $imp<&Polynomial<T>> for Polynomial<T> {
+            type Output = Self;
+
+            fn $method(mut self, rhs: &Self) -> Self {
+                self.$method_assign(rhs);
+                self
+            }
+        }
+
+        impl<T: Num + Copy> $imp<Polynomial<T>> for &Polynomial<T> {
+            type Output = Polynomial<T>;
+
+            fn $method(self, rhs: Polynomial<T>) -> Polynomial<T> {
+                self.clone().$method(&rhs)
+            }
+        }
+
+        impl<T: Num + Copy> $imp<&Polynomial<T>> for &Polynomial<T> {
+            type Output = Polynomial<T>;
+
+            fn $method(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+                self.clone().$method(rhs)
+            }
+        }
+    };
+}
+
+forward_binop_to_assign!(Add, add, AddAssign, add_assign);
+forward_binop_to_assign!(Sub, sub, SubAssign, sub_assign);
+
+#[cfg(feature = "std")]
+impl Polynomial<f32> {
+    /// Multiplication of polynomials using the FFT, which takes O(n log(n))
+    /// time instead of the O(n^2) of the naive `Mul` implementation.
+    ///
+    /// Both polynomials are zero-padded to the smallest power of 2 that fits
+    /// the product, evaluated at the roots of unity, multiplied point-wise
+    /// and then interpolated back via the inverse FFT. Since the computation
+    /// is done in floating point, the resulting coefficients are subject to
+    /// rounding errors.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let p = Polynomial::new(vec![1.0, 2.0]);
+    /// let q = Polynomial::new(vec![3.0, 1.0]);
+    /// let r = p.mul_fft(q);
+    /// assert_eq!(r.coeff.len(), 3);
+    /// assert!((r.coeff[0] - 3.0).abs() < 1.0e-5);
+    /// assert!((r.coeff[1] - 7.0).abs() < 1.0e-5);
+    /// assert!((r.coeff[2] - 2.0).abs() < 1.0e-5);
+    /// ```
+    pub fn mul_fft(self, rhs: Self) -> Self {
+        let ls = (self.coeff.len(), rhs.coeff.len());
+        if ls.0 * ls.1 == 0 {
+            // The product of a polynomial by a zero polynomial is always zero
+            return Polynomial::new(vec![]);
+        }
+
+        let len = ls.0 + ls.1 - 1;
+        let n = next_power_of_2(len).expect("transform length overflows usize");
+        let (mut a, mut b) = (self.coeff, rhs.coeff);
+        a.resize(n, 0.0);
+        b.resize(n, 0.0);
+
+        let ya = fft(Polynomial::new(a));
+        let yb = fft(Polynomial::new(b));
+        let y = ya.into_iter().zip(yb).map(|(za, zb)| za * zb).collect();
+
+        let mut coeff: Vec<f32> = ifft(y).into_iter().map(|z| z.re).collect();
+        coeff.truncate(len);
+        Polynomial::new(coeff)
+    }
+}
+
+/// Implements `eval_accurate` for a float type, given the splitting factor
+/// `2^s + 1` of Dekker's product, where `s` is half the number of bits of
+/// the significand, rounded up.
+macro_rules! eval_accurate_impl {
+    ($($t: ty, $split: expr);*) => ($(
+        impl Polynomial<$t> {
+            /// Evaluates the polynomial at `x` by the compensated Horner
+            /// scheme, as accurately as if Horner's method were run in twice
+            /// the working precision and the result rounded.
+            ///
+            /// Each step of Horner's method rounds a product and a sum. Their
+            /// exact errors are recovered by the error-free transformations
+            /// of Knuth's `two_sum` and Dekker's `two_prod`, and are themselves
+            /// evaluated by Horner's method to correct the result, which
+            /// matters near the roots of high-degree or ill-conditioned
+            /// polynomials, where `eval` loses most significant digits.
+            ///
+            /// Example:
+            /// ```
+            /// use ralg::math::poly::Polynomial;
+            ///
+            /// // (x - 1)^3, whose value at 1 + 2^-20 is 2^-60
+            /// let p: Polynomial<f64> =
+            ///     Polynomial::new(vec![-1.0, 3.0, -3.0, 1.0]);
+            /// let x = 1.0 + 2.0_f64.powi(-20);
+            /// assert_eq!(p.eval_accurate(x), 2.0_f64.powi(-60));
+            /// assert_ne!(p.eval(x), 2.0_f64.powi(-60));
+            /// ```
+            pub fn eval_accurate(&self, x: $t) -> $t {
+                /// Sum `a + b` rounded, along with its exact rounding error.
+                fn two_sum(a: $t, b: $t) -> ($t, $t) {
+                    let s = a + b;
+                    let z = s - a;
+                    (s, (a - (s - z)) + (b - z))
+                }
+
+                /// Splits `a` into two halves with non-overlapping bits.
+                fn split(a: $t) -> ($t, $t) {
+                    let c = $split * a;
+                    let high = c - (c - a);
+                    (high, a - high)
+                }
+
+                /// Product `a b` rounded, along with its exact rounding
+                /// error, computed from the products of the halves.
+                fn two_prod(a: $t, b: $t) -> ($t, $t) {
+                    let p = a * b;
+                    let ((ah, al), (bh, bl)) = (split(a), split(b));
+                    (p, al * bl - (((p - ah * bh) - al * bh) - ah * bl))
+                }
+
+                let mut coeff = self.coeff.iter().rev();
+                let mut s = match coeff.next() {
+                    Some(&a) => a,
+                    None => return 0.0,
+                };
+                // Horner's method on the errors of each step
+                let mut correction = 0.0;
+                for &a in coeff {
+                    let (p, product_error) = two_prod(s, x);
+                    let (sum, sum_error) = two_sum(p, a);
+                    s = sum;
+                    correction = correction * x + (product_error + sum_error);
+                }
+                s + correction
+            }
+        }
+    )*)
+}
+
+eval_accurate_impl!(f32, 4097.0; f64, 134217729.0);
+
+/// This multiplication is O(n^2), which is not great. For large polynomials
+/// over `f32`, consider `Polynomial::mul_fft`, which runs in O(n log(n)).
+impl<T: Num + Copy> Mul<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let ls = (self.coeff.len(), rhs.coeff.len());
+        if ls.0 * ls.1 == 0 {
+            // The product of a polynomial by a zero polynomial is always zero
+            return Polynomial::new(vec![]);
+        }
+
+        let mut coeff = vec![T::zero(); ls.0 + ls.1 - 1];
+        for i in 0..ls.0 {
+            for j in 0..ls.1 {
+                coeff[i + j] = coeff[i + j] + self.coeff[i] * rhs.coeff[j];
+            }
+        }
+        Polynomial::new(coeff)
+    }
+}
+
+impl<T: Num + Copy> Mul for Polynomial<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        &self * &rhs
+    }
+}
+
+impl<T: Num + Copy> Mul<&Polynomial<T>> for Polynomial<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: &Self) -> Self {
+        &self * rhs
+    }
+}
+
+impl<T: Num + Copy> Mul<Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, rhs: Polynomial<T>) -> Polynomial<T> {
+        self * &rhs
+    }
+}
+
+impl<T: Num + Copy> MulAssign<&Polynomial<T>> for Polynomial<T> {
+    fn mul_assign(&mut self, rhs: &Self) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<T: Num + Copy> MulAssign for Polynomial<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = &*self * &rhs;
+    }
+}
+
+/// Polynomial in point-value representation: a polynomial of degree-bound `n`
+/// is determined by its values at `n` distinct points. In this form, addition
+/// and multiplication take O(n) time, as long as both operands are sampled at
+/// the same points (and, for multiplication, enough points are sampled to
+/// determine the product).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointValuePoly<T: Num + Copy> {
+    /// Pairs `(x, y)` of distinct points `x` and the value `y` of the
+    /// polynomial at `x`.
+    pub points: Vec<(T, T)>,
+}
+
+impl<T: Num + Copy> PointValuePoly<T> {
+    pub fn new(points: Vec<(T, T)>) -> Self {
+        PointValuePoly { points }
+    }
+
+    /// Converts a polynomial from coefficient representation by evaluating it
+    /// at each of the points `xs`, which takes O(n^2) time using Horner's
+    /// method.
+    pub fn from_coeff(p: &Polynomial<T>, xs: &[T]) -> Self {
+        PointValuePoly::new(xs.iter().map(|&x| (x, p.eval(x))).collect())
+    }
+
+    /// Checks that both polynomials are sampled at the same points.
+    fn assert_same_points(&self, rhs: &Self) {
+        assert!(
+            self.points.len() == rhs.points.len()
+                && self.points.iter().zip(&rhs.points).all(|(a, b)| a.0 == b.0),
+            "point-value polynomials must be sampled at the same points"
+        );
+    }
+}
+
+impl<T: Num + Copy + Div<Output = T>> PointValuePoly<T> {
+    /// Converts the polynomial to coefficient representation via Lagrange
+    /// interpolation. Given the points `(x_0, y_0), ..., (x_{n-1}, y_{n-1})`,
+    /// the interpolating polynomial is the sum over `k` of
+    /// `y_k * prod_{j != k} (x - x_j) / (x_k - x_j)`.
+    ///
+    /// We first compute `m(x) = prod_j (x - x_j)`, from which each numerator
+    /// is obtained by synthetic division by `x - x_k`, taking O(n^2) time.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::{PointValuePoly, Polynomial};
+    ///
+    /// let pv = PointValuePoly::new(vec![(0.0, 1.0), (1.0, 3.0), (2.0, 7.0)]);
+    /// assert_eq!(pv.to_coeff(), Polynomial::new(vec![1.0, 1.0, 1.0]));
+    /// ```
+    pub fn to_coeff(&self) -> Polynomial<T> {
+        let n = self.points.len();
+        if n == 0 {
+            return Polynomial::new(vec![]);
+        }
+
+        // m(x) = prod_j (x - x_j), built one factor at a time
+        let mut m = vec![T::zero(); n + 1];
+        m[0] = T::one();
+        for (deg, &(xj, _)) in self.points.iter().enumerate() {
+            for k in (0..=deg + 1).rev() {
+                let shifted = if k > 0 { m[k - 1] } else { T::zero() };
+                m[k] = shifted - xj * m[k];
+            }
+        }
+
+        let mut coeff = vec![T::zero(); n];
+        for (k, &(xk, yk)) in self.points.iter().enumerate() {
+            // Synthetic division of m(x) by (x - x_k)
+            let mut q = vec![T::zero(); n];
+            q[n - 1] = m[n];
+            for i in (1..n).rev() {
+                q[i - 1] = m[i] + xk * q[i];
+            }
+
+            let denom = self
+                .points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != k)
+                .fold(T::one(), |acc, (_, &(xj, _))| acc * (xk - xj));
+            let scale = yk / denom;
+            for (c, qi) in coeff.iter_mut().zip(q) {
+                *c = *c + scale * qi;
+            }
+        }
+        Polynomial::new(coeff)
+    }
+}
+
+impl<T: Num + Copy> Add for PointValuePoly<T> {
+    type Output = Self;
+
+    /// Point-wise addition. Panics if the operands are not sampled at the
+    /// same points.
+    fn add(self, rhs: Self) -> Self {
+        self.assert_same_points(&rhs);
+        let points = self
+            .points
+            .into_iter()
+            .zip(rhs.points)
+            .map(|((x, a), (_, b))| (x, a + b))
+            .collect();
+        PointValuePoly::new(points)
+    }
+}
+
+impl<T: Num + Copy> Mul for PointValuePoly<T> {
+    type Output = Self;
+
+    /// Point-wise multiplication. Panics if the operands are not sampled at
+    /// the same points. Notice that the product is only determined by the
+    /// result if the number of points exceeds the degree of the product.
+    fn mul(self, rhs: Self) -> Self {
+        self.assert_same_points(&rhs);
+        let points = self
+            .points
+            .into_iter()
+            .zip(rhs.points)
+            .map(|((x, a), (_, b))| (x, a * b))
+            .collect();
+        PointValuePoly::new(points)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::num::rational::Rational;
+
+    #[test]
+    fn new() {
+        let p = Polynomial::new(vec![0, 1, 5, 6]);
+        assert_eq!(p.coeff, vec![0, 1, 5, 6]);
+    }
+
+    #[test]
+    fn deg() {
+        // Zero polynomials
+        let p: Polynomial<i32> = Polynomial::new(vec![]);
+        assert_eq!(p.degree_bound(), None);
+        assert_eq!(p.degree(), None);
+
+        let p: Polynomial<f32> = Polynomial::new(vec![0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(p.degree_bound(), None);
+        assert_eq!(p.degree(), None);
+
+        let p = Polynomial::new(vec![5, 2, 0, 1]);
+        assert_eq!(p.degree_bound(), Some(3));
+
+        // Degree bound >= degree
+        let p = Polynomial::new(vec![0, 3, 4, 0]);
+        assert_eq!(p.degree_bound(), Some(3));
+        assert_eq!(p.degree(), Some(2));
+
+        let q = Polynomial::new(vec![0.1, 0.0, 0.0, 0.0]);
+        assert_eq!(q.degree_bound(), Some(3));
+        assert_eq!(q.degree(), Some(0));
+
+        // Degree bound == degree
+        let p = Polynomial::new(vec![1, 5, 0, 6, 7]);
+        assert_eq!(p.degree_bound(), Some(4));
+        assert_eq!(p.degree(), Some(4));
+    }
+
+    #[test]
+    fn set_degree_bound() {
+        let mut p = Polynomial::new(vec![1, 2]);
+        p.set_degree_bound(3);
+        assert_eq!(p.coeff, vec![1, 2, 0, 0]);
+        p.set_degree_bound(1);
+        assert_eq!(p.coeff, vec![1, 2, 0, 0]);
+
+        // The zero polynomial is padded as well
+        let mut p: Polynomial<i32> = Polynomial::new(vec![]);
+        p.set_degree_bound(2);
+        assert_eq!(p.coeff, vec![0, 0, 0]);
+        assert_eq!(p.degree_bound(), None);
+    }
+
+    #[test]
+    fn eval() {
+        let p = Polynomial::new(vec![]);
+        assert_eq!(p.eval(3.0), 0.0);
+
+        let q = Polynomial::new(vec![2, 3, 5, 6]);
+        assert_eq!(q.eval(0), 2);
+        assert_eq!(q.eval(1), 16);
+        assert_eq!(q.eval(-1), -2);
+        assert_eq!(q.eval(2), 76);
+        assert_eq!(q.eval(4), 478);
+    }
+
+    #[test]
+    fn eval_many() {
+        let p = Polynomial::new(vec![3, -1, 0, 2]);
+        assert!(p.eval_many(&[]).is_empty());
+        assert_eq!(p.eval_many(&[0, 2, -2]), [3, 17, -11]);
+
+        // Exact over the rationals, for any number of points
+        let r = |n| Rational::from_integer(n);
+        let q = Polynomial::new((0..9).map(|k| r(k * k - 7)).collect());
+        for n in 0..20 {
+            let xs: Vec<Rational> =
+                (0..n).map(|k| Rational::new(k - 4, 3)).collect();
+            assert_eq!(q.eval_many_subproduct(&xs), q.eval_many(&xs));
+        }
+        let zero = Polynomial::new(vec![]);
+        assert_eq!(zero.eval_many_subproduct(&[r(1), r(2)]), [r(0), r(0)]);
+
+        let f = Polynomial::new(vec![0.5, -2.0, 0.0, 1.0, 0.25]);
+        let xs: Vec<f64> = (0..30).map(|k| k as f64 / 10.0 - 1.5).collect();
+        let fast = f.eval_many_subproduct(&xs);
+        for (y, &x) in fast.iter().zip(&xs) {
+            assert!((y - f.eval(x)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn eval_accurate() {
+        assert_eq!(Polynomial::<f32>::new(vec![]).eval_accurate(2.0), 0.0);
+        assert_eq!(Polynomial::new(vec![1.5_f32]).eval_accurate(7.0), 1.5);
+
+        // (x - 2)^9, whose value is exactly computed from x - 2 near 2
+        let mut binomial = 1.0;
+        let mut coeff = vec![0.0; 10];
+        for (k, c) in coeff.iter_mut().enumerate() {
+            let mut power = 1.0;
+            for _ in k..9 {
+                power *= -2.0;
+            }
+            *c = binomial * power;
+            binomial = binomial * (9 - k) as f64 / (k + 1) as f64;
+        }
+        let p = Polynomial::new(coeff);
+        let mut worst_plain: f64 = 0.0;
+        for i in 0..200 {
+            let d = 0.03 + i as f64 * 1e-3;
+            for x in [2.0 - d, 2.0 + d] {
+                let mut exact = 1.0;
+                for _ in 0..9 {
+                    exact *= x - 2.0;
+                }
+                let error = |y: f64| ((y - exact) / exact).abs();
+                assert!(error(p.eval_accurate(x)) < 1e-10);
+                worst_plain = worst_plain.max(error(p.eval(x)));
+            }
+        }
+        assert!(worst_plain > 1e-3);
+    }
+
+    #[test]
+    fn add() {
+        // Sum of zero polynomials is zero
+        let p: Polynomial<f32> = Polynomial::new(vec![]);
+        let q: Polynomial<f32> = Polynomial::new(vec![]);
+        assert_eq!(p + q, Polynomial::new(vec![]));
+
+        let p = Polynomial::new(vec![1, 2, 4]);
+        let q = Polynomial::new(vec![5, -2, 17]);
+        assert_eq!(&p + &q, Polynomial::new(vec![6, 0, 21]));
+        assert_eq!(q + p, Polynomial::new(vec![6, 0, 21]));
+    }
+
+    #[test]
+    fn ref_ops() {
+        let p = Polynomial::new(vec![1, 2, 4]);
+        let q = Polynomial::new(vec![5, -2]);
+        let sum = Polynomial::new(vec![6, 0, 4]);
+        let diff = Polynomial::new(vec![-4, 4, 4]);
+        let prod = Polynomial::new(vec![5, 8, 16, -8]);
+
+        assert_eq!(&p + &q, sum);
+        assert_eq!(p.clone() + &q, sum);
+        assert_eq!(&p + q.clone(), sum);
+        assert_eq!(&p - &q, diff);
+        assert_eq!(&q - &p, Polynomial::new(vec![4, -4, -4]));
+        assert_eq!(p.clone() - &q, diff);
+        assert_eq!(&p - q.clone(), diff);
+        assert_eq!(&p * &q, prod);
+        assert_eq!(p.clone() * &q, prod);
+        assert_eq!(&p * q.clone(), prod);
+    }
+
+    #[test]
+    fn assign_ops() {
+        let p = Polynomial::new(vec![1, 2, 4]);
+        let q = Polynomial::new(vec![5, -2]);
+
+        let mut r = q.clone();
+        r += &p;
+        assert_eq!(r, Polynomial::new(vec![6, 0, 4]));
+        r -= p.clone();
+        assert_eq!(r, Polynomial::new(vec![5, -2, 0]));
+        r *= &p;
+        assert_eq!(r, Polynomial::new(vec![5, 8, 16, -8, 0]));
+
+        let mut r = p.clone();
+        r -= &q;
+        assert_eq!(r, Polynomial::new(vec![-4, 4, 4]));
+        r += q.clone();
+        assert_eq!(r, p);
+        r *= Polynomial::new(vec![]);
+        assert_eq!(r, Polynomial::new(vec![]));
+    }
+
+    #[test]
+    fn mul() {
+        // Zero polynomials
+        let p: Polynomial<i32> = Polynomial::new(vec![]);
+        let q: Polynomial<i32> = Polynomial::new(vec![]);
+        assert_eq!(p * q, Polynomial::new(vec![]));
+
+        let p = Polynomial::new(vec![3.4, 9.0, 1.1]);
+        let q: Polynomial<f32> = Polynomial::new(vec![]);
+        assert_eq!(p * q, Polynomial::new(vec![]));
+
+        // Same length
+        let p = Polynomial::new(vec![0, 3, 5]);
+        let q = Polynomial::new(vec![4, 7, 8]);
+        assert_eq!(&p * &q, Polynomial::new(vec![0, 12, 41, 59, 40]));
+        assert_eq!(q * p, Polynomial::new(vec![0, 12, 41, 59, 40]));
+
+        // Different length
+        let p = Polynomial::new(vec![5, 0, 10, 6]);
+        let q = Polynomial::new(vec![1, 2, 4]);
+        assert_eq!(&p * &q, Polynomial::new(vec![5, 10, 30, 26, 52, 24]),);
+        assert_eq!(q * p, Polynomial::new(vec![5, 10, 30, 26, 52, 24]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn mul_fft() {
+        let eps = 1.0e-3;
+        let check = |p: Polynomial<f32>, q: Polynomial<f32>| {
+            let expected = &p * &q;
+            let result = p.mul_fft(q);
+            assert_eq!(result.coeff.len(), expected.coeff.len());
+            for (r, e) in result.coeff.iter().zip(expected.coeff) {
+                assert!((r - e).abs() < eps);
+            }
+        };
+
+        // Zero polynomials
+        check(Polynomial::new(vec![]), Polynomial::new(vec![1.0, 2.0]));
+
+        // Same length
+        check(
+            Polynomial::new(vec![0.0, 3.0, 5.0]),
+            Polynomial::new(vec![4.0, 7.0, 8.0]),
+        );
+
+        // Different length
+        check(
+            Polynomial::new(vec![5.0, 0.0, 10.0, 6.0]),
+            Polynomial::new(vec![1.0, 2.0, 4.0]),
+        );
+
+        // Larger polynomials
+        let p = Polynomial::new((0..100).map(|x| (x % 7) as f32).collect());
+        let q =
+            Polynomial::new((0..57).map(|x| (x % 5) as f32 - 2.0).collect());
+        check(p, q);
+    }
+
+    #[test]
+    fn derivative() {
+        let p: Polynomial<i32> = Polynomial::new(vec![]);
+        assert_eq!(p.derivative(), Polynomial::new(vec![]));
+
+        let p = Polynomial::new(vec![7]);
+        assert_eq!(p.derivative(), Polynomial::new(vec![]));
+
+        let p = Polynomial::new(vec![1, -3, 0, 2, 5]);
+        assert_eq!(p.derivative(), Polynomial::new(vec![-3, 0, 6, 20]));
+
+        let p = Polynomial::new(vec![0.5, 1.5, 2.0]);
+        assert_eq!(p.derivative(), Polynomial::new(vec![1.5, 4.0]));
+    }
+
+    #[test]
+    fn antiderivative() {
+        let p: Polynomial<f64> = Polynomial::new(vec![]);
+        assert_eq!(p.antiderivative(2.0), Polynomial::new(vec![2.0]));
+
+        let p = Polynomial::new(vec![3.0, 4.0, 9.0]);
+        let anti = p.antiderivative(-1.0);
+        assert_eq!(anti, Polynomial::new(vec![-1.0, 3.0, 2.0, 3.0]));
+
+        // The derivative undoes the antiderivative
+        assert_eq!(anti.derivative(), p);
+    }
+
+    #[test]
+    fn shift() {
+        let p: Polynomial<i32> = Polynomial::new(vec![]);
+        assert_eq!(p.shift(3), p);
+
+        let p = Polynomial::new(vec![4, -1, 0, 3, 2]);
+        for c in -4..5 {
+            let q = p.shift(c);
+            assert_eq!(q.coeff.len(), p.coeff.len());
+            for x in -5..6 {
+                assert_eq!(q.eval(x), p.eval(x + c));
+            }
+            assert_eq!(q.shift(-c), p);
+        }
+
+        // Constant term and derivative at c are the first Taylor coefficients
+        let p = Polynomial::new(vec![
+            Rational::new(1, 2),
+            Rational::new(-3, 4),
+            Rational::new(2, 3),
+        ]);
+        let c = Rational::new(5, 7);
+        let q = p.shift(c);
+        assert_eq!(q.coeff[0], p.eval(c));
+        assert_eq!(q.coeff[1], p.derivative().eval(c));
+        assert_eq!(q.coeff[2], p.coeff[2]);
+    }
+
+    #[test]
+    fn scale() {
+        let p = Polynomial::new(vec![3, 0, -2, 1]);
+        assert_eq!(p.scale(1), p);
+        assert_eq!(p.scale(0), Polynomial::new(vec![3, 0, 0, 0]));
+        for k in -3..4 {
+            for x in -4..5 {
+                assert_eq!(p.scale(k).eval(x), p.eval(k * x));
+            }
+        }
+
+        // Roots r of (x - 1)(x - 4) become r / 2
+        let p = Polynomial::new(vec![4.0, -5.0, 1.0]);
+        let q = p.scale(2.0);
+        assert_eq!(q.eval(0.5), 0.0);
+        assert_eq!(q.eval(2.0), 0.0);
+    }
+
+    #[test]
+    fn integrate() {
+        // Integral of 3x^2 over [0, 2] is 8
+        let p = Polynomial::new(vec![0.0, 0.0, 3.0]);
+        assert_eq!(p.integrate(0.0, 2.0), 8.0);
+        assert_eq!(p.integrate(2.0, 0.0), -8.0);
+
+        // Integral of 1 + x over [-1, 1] is 2
+        let p = Polynomial::new(vec![1.0_f32, 1.0]);
+        assert!((p.integrate(-1.0, 1.0) - 2.0).abs() < 1.0e-6);
+
+        let p: Polynomial<f64> = Polynomial::new(vec![]);
+        assert_eq!(p.integrate(-3.0, 5.0), 0.0);
+    }
+
+    #[test]
+    fn point_value() {
+        let p = Polynomial::new(vec![2, 3, 5, 6]);
+        let pv = PointValuePoly::from_coeff(&p, &[0, 1, -1, 2]);
+        assert_eq!(pv.points, vec![(0, 2), (1, 16), (-1, -2), (2, 76)]);
+
+        // Round trip through the point-value representation
+        let p: Polynomial<f64> = Polynomial::new(vec![2.0, -3.0, 0.5, 6.0]);
+        let pv = PointValuePoly::from_coeff(&p, &[0.0, 1.0, -1.0, 2.0]);
+        let q = pv.to_coeff();
+        assert_eq!(q.coeff.len(), p.coeff.len());
+        for (a, b) in q.coeff.iter().zip(&p.coeff) {
+            assert!((a - b).abs() < 1.0e-12);
+        }
+
+        let pv: PointValuePoly<f64> = PointValuePoly::new(vec![]);
+        assert_eq!(pv.to_coeff(), Polynomial::new(vec![]));
+
+        let pv = PointValuePoly::new(vec![(3.0, 4.0)]);
+        assert_eq!(pv.to_coeff(), Polynomial::new(vec![4.0]));
+    }
+
+    #[test]
+    fn point_value_arithmetic() {
+        let xs = [-2.0, -1.0, 0.0, 1.0, 2.0];
+        let p: Polynomial<f64> = Polynomial::new(vec![1.0, 2.0]);
+        let q = Polynomial::new(vec![-1.0, 0.0, 3.0]);
+        let pv = PointValuePoly::from_coeff(&p, &xs);
+        let qv = PointValuePoly::from_coeff(&q, &xs);
+
+        let sum = (pv.clone() + qv.clone()).to_coeff();
+        let expected = &p + &q;
+        for (a, b) in sum.coeff.iter().zip(&expected.coeff) {
+            assert!((a - b).abs() < 1.0e-12);
+        }
+
+        let prod = (pv * qv).to_coeff();
+        let expected = p * q;
+        for (a, b) in prod.coeff.iter().zip(&expected.coeff) {
+            assert!((a - b).abs() < 1.0e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn point_value_different_points() {
+        let p = PointValuePoly::new(vec![(0, 1), (1, 2)]);
+        let q = PointValuePoly::new(vec![(0, 1), (2, 2)]);
+        let _ = p + q;
+    }
+
+    #[test]
+    fn div_rem() {
+        // (2x^2 + 3x + 1) = (x + 1)(2x + 1)
+        let p = Polynomial::new(vec![1.0, 3.0, 2.0]);
+        let (q, r) = p.div_rem(&Polynomial::new(vec![1.0, 1.0]));
+        assert_eq!(q, Polynomial::new(vec![1.0, 2.0]));
+        assert_eq!(r, Polynomial::new(vec![]));
+
+        // x^3 + 2 = x^2 (x) + 2, divisor with trailing zeros
+        let p = Polynomial::new(vec![2.0, 0.0, 0.0, 1.0]);
+        let (q, r) = p.div_rem(&Polynomial::new(vec![0.0, 1.0, 0.0]));
+        assert_eq!(q, Polynomial::new(vec![0.0, 0.0, 1.0]));
+        assert_eq!(r, Polynomial::new(vec![2.0]));
+
+        // Dividend of smaller degree
+        let p = Polynomial::new(vec![5.0, 1.0]);
+        let (q, r) = p.div_rem(&Polynomial::new(vec![1.0, 0.0, 3.0]));
+        assert_eq!(q, Polynomial::new(vec![]));
+        assert_eq!(r, Polynomial::new(vec![5.0, 1.0]));
+    }
+
+    #[test]
+    fn div_rem_rounding() {
+        // Rounding used to leave a tiny leading coefficient in the remainder
+        let a = Polynomial::new(vec![
+            55.0,
+            -24.0,
+            -47.857142857142854,
+            -56.42857142857143,
+        ]);
+        let b = Polynomial::new(vec![-100.57142857142857, 91.28571428571429]);
+        let (q, r) = a.div_rem(&b);
+        assert_eq!(q.coeff.len(), 3);
+        assert!(r.coeff.len() <= 1);
+
+        // Remainders always have a smaller degree than the divisor
+        let mut rng = crate::random::Pcg32::seed(0xd1f5);
+        for _ in 0..500 {
+            let mut random = |len| {
+                let coeff = (0..len).map(|_| rng.gen_range(-100.0..100.0));
+                Polynomial::new(coeff.collect())
+            };
+            let (a, b) = (random(7), random(3));
+            let (q, r) = a.div_rem(&b);
+            assert!(r.coeff.len() < 3);
+            let mut back = &q * &b;
+            back += &r;
+            let scale = q.coeff.iter().fold(1.0_f64, |m, c| m.max(c.abs()));
+            for (x, y) in back.coeff.iter().zip(&a.coeff) {
+                assert!((x - y).abs() < 1e-9 * scale);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_rem_by_zero() {
+        let p = Polynomial::new(vec![1.0, 2.0]);
+        p.div_rem(&Polynomial::new(vec![0.0]));
+    }
+
+    #[test]
+    fn reduce() {
+        let mut p = Polynomial::new(vec![1, 0, 0]);
+        p.reduce();
+        assert_eq!(p, Polynomial::new(vec![1]));
+
+        let mut p = Polynomial::new(vec![1.0, 0.0, 4.5, 0.0]);
+        p.reduce();
+        assert_eq!(p, Polynomial::new(vec![1.0, 0.0, 4.5]));
+
+        let mut p = Polynomial::new(vec![0.0, 0.0, 0.0, 0.0]);
+        p.reduce();
+        assert_eq!(p, Polynomial::new(vec![]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use crate::math::complex::Complex;
+        use serde_test::{assert_tokens, Token};
+
+        let p = Polynomial::new(vec![Complex::new(1.0, -2.0)]);
+        assert_tokens(
+            &p,
+            &[
+                Token::Struct {
+                    name: "Polynomial",
+                    len: 1,
+                },
+                Token::Str("coeff"),
+                Token::Seq { len: Some(1) },
+                Token::Struct {
+                    name: "Complex",
+                    len: 2,
+                },
+                Token::Str("re"),
+                Token::F64(1.0),
+                Token::Str("im"),
+                Token::F64(-2.0),
+                Token::StructEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+}
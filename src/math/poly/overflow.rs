@@ -0,0 +1,150 @@
+//! Coefficient arithmetic of integer polynomials that cannot overflow silently
+use super::Polynomial;
+use crate::math::num::{
+    CheckedAdd, CheckedMul, Num, SaturatingAdd, WrappingAdd,
+};
+use alloc::{vec, vec::Vec};
+
+impl<T: Num + Copy> Polynomial<T> {
+    /// Sum of `self` and `rhs` computed coefficientwise by `add`, where the
+    /// missing coefficients of the shorter polynomial are zeros that are
+    /// copied over.
+    fn zip_coeff<F: Fn(T, T) -> Option<T>>(
+        &self,
+        rhs: &Self,
+        add: F,
+    ) -> Option<Self> {
+        let (long, short) = if self.coeff.len() >= rhs.coeff.len() {
+            (&self.coeff, &rhs.coeff)
+        } else {
+            (&rhs.coeff, &self.coeff)
+        };
+        let mut coeff = Vec::with_capacity(long.len());
+        for (i, &a) in long.iter().enumerate() {
+            coeff.push(match short.get(i) {
+                Some(&b) => add(a, b)?,
+                None => a,
+            });
+        }
+        Some(Polynomial::new(coeff))
+    }
+}
+
+impl<T: Num + Copy + CheckedAdd> Polynomial<T> {
+    /// Sum of polynomials, returning `None` if some coefficient overflows.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// let p = Polynomial::new(vec![100_u8, 200]);
+    /// let q = Polynomial::new(vec![100_u8, 50, 1]);
+    /// assert_eq!(p.checked_add(&q), Some(Polynomial::new(vec![200, 250, 1])));
+    /// assert_eq!(p.checked_add(&p), None);
+    /// ```
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        self.zip_coeff(rhs, T::checked_add)
+    }
+}
+
+impl<T: Num + Copy + CheckedAdd + CheckedMul> Polynomial<T> {
+    /// Product of polynomials by the schoolbook algorithm, returning `None`
+    /// if some coefficient, or some partial sum of a coefficient, overflows.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // The coefficients of (1 + x)^n are the binomials C(n, k), the
+    /// // largest being C(9, 4) = 126 for n = 9 and C(10, 5) = 252 for n = 10
+    /// let p = Polynomial::new(vec![1_i8, 1]);
+    /// let p9 = (1..9).try_fold(p.clone(), |acc, _| acc.checked_mul(&p));
+    /// assert_eq!(p9.as_ref().unwrap().coeff[4], 126);
+    /// assert_eq!(p9.unwrap().checked_mul(&p), None);
+    /// ```
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        if self.coeff.is_empty() || rhs.coeff.is_empty() {
+            return Some(Polynomial::new(vec![]));
+        }
+        let mut coeff = vec![T::zero(); self.coeff.len() + rhs.coeff.len() - 1];
+        for (i, &a) in self.coeff.iter().enumerate() {
+            for (j, &b) in rhs.coeff.iter().enumerate() {
+                coeff[i + j] = coeff[i + j].checked_add(a.checked_mul(b)?)?;
+            }
+        }
+        Some(Polynomial::new(coeff))
+    }
+}
+
+impl<T: Num + Copy + WrappingAdd> Polynomial<T> {
+    /// Sum of polynomials whose coefficients wrap around the bounds of the
+    /// type, that is, the sum modulo `2^bits` for integers of `bits` bits.
+    pub fn wrapping_add(&self, rhs: &Self) -> Self {
+        self.zip_coeff(rhs, |a, b| Some(T::wrapping_add(a, b)))
+            .unwrap()
+    }
+}
+
+impl<T: Num + Copy + SaturatingAdd> Polynomial<T> {
+    /// Sum of polynomials whose coefficients are clamped to the bounds of the
+    /// type.
+    pub fn saturating_add(&self, rhs: &Self) -> Self {
+        self.zip_coeff(rhs, |a, b| Some(T::saturating_add(a, b)))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::combinatorics::binomial;
+
+    #[test]
+    fn sums() {
+        let p = Polynomial::new(vec![250_u8, 3]);
+        let q = Polynomial::new(vec![10_u8]);
+        assert_eq!(p.checked_add(&q), None);
+        assert_eq!(q.checked_add(&p), None);
+        assert_eq!(p.wrapping_add(&q), Polynomial::new(vec![4, 3]));
+        assert_eq!(q.saturating_add(&p), Polynomial::new(vec![255, 3]));
+        let r = Polynomial::new(vec![-100_i8, 0, 5]);
+        assert_eq!(r.saturating_add(&r), Polynomial::new(vec![-128, 0, 10]));
+        assert_eq!(r.checked_add(&Polynomial::new(vec![])), Some(r.clone()));
+    }
+
+    #[test]
+    fn binomial_powers() {
+        // (1 + x)^n fits in u64 as long as its middle coefficient does
+        let p = Polynomial::new(vec![1_u64, 1]);
+        let mut power = p.clone();
+        for n in 2..70 {
+            match power.checked_mul(&p) {
+                Some(next) => {
+                    assert_eq!(
+                        next.coeff[n / 2],
+                        binomial(n as u64, n as u64 / 2).unwrap()
+                    );
+                    power = next;
+                }
+                None => {
+                    assert_eq!(binomial(n as u64, n as u64 / 2), None);
+                    return;
+                }
+            }
+        }
+        panic!("no overflow");
+    }
+
+    #[test]
+    fn products_agree_with_mul() {
+        let p = Polynomial::new(vec![3_i64, -2, 0, 7]);
+        let q = Polynomial::new(vec![-1_i64, 4]);
+        assert_eq!(p.checked_mul(&q), Some(&p * &q));
+        assert_eq!(
+            p.checked_mul(&Polynomial::new(vec![])),
+            Some(Polynomial::new(vec![]))
+        );
+        let big = Polynomial::new(vec![i64::MAX, 1]);
+        assert_eq!(big.checked_mul(&q), None);
+    }
+}
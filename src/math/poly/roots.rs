@@ -0,0 +1,291 @@
+//! Roots of real polynomials, possibly complex
+use super::Polynomial;
+use crate::math::{
+    complex::Complex,
+    num::{Num, Signed},
+};
+use alloc::{vec, vec::Vec};
+
+/// Default tolerance used by `Polynomial::roots`.
+pub const DEFAULT_TOLERANCE: f64 = 1.0e-12;
+
+/// Default maximum number of Durand-Kerner iterations used by
+/// `Polynomial::roots`.
+pub const DEFAULT_MAX_ITER: usize = 1000;
+
+impl<T: Num + Copy + Into<f64>> Polynomial<T> {
+    /// Computes all roots of the polynomial, repeated according to their
+    /// multiplicity. The zero polynomial and nonzero constants yield no roots.
+    /// The computation is carried out in double precision, which is why the
+    /// coefficients should be convertible to `f64` (such as `f32`, `f64` and
+    /// the small integer types).
+    ///
+    /// Polynomials of degree up to 3 are solved with closed formulas, while
+    /// higher degrees use the Durand-Kerner iteration, see `roots_with`.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // x^2 + 1 has roots i and -i
+    /// let p = Polynomial::new(vec![1.0, 0.0, 1.0]);
+    /// let roots = p.roots();
+    /// assert_eq!(roots.len(), 2);
+    /// assert!(roots.iter().all(|z| z.re.abs() < 1.0e-12));
+    /// assert!(roots.iter().any(|z| (z.im - 1.0).abs() < 1.0e-12));
+    /// ```
+    pub fn roots(&self) -> Vec<Complex<f64>> {
+        self.roots_with(DEFAULT_TOLERANCE, DEFAULT_MAX_ITER)
+    }
+
+    /// Same as `roots`, where the Durand-Kerner iteration stops once no
+    /// approximation moves by more than `tol` (relative to its modulus, when
+    /// greater than one), or after `max_iter` iterations. Roots of high
+    /// multiplicity converge slowly, so their accuracy may be worse than
+    /// `tol`.
+    pub fn roots_with(&self, tol: f64, max_iter: usize) -> Vec<Complex<f64>> {
+        let mut p: Polynomial<f64> =
+            Polynomial::new(self.coeff.iter().map(|&c| c.into()).collect());
+        p.reduce();
+        let n = p.coeff.len().saturating_sub(1);
+
+        // Normalize to a monic polynomial
+        let lead = p.coeff.last().copied().unwrap_or(1.0);
+        let a: Vec<f64> = p.coeff.iter().map(|c| c / lead).collect();
+        match n {
+            0 => vec![],
+            1 => vec![Complex::from_real(-a[0])],
+            2 => quadratic(a[1], a[0]),
+            3 => cubic(a[2], a[1], a[0]),
+            _ => durand_kerner(&a, tol, max_iter),
+        }
+    }
+}
+
+/// Roots of the monic quadratic `x^2 + b x + c`. To avoid cancellation, we
+/// compute the root of greatest modulus `q` first and obtain the other one as
+/// `c / q`, since their product is `c`.
+fn quadratic(b: f64, c: f64) -> Vec<Complex<f64>> {
+    let b = Complex::from_real(b);
+    let mut s = sqrt(b * b - 4.0 * c);
+    // Choose the square root pointing in the same direction as `b`
+    if b.re * s.re + b.im * s.im < 0.0 {
+        s = -s;
+    }
+    let q = -(b + s).divide(2.0);
+    if Complex::norm(q) == 0.0 {
+        return vec![q, q];
+    }
+    vec![q, Complex::from_real(c) / q]
+}
+
+/// Roots of the monic cubic `x^3 + b x^2 + c x + d` via Cardano's formula:
+/// the substitution `x = t - b / 3` yields the depressed cubic
+/// `t^3 + p t + q`, whose roots are `u + v`, where `u^3` and `v^3` are the
+/// roots of `z^2 + q z - p^3 / 27`, and `u v = -p / 3`.
+fn cubic(b: f64, c: f64, d: f64) -> Vec<Complex<f64>> {
+    let p = c - b * b / 3.0;
+    let q = 2.0 * b * b * b / 27.0 - b * c / 3.0 + d;
+    let shift = Complex::from_real(-b / 3.0);
+
+    // Pick the root `u^3` of greatest modulus for numerical stability
+    let s = sqrt(Complex::from_real(q * q / 4.0 + p * p * p / 27.0));
+    let (w1, w2) = (s - q / 2.0, -s - q / 2.0);
+    let u3 = if Complex::norm(w1) >= Complex::norm(w2) {
+        w1
+    } else {
+        w2
+    };
+    if Complex::norm(u3) == 0.0 {
+        // p == q == 0: triple root
+        return vec![shift; 3];
+    }
+
+    let u = cbrt(u3);
+    let v = Complex::from_real(-p / 3.0) / u;
+    let omega = Complex::from_polar_f64(1.0, 2.0 * core::f64::consts::PI / 3.0);
+    let omega2 = omega * omega;
+    vec![
+        u + v + shift,
+        omega * u + omega2 * v + shift,
+        omega2 * u + omega * v + shift,
+    ]
+}
+
+/// Durand-Kerner (Weierstrass) iteration for the monic polynomial with
+/// coefficients `a`: all roots are refined simultaneously through
+/// `z_k <- z_k - p(z_k) / prod_{j != k} (z_k - z_j)`.
+fn durand_kerner(a: &[f64], tol: f64, max_iter: usize) -> Vec<Complex<f64>> {
+    let n = a.len() - 1;
+
+    // Start from points spread over a circle containing every root (Cauchy
+    // bound), slightly rotated to avoid symmetric configurations
+    let radius = 1.0 + a[..n].iter().fold(0.0_f64, |m, c| m.max(c.abs()));
+    let mut z: Vec<Complex<f64>> = (0..n)
+        .map(|k| {
+            let theta = 2.0 * core::f64::consts::PI * k as f64 / n as f64;
+            Complex::from_polar_f64(radius, theta + 0.4)
+        })
+        .collect();
+
+    for _ in 0..max_iter {
+        let mut converged = true;
+        for k in 0..n {
+            let denom = (0..n)
+                .filter(|&j| j != k)
+                .fold(Complex::from_real(1.0), |acc, j| acc * (z[k] - z[j]));
+            if Complex::norm(denom) == 0.0 {
+                continue;
+            }
+
+            let delta = eval(a, z[k]) / denom;
+            z[k] -= delta;
+            if delta.abs_f64() > tol * z[k].abs_f64().max(1.0) {
+                converged = false;
+            }
+        }
+        if converged {
+            break;
+        }
+    }
+    z
+}
+
+/// Evaluates the polynomial with real coefficients `a` at `z` with Horner's
+/// method.
+fn eval(a: &[f64], z: Complex<f64>) -> Complex<f64> {
+    a.iter()
+        .rev()
+        .fold(Complex::from_real(0.0), |acc, &c| acc * z + c)
+}
+
+/// Principal square root.
+fn sqrt(z: Complex<f64>) -> Complex<f64> {
+    let (r, theta) = z.to_polar_f64();
+    Complex::from_polar_f64(r.sqrt(), theta / 2.0)
+}
+
+/// Principal cube root.
+fn cbrt(z: Complex<f64>) -> Complex<f64> {
+    let (r, theta) = z.to_polar_f64();
+    Complex::from_polar_f64(r.cbrt(), theta / 3.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Checks that `roots` and `expected` agree as multisets, up to `eps`.
+    fn check_roots(
+        roots: Vec<Complex<f64>>,
+        expected: &[(f64, f64)],
+        eps: f64,
+    ) {
+        assert_eq!(roots.len(), expected.len());
+        let mut used = vec![false; roots.len()];
+        for &(re, im) in expected {
+            let found = roots.iter().enumerate().position(|(i, z)| {
+                !used[i] && (z.re - re).abs() < eps && (z.im - im).abs() < eps
+            });
+            match found {
+                Some(i) => used[i] = true,
+                None => {
+                    panic!("root {} + {}i not found in {:?}", re, im, roots)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn trivial() {
+        let p: Polynomial<f64> = Polynomial::new(vec![]);
+        assert!(p.roots().is_empty());
+
+        let p = Polynomial::new(vec![3.0, 0.0]);
+        assert!(p.roots().is_empty());
+
+        let p = Polynomial::new(vec![3.0, 2.0, 0.0]);
+        check_roots(p.roots(), &[(-1.5, 0.0)], 1.0e-12);
+    }
+
+    #[test]
+    fn quadratic() {
+        // (x - 2)(x + 5)
+        let p = Polynomial::new(vec![-10.0, 3.0, 1.0]);
+        check_roots(p.roots(), &[(2.0, 0.0), (-5.0, 0.0)], 1.0e-12);
+
+        // 2x^2 + 2x + 5 has roots -1/2 +- 3/2 i
+        let p = Polynomial::new(vec![5.0, 2.0, 2.0]);
+        check_roots(p.roots(), &[(-0.5, 1.5), (-0.5, -1.5)], 1.0e-12);
+
+        // Double root at zero and badly conditioned roots
+        let p = Polynomial::new(vec![0.0, 0.0, 1.0]);
+        check_roots(p.roots(), &[(0.0, 0.0), (0.0, 0.0)], 1.0e-12);
+        let p = Polynomial::new(vec![1.0, 1.0e8, 1.0]);
+        check_roots(p.roots(), &[(-1.0e8, 0.0), (-1.0e-8, 0.0)], 1.0e-16);
+    }
+
+    #[test]
+    fn cubic() {
+        // (x - 1)(x - 2)(x - 3)
+        let p = Polynomial::new(vec![-6.0, 11.0, -6.0, 1.0]);
+        check_roots(p.roots(), &[(1.0, 0.0), (2.0, 0.0), (3.0, 0.0)], 1.0e-9);
+
+        // (x + 2)(x^2 + 4)
+        let p = Polynomial::new(vec![8.0, 4.0, 2.0, 1.0]);
+        check_roots(p.roots(), &[(-2.0, 0.0), (0.0, 2.0), (0.0, -2.0)], 1.0e-9);
+
+        // (x - 1)^3
+        let p = Polynomial::new(vec![-1.0, 3.0, -3.0, 1.0]);
+        check_roots(p.roots(), &[(1.0, 0.0); 3], 1.0e-9);
+
+        // Non-monic: 2x^3 - 2x = 2x(x - 1)(x + 1)
+        let p = Polynomial::new(vec![0.0, -2.0, 0.0, 2.0]);
+        check_roots(p.roots(), &[(0.0, 0.0), (1.0, 0.0), (-1.0, 0.0)], 1.0e-9);
+    }
+
+    #[test]
+    fn higher_degree() {
+        // (x - 1)(x + 2)(x - 3)(x + 4)(x - 5)
+        let p = Polynomial::new(vec![-120.0, 94.0, 51.0, -23.0, -3.0, 1.0]);
+        let expected =
+            [(1.0, 0.0), (-2.0, 0.0), (3.0, 0.0), (-4.0, 0.0), (5.0, 0.0)];
+        check_roots(p.roots(), &expected, 1.0e-9);
+
+        // x^4 + 1: primitive 8th roots of unity
+        let p = Polynomial::new(vec![1.0, 0.0, 0.0, 0.0, 1.0]);
+        let h = core::f64::consts::FRAC_1_SQRT_2;
+        let expected = [(h, h), (-h, h), (h, -h), (-h, -h)];
+        check_roots(p.roots(), &expected, 1.0e-9);
+
+        // Looser tolerance still converges close to the roots
+        let p = Polynomial::new(vec![24.0, -50.0, 35.0, -10.0, 1.0]);
+        let expected = [(1.0, 0.0), (2.0, 0.0), (3.0, 0.0), (4.0, 0.0)];
+        check_roots(p.roots_with(1.0e-6, 100), &expected, 1.0e-4);
+    }
+
+    #[test]
+    fn root_bound() {
+        let p: Polynomial<f64> = Polynomial::new(vec![0.0, 0.0]);
+        assert_eq!(p.root_bound(), None);
+        let p = Polynomial::new(vec![-7.0, 0.0]);
+        assert_eq!(p.root_bound(), Some(1.0));
+
+        let p = Polynomial::new(vec![-120.0, 94.0, 51.0, -23.0, -3.0, 1.0]);
+        let bound = p.root_bound().unwrap();
+        assert_eq!(bound, 121.0);
+        assert!(p.roots().iter().all(|z| z.abs_f64() < bound));
+
+        let p = Polynomial::new(vec![3, -12, 4, 0]);
+        assert_eq!(p.root_bound(), Some(4));
+    }
+
+    #[test]
+    fn other_coefficient_types() {
+        let p: Polynomial<f32> = Polynomial::new(vec![-10.0, 3.0, 1.0]);
+        check_roots(p.roots(), &[(2.0, 0.0), (-5.0, 0.0)], 1.0e-6);
+
+        let p: Polynomial<i32> = Polynomial::new(vec![-10, 3, 1]);
+        check_roots(p.roots(), &[(2.0, 0.0), (-5.0, 0.0)], 1.0e-12);
+    }
+}
@@ -0,0 +1,211 @@
+//! Classical orthogonal polynomials
+//!
+//! Each family is generated by its three-term recurrence, which gives exact
+//! integer coefficients for the Chebyshev and Hermite polynomials, the
+//! Legendre ones being rounded to the nearest float.
+use super::Polynomial;
+use alloc::{vec, vec::Vec};
+
+/// The `n`th polynomial of the family given by `p_0 = first`, `p_1 = second`
+/// and `p_(k + 1) = a x p_k - b p_(k - 1)`, where `(a, b) = step(k)`.
+fn three_term<F: Fn(usize) -> (f64, f64)>(
+    n: usize,
+    first: Vec<f64>,
+    second: Vec<f64>,
+    step: F,
+) -> Polynomial<f64> {
+    if n == 0 {
+        return Polynomial::new(first);
+    }
+    let (mut prev, mut current) = (first, second);
+    for k in 1..n {
+        let (a, b) = step(k);
+        let mut next = vec![0.0; k + 2];
+        for (i, &c) in current.iter().enumerate() {
+            next[i + 1] += a * c;
+        }
+        for (i, &c) in prev.iter().enumerate() {
+            next[i] -= b * c;
+        }
+        prev = core::mem::replace(&mut current, next);
+    }
+    Polynomial::new(current)
+}
+
+/// Chebyshev polynomial of the first kind `T_n`, with `T_n(cos t) = cos(nt)`.
+///
+/// Example:
+/// ```
+/// use ralg::math::poly::{special::chebyshev_t, Polynomial};
+///
+/// assert_eq!(chebyshev_t(3), Polynomial::new(vec![0.0, -3.0, 0.0, 4.0]));
+/// ```
+pub fn chebyshev_t(n: usize) -> Polynomial<f64> {
+    three_term(n, vec![1.0], vec![0.0, 1.0], |_| (2.0, 1.0))
+}
+
+/// Chebyshev polynomial of the second kind `U_n`, with
+/// `U_n(cos t) sin(t) = sin((n + 1) t)`.
+pub fn chebyshev_u(n: usize) -> Polynomial<f64> {
+    three_term(n, vec![1.0], vec![0.0, 2.0], |_| (2.0, 1.0))
+}
+
+/// Legendre polynomial `P_n`, orthogonal on `[-1, 1]` and with `P_n(1) = 1`.
+///
+/// Example:
+/// ```
+/// use ralg::math::poly::{special::legendre, Polynomial};
+///
+/// assert_eq!(legendre(2), Polynomial::new(vec![-0.5, 0.0, 1.5]));
+/// ```
+pub fn legendre(n: usize) -> Polynomial<f64> {
+    three_term(n, vec![1.0], vec![0.0, 1.0], |k| {
+        let k = k as f64;
+        ((2.0 * k + 1.0) / (k + 1.0), k / (k + 1.0))
+    })
+}
+
+/// Hermite polynomial `H_n`, in the physicists' normalization, orthogonal
+/// for the weight `exp(-x^2)` and with leading coefficient `2^n`.
+pub fn hermite(n: usize) -> Polynomial<f64> {
+    three_term(n, vec![1.0], vec![0.0, 2.0], |k| (2.0, 2.0 * k as f64))
+}
+
+/// Polynomial `c[0] T_0 + c[1] T_1 + ...` in the monomial basis, summed by
+/// Clenshaw's recurrence on polynomials.
+///
+/// Example:
+/// ```
+/// use ralg::math::poly::{special::*, Polynomial};
+///
+/// let p = chebyshev_to_monomial(&[1.0, 0.0, 2.0]);
+/// assert_eq!(p, Polynomial::new(vec![-1.0, 0.0, 4.0]));
+/// assert_eq!(monomial_to_chebyshev(&p), [1.0, 0.0, 2.0]);
+/// ```
+pub fn chebyshev_to_monomial(c: &[f64]) -> Polynomial<f64> {
+    // b_k = c_k + 2 x b_(k + 1) - b_(k + 2), the sum being c_0 + x b_1 - b_2
+    let mut b1: Vec<f64> = Vec::new();
+    let mut b2: Vec<f64> = Vec::new();
+    for (k, &ck) in c.iter().enumerate().rev() {
+        let scale = if k == 0 { 1.0 } else { 2.0 };
+        let mut b = vec![0.0; (b1.len() + 1).max(b2.len())];
+        b[0] += ck;
+        for (i, &x) in b1.iter().enumerate() {
+            b[i + 1] += scale * x;
+        }
+        for (i, &x) in b2.iter().enumerate() {
+            b[i] -= x;
+        }
+        b2 = core::mem::replace(&mut b1, b);
+    }
+    Polynomial::new(b1)
+}
+
+/// Coefficients of `p` in the Chebyshev basis, so that `p` is
+/// `c[0] T_0 + c[1] T_1 + ...`, by Horner's method using
+/// `x T_k = (T_(k + 1) + T_(k - 1)) / 2` for `k > 0` and `x T_0 = T_1`.
+pub fn monomial_to_chebyshev(p: &Polynomial<f64>) -> Vec<f64> {
+    let mut c: Vec<f64> = Vec::new();
+    for &a in p.coeff.iter().rev() {
+        // c <- x c + a
+        let mut next = vec![0.0; c.len() + 1];
+        for (k, &ck) in c.iter().enumerate() {
+            if k == 0 {
+                next[1] += ck;
+            } else {
+                next[k + 1] += ck / 2.0;
+                next[k - 1] += ck / 2.0;
+            }
+        }
+        next[0] += a;
+        c = next;
+    }
+    c
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    #[test]
+    fn low_degrees() {
+        let t4 = Polynomial::new(vec![1.0, 0.0, -8.0, 0.0, 8.0]);
+        assert_eq!(chebyshev_t(4), t4);
+        let u3 = Polynomial::new(vec![0.0, -4.0, 0.0, 8.0]);
+        assert_eq!(chebyshev_u(3), u3);
+        let p3 = Polynomial::new(vec![0.0, -1.5, 0.0, 2.5]);
+        assert_eq!(legendre(3), p3);
+        let h3 = Polynomial::new(vec![0.0, -12.0, 0.0, 8.0]);
+        assert_eq!(hermite(3), h3);
+        assert_eq!(hermite(0), Polynomial::new(vec![1.0]));
+        assert_eq!(chebyshev_u(1), Polynomial::new(vec![0.0, 2.0]));
+    }
+
+    #[test]
+    fn values_at_one() {
+        for n in 0..20 {
+            assert_eq!(chebyshev_t(n).eval(1.0), 1.0);
+            assert_eq!(
+                chebyshev_t(n).eval(-1.0),
+                if n % 2 == 0 { 1.0 } else { -1.0 }
+            );
+            assert_eq!(chebyshev_u(n).eval(1.0), (n + 1) as f64);
+            assert!((legendre(n).eval(1.0) - 1.0).abs() < 1e-12);
+            assert_eq!(hermite(n).degree(), Some(n));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn trigonometric_identities() {
+        for n in 0..12 {
+            let (t, u) = (chebyshev_t(n), chebyshev_u(n));
+            for i in 1..20 {
+                let theta = i as f64 * 0.15;
+                let x = theta.cos();
+                assert!((t.eval(x) - (n as f64 * theta).cos()).abs() < 1e-9);
+                let sin = ((n + 1) as f64 * theta).sin();
+                assert!((u.eval(x) * theta.sin() - sin).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn legendre_orthogonality() {
+        for m in 0..8 {
+            for n in 0..8 {
+                let product = &legendre(m) * &legendre(n);
+                let integral = product.integrate(-1.0, 1.0);
+                let expected = if m == n {
+                    2.0 / (2 * n + 1) as f64
+                } else {
+                    0.0
+                };
+                assert!((integral - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn basis_conversions() {
+        assert_eq!(chebyshev_to_monomial(&[]), Polynomial::new(vec![]));
+        for n in 0..10 {
+            let mut unit = vec![0.0; n + 1];
+            unit[n] = 1.0;
+            assert_eq!(chebyshev_to_monomial(&unit), chebyshev_t(n));
+            assert_eq!(monomial_to_chebyshev(&chebyshev_t(n)), unit);
+        }
+
+        let mut rng = Pcg32::seed(0x5bec);
+        for _ in 0..50 {
+            let c: Vec<f64> = (0..rng.gen_range(1..12))
+                .map(|_| rng.gen_range(-4.0..4.0))
+                .collect();
+            let p = chebyshev_to_monomial(&c);
+            let back = monomial_to_chebyshev(&p);
+            assert_eq!(back.len(), c.len());
+            assert!(back.iter().zip(&c).all(|(a, b)| (a - b).abs() < 1e-9));
+        }
+    }
+}
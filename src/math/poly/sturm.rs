@@ -0,0 +1,426 @@
+//! Sturm sequences, counting and isolation of the real roots of polynomials
+//!
+//! The Sturm sequence of `p` starts with `p` and `p'`, and continues with the
+//! negated remainders of the Euclidean algorithm. By Sturm's theorem, the
+//! number of distinct real roots of `p` in `(a, b]` is `V(a) - V(b)`, where
+//! `V(x)` counts the sign changes along the sequence evaluated at `x`, zeros
+//! skipped. This holds for multiple roots as well.
+//!
+//! Over the rationals every count is exact, although the coefficients of the
+//! remainders grow quickly with the degree and may overflow the 128-bit
+//! numerators and denominators of `Rational`. Over floating point types, the
+//! remainders are subject to the same rounding errors as in `gcd`, and the
+//! counts may be off for clustered or multiple roots.
+use super::Polynomial;
+use crate::math::num::Signed;
+use alloc::{vec, vec::Vec};
+use core::ops::Div;
+
+impl<T: Signed + Copy + PartialOrd + Div<Output = T>> Polynomial<T> {
+    /// Cauchy bound `1 + max |a_i / a_n|`, where `a_n` is the leading
+    /// coefficient: every complex root `z` satisfies `|z| < bound`. Returns
+    /// `None` for the zero polynomial, since every point is a root.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::{num::Rational, poly::Polynomial};
+    ///
+    /// // 2x^2 - 3x - 8 has roots of modulus less than 1 + 8/2
+    /// let p = Polynomial::new(vec![-8, -3, 2]);
+    /// assert_eq!(p.root_bound(), Some(1 + 8 / 2));
+    ///
+    /// let p = Polynomial::new(vec![Rational::from(-3), Rational::from(2)]);
+    /// assert_eq!(p.root_bound(), Some(Rational::new(5, 2)));
+    /// ```
+    pub fn root_bound(&self) -> Option<T> {
+        let n = self.coeff.iter().rposition(|c| *c != T::zero())?;
+        let lead = self.coeff[n];
+        let max = self.coeff[..n]
+            .iter()
+            .map(|&c| (c / lead).abs())
+            .fold(T::zero(), |m, c| if c > m { c } else { m });
+        Some(T::one() + max)
+    }
+
+    /// Sturm sequence `p, p', -rem(p, p'), ...` of the polynomial, ending
+    /// with the last nonzero remainder, which is a multiple of `gcd(p, p')`.
+    /// Every remainder is divided by the absolute value of its leading
+    /// coefficient, which keeps the signs and the size of the coefficients
+    /// in check. The sequence of the zero polynomial is empty.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // x^3 - x has the sequence x^3 - x, 3x^2 - 1, x, 1
+    /// let p = Polynomial::new(vec![0.0, -1.0, 0.0, 1.0]);
+    /// let seq = p.sturm_sequence();
+    /// assert_eq!(seq.len(), 4);
+    /// assert_eq!(seq[2], Polynomial::new(vec![0.0, 1.0]));
+    /// assert_eq!(seq[3], Polynomial::new(vec![1.0]));
+    /// ```
+    pub fn sturm_sequence(&self) -> Vec<Self> {
+        let mut p = self.clone();
+        p.reduce();
+        if p.coeff.is_empty() {
+            return vec![];
+        }
+        let mut dp = p.derivative();
+        dp.reduce();
+        let mut seq = vec![p];
+        while !dp.coeff.is_empty() {
+            let (_, r) = seq[seq.len() - 1].div_rem(&dp);
+            seq.push(dp);
+            dp = match r.coeff.last() {
+                Some(&lead) => {
+                    let scale = -lead.abs();
+                    let coeff = r.coeff.iter().map(|&c| c / scale).collect();
+                    Polynomial::new(coeff)
+                }
+                None => r,
+            };
+        }
+        seq
+    }
+
+    /// Sturm sequence divided by its last polynomial, a multiple of
+    /// `gcd(p, p')`, which starts with the square-free part of `p`. Both
+    /// sequences have the same sign changes away from the roots of the gcd,
+    /// but only the divided one vanishes nowhere at the multiple roots.
+    fn square_free_sturm_sequence(&self) -> Vec<Self> {
+        let mut seq = self.sturm_sequence();
+        if let Some(g) = seq.last().cloned() {
+            seq.iter_mut().for_each(|q| *q = q.div_rem(&g).0);
+        }
+        seq
+    }
+
+    /// Number of distinct real roots in the half-open interval `(a, b]`,
+    /// which is zero unless `a < b`, and also for the zero polynomial. Over
+    /// floating point types, a count that rounding makes negative is zero.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::{num::Rational, poly::Polynomial};
+    ///
+    /// // (x^2 - 2)(3x - 1) has roots -1.414..., 1/3 and 1.414...
+    /// let p = Polynomial::new(vec![2, -6, -1, 3]);
+    /// let p = Polynomial::new(p.coeff.into_iter().map(Rational::from).collect());
+    /// let r = |n, d| Rational::new(n, d);
+    /// assert_eq!(p.count_real_roots_in(r(-2, 1), r(2, 1)), 3);
+    /// assert_eq!(p.count_real_roots_in(r(0, 1), r(1, 3)), 1);
+    /// assert_eq!(p.count_real_roots_in(r(1, 3), r(7, 5)), 0);
+    /// ```
+    pub fn count_real_roots_in(&self, a: T, b: T) -> usize {
+        if a >= b {
+            return 0;
+        }
+        let seq = self.square_free_sturm_sequence();
+        sign_changes(&seq, a).saturating_sub(sign_changes(&seq, b))
+    }
+
+    /// Number of distinct real roots, found from the signs of the Sturm
+    /// sequence at both infinities, that is, from the leading coefficients
+    /// and the parities of the degrees. The zero polynomial has none.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // (x - 1)^2 (x^2 + 1) has the single real root 1
+    /// let p = Polynomial::new(vec![1.0, -2.0, 2.0, -2.0, 1.0]);
+    /// assert_eq!(p.count_real_roots(), 1);
+    /// ```
+    pub fn count_real_roots(&self) -> usize {
+        let seq = self.square_free_sturm_sequence();
+        let signs = |at_positive: bool| {
+            let signs = seq.iter().map(|q| {
+                let lead = q.coeff[q.coeff.len() - 1];
+                let odd = q.coeff.len() % 2 == 0;
+                if at_positive || !odd {
+                    lead
+                } else {
+                    -lead
+                }
+            });
+            count_changes(signs)
+        };
+        signs(false).saturating_sub(signs(true))
+    }
+
+    /// Disjoint intervals `(lo, hi]`, in increasing order, each containing
+    /// exactly one distinct real root of the polynomial, found by bisecting
+    /// `(-bound, bound]` for the Cauchy bound of `root_bound` until every
+    /// piece holds at most one root. The zero polynomial yields no intervals.
+    ///
+    /// The endpoints are exact over the rationals. Over floating point types,
+    /// an interval that can no longer be split is returned as it is, even if
+    /// the count says it holds more than one root. So is an interval whose
+    /// count comes out negative because of rounding.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::poly::Polynomial;
+    ///
+    /// // (x + 3)(x - 1)(x - 2) has bound 8
+    /// let p = Polynomial::new(vec![6.0, -7.0, 0.0, 1.0]);
+    /// let intervals = p.isolate_real_roots();
+    /// assert_eq!(intervals, vec![(-8.0, 0.0), (0.0, 1.0), (1.0, 2.0)]);
+    /// ```
+    pub fn isolate_real_roots(&self) -> Vec<(T, T)> {
+        match self.root_bound() {
+            Some(bound) => self.isolate_real_roots_in(-bound, bound),
+            None => vec![],
+        }
+    }
+
+    /// Disjoint intervals `(lo, hi]` contained in `(a, b]`, in increasing
+    /// order, each containing exactly one distinct real root, as in
+    /// `isolate_real_roots`. Each can be refined by bisection on the sign of
+    /// the polynomial, or on the Sturm count in case of multiple roots.
+    ///
+    /// Example:
+    /// ```
+    /// use ralg::math::{num::Rational, poly::Polynomial};
+    ///
+    /// // x^2 - 2 has the roots -sqrt(2) in (-2, 0] and sqrt(2) in (0, 2]
+    /// let p = Polynomial::new(vec![-2, 0, 1]);
+    /// let p = Polynomial::new(p.coeff.into_iter().map(Rational::from).collect());
+    /// let intervals = p.isolate_real_roots_in(Rational::from(-2), Rational::from(2));
+    /// let r = |a, b| (Rational::from(a), Rational::from(b));
+    /// assert_eq!(intervals, vec![r(-2, 0), r(0, 2)]);
+    /// ```
+    pub fn isolate_real_roots_in(&self, a: T, b: T) -> Vec<(T, T)> {
+        let mut intervals = vec![];
+        if a >= b {
+            return intervals;
+        }
+        let seq = self.square_free_sturm_sequence();
+        let two = T::one() + T::one();
+        // Pending intervals along with their sign changes, the leftmost last
+        let mut stack =
+            vec![(a, sign_changes(&seq, a), b, sign_changes(&seq, b))];
+        while let Some((lo, v_lo, hi, v_hi)) = stack.pop() {
+            // Rounding may leave more sign changes at the right end
+            let Some(count) = v_lo.checked_sub(v_hi) else {
+                intervals.push((lo, hi));
+                continue;
+            };
+            if count == 0 {
+                continue;
+            }
+            let mid = (lo + hi) / two;
+            if count == 1 || mid <= lo || mid >= hi {
+                intervals.push((lo, hi));
+                continue;
+            }
+            let v_mid = sign_changes(&seq, mid);
+            stack.push((mid, v_mid, hi, v_hi));
+            stack.push((lo, v_lo, mid, v_mid));
+        }
+        intervals
+    }
+}
+
+/// Number of sign changes of the Sturm sequence `seq` evaluated at `x`.
+fn sign_changes<T>(seq: &[Polynomial<T>], x: T) -> usize
+where
+    T: Signed + Copy + PartialOrd,
+{
+    count_changes(seq.iter().map(|q| q.eval(x)))
+}
+
+/// Number of sign changes along `values`, ignoring zeros.
+fn count_changes<T, I>(values: I) -> usize
+where
+    T: Signed + Copy,
+    I: Iterator<Item = T>,
+{
+    let mut changes = 0;
+    let mut last: Option<bool> = None;
+    for v in values {
+        if v == T::zero() {
+            continue;
+        }
+        let negative = v.is_negative();
+        if last.is_some_and(|l| l != negative) {
+            changes += 1;
+        }
+        last = Some(negative);
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::num::Rational;
+    use crate::random::Pcg32;
+
+    /// Monic polynomial with the given roots, repeated by multiplicity.
+    fn from_roots(roots: &[Rational]) -> Polynomial<Rational> {
+        roots
+            .iter()
+            .fold(Polynomial::new(vec![Rational::from(1)]), |p, &r| {
+                &p * &Polynomial::new(vec![-r, Rational::from(1)])
+            })
+    }
+
+    #[test]
+    fn sequences() {
+        let zero: Polynomial<f64> = Polynomial::new(vec![0.0]);
+        assert!(zero.sturm_sequence().is_empty());
+        assert_eq!(zero.count_real_roots(), 0);
+        assert!(zero.isolate_real_roots().is_empty());
+
+        let c = Polynomial::new(vec![-3.0]);
+        assert_eq!(c.sturm_sequence(), vec![c.clone()]);
+        assert_eq!(c.count_real_roots(), 0);
+
+        // Square-free polynomials end with a nonzero constant, others with
+        // a multiple of gcd(p, p')
+        let p = from_roots(&[1, 1, 1, -2].map(Rational::from));
+        let seq = p.sturm_sequence();
+        let last = seq.last().unwrap().clone();
+        let expected = from_roots(&[1, 1].map(Rational::from));
+        assert_eq!(last.monic(), expected);
+    }
+
+    #[test]
+    fn exact_counts() {
+        let r = Rational::new;
+        // Roots -3/2, -1/3 (double), 0 and 5/4 (double)
+        let roots =
+            [r(-3, 2), r(-1, 3), r(-1, 3), r(0, 1), r(5, 4), r(5, 4)].to_vec();
+        let p = from_roots(&roots);
+        assert_eq!(p.count_real_roots(), 4);
+        assert_eq!(p.count_real_roots_in(r(-10, 1), r(10, 1)), 4);
+        // Half-open intervals
+        assert_eq!(p.count_real_roots_in(r(-1, 3), r(5, 4)), 2);
+        assert_eq!(p.count_real_roots_in(r(-1, 2), r(-1, 3)), 1);
+        assert_eq!(p.count_real_roots_in(r(0, 1), r(1, 1)), 0);
+        assert_eq!(p.count_real_roots_in(r(6, 1), r(-6, 1)), 0);
+
+        let intervals = p.isolate_real_roots();
+        assert_eq!(intervals.len(), 4);
+        let mut distinct = roots.clone();
+        distinct.dedup();
+        for ((lo, hi), root) in intervals.iter().zip(distinct) {
+            assert!(*lo < root && root <= *hi);
+            assert_eq!(p.count_real_roots_in(*lo, *hi), 1);
+        }
+        assert!(intervals.windows(2).all(|w| w[0].1 <= w[1].0));
+
+        // x^4 + 1 has no real roots
+        let p = Polynomial::new([1, 0, 0, 0, 1].map(Rational::from).to_vec());
+        assert_eq!(p.count_real_roots(), 0);
+        assert!(p.isolate_real_roots().is_empty());
+
+        // The remainders of this quintic need more than 64 bits
+        let roots = [r(-9, 2), r(-2, 3), r(1, 1), r(7, 3), r(5, 2)];
+        let p = from_roots(&roots);
+        assert_eq!(p.count_real_roots_in(r(-10, 1), r(10, 1)), 5);
+        assert_eq!(p.count_real_roots_in(r(2, 1), r(3, 1)), 2);
+        assert_eq!(p.isolate_real_roots().len(), 5);
+    }
+
+    #[test]
+    fn random_rational_roots() {
+        let mut rng = Pcg32::seed(0x2f7a);
+        for _ in 0..20 {
+            let mut roots: Vec<Rational> = (0..rng.gen_range(1..6))
+                .map(|_| {
+                    let num: i64 = rng.gen_range(-9..10);
+                    let den: i64 = rng.gen_range(1..4);
+                    Rational::new(num.into(), den.into())
+                })
+                .collect();
+            let p = from_roots(&roots);
+            roots.sort();
+            roots.dedup();
+            assert_eq!(p.count_real_roots(), roots.len());
+            let intervals = p.isolate_real_roots();
+            assert_eq!(intervals.len(), roots.len());
+            for ((lo, hi), root) in intervals.into_iter().zip(roots) {
+                assert!(lo < root && root <= hi);
+            }
+        }
+    }
+
+    #[test]
+    fn float_coefficients() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)(x - 5)
+        let p = Polynomial::new(vec![-120.0, 274.0, -225.0, 85.0, -15.0, 1.0]);
+        assert_eq!(p.count_real_roots(), 5);
+        assert_eq!(p.count_real_roots_in(1.5, 4.5), 3);
+        let intervals = p.isolate_real_roots();
+        assert_eq!(intervals.len(), 5);
+        for (k, (lo, hi)) in intervals.into_iter().enumerate() {
+            let root = (k + 1) as f64;
+            assert!(lo < root && root <= hi);
+        }
+
+        // x^2 - 2x + 1 - 1e-6 has roots 1 +- 1e-3
+        let p = Polynomial::new(vec![1.0 - 1e-6, -2.0, 1.0]);
+        assert_eq!(p.count_real_roots(), 2);
+        assert_eq!(p.isolate_real_roots_in(0.0, 2.0).len(), 2);
+
+        // Rounding in the remainders used to break the count
+        let p = Polynomial::new(vec![-123.43, 141.43, 22.0, 63.29, 70.57]);
+        assert_eq!(p.count_real_roots(), 2);
+
+        // Rounding may also give more sign changes at the right end of an
+        // interval than at the left end
+        let p = Polynomial::new(vec![
+            -1.84e-15, 3.30e-12, 7.70e-9, -7.77e-6, -0.0110, -0.165, -0.525,
+            -0.0935, 1.2497, 1.0,
+        ]);
+        let intervals = p.isolate_real_roots();
+        assert!(intervals.windows(2).all(|w| w[0].1 <= w[1].0));
+        assert!(p.count_real_roots() <= 9);
+        let p = Polynomial::new(vec![
+            -0.7093,
+            2.892e-15,
+            -0.005567,
+            3.5890000000000005e-15,
+            2.068e-8,
+            -0.005125,
+            5.036e-15,
+            -5.839e-14,
+            1.0,
+        ]);
+        let intervals = p.isolate_real_roots();
+        assert!(intervals.windows(2).all(|w| w[0].1 <= w[1].0));
+        let bound = p.root_bound().unwrap();
+        assert!(p.count_real_roots_in(-bound, bound) <= 8);
+    }
+
+    /// Number of sign changes of `p` sampled at `n + 1` evenly spaced points
+    /// of `[a, b]`, a lower bound for its number of roots in the interval.
+    fn sampled_sign_changes(p: &Polynomial<f64>, a: f64, b: f64) -> usize {
+        let n = 5000;
+        let values = (0..=n).map(|i| p.eval(a + (b - a) * i as f64 / n as f64));
+        count_changes(values)
+    }
+
+    #[test]
+    fn random_float_quartics() {
+        let mut rng = Pcg32::seed(0x57e2);
+        for _ in 0..2000 {
+            let coeff: Vec<f64> = (0..5)
+                .map(|_| (rng.gen_range(-10000..10000) as f64) / 100.0)
+                .collect();
+            let p = Polynomial::new(coeff);
+            let seq = p.sturm_sequence();
+            assert!(seq
+                .windows(2)
+                .all(|w| w[0].coeff.len() > w[1].coeff.len()));
+
+            let bound = p.root_bound().unwrap();
+            let sampled = sampled_sign_changes(&p, -bound, bound);
+            assert_eq!(p.count_real_roots(), sampled, "{:?}", p.coeff);
+            assert_eq!(p.count_real_roots_in(-bound, bound), sampled);
+            assert_eq!(p.isolate_real_roots().len(), sampled);
+        }
+    }
+}
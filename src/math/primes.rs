@@ -0,0 +1,217 @@
+//! Prime numbers: sieving, primality testing and factorization
+use crate::ds::bitvec::BitVec;
+use crate::math::{misc::mod_pow, numtheory::gcd_u128};
+use alloc::{vec, vec::Vec};
+
+/// Returns all primes less than or equal to `n`, in increasing order, using
+/// the sieve of Eratosthenes. Takes time `O(n log(log(n)))` and `O(n)` space.
+///
+/// Example:
+/// ```
+/// use ralg::math::primes::sieve_of_eratosthenes;
+///
+/// assert_eq!(sieve_of_eratosthenes(20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+/// ```
+pub fn sieve_of_eratosthenes(n: usize) -> Vec<usize> {
+    if n < 2 {
+        return Vec::new();
+    }
+
+    let mut composite = BitVec::new(n + 1);
+    let mut p = 2;
+    while p * p <= n {
+        if !composite.get(p) {
+            // Smaller multiples of `p` were already crossed out by smaller
+            // primes
+            for m in (p * p..=n).step_by(p) {
+                composite.set(m, true);
+            }
+        }
+        p += 1;
+    }
+    (2..=n).filter(|&k| !composite.get(k)).collect()
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// Bases for which the Miller-Rabin test is known to be correct for every
+/// 64-bit integer.
+const MILLER_RABIN_BASES: [u64; 12] =
+    [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Deterministic Miller-Rabin primality test for 64-bit integers.
+///
+/// Writing `n - 1 == 2^s d` with `d` odd, a prime `n` satisfies, for every
+/// base `a`, either `a^d == 1` or `a^(2^r d) == -1 (mod n)` for some `r < s`.
+/// Testing the first 12 primes as bases is enough to rule out every composite
+/// below `2^64`, so the answer is always exact.
+///
+/// Example:
+/// ```
+/// use ralg::math::primes::is_prime;
+///
+/// assert!(is_prime(2));
+/// assert!(!is_prime(561));
+/// assert!(is_prime(18_446_744_073_709_551_557));
+/// ```
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in &MILLER_RABIN_BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    MILLER_RABIN_BASES.iter().all(|&a| {
+        let mut x = mod_pow(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Primes used for trial division before resorting to Pollard's rho.
+const TRIAL_PRIMES: [u64; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+    73, 79, 83, 89, 97,
+];
+
+/// Prime factorization of `n`, returned as the prime factors in increasing
+/// order, repeated according to their multiplicity. Both `0` and `1` have no
+/// prime factors, so an empty vector is returned for them.
+///
+/// Small factors are removed by trial division, and the remaining cofactor is
+/// split by Pollard's rho algorithm, which finds a factor `p` in expected
+/// `O(sqrt(p))` steps.
+///
+/// Example:
+/// ```
+/// use ralg::math::primes::factorize;
+///
+/// assert_eq!(factorize(360), vec![2, 2, 2, 3, 3, 5]);
+/// assert_eq!(factorize(600_851_475_143), vec![71, 839, 1471, 6857]);
+/// ```
+pub fn factorize(mut n: u64) -> Vec<u64> {
+    let mut factors = Vec::new();
+    if n == 0 {
+        return factors;
+    }
+
+    for &p in &TRIAL_PRIMES {
+        while n.is_multiple_of(p) {
+            factors.push(p);
+            n /= p;
+        }
+    }
+
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            factors.push(m);
+            continue;
+        }
+        let d = pollard_rho(m);
+        stack.push(d);
+        stack.push(m / d);
+    }
+
+    factors.sort_unstable();
+    factors
+}
+
+/// Finds a non-trivial factor of the odd composite `n` with Pollard's rho
+/// algorithm, iterating `x -> x^2 + c (mod n)` and detecting cycles modulo a
+/// factor by Floyd's tortoise and hare. On failure, another `c` is tried.
+fn pollard_rho(n: u64) -> u64 {
+    let step = |x: u64, c: u64| (mul_mod(x, x, n) + c) % n;
+    for c in 1.. {
+        let (mut x, mut y, mut d) = (2, 2, 1);
+        while d == 1 {
+            x = step(x, c);
+            y = step(step(y, c), c);
+            d = gcd_u128(x.abs_diff(y) as u128, n as u128) as u64;
+        }
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sieve() {
+        assert!(sieve_of_eratosthenes(0).is_empty());
+        assert!(sieve_of_eratosthenes(1).is_empty());
+        assert_eq!(sieve_of_eratosthenes(2), vec![2]);
+        assert_eq!(
+            sieve_of_eratosthenes(30),
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]
+        );
+        assert_eq!(sieve_of_eratosthenes(10_000).len(), 1229);
+    }
+
+    #[test]
+    fn primality() {
+        let primes = sieve_of_eratosthenes(5000);
+        for n in 0..5000 {
+            assert_eq!(
+                is_prime(n as u64),
+                primes.binary_search(&n).is_ok(),
+                "n = {}",
+                n
+            );
+        }
+
+        // Carmichael numbers and strong pseudoprimes to several bases
+        for n in [561, 1105, 1729, 3_215_031_751, 3_825_123_056_546_413_051] {
+            assert!(!is_prime(n));
+        }
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime((1 << 61) - 1));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn factorization() {
+        assert!(factorize(0).is_empty());
+        assert!(factorize(1).is_empty());
+        assert_eq!(factorize(97), vec![97]);
+        assert_eq!(factorize(1 << 20), vec![2; 20]);
+        for n in 2..2000 {
+            let fs = factorize(n);
+            assert_eq!(fs.iter().product::<u64>(), n);
+            assert!(fs.iter().all(|&p| is_prime(p)));
+            assert!(fs.windows(2).all(|w| w[0] <= w[1]));
+        }
+
+        // Semiprimes with large factors need Pollard's rho
+        let (p, q) = (1_000_000_007, 998_244_353);
+        assert_eq!(factorize(p * q), vec![q, p]);
+        assert_eq!(
+            factorize(u64::MAX),
+            vec![3, 5, 17, 257, 641, 65537, 6_700_417]
+        );
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        assert_eq!(factorize(p * q), vec![q, p]);
+    }
+}
@@ -0,0 +1,273 @@
+//! Numerical integration
+//!
+//! Every rule integrates a function over `[a, b]` and estimates the absolute
+//! error of the result: the composite rules by Richardson extrapolation from
+//! a coarser grid made of every other sample, and the Gauss-Kronrod rule by
+//! the difference with its embedded Gauss rule.
+use crate::heap::MaxHeap;
+
+/// Approximation of an integral.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Integral {
+    pub value: f64,
+    /// Estimate of the absolute error of `value`.
+    pub error: f64,
+}
+
+/// Composite trapezoid rule over `n` subintervals of `[a, b]`, rounded up to
+/// an even number, with an error in O(h^2) for a step `h`.
+///
+/// Panics if `n == 0`.
+///
+/// Example:
+/// ```
+/// use ralg::math::quadrature::trapezoid;
+///
+/// let integral = trapezoid(|x| x * x, 0.0, 3.0, 1000);
+/// assert!((integral.value - 9.0).abs() < 1e-4);
+/// assert!(integral.error < 1e-4);
+/// ```
+pub fn trapezoid<F: Fn(f64) -> f64>(
+    f: F,
+    a: f64,
+    b: f64,
+    n: usize,
+) -> Integral {
+    assert!(n > 0, "no subintervals");
+    let n = n + n % 2;
+    let h = (b - a) / n as f64;
+    let (mut even, mut odd) = (0.0, 0.0);
+    for i in 1..n {
+        let y = f(a + i as f64 * h);
+        if i % 2 == 0 {
+            even += y;
+        } else {
+            odd += y;
+        }
+    }
+    let ends = (f(a) + f(b)) / 2.0;
+    let fine = h * (ends + even + odd);
+    let coarse = 2.0 * h * (ends + even);
+    Integral {
+        value: fine,
+        error: (fine - coarse).abs() / 3.0,
+    }
+}
+
+/// Composite Simpson rule over `n` subintervals of `[a, b]`, rounded up to a
+/// multiple of four, with an error in O(h^4) for a step `h`. It is exact for
+/// polynomials of degree at most three.
+///
+/// Panics if `n == 0`.
+///
+/// Example:
+/// ```
+/// use ralg::math::quadrature::simpson;
+///
+/// let integral = simpson(|x| x * x * x - x, -1.0, 2.0, 4);
+/// assert!((integral.value - 2.25).abs() < 1e-12);
+/// ```
+pub fn simpson<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, n: usize) -> Integral {
+    assert!(n > 0, "no subintervals");
+    let n = n.div_ceil(4) * 4;
+    let h = (b - a) / n as f64;
+    // Interior samples by their index modulo 4: odd ones only belong to the
+    // fine grid, where the coarse grid has its odd ones at index 2 mod 4
+    let (mut odd, mut two, mut four) = (0.0, 0.0, 0.0);
+    for i in 1..n {
+        let y = f(a + i as f64 * h);
+        match i % 4 {
+            0 => four += y,
+            2 => two += y,
+            _ => odd += y,
+        }
+    }
+    let ends = f(a) + f(b);
+    let fine = h / 3.0 * (ends + 4.0 * odd + 2.0 * (two + four));
+    let coarse = 2.0 * h / 3.0 * (ends + 4.0 * two + 2.0 * four);
+    Integral {
+        value: fine,
+        error: (fine - coarse).abs() / 15.0,
+    }
+}
+
+/// Nodes of the 15-point Kronrod rule on `[-1, 1]`, from the outermost; the
+/// odd ones are the nodes of the 7-point Gauss rule.
+#[allow(clippy::excessive_precision)]
+const KRONROD_NODES: [f64; 8] = [
+    0.991455371120812639206854697526329,
+    0.949107912342758524526189684047851,
+    0.864864423359769072789712788640926,
+    0.741531185599394439863864773280788,
+    0.586087235467691130294144845693013,
+    0.405845151377397166906606412076961,
+    0.207784955007898467600689403773245,
+    0.0,
+];
+
+#[allow(clippy::excessive_precision)]
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022935322010529224963732008058970,
+    0.063092092629978553290700663189204,
+    0.104790010322250183839876322541518,
+    0.140653259715525918745189590510238,
+    0.169004726639267902826583426598550,
+    0.190350578064785409913256402421014,
+    0.204432940075298892414161999234649,
+    0.209482141084727828012999174891714,
+];
+
+/// Weights of the 7-point Gauss rule, at the odd Kronrod nodes.
+#[allow(clippy::excessive_precision)]
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129484966168869693270611432679082,
+    0.279705391489276667901467771423780,
+    0.381830050505118944950369775488975,
+    0.417959183673469387755102040816327,
+];
+
+/// Greatest number of subintervals of `gauss_kronrod`.
+const MAX_SEGMENTS: usize = 1000;
+
+/// The 15-point Gauss-Kronrod rule over `[a, b]`, exact for polynomials of
+/// degree at most 22.
+fn kronrod_15<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64) -> Integral {
+    let (center, half) = ((a + b) / 2.0, (b - a) / 2.0);
+    let (mut kronrod, mut gauss) = (0.0, 0.0);
+    for (i, (&x, &w)) in KRONROD_NODES.iter().zip(&KRONROD_WEIGHTS).enumerate()
+    {
+        let y = if x == 0.0 {
+            f(center)
+        } else {
+            f(center - half * x) + f(center + half * x)
+        };
+        kronrod += w * y;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * y;
+        }
+    }
+    Integral {
+        value: half * kronrod,
+        error: (half * (kronrod - gauss)).abs(),
+    }
+}
+
+/// Globally adaptive integration by the 15-point Gauss-Kronrod rule: the
+/// subinterval of largest estimated error is bisected until the total error
+/// is at most `tolerance`, or the interval is cut into too many pieces, in
+/// which case the estimated error tells how far the result is from the
+/// requested accuracy.
+///
+/// Example:
+/// ```
+/// use ralg::math::quadrature::gauss_kronrod;
+///
+/// let integral = gauss_kronrod(|x| 4.0 / (1.0 + x * x), 0.0, 1.0, 1e-12);
+/// assert!((integral.value - std::f64::consts::PI).abs() < 1e-12);
+/// assert!(integral.error <= 1e-12);
+/// ```
+pub fn gauss_kronrod<F: Fn(f64) -> f64>(
+    f: F,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+) -> Integral {
+    let whole = kronrod_15(&f, a, b);
+    // Subintervals keyed by their error first
+    let mut segments = MaxHeap::new();
+    segments.push((whole.error, a, b, whole.value));
+    let mut error = whole.error;
+    while error > tolerance && segments.len() < MAX_SEGMENTS {
+        let (worst, lo, hi, _) = segments.pop().expect("at least one segment");
+        let mid = (lo + hi) / 2.0;
+        let (left, right) = (kronrod_15(&f, lo, mid), kronrod_15(&f, mid, hi));
+        error += left.error + right.error - worst;
+        segments.push((left.error, lo, mid, left.value));
+        segments.push((right.error, mid, hi, right.value));
+    }
+
+    // Sum again, free of the rounding drift of the running error
+    let mut total = Integral {
+        value: 0.0,
+        error: 0.0,
+    };
+    for (error, _, _, value) in segments.into_sorted_vec() {
+        total.value += value;
+        total.error += error;
+    }
+    total
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::poly::Polynomial;
+    use alloc::vec;
+    use core::f64::consts::PI;
+
+    #[test]
+    fn polynomials() {
+        let p = Polynomial::new(vec![1.0, -2.0, 0.5, 3.0]);
+        let exact = p.integrate(-1.0, 2.0);
+        let s = simpson(|x| p.eval(x), -1.0, 2.0, 1);
+        assert!((s.value - exact).abs() < 1e-12 && s.error < 1e-12);
+        let t = trapezoid(|x| p.eval(x), -1.0, 2.0, 3);
+        assert!((t.value - exact).abs() > 0.1);
+
+        let q =
+            Polynomial::new((0..23).map(|k| (k % 5) as f64 - 2.0).collect());
+        let g = gauss_kronrod(|x| q.eval(x), 0.0, 1.0, 1e-9);
+        assert!((g.value - q.integrate(0.0, 1.0)).abs() < 1e-9);
+        // Reversed bounds flip the sign
+        let r = gauss_kronrod(|x| q.eval(x), 1.0, 0.0, 1e-9);
+        assert!((r.value + g.value).abs() < 1e-9);
+    }
+
+    #[test]
+    fn orders_of_convergence() {
+        let f = |x: f64| 1.0 / (1.0 + x * x);
+        let exact = PI / 4.0;
+        let mut previous = (f64::INFINITY, f64::INFINITY);
+        for n in [8, 16, 32, 64] {
+            let t = trapezoid(f, 0.0, 1.0, n);
+            let s = simpson(f, 0.0, 1.0, n);
+            let errors = ((t.value - exact).abs(), (s.value - exact).abs());
+            // The estimates are close to the actual errors
+            assert!(errors.0 <= 2.0 * t.error && t.error <= 2.0 * errors.0);
+            assert!(errors.1 <= 2.0 * s.error);
+            // Halving the step divides the errors by about 4 and 16
+            assert!(errors.0 < previous.0 / 3.5);
+            assert!(errors.1 < previous.1 / 12.0);
+            previous = errors;
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn adaptive_on_a_peak() {
+        // Sharp peak at 0.3, whose integral is 100 (atan(70) + atan(30))
+        let f = |x: f64| 1.0 / ((x - 0.3) * (x - 0.3) + 1e-4);
+        let exact = 100.0 * (70.0_f64.atan() + 30.0_f64.atan());
+        let g = gauss_kronrod(f, 0.0, 1.0, 1e-10);
+        assert!((g.value - exact).abs() < 1e-9);
+        assert!(g.error <= 1e-10);
+    }
+
+    #[test]
+    fn adaptive_on_singularities() {
+        // Bisection closes in on a jump
+        let step = |x: f64| if x < 1.0 / 3.0 { 0.0 } else { 1.0 };
+        let g = gauss_kronrod(step, 0.0, 1.0, 1e-12);
+        assert!((g.value - 2.0 / 3.0).abs() <= 1e-12);
+
+        // A divergent integral keeps a large error after the last subdivision
+        let g = gauss_kronrod(|x| 1.0 / x, 0.0, 1.0, 1e-6);
+        assert!(g.error > 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn no_subintervals() {
+        simpson(|x| x, 0.0, 1.0, 0);
+    }
+}
@@ -0,0 +1,155 @@
+//! Linear recurrences with constant coefficients
+use crate::math::{matrix::Matrix, num::Num};
+use alloc::{vec, vec::Vec};
+
+/// Computes the `n`th Fibonacci number, with `F(0) == 0` and `F(1) == 1`, in
+/// `O(log(n))` arithmetic operations, using the identity
+///
+/// ```text
+/// [[1, 1], [1, 0]]^n == [[F(n + 1), F(n)], [F(n), F(n - 1)]]
+/// ```
+///
+/// Being generic, it can compute Fibonacci numbers in any numeric type, for
+/// instance modulo a prime with `Zmod`. Notice that `F(n + 1)` is computed
+/// along the way, so fixed width integer types overflow one term earlier than
+/// strictly necessary.
+///
+/// Example:
+/// ```
+/// use ralg::math::{modular::Zmod, recurrence::fibonacci};
+///
+/// assert_eq!(fibonacci::<u64>(10), 55);
+/// assert_eq!(fibonacci::<u128>(150), 9_969_216_677_189_303_386_214_405_760_200);
+/// assert_eq!(fibonacci::<Zmod<1_000_000_007>>(1_000_000).value(), 918_091_266);
+/// ```
+pub fn fibonacci<T: Num + Copy>(n: u64) -> T {
+    let q = Matrix::from_rows(vec![
+        vec![T::one(), T::one()],
+        vec![T::one(), T::zero()],
+    ]);
+    q.pow(n)[(0, 1)]
+}
+
+/// Linear recurrence `a(n) == c[0] a(n - 1) + c[1] a(n - 2) + ... +
+/// c[k - 1] a(n - k)` of order `k` with constant coefficients, determined by
+/// its coefficients and its first `k` terms.
+///
+/// The `n`th term is found by raising the `k x k` companion matrix of the
+/// recurrence to the power `n - k + 1`, taking time `O(k^3 log(n))`.
+///
+/// Example:
+/// ```
+/// use ralg::math::recurrence::LinearRecurrence;
+///
+/// // Tribonacci numbers: a(n) = a(n - 1) + a(n - 2) + a(n - 3)
+/// let trib = LinearRecurrence::new(vec![1, 1, 1], vec![0, 0, 1]);
+/// assert_eq!(trib.term(10), 81);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearRecurrence<T: Num + Copy> {
+    coeffs: Vec<T>,
+    initial: Vec<T>,
+}
+
+impl<T: Num + Copy> LinearRecurrence<T> {
+    /// Creates the recurrence with coefficients `coeffs` and first terms
+    /// `initial`. Panics if they have different lengths.
+    pub fn new(coeffs: Vec<T>, initial: Vec<T>) -> Self {
+        assert_eq!(
+            coeffs.len(),
+            initial.len(),
+            "a recurrence of order k needs k initial terms"
+        );
+        LinearRecurrence { coeffs, initial }
+    }
+
+    /// Order of the recurrence, that is, the number of previous terms each
+    /// term depends on.
+    pub fn order(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// Computes the `n`th term of the recurrence, starting from `a(0)`. A
+    /// recurrence of order zero is identically zero.
+    pub fn term(&self, n: u64) -> T {
+        let k = self.order();
+        if k == 0 {
+            return T::zero();
+        }
+        if n < k as u64 {
+            return self.initial[n as usize];
+        }
+
+        // Companion matrix, taking `(a(m - 1), ..., a(m - k))` to
+        // `(a(m), ..., a(m - k + 1))`
+        let mut c = Matrix::zeros(k, k);
+        for (j, &cj) in self.coeffs.iter().enumerate() {
+            c[(0, j)] = cj;
+        }
+        for i in 1..k {
+            c[(i, i - 1)] = T::one();
+        }
+
+        let p = c.pow(n - k as u64 + 1);
+        (0..k).fold(T::zero(), |acc, j| {
+            acc + p[(0, j)] * self.initial[k - 1 - j]
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::modular::Zmod;
+
+    #[test]
+    fn fibonacci_numbers() {
+        let mut fs = vec![0_u64, 1];
+        for i in 2..=92 {
+            fs.push(fs[i - 1] + fs[i - 2]);
+        }
+        for (n, &f) in fs.iter().enumerate() {
+            assert_eq!(fibonacci::<u64>(n as u64), f);
+        }
+
+        // Pisano period modulo 10 is 60
+        type Z10 = Zmod<10>;
+        for n in 0..60 {
+            assert_eq!(
+                fibonacci::<Z10>(n),
+                fibonacci::<Z10>(n + 60 * 1_000_000_007)
+            );
+        }
+    }
+
+    #[test]
+    fn recurrences() {
+        // Fibonacci as a recurrence agrees with `fibonacci`
+        let fib = LinearRecurrence::new(vec![1_u64, 1], vec![0, 1]);
+        for n in 0..90 {
+            assert_eq!(fib.term(n), fibonacci(n));
+        }
+
+        // a(n) = 2 a(n - 1) - a(n - 2) + 0 a(n - 3) gives an arithmetic
+        // progression
+        let arith = LinearRecurrence::new(vec![2_i64, -1, 0], vec![5, 8, 11]);
+        for n in 0..50 {
+            assert_eq!(arith.term(n), 5 + 3 * n as i64);
+        }
+
+        // Powers of 3, as a recurrence of order one
+        let pow3 = LinearRecurrence::new(vec![3_u64], vec![1]);
+        assert_eq!(pow3.term(20), 3_u64.pow(20));
+
+        let empty: LinearRecurrence<i32> =
+            LinearRecurrence::new(vec![], vec![]);
+        assert_eq!(empty.order(), 0);
+        assert_eq!(empty.term(7), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatched_lengths() {
+        LinearRecurrence::new(vec![1, 1], vec![1]);
+    }
+}
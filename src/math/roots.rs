@@ -0,0 +1,423 @@
+//! Roots of real functions
+//!
+//! Every method stops once the root is known within an absolute tolerance
+//! `tol`, or fails after `max_iter` iterations. Bracketing methods need an
+//! interval where the function changes sign, and always converge for a
+//! continuous function, while Newton's method starts from a single guess and
+//! converges much faster from a good one.
+use crate::math::diff::derivative;
+use core::{error::Error, fmt};
+
+/// Default tolerance for the methods of this module.
+pub const DEFAULT_TOLERANCE: f64 = 1.0e-12;
+
+/// Default maximum number of iterations for the methods of this module.
+pub const DEFAULT_MAX_ITER: usize = 100;
+
+/// Error returned when a root cannot be found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RootError {
+    /// The function has the same sign, given by `fa` and `fb`, at both ends
+    /// of the interval `[a, b]`.
+    NotBracketed { a: f64, b: f64, fa: f64, fb: f64 },
+
+    /// The function is NaN or infinite at the given point.
+    NotFinite(f64),
+
+    /// The derivative is zero or not finite at the given point, so that
+    /// Newton's method cannot take a step.
+    BadDerivative(f64),
+
+    /// The tolerance is negative or NaN.
+    InvalidTolerance(f64),
+
+    /// No root was found within the tolerance after `iterations` iterations,
+    /// the last approximation being `last`.
+    NoConvergence { iterations: usize, last: f64 },
+}
+
+impl fmt::Display for RootError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotBracketed { a, b, fa, fb } => write!(
+                f,
+                "no sign change on [{}, {}], with values {} and {}",
+                a, b, fa, fb
+            ),
+            Self::NotFinite(x) => write!(f, "function is not finite at {}", x),
+            Self::BadDerivative(x) => {
+                write!(f, "derivative is zero or not finite at {}", x)
+            }
+            Self::InvalidTolerance(tol) => {
+                write!(f, "invalid tolerance `{}`", tol)
+            }
+            Self::NoConvergence { iterations, last } => write!(
+                f,
+                "no convergence after {} iterations, last approximation {}",
+                iterations, last
+            ),
+        }
+    }
+}
+
+impl Error for RootError {}
+
+/// Evaluates `f` at `x`, failing if the value is not finite.
+fn eval<F: Fn(f64) -> f64>(f: &F, x: f64) -> Result<f64, RootError> {
+    let y = f(x);
+    if y.is_finite() {
+        Ok(y)
+    } else {
+        Err(RootError::NotFinite(x))
+    }
+}
+
+fn check_tolerance(tol: f64) -> Result<(), RootError> {
+    if tol >= 0.0 {
+        Ok(())
+    } else {
+        Err(RootError::InvalidTolerance(tol))
+    }
+}
+
+/// Outcome of checking the ends of an interval.
+enum Bracket {
+    /// The function vanishes at an end.
+    Root(f64),
+    /// The function changes sign, with these values at the ends.
+    Values(f64, f64),
+}
+
+fn bracket<F: Fn(f64) -> f64>(
+    f: &F,
+    a: f64,
+    b: f64,
+) -> Result<Bracket, RootError> {
+    let (fa, fb) = (eval(f, a)?, eval(f, b)?);
+    if fa == 0.0 {
+        Ok(Bracket::Root(a))
+    } else if fb == 0.0 {
+        Ok(Bracket::Root(b))
+    } else if (fa > 0.0) == (fb > 0.0) {
+        Err(RootError::NotBracketed { a, b, fa, fb })
+    } else {
+        Ok(Bracket::Values(fa, fb))
+    }
+}
+
+/// Root of `f` in `[a, b]` by bisection, halving the interval at each
+/// iteration while keeping a sign change in it. Converges linearly, but
+/// only needs `f` to be continuous.
+///
+/// Example:
+/// ```
+/// use ralg::math::roots::bisection;
+///
+/// let root = bisection(|x| x * x - 2.0, 0.0, 2.0, 1e-12, 100).unwrap();
+/// assert!((root - std::f64::consts::SQRT_2).abs() < 1e-12);
+/// ```
+pub fn bisection<F: Fn(f64) -> f64>(
+    f: F,
+    mut a: f64,
+    mut b: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Result<f64, RootError> {
+    check_tolerance(tol)?;
+    let mut fa = match bracket(&f, a, b)? {
+        Bracket::Root(root) => return Ok(root),
+        Bracket::Values(fa, _) => fa,
+    };
+    for _ in 0..max_iter {
+        let mid = a + (b - a) / 2.0;
+        if (b - a).abs() / 2.0 <= tol || mid == a || mid == b {
+            return Ok(mid);
+        }
+        let fm = eval(&f, mid)?;
+        if fm == 0.0 {
+            return Ok(mid);
+        }
+        if (fm > 0.0) == (fa > 0.0) {
+            (a, fa) = (mid, fm);
+        } else {
+            b = mid;
+        }
+    }
+    Err(RootError::NoConvergence {
+        iterations: max_iter,
+        last: a + (b - a) / 2.0,
+    })
+}
+
+/// Root of `f` by the Newton-Raphson method from the guess `x0`, where `df`
+/// is the derivative of `f`. Stops once a step is at most `tol`. Converges
+/// quadratically near a simple root, but may diverge or cycle from a bad
+/// guess.
+///
+/// Example:
+/// ```
+/// use ralg::math::roots::newton;
+///
+/// let root = newton(|x| x * x * x - 2.0, |x| 3.0 * x * x, 1.0, 1e-14, 50);
+/// assert!((root.unwrap() - 1.2599210498948732).abs() < 1e-14);
+/// ```
+pub fn newton<F, D>(
+    f: F,
+    df: D,
+    x0: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Result<f64, RootError>
+where
+    F: Fn(f64) -> f64,
+    D: Fn(f64) -> f64,
+{
+    check_tolerance(tol)?;
+    let mut x = x0;
+    for _ in 0..max_iter {
+        let y = eval(&f, x)?;
+        if y == 0.0 {
+            return Ok(x);
+        }
+        let slope = df(x);
+        if slope == 0.0 || !slope.is_finite() {
+            return Err(RootError::BadDerivative(x));
+        }
+        let step = y / slope;
+        x -= step;
+        if step.abs() <= tol {
+            return Ok(x);
+        }
+    }
+    Err(RootError::NoConvergence {
+        iterations: max_iter,
+        last: x,
+    })
+}
+
+/// Same as `newton`, with the derivative approximated by
+/// `diff::derivative`.
+///
+/// Example:
+/// ```
+/// use ralg::math::roots::newton_numeric;
+///
+/// let root = newton_numeric(|x| x * x - 2.0, 1.0, 1e-12, 50).unwrap();
+/// assert!((root - std::f64::consts::SQRT_2).abs() < 1e-12);
+/// ```
+pub fn newton_numeric<F: Fn(f64) -> f64>(
+    f: F,
+    x0: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Result<f64, RootError> {
+    newton(&f, |x| derivative(&f, x), x0, tol, max_iter)
+}
+
+/// Root of `f` in `[a, b]` by Brent's method, which combines inverse
+/// quadratic interpolation and the secant method with bisection steps
+/// whenever those converge too slowly. It is as safe as `bisection`, and
+/// converges superlinearly for smooth functions.
+///
+/// Example:
+/// ```
+/// use ralg::math::roots::brent;
+///
+/// let root = brent(|x| x * x * x - 2.0 * x - 5.0, 2.0, 3.0, 1e-14, 100);
+/// assert!((root.unwrap() - 2.0945514815423265).abs() < 1e-14);
+/// ```
+pub fn brent<F: Fn(f64) -> f64>(
+    f: F,
+    mut a: f64,
+    mut b: f64,
+    tol: f64,
+    max_iter: usize,
+) -> Result<f64, RootError> {
+    check_tolerance(tol)?;
+    let (mut fa, mut fb) = match bracket(&f, a, b)? {
+        Bracket::Root(root) => return Ok(root),
+        Bracket::Values(fa, fb) => (fa, fb),
+    };
+    // The root lies between b, the best approximation, and c, while a is the
+    // previous value of b. The last two steps are d and e.
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+    for _ in 0..max_iter {
+        if (fb > 0.0) == (fc > 0.0) {
+            (c, fc) = (a, fa);
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            (a, fa) = (b, fb);
+            (b, fb) = (c, fc);
+            (c, fc) = (a, fa);
+        }
+        let tol1 = 2.0 * f64::EPSILON * b.abs() + tol / 2.0;
+        let half = (c - b) / 2.0;
+        if half.abs() <= tol1 || fb == 0.0 {
+            return Ok(b);
+        }
+
+        if e.abs() >= tol1 && fa.abs() > fb.abs() {
+            // Interpolate, with the step p / q
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * half * s, 1.0 - s)
+            } else {
+                let (q, r) = (fa / fc, fb / fc);
+                let p = s * (2.0 * half * q * (q - r) - (b - a) * (r - 1.0));
+                (p, (q - 1.0) * (r - 1.0) * (s - 1.0))
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            // Accept the step only if it stays in the bracket and shrinks
+            // faster than bisection would
+            let bound = (3.0 * half * q - (tol1 * q).abs()).min((e * q).abs());
+            if 2.0 * p < bound {
+                e = d;
+                d = p / q;
+            } else {
+                d = half;
+                e = d;
+            }
+        } else {
+            d = half;
+            e = d;
+        }
+
+        (a, fa) = (b, fb);
+        b += if d.abs() > tol1 {
+            d
+        } else if half > 0.0 {
+            tol1
+        } else {
+            -tol1
+        };
+        fb = eval(&f, b)?;
+    }
+    Err(RootError::NoConvergence {
+        iterations: max_iter,
+        last: b,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::math::poly::Polynomial;
+    use crate::random::Pcg32;
+    use alloc::{vec, vec::Vec};
+
+    const CUBIC_ROOT: f64 = 2.0945514815423265;
+
+    fn cubic(x: f64) -> f64 {
+        x * x * x - 2.0 * x - 5.0
+    }
+
+    #[test]
+    fn simple_roots() {
+        let tol = DEFAULT_TOLERANCE;
+        let max_iter = DEFAULT_MAX_ITER;
+        let b = bisection(cubic, 2.0, 3.0, tol, max_iter).unwrap();
+        assert!((b - CUBIC_ROOT).abs() <= tol);
+        let n = newton(cubic, |x| 3.0 * x * x - 2.0, 3.0, tol, max_iter);
+        assert!((n.unwrap() - CUBIC_ROOT).abs() <= tol);
+        let n = newton_numeric(cubic, 3.0, tol, max_iter).unwrap();
+        assert!((n - CUBIC_ROOT).abs() <= tol);
+        let r = brent(cubic, 3.0, 2.0, tol, max_iter).unwrap();
+        assert!((r - CUBIC_ROOT).abs() <= tol);
+
+        // Roots at the ends, and a zero tolerance down to the last bit
+        assert_eq!(brent(|x| x - 1.0, 1.0, 5.0, tol, max_iter), Ok(1.0));
+        assert_eq!(bisection(|x| x - 5.0, 1.0, 5.0, tol, max_iter), Ok(5.0));
+        let b = bisection(cubic, 2.0, 3.0, 0.0, 200).unwrap();
+        assert!((b - CUBIC_ROOT).abs() <= 4.0 * f64::EPSILON);
+    }
+
+    #[test]
+    fn brent_against_bisection() {
+        let mut rng = Pcg32::seed(0x4007);
+        for _ in 0..200 {
+            // Polynomials of odd degree, with their real roots in [-2, 2]
+            let roots: Vec<f64> = (0..rng.gen_range(1..4) * 2 - 1)
+                .map(|_| rng.gen_range(-2.0..2.0))
+                .collect();
+            let p = roots.iter().fold(Polynomial::new(vec![1.0]), |p, &r| {
+                &p * &Polynomial::new(vec![-r, 1.0])
+            });
+            let (a, b) = (-2.5, 2.5);
+            let bis = bisection(|x| p.eval(x), a, b, 1e-13, 200).unwrap();
+            let root = brent(|x| p.eval(x), a, b, 1e-13, 200).unwrap();
+            assert!(p.eval(root).abs() < 1e-9);
+            assert!(roots.iter().any(|r| (r - root).abs() < 1e-6));
+            assert!(roots.iter().any(|r| (r - bis).abs() < 1e-6));
+        }
+    }
+
+    #[test]
+    fn brent_is_fast_on_smooth_functions() {
+        let quartic = |x: f64| x * x * x * x - 2.0;
+        assert!(brent(quartic, 0.0, 4.0, 1e-14, 12).is_ok());
+        assert!(matches!(
+            bisection(quartic, 0.0, 4.0, 1e-14, 12),
+            Err(RootError::NoConvergence { iterations: 12, .. })
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        let e = brent(|x| x * x + 1.0, -1.0, 1.0, 1e-12, 100);
+        assert_eq!(
+            e,
+            Err(RootError::NotBracketed {
+                a: -1.0,
+                b: 1.0,
+                fa: 2.0,
+                fb: 2.0
+            })
+        );
+        // The derivative vanishes at the start
+        let e = newton(|x| x * x - 1.0, |x| 2.0 * x, 0.0, 1e-12, 100);
+        assert_eq!(e, Err(RootError::BadDerivative(0.0)));
+        // Newton's method cycles between 0 and 1
+        let p = |x: f64| x * x * x - 2.0 * x + 2.0;
+        let e = newton(p, |x| 3.0 * x * x - 2.0, 0.0, 1e-12, 10);
+        assert_eq!(
+            e,
+            Err(RootError::NoConvergence {
+                iterations: 10,
+                last: 0.0
+            })
+        );
+        let nan = |x: f64| if x < 0.5 { x - 1.0 } else { f64::NAN };
+        let e = bisection(nan, 0.0, 1.0, 1e-12, 100);
+        assert_eq!(e, Err(RootError::NotFinite(1.0)));
+        let e = brent(cubic, 2.0, 3.0, -1.0, 100);
+        assert_eq!(e, Err(RootError::InvalidTolerance(-1.0)));
+        assert_eq!(
+            RootError::NotFinite(0.5).to_string(),
+            "function is not finite at 0.5"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn transcendental() {
+        let f = |x: f64| x.cos() - x;
+        let root = 0.7390851332151607;
+        let tol = 1e-14;
+        for x in [
+            bisection(f, 0.0, 1.0, tol, 100),
+            brent(f, 0.0, 1.0, tol, 100),
+            newton(f, |x| -x.sin() - 1.0, 0.0, tol, 100),
+            newton_numeric(f, 0.0, tol, 100),
+        ] {
+            assert!((x.unwrap() - root).abs() <= 2.0 * tol);
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Pseudorandom number generation
+//!
+//! A small PCG generator, for randomized algorithms and tests. It is fast and
+//! statistically sound, but not cryptographically secure.
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+/// Multiplier of the underlying linear congruential generator.
+const MULTIPLIER: u64 = 6364136223846793005;
+
+/// Seed used by `Pcg32::new` without a source of entropy.
+#[cfg(not(feature = "std"))]
+const DEFAULT_SEED: u64 = 0x853c49e6748fea9b;
+
+/// Stream used by `Pcg32::seed`, any odd increment being valid.
+const DEFAULT_STREAM: u64 = 0xda3e39cb94b95bdb;
+
+/// PCG-XSH-RR generator, with 64 bits of state and 32-bit outputs: a linear
+/// congruential generator whose state goes through a xorshift and a random
+/// rotation to produce each output.
+///
+/// Example:
+/// ```
+/// use ralg::random::Pcg32;
+///
+/// let mut rng = Pcg32::seed(42);
+/// let die = rng.gen_range(1..7);
+/// assert!((1..7).contains(&die));
+///
+/// // The same seed gives the same sequence
+/// let mut xs: Vec<u32> = (0..10).collect();
+/// let mut ys = xs.clone();
+/// Pcg32::seed(7).shuffle(&mut xs);
+/// Pcg32::seed(7).shuffle(&mut ys);
+/// assert_eq!(xs, ys);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pcg32 {
+    state: u64,
+    /// Increment of the LCG, which must be odd.
+    inc: u64,
+}
+
+impl Pcg32 {
+    /// Generator seeded from the standard library's randomly keyed hasher,
+    /// so that each one gives a different sequence.
+    ///
+    /// Without the `std` feature there is no source of entropy, and every
+    /// generator is seeded the same way.
+    pub fn new() -> Self {
+        #[cfg(feature = "std")]
+        {
+            let mut hasher = RandomState::new().build_hasher();
+            let seed = hasher.finish();
+            hasher.write_u64(seed);
+            Pcg32::seed_with_stream(seed, hasher.finish())
+        }
+        #[cfg(not(feature = "std"))]
+        Pcg32::seed(DEFAULT_SEED)
+    }
+
+    /// Generator giving a fixed sequence for each `seed`.
+    pub fn seed(seed: u64) -> Self {
+        Pcg32::seed_with_stream(seed, DEFAULT_STREAM)
+    }
+
+    /// Generator seeded with `seed`, on one of 2^63 streams: generators on
+    /// different streams give different sequences for the same seed.
+    pub fn seed_with_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Pcg32 {
+            state: 0,
+            inc: (stream << 1) | 1,
+        };
+        rng.step();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.step();
+        rng
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(MULTIPLIER).wrapping_add(self.inc);
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let high = u64::from(self.next_u32());
+        (high << 32) | u64::from(self.next_u32())
+    }
+
+    /// Uniform float in `[0, 1)`, with 53 random bits.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Uniform `u64` in `[0, bound)`, rejecting the outputs which would bias
+    /// the remainder. Assumes `bound > 0`.
+    fn below(&mut self, bound: u64) -> u64 {
+        // Outputs under this threshold are in an incomplete last block
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let x = self.next_u64();
+            if x >= threshold {
+                return x % bound;
+            }
+        }
+    }
+
+    /// Uniform value in the half-open `range`.
+    ///
+    /// Panics if the range is empty.
+    pub fn gen_range<T: SampleUniform>(&mut self, range: Range<T>) -> T {
+        T::sample(self, range)
+    }
+
+    /// Shuffles `xs` uniformly with the Fisher-Yates algorithm: each position,
+    /// from the last, gets swapped with a random one not after it.
+    pub fn shuffle<T>(&mut self, xs: &mut [T]) {
+        for i in (1..xs.len()).rev() {
+            xs.swap(i, self.gen_range(0..i + 1));
+        }
+    }
+
+    /// Uniformly chosen element of `xs`, or `None` if it is empty.
+    pub fn choose<'a, T>(&mut self, xs: &'a [T]) -> Option<&'a T> {
+        if xs.is_empty() {
+            None
+        } else {
+            Some(&xs[self.gen_range(0..xs.len())])
+        }
+    }
+}
+
+impl Default for Pcg32 {
+    fn default() -> Self {
+        Pcg32::new()
+    }
+}
+
+/// Types whose values can be drawn uniformly from a range by `gen_range`.
+pub trait SampleUniform: Sized {
+    /// Uniform value in `range`, panicking if it is empty.
+    fn sample(rng: &mut Pcg32, range: Range<Self>) -> Self;
+}
+
+/// Implements `SampleUniform` for an integer type, computing offsets in the
+/// unsigned type of the same width.
+macro_rules! sample_int_impl {
+    ($(($t: ty, $u: ty)),*) => ($(
+        impl SampleUniform for $t {
+            fn sample(rng: &mut Pcg32, range: Range<$t>) -> $t {
+                assert!(range.start < range.end, "empty range");
+                let span = (range.end as $u).wrapping_sub(range.start as $u);
+                let offset = rng.below(span as u64);
+                range.start.wrapping_add(offset as $t)
+            }
+        }
+    )*)
+}
+
+sample_int_impl!((u8, u8), (u16, u16), (u32, u32), (u64, u64), (usize, usize));
+sample_int_impl!((i8, u8), (i16, u16), (i32, u32), (i64, u64), (isize, usize));
+
+impl SampleUniform for f64 {
+    fn sample(rng: &mut Pcg32, range: Range<f64>) -> f64 {
+        assert!(range.start < range.end, "empty range");
+        let x = range.start + (range.end - range.start) * rng.next_f64();
+        // Rounding may land on the excluded end
+        if x < range.end {
+            x
+        } else {
+            range.start
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reference_outputs() {
+        // First outputs of the reference implementation, pcg32_srandom(42, 54)
+        let mut rng = Pcg32::seed_with_stream(42, 54);
+        let xs: Vec<u32> = (0..6).map(|_| rng.next_u32()).collect();
+        assert_eq!(
+            xs,
+            [
+                0xa15c02b7, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b,
+                0xcbed606e
+            ]
+        );
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Pcg32::seed(1);
+        let mut counts = [0; 6];
+        for _ in 0..60_000 {
+            counts[rng.gen_range(0..6)] += 1;
+            let x = rng.gen_range(-3_i8..2);
+            assert!((-3..2).contains(&x));
+            assert_eq!(rng.gen_range(i64::MAX - 1..i64::MAX), i64::MAX - 1);
+            let y = rng.gen_range(i64::MIN..i64::MAX);
+            assert!(y < i64::MAX);
+            let f = rng.gen_range(-1.5..0.5);
+            assert!((-1.5..0.5).contains(&f));
+        }
+        // Each face within 5% of its expected count
+        assert!(counts.iter().all(|&c| (9_500..10_500).contains(&c)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_range() {
+        Pcg32::seed(0).gen_range(3_u32..3);
+    }
+
+    #[test]
+    fn shuffle_is_uniform() {
+        let mut rng = Pcg32::seed(2024);
+        // Each of the 6 permutations of 3 elements, by the position of 0 and 1
+        let mut counts = [[0; 3]; 3];
+        for _ in 0..60_000 {
+            let mut xs = [0, 1, 2];
+            rng.shuffle(&mut xs);
+            let at = |v| xs.iter().position(|&x| x == v).unwrap();
+            counts[at(0)][at(1)] += 1;
+        }
+        for (i, row) in counts.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                if i == j {
+                    assert_eq!(c, 0);
+                } else {
+                    assert!((9_500..10_500).contains(&c));
+                }
+            }
+        }
+        assert_eq!(rng.choose::<u8>(&[]), None);
+        assert_eq!(rng.choose(&[5]), Some(&5));
+    }
+}
@@ -0,0 +1,51 @@
+use super::Sorter;
+use crate::heap::binary::{heapify_by, sift_down_by};
+use core::cmp::Ordering;
+
+pub struct HeapSort;
+
+impl Sorter for HeapSort {
+    /// Builds a max-heap in place and repeatedly moves its root to the end of
+    /// the unsorted part of `xs`. Takes time `O(n log(n))` and no extra space.
+    fn sort_by<T, F: FnMut(&T, &T) -> Ordering>(xs: &mut [T], mut cmp: F) {
+        let mut precedes = |a: &T, b: &T| cmp(a, b) == Ordering::Greater;
+        heapify_by(xs, &mut precedes);
+        for end in (1..xs.len()).rev() {
+            xs.swap(0, end);
+            sift_down_by(&mut xs[..end], 0, &mut precedes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sorting;
+
+    #[test]
+    fn sort() {
+        let mut xs = vec![123, 91847, 1, 0, -1, -450, 800, 555];
+        HeapSort::sort(&mut xs);
+        assert!(sorting::is_sorted(&xs));
+
+        let mut xs: Vec<i32> = vec![];
+        HeapSort::sort(&mut xs);
+        assert!(xs.is_empty());
+
+        let mut xs: Vec<i32> = (0..100).rev().collect();
+        HeapSort::sort(&mut xs);
+        assert_eq!(xs, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sort_by() {
+        let mut xs: Vec<String> =
+            ["pear", "fig", "banana", "kiwi"].map(String::from).to_vec();
+        HeapSort::sort_by(&mut xs, |a, b| b.cmp(a));
+        assert_eq!(xs, ["pear", "kiwi", "fig", "banana"]);
+
+        HeapSort::sort_by_key(&mut xs, |s| s.len());
+        assert_eq!(xs[0], "fig");
+        assert_eq!(xs[3], "banana");
+    }
+}
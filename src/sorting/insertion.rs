@@ -1,13 +1,13 @@
-use super::Sorter;
-use std::cmp::PartialOrd;
+use super::{Sorter, StableSorter};
+use core::cmp::Ordering;
 
 pub struct InsertionSort;
 
 impl Sorter for InsertionSort {
-    fn sort<T: PartialOrd + Copy>(xs: &mut [T]) {
+    fn sort_by<T, F: FnMut(&T, &T) -> Ordering>(xs: &mut [T], mut cmp: F) {
         for not_sorted in 1..xs.len() {
             let mut i = not_sorted;
-            while i > 0 && xs[i - 1] > xs[i] {
+            while i > 0 && cmp(&xs[i - 1], &xs[i]) == Ordering::Greater {
                 xs.swap(i - 1, i);
                 i -= 1;
             }
@@ -15,6 +15,8 @@ impl Sorter for InsertionSort {
     }
 }
 
+impl StableSorter for InsertionSort {}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -26,4 +28,15 @@ mod test {
         InsertionSort::sort(&mut xs);
         assert!(sorting::is_sorted(&xs));
     }
+
+    #[test]
+    fn sort_by() {
+        let mut xs: Vec<String> =
+            ["pear", "fig", "banana", "kiwi"].map(String::from).to_vec();
+        InsertionSort::sort_by(&mut xs, |a, b| b.cmp(a));
+        assert_eq!(xs, ["pear", "kiwi", "fig", "banana"]);
+
+        InsertionSort::sort_by_key(&mut xs, |s| s.len());
+        assert_eq!(xs, ["fig", "pear", "kiwi", "banana"]);
+    }
 }
@@ -1,20 +1,58 @@
-use super::Sorter;
+use super::{Sorter, StableSorter};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 
 pub struct MergeSort;
 
 impl Sorter for MergeSort {
-    fn sort<T: PartialOrd + Copy>(xs: &mut [T]) {
-        merge_sort(xs, 0, xs.len() - 1)
+    /// Since merging requires moving elements out of `xs`, we sort the
+    /// indices of `xs` instead and then rearrange `xs` accordingly, so that no
+    /// bounds are needed on `T` and no element is cloned.
+    fn sort_by<T, F: FnMut(&T, &T) -> Ordering>(xs: &mut [T], mut cmp: F) {
+        if xs.len() < 2 {
+            return;
+        }
+
+        let mut idx: Vec<usize> = (0..xs.len()).collect();
+        let top = idx.len() - 1;
+        merge_sort(&mut idx, 0, top, &mut |&i, &j| cmp(&xs[i], &xs[j]));
+        permute(xs, idx);
     }
 }
 
+impl StableSorter for MergeSort {}
+
 /// Recursively sort: divide-and-conquer
-fn merge_sort<T: PartialOrd + Copy>(xs: &mut [T], low: usize, top: usize) {
+pub(super) fn merge_sort<T: Clone, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    low: usize,
+    top: usize,
+    cmp: &mut F,
+) {
     if low < top {
         let mid = (low + top) / 2;
-        merge_sort(xs, low, mid);
-        merge_sort(xs, mid + 1, top);
-        merge(xs, low, mid, top);
+        merge_sort(xs, low, mid, cmp);
+        merge_sort(xs, mid + 1, top, cmp);
+        merge(xs, low, mid, top, cmp);
+    }
+}
+
+/// Rearranges `xs` so that its `k`th element becomes the element previously
+/// found at `xs[idx[k]]`. Each cycle of the permutation `idx` is walked once,
+/// so this takes time `O(n)`.
+pub(super) fn permute<T>(xs: &mut [T], mut idx: Vec<usize>) {
+    for start in 0..xs.len() {
+        let mut cur = start;
+        while idx[cur] != cur {
+            let next = idx[cur];
+            // Mark the position as settled
+            idx[cur] = cur;
+            if next == start {
+                break;
+            }
+            xs.swap(cur, next);
+            cur = next;
+        }
     }
 }
 
@@ -23,40 +61,32 @@ fn merge_sort<T: PartialOrd + Copy>(xs: &mut [T], low: usize, top: usize) {
 /// * `mid`: index of middle element.
 /// * `top`: index of the last element.
 ///
-/// Takes time `O(n)` where `n = top - low + 1`.
-fn merge<T: PartialOrd + Copy>(
+/// Takes time `O(n)` where `n = top - low + 1`. Only the left array is
+/// cloned: the elements of the right one are swapped into slots whose values
+/// already have a copy in the buffer.
+pub(super) fn merge<T: Clone, F: FnMut(&T, &T) -> Ordering>(
     xs: &mut [T],
     low: usize,
     mid: usize,
     top: usize,
+    cmp: &mut F,
 ) {
-    let left = xs[low..=mid].to_vec();
-    let right = xs[(mid + 1)..=top].to_vec();
-
-    // Merge `left` and `right` into `xs`
-    xs[low..=top].iter_mut().fold((0, 0), |(i, j), x| {
-        let in_left = i < left.len();
-        let in_right = j < right.len();
-
-        if in_left && in_right {
-            // Check for the minimum element
-            if left[i] <= right[j] {
-                *x = left[i];
-                (i + 1, j)
-            } else {
-                *x = right[j];
-                (i, j + 1)
-            }
-        } else if in_left {
-            // Dump the remainder of the left array
-            *x = left[i];
-            (i + 1, j)
+    let buffer = xs[low..=mid].to_vec();
+    let mut left = buffer.into_iter().peekable();
+    let mut j = mid + 1;
+    for k in low..=top {
+        // Once the left array is exhausted, the rest of the right one is
+        // already in place
+        let Some(x) = left.peek() else { break };
+        // Taking from the left on ties keeps the merge stable. Since the left
+        // array isn't exhausted, `k < j`
+        if j <= top && cmp(x, &xs[j]) == Ordering::Greater {
+            xs.swap(k, j);
+            j += 1;
         } else {
-            // Dump the remander of the right array
-            *x = right[j];
-            (i, j + 1)
+            xs[k] = left.next().expect("the left array isn't exhausted");
         }
-    });
+    }
 }
 
 #[cfg(test)]
@@ -69,20 +99,47 @@ mod test {
         let mut xs = vec![123, 91847, 1, 0, -1, -450, 800, 555];
         MergeSort::sort(&mut xs);
         assert!(sorting::is_sorted(&xs));
+
+        let mut xs: Vec<i32> = vec![];
+        MergeSort::sort(&mut xs);
+        assert!(xs.is_empty());
     }
 
     #[test]
-    fn merge_arrays() {
-        struct Index {
-            index: usize,
-        }
+    fn sort_by() {
+        let mut xs: Vec<String> =
+            ["pear", "fig", "banana", "kiwi"].map(String::from).to_vec();
+        MergeSort::sort_by(&mut xs, |a, b| b.cmp(a));
+        assert_eq!(xs, ["pear", "kiwi", "fig", "banana"]);
+
+        MergeSort::sort_by_key(&mut xs, |s| s.len());
+        assert_eq!(xs, ["fig", "pear", "kiwi", "banana"]);
+
+        let mut xs: Vec<Vec<u8>> =
+            (0..50u32).map(|x| vec![((x * 37) % 50) as u8]).collect();
+        MergeSort::sort_by(&mut xs, |a, b| a.cmp(b));
+        assert_eq!(xs, (0..50).map(|x| vec![x]).collect::<Vec<_>>());
+    }
 
+    #[test]
+    fn permute() {
+        let mut xs = vec!['a', 'b', 'c', 'd', 'e'];
+        super::permute(&mut xs, vec![3, 0, 4, 1, 2]);
+        assert_eq!(xs, vec!['d', 'a', 'e', 'b', 'c']);
+    }
+
+    #[test]
+    fn merge_arrays() {
         let mut xs = vec![5, 6, 7, 8, 1, 2, 3, 4];
-        super::merge(&mut xs, 0, 3, 7);
+        super::merge(&mut xs, 0, 3, 7, &mut i32::cmp);
         assert_eq!(xs, vec![1, 2, 3, 4, 5, 6, 7, 8]);
 
         let mut xs = vec![13, 11, 5, 6, 7, 8, 1, 2, 3, 4, 90, 21];
-        super::merge(&mut xs, 2, 5, 9);
+        super::merge(&mut xs, 2, 5, 9, &mut i32::cmp);
         assert_eq!(xs, vec![13, 11, 1, 2, 3, 4, 5, 6, 7, 8, 90, 21]);
+
+        let mut xs = ["b", "d", "e", "a", "c", "f"].map(String::from).to_vec();
+        super::merge(&mut xs, 0, 2, 5, &mut String::cmp);
+        assert_eq!(xs, ["a", "b", "c", "d", "e", "f"]);
     }
 }
@@ -1,28 +1,184 @@
-mod insertion;
-mod merge;
-mod search;
-mod tim;
+pub mod heap;
+pub mod insertion;
+pub mod merge;
+#[cfg(feature = "std")]
+pub mod parallel;
+pub mod quick;
+pub mod search;
+pub mod select;
+pub mod tim;
 
-use std::cmp::PartialOrd;
+use crate::math::num::OrderedFloat;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, PartialOrd};
 
-trait Sorter {
-    fn sort<T: PartialOrd + Copy>(xs: &mut [T]);
+/// Common interface of the sorting algorithms in this module.
+pub trait Sorter {
+    /// Sorts `xs` in ascending order.
+    fn sort<T: PartialOrd>(xs: &mut [T]) {
+        Self::sort_by(xs, partial_cmp)
+    }
+
+    /// Sorts `xs` in ascending order with respect to the comparator `cmp`.
+    /// No bounds are imposed on `T`, so that types which are neither `Copy`
+    /// nor `PartialOrd` can also be sorted.
+    fn sort_by<T, F: FnMut(&T, &T) -> Ordering>(xs: &mut [T], cmp: F);
+
+    /// Sorts `xs` in ascending order with respect to the keys extracted by
+    /// `key`.
+    fn sort_by_key<T, K: PartialOrd, F: FnMut(&T) -> K>(
+        xs: &mut [T],
+        mut key: F,
+    ) {
+        Self::sort_by(xs, |a, b| partial_cmp(&key(a), &key(b)))
+    }
 }
 
-/// Checks if array `xs` is sorted
-pub fn is_sorted(xs: &[impl PartialOrd]) -> bool {
-    let mut last = &xs[0];
-    for next in xs {
-        if last > next {
-            return false;
-        }
-        last = next;
+/// Marker trait for sorting algorithms which are stable, that is, elements
+/// comparing as equal keep their original relative order after sorting.
+pub trait StableSorter: Sorter {}
+
+/// Compares `a` and `b`, regarding incomparable elements as equal.
+pub(crate) fn partial_cmp<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Checks if array `xs` is sorted, that is, no element is greater than the
+/// one following it. Empty and single element arrays are sorted.
+pub fn is_sorted<T: PartialOrd>(xs: &[T]) -> bool {
+    is_sorted_by(xs, partial_cmp)
+}
+
+/// Checks if `xs` is sorted with respect to the comparator `cmp`, as would be
+/// left by `Sorter::sort_by`.
+///
+/// Example:
+/// ```
+/// use ralg::sorting::is_sorted_by;
+///
+/// assert!(is_sorted_by(&[5, 3, 3, 1], |a, b| b.cmp(a)));
+/// assert!(!is_sorted_by(&[1, 2], |a, b| b.cmp(a)));
+/// ```
+pub fn is_sorted_by<T, F: FnMut(&T, &T) -> Ordering>(
+    xs: &[T],
+    mut cmp: F,
+) -> bool {
+    xs.windows(2)
+        .all(|w| cmp(&w[0], &w[1]) != Ordering::Greater)
+}
+
+/// Checks if `xs` is sorted with respect to the keys extracted by `key`, as
+/// would be left by `Sorter::sort_by_key`.
+///
+/// Example:
+/// ```
+/// use ralg::sorting::is_sorted_by_key;
+///
+/// assert!(is_sorted_by_key(&["c", "ab", "abc"], |s| s.len()));
+/// ```
+pub fn is_sorted_by_key<T, K: PartialOrd, F: FnMut(&T) -> K>(
+    xs: &[T],
+    mut key: F,
+) -> bool {
+    is_sorted_by(xs, |a, b| partial_cmp(&key(a), &key(b)))
+}
+
+/// Checks if every element of `xs` is less than the one following it, hence
+/// that `xs` is sorted and has no repeated elements.
+///
+/// Example:
+/// ```
+/// use ralg::sorting::is_strictly_sorted;
+///
+/// assert!(is_strictly_sorted(&[1, 2, 4]));
+/// assert!(!is_strictly_sorted(&[1, 2, 2]));
+/// ```
+pub fn is_strictly_sorted<T: PartialOrd>(xs: &[T]) -> bool {
+    xs.windows(2).all(|w| w[0] < w[1])
+}
+
+/// Sorts the floats `xs` in ascending order with the algorithm `S`, under the
+/// total order of `OrderedFloat`: NaNs are put last, instead of being treated
+/// as equal to every value, which leaves an arbitrary order.
+///
+/// Example:
+/// ```
+/// use ralg::sorting::{quick::QuickSort, sort_floats};
+///
+/// let mut xs = [0.5, f32::NAN, -2.0, 1.0];
+/// sort_floats::<QuickSort, _>(&mut xs);
+/// assert_eq!(xs[..3], [-2.0, 0.5, 1.0]);
+/// assert!(xs[3].is_nan());
+/// ```
+pub fn sort_floats<S: Sorter, T: Copy>(xs: &mut [T])
+where
+    OrderedFloat<T>: Ord,
+{
+    S::sort_by_key(xs, |&x| OrderedFloat(x))
+}
+
+/// Sorts `xs` in ascending order of the float keys extracted by `key`, with
+/// the algorithm `S`, putting the elements with NaN keys last.
+pub fn sort_by_float_key<S, T, K, F>(xs: &mut [T], mut key: F)
+where
+    S: Sorter,
+    K: Copy,
+    OrderedFloat<K>: Ord,
+    F: FnMut(&T) -> K,
+{
+    S::sort_by_key(xs, |x| OrderedFloat(key(x)))
+}
+
+/// Key tagged with its position in the original input, used to check the
+/// stability of sorting algorithms. Comparisons only take the `key` into
+/// account.
+#[derive(Clone, Copy, Debug)]
+pub struct Indexed<K> {
+    pub key: K,
+    pub index: usize,
+}
+
+impl<K: PartialEq> PartialEq for Indexed<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: PartialOrd> PartialOrd for Indexed<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
     }
-    true
+}
+
+/// Tags each element of `xs` with its index.
+pub fn indexed<K: Clone>(xs: &[K]) -> Vec<Indexed<K>> {
+    xs.iter()
+        .enumerate()
+        .map(|(index, key)| Indexed {
+            key: key.clone(),
+            index,
+        })
+        .collect()
+}
+
+/// Checks if `xs` is sorted by key and whether elements with equal keys
+/// retained the order of their original indices.
+pub fn is_stable_sorted<K: PartialOrd>(xs: &[Indexed<K>]) -> bool {
+    xs.windows(2)
+        .all(|w| match w[0].key.partial_cmp(&w[1].key) {
+            Some(Ordering::Less) => true,
+            Some(Ordering::Equal) => w[0].index < w[1].index,
+            _ => false,
+        })
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{heap::HeapSort, quick::QuickSort};
+    use super::{indexed, insertion::InsertionSort, merge::MergeSort};
+    use super::{tim::TimSort, Indexed, Sorter, StableSorter};
+    use crate::random::Pcg32;
+
     #[test]
     fn is_sorted() {
         let v1 = vec![1, 2, 3, 4, 5, 5];
@@ -30,5 +186,167 @@ mod tests {
 
         let v2 = vec![0, 3, 9, 8, 10];
         assert!(!super::is_sorted(&v2));
+
+        assert!(super::is_sorted::<i32>(&[]));
+        assert!(super::is_sorted(&[7]));
+        assert!(super::is_strictly_sorted::<i32>(&[]));
+        assert!(!super::is_strictly_sorted(&v1));
+        assert!(super::is_strictly_sorted(&v1[..5]));
+        // Incomparable neighbours are not in the wrong order, but neither is
+        // one less than the other
+        assert!(super::is_sorted(&[1.0, f64::NAN, 0.0]));
+        assert!(!super::is_strictly_sorted(&[1.0, f64::NAN, 2.0]));
+
+        let words = ["kiwi", "fig", "banana"];
+        assert!(super::is_sorted_by(&words, |a, b| b.cmp(a)));
+        assert!(!super::is_sorted_by_key(&words, |w| w.len()));
+        assert!(super::is_sorted_by_key(&words[1..], |w| w.len()));
+    }
+
+    #[test]
+    fn is_stable_sorted() {
+        let xs = vec![
+            Indexed { key: 1, index: 3 },
+            Indexed { key: 2, index: 0 },
+            Indexed { key: 2, index: 2 },
+        ];
+        assert!(super::is_stable_sorted(&xs));
+
+        let xs = vec![
+            Indexed { key: 1, index: 3 },
+            Indexed { key: 2, index: 2 },
+            Indexed { key: 2, index: 0 },
+        ];
+        assert!(!super::is_stable_sorted(&xs));
+
+        assert!(super::is_stable_sorted::<i32>(&[]));
+    }
+
+    fn assert_stable<S: StableSorter>() {
+        // Many repeated keys, in no particular order
+        let keys: Vec<i32> = (0..200).map(|x| (x * 17) % 7).collect();
+        let mut xs = indexed(&keys);
+        S::sort(&mut xs);
+        assert!(super::is_stable_sorted(&xs));
+
+        // Reversing a stable descending sort leaves equal keys with their
+        // indices in decreasing order
+        let mut xs = indexed(&keys);
+        S::sort_by(&mut xs, |a, b| b.key.cmp(&a.key));
+        xs.reverse();
+        assert!(!super::is_stable_sorted(&xs));
+
+        let words = ["bb", "a", "cc", "d", "ee", "f", "g", "hh"];
+        let mut xs = indexed(&words);
+        S::sort_by_key(&mut xs, |x| x.key.len());
+        assert!(xs.windows(2).all(
+            |w| w[0].key.len() < w[1].key.len() || w[0].index < w[1].index
+        ));
+    }
+
+    #[test]
+    fn stable_sorters() {
+        assert_stable::<MergeSort>();
+        assert_stable::<InsertionSort>();
+        assert_stable::<TimSort>();
+    }
+
+    /// Sorts random inputs, of random lengths and ranges of values, checking
+    /// the result against the standard library.
+    fn assert_sorts_random<S: Sorter>(rng: &mut Pcg32) {
+        for _ in 0..200 {
+            let n = rng.gen_range(0..300);
+            let range = rng.gen_range(1..1000);
+            let xs: Vec<i32> =
+                (0..n).map(|_| rng.gen_range(0..range)).collect();
+            let mut expected = xs.clone();
+            expected.sort();
+
+            let mut ys = xs.clone();
+            S::sort(&mut ys);
+            assert_eq!(ys, expected);
+
+            // Shuffling the sorted values gives back the same multiset
+            rng.shuffle(&mut ys);
+            S::sort_by(&mut ys, |a, b| b.cmp(a));
+            expected.reverse();
+            assert_eq!(ys, expected);
+        }
+    }
+
+    /// Sorts owned strings, which are neither `Copy` nor cheap to clone.
+    fn assert_sorts_strings<S: Sorter>() {
+        let mut words: Vec<String> =
+            "the quick brown fox jumps over the lazy dog"
+                .split(' ')
+                .map(String::from)
+                .collect();
+        let mut expected = words.clone();
+        expected.sort();
+        S::sort(&mut words);
+        assert_eq!(words, expected);
+    }
+
+    #[test]
+    fn non_copy() {
+        assert_sorts_strings::<HeapSort>();
+        assert_sorts_strings::<InsertionSort>();
+        assert_sorts_strings::<MergeSort>();
+        assert_sorts_strings::<QuickSort>();
+        assert_sorts_strings::<TimSort>();
+    }
+
+    #[test]
+    fn floats_with_nans() {
+        let nan = f64::NAN;
+        let xs = [3.0, nan, -0.5, f64::NEG_INFINITY, nan, 0.0, 2.0, -7.25];
+        let check = |ys: &[f64]| {
+            assert!(super::is_sorted(&ys[..6]));
+            assert!(ys[6..].iter().all(|y| y.is_nan()));
+        };
+        let mut ys = xs;
+        super::sort_floats::<HeapSort, _>(&mut ys);
+        check(&ys);
+        let mut ys = xs;
+        super::sort_floats::<MergeSort, _>(&mut ys);
+        check(&ys);
+        let mut ys = xs;
+        super::sort_floats::<QuickSort, _>(&mut ys);
+        check(&ys);
+        let mut ys = xs;
+        super::sort_floats::<TimSort, _>(&mut ys);
+        check(&ys);
+
+        let mut points = [(1, 2.5_f32), (2, f32::NAN), (3, -1.0), (4, 2.5)];
+        super::sort_by_float_key::<InsertionSort, _, _, _>(&mut points, |p| {
+            p.1
+        });
+        let order: Vec<i32> = points.iter().map(|p| p.0).collect();
+        assert_eq!(order, [3, 1, 4, 2]);
+    }
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = Pcg32::seed(0x5eed);
+        assert_sorts_random::<HeapSort>(&mut rng);
+        assert_sorts_random::<InsertionSort>(&mut rng);
+        assert_sorts_random::<MergeSort>(&mut rng);
+        assert_sorts_random::<QuickSort>(&mut rng);
+        assert_sorts_random::<TimSort>(&mut rng);
+
+        // Stability on random keys with many repetitions
+        for _ in 0..50 {
+            let n = rng.gen_range(0..500);
+            let keys: Vec<u8> = (0..n).map(|_| rng.gen_range(0..8)).collect();
+            let mut xs = indexed(&keys);
+            MergeSort::sort(&mut xs);
+            assert!(super::is_stable_sorted(&xs));
+            let mut xs = indexed(&keys);
+            InsertionSort::sort(&mut xs);
+            assert!(super::is_stable_sorted(&xs));
+            let mut xs = indexed(&keys);
+            TimSort::sort(&mut xs);
+            assert!(super::is_stable_sorted(&xs));
+        }
     }
 }
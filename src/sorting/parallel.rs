@@ -0,0 +1,122 @@
+use super::merge::{merge, merge_sort};
+use super::partial_cmp;
+use core::cmp::Ordering;
+use core::num::NonZeroUsize;
+use std::thread;
+
+/// Slices shorter than this are sorted sequentially, since spawning a thread
+/// costs more than sorting them.
+const SEQUENTIAL_THRESHOLD: usize = 4096;
+
+/// Merge sort whose recursive calls run in parallel using scoped threads.
+///
+/// The slice is halved recursively and each half is handed to its own thread
+/// until the requested number of threads is exhausted, after which each part
+/// is sorted sequentially. Parts are then merged back as the recursion
+/// unwinds.
+pub struct ParallelMergeSort;
+
+impl ParallelMergeSort {
+    /// Sorts `xs` in ascending order using as many threads as the available
+    /// parallelism of the machine.
+    pub fn sort<T: PartialOrd + Clone + Send>(xs: &mut [T]) {
+        Self::sort_with_threads(xs, available_threads())
+    }
+
+    /// Sorts `xs` in ascending order using at most `threads` threads.
+    pub fn sort_with_threads<T: PartialOrd + Clone + Send>(
+        xs: &mut [T],
+        threads: usize,
+    ) {
+        Self::sort_by(xs, threads, partial_cmp)
+    }
+
+    /// Sorts `xs` with respect to the comparator `cmp` using at most `threads`
+    /// threads. The comparator is shared among the threads, hence the `Sync`
+    /// bound.
+    pub fn sort_by<T, F>(xs: &mut [T], threads: usize, cmp: F)
+    where
+        T: Clone + Send,
+        F: Fn(&T, &T) -> Ordering + Sync,
+    {
+        par_merge_sort(xs, threads.max(1), &cmp);
+    }
+}
+
+fn par_merge_sort<T, F>(xs: &mut [T], threads: usize, cmp: &F)
+where
+    T: Clone + Send,
+    F: Fn(&T, &T) -> Ordering + Sync,
+{
+    let n = xs.len();
+    if n < 2 {
+        return;
+    }
+
+    if threads == 1 || n < SEQUENTIAL_THRESHOLD {
+        merge_sort(xs, 0, n - 1, &mut &*cmp);
+        return;
+    }
+
+    let mid = n / 2;
+    let (left, right) = xs.split_at_mut(mid);
+    let left_threads = threads / 2;
+    thread::scope(|s| {
+        s.spawn(|| par_merge_sort(left, left_threads, cmp));
+        par_merge_sort(right, threads - left_threads, cmp);
+    });
+    merge(xs, 0, mid - 1, n - 1, &mut &*cmp);
+}
+
+/// Number of threads the machine can run in parallel, defaulting to one if it
+/// cannot be determined.
+fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sorting;
+
+    /// Deterministic scrambled sequence of `n` integers.
+    fn scrambled(n: usize) -> Vec<i64> {
+        (0..n as i64).map(|x| (x * 7919) % 10007 - 5000).collect()
+    }
+
+    #[test]
+    fn sort() {
+        let mut xs = vec![123, 91847, 1, 0, -1, -450, 800, 555];
+        ParallelMergeSort::sort(&mut xs);
+        assert!(sorting::is_sorted(&xs));
+
+        let mut xs: Vec<i32> = vec![];
+        ParallelMergeSort::sort(&mut xs);
+        assert!(xs.is_empty());
+
+        let mut xs: Vec<String> =
+            (0..5000).map(|x| (x * 7919 % 5000).to_string()).collect();
+        ParallelMergeSort::sort_with_threads(&mut xs, 4);
+        assert!(sorting::is_sorted(&xs));
+    }
+
+    #[test]
+    fn sort_with_threads() {
+        let xs = scrambled(50_000);
+        let mut expected = xs.clone();
+        expected.sort();
+
+        for threads in [0, 1, 2, 3, 4, 7, 16] {
+            let mut ys = xs.clone();
+            ParallelMergeSort::sort_with_threads(&mut ys, threads);
+            assert_eq!(ys, expected);
+        }
+    }
+
+    #[test]
+    fn sort_by() {
+        let mut xs = scrambled(20_000);
+        ParallelMergeSort::sort_by(&mut xs, 4, |a, b| b.cmp(a));
+        assert!(xs.windows(2).all(|w| w[0] >= w[1]));
+    }
+}
@@ -0,0 +1,215 @@
+use super::{partial_cmp, select::partition3, Sorter};
+use crate::random::Pcg32;
+use core::cmp::Ordering;
+
+/// Strategy used by `QuickSort` to choose the pivot of each partition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pivot {
+    /// First element of the slice. Degrades to O(n^2) on sorted inputs.
+    First,
+
+    /// Last element of the slice, as in CLRS. Degrades to O(n^2) on sorted
+    /// inputs.
+    Last,
+
+    /// Median of the first, middle and last elements of the slice.
+    MedianOfThree,
+
+    /// Uniformly random element of the slice, giving expected O(n log(n))
+    /// time for any input. Without the `std` feature, the generator always
+    /// starts from the same seed, see `Pcg32::new`.
+    Random,
+}
+
+pub struct QuickSort;
+
+impl QuickSort {
+    /// Sorts `xs` using the given `pivot` strategy.
+    pub fn sort_with<T: PartialOrd>(xs: &mut [T], pivot: Pivot) {
+        QuickSort::sort_by_with(xs, partial_cmp, pivot)
+    }
+
+    /// Sorts `xs` with respect to the comparator `cmp` using the given
+    /// `pivot` strategy.
+    pub fn sort_by_with<T, F: FnMut(&T, &T) -> Ordering>(
+        xs: &mut [T],
+        mut cmp: F,
+        pivot: Pivot,
+    ) {
+        quick_sort(xs, pivot, &mut cmp, &mut None);
+    }
+}
+
+impl Sorter for QuickSort {
+    fn sort_by<T, F: FnMut(&T, &T) -> Ordering>(xs: &mut [T], cmp: F) {
+        QuickSort::sort_by_with(xs, cmp, Pivot::MedianOfThree)
+    }
+}
+
+/// Sorts `xs` by recursing into the smaller partition and looping over the
+/// larger one, so that the stack depth stays O(log(n)) even when the pivot
+/// choices are bad. Elements equal to the pivot are left out of both
+/// partitions, so that repeated keys don't degrade to O(n^2) time. The
+/// generator `rng` is only created once a random pivot is needed.
+fn quick_sort<T, F: FnMut(&T, &T) -> Ordering>(
+    mut xs: &mut [T],
+    pivot: Pivot,
+    cmp: &mut F,
+    rng: &mut Option<Pcg32>,
+) {
+    while xs.len() > 1 {
+        let p = choose_pivot(xs, pivot, cmp, rng);
+        let (lt, gt) = partition3(xs, p, cmp);
+
+        let (left, right) = xs.split_at_mut(lt);
+        let right = &mut right[gt - lt..];
+        if left.len() < right.len() {
+            quick_sort(left, pivot, cmp, rng);
+            xs = right;
+        } else {
+            quick_sort(right, pivot, cmp, rng);
+            xs = left;
+        }
+    }
+}
+
+/// Returns the index of the pivot element according to the `pivot` strategy.
+/// Assumes `xs` is non-empty.
+fn choose_pivot<T, F: FnMut(&T, &T) -> Ordering>(
+    xs: &[T],
+    pivot: Pivot,
+    cmp: &mut F,
+    rng: &mut Option<Pcg32>,
+) -> usize {
+    let last = xs.len() - 1;
+    match pivot {
+        Pivot::First => 0,
+        Pivot::Last => last,
+        Pivot::MedianOfThree => {
+            let mid = last / 2;
+            let (a, b, c) = (&xs[0], &xs[mid], &xs[last]);
+            let mut le = |x, y| cmp(x, y) != Ordering::Greater;
+            if (le(a, b) && le(b, c)) || (le(c, b) && le(b, a)) {
+                mid
+            } else if (le(b, a) && le(a, c)) || (le(c, a) && le(a, b)) {
+                0
+            } else {
+                last
+            }
+        }
+        Pivot::Random => {
+            rng.get_or_insert_with(Pcg32::new).gen_range(0..xs.len())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sorting;
+
+    const STRATEGIES: [Pivot; 4] = [
+        Pivot::First,
+        Pivot::Last,
+        Pivot::MedianOfThree,
+        Pivot::Random,
+    ];
+
+    #[test]
+    fn sort() {
+        let mut xs = vec![123, 91847, 1, 0, -1, -450, 800, 555];
+        QuickSort::sort(&mut xs);
+        assert!(sorting::is_sorted(&xs));
+    }
+
+    #[test]
+    fn small_inputs() {
+        for pivot in STRATEGIES {
+            let mut xs: Vec<i32> = vec![];
+            QuickSort::sort_with(&mut xs, pivot);
+            assert!(xs.is_empty());
+
+            let mut xs = vec![4];
+            QuickSort::sort_with(&mut xs, pivot);
+            assert_eq!(xs, vec![4]);
+
+            let mut xs = vec![4, 3];
+            QuickSort::sort_with(&mut xs, pivot);
+            assert_eq!(xs, vec![3, 4]);
+        }
+    }
+
+    #[test]
+    fn adversarial_inputs() {
+        let n = 2000;
+        for pivot in STRATEGIES {
+            // Already sorted
+            let mut xs: Vec<i32> = (0..n).collect();
+            QuickSort::sort_with(&mut xs, pivot);
+            assert_eq!(xs, (0..n).collect::<Vec<_>>());
+
+            // Reverse sorted
+            let mut xs: Vec<i32> = (0..n).rev().collect();
+            QuickSort::sort_with(&mut xs, pivot);
+            assert_eq!(xs, (0..n).collect::<Vec<_>>());
+
+            // All equal
+            let mut xs = vec![7; n as usize];
+            QuickSort::sort_with(&mut xs, pivot);
+            assert_eq!(xs, vec![7; n as usize]);
+
+            // Organ pipe
+            let mut xs: Vec<i32> = (0..n / 2).chain((0..n / 2).rev()).collect();
+            QuickSort::sort_with(&mut xs, pivot);
+            assert!(sorting::is_sorted(&xs));
+        }
+    }
+
+    #[test]
+    fn equal_keys() {
+        let n = 10000;
+        for pivot in STRATEGIES {
+            // A single partition takes care of equal keys
+            let mut xs = vec![7; n];
+            let mut comparisons = 0;
+            let cmp = |x: &i32, y: &i32| {
+                comparisons += 1;
+                x.cmp(y)
+            };
+            QuickSort::sort_by_with(&mut xs, cmp, pivot);
+            assert!(comparisons < 2 * n, "{pivot:?}");
+
+            // Few distinct keys
+            let mut xs: Vec<usize> = (0..n).map(|i| i * i % 7).collect();
+            QuickSort::sort_with(&mut xs, pivot);
+            assert!(sorting::is_sorted(&xs));
+            assert_eq!(xs.iter().filter(|&&x| x == 0).count(), n.div_ceil(7));
+        }
+    }
+
+    #[test]
+    fn floats() {
+        let mut xs = vec![3.5, -1.25, 0.0, 9.75, 2.0, -8.5];
+        QuickSort::sort_with(&mut xs, Pivot::Random);
+        assert_eq!(xs, vec![-8.5, -1.25, 0.0, 2.0, 3.5, 9.75]);
+    }
+
+    #[test]
+    fn sort_by() {
+        let mut xs: Vec<String> =
+            ["pear", "fig", "banana", "kiwi"].map(String::from).to_vec();
+        QuickSort::sort_by(&mut xs, |a, b| b.cmp(a));
+        assert_eq!(xs, ["pear", "kiwi", "fig", "banana"]);
+
+        for pivot in STRATEGIES {
+            let mut xs: Vec<String> =
+                (0..200).map(|x| format!("{:03}", (x * 7) % 200)).collect();
+            QuickSort::sort_by_with(&mut xs, |a, b| a.cmp(b), pivot);
+            assert!(sorting::is_sorted(&xs));
+        }
+
+        QuickSort::sort_by_key(&mut xs, |s| s.len());
+        assert_eq!(xs[0], "fig");
+        assert_eq!(xs[3], "banana");
+    }
+}
@@ -1,39 +1,315 @@
+use super::{merge::MergeSort, partial_cmp, Sorter};
+use crate::math::num::{CheckedAdd, Num};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
 /// Binary searches for the `search`. Assumes `xs` is sorted. If the element is
-/// found at `xs[idx]`, returns `Some(idx)`, otherwise, returns `None`.
+/// found at `xs[idx]`, returns `Some(idx)`, otherwise, returns `None`. If
+/// `search` occurs more than once, the index of its first occurrence is
+/// returned.
 pub fn binary_search<T: PartialOrd>(xs: &[T], search: &T) -> Option<usize> {
-    _binary_search(xs, 0, xs.len() - 1, search)
+    binary_search_insertion_point(xs, search).ok()
 }
 
-fn _binary_search<T: PartialOrd>(
+/// Binary searches for the `search`. Assumes `xs` is sorted. If the element is
+/// found at `xs[idx]`, returns `Ok(idx)`, otherwise, returns `Err(idx)` where
+/// `idx` is the position where `search` could be inserted while keeping `xs`
+/// sorted.
+///
+/// Example:
+/// ```
+/// use ralg::sorting::search::binary_search_insertion_point;
+///
+/// let xs = vec![1, 3, 5, 7];
+/// assert_eq!(binary_search_insertion_point(&xs, &5), Ok(2));
+/// assert_eq!(binary_search_insertion_point(&xs, &4), Err(2));
+/// assert_eq!(binary_search_insertion_point(&xs, &8), Err(4));
+/// ```
+pub fn binary_search_insertion_point<T: PartialOrd>(
     xs: &[T],
-    low: usize,
-    top: usize,
     search: &T,
-) -> Option<usize> {
-    if top < low {
-        // Check if this is be the last search, if so, the element was not found
+) -> Result<usize, usize> {
+    let idx = lower_bound(xs, search);
+    if idx < xs.len() && &xs[idx] == search {
+        Ok(idx)
+    } else {
+        Err(idx)
+    }
+}
+
+/// Returns the index of the first element of `xs` which is not less than
+/// `search`, or `xs.len()` if there is no such element. Assumes `xs` is
+/// sorted.
+pub fn lower_bound<T: PartialOrd>(xs: &[T], search: &T) -> usize {
+    partition_point(xs, |x| x < search)
+}
+
+/// Returns the index of the first element of `xs` which is greater than
+/// `search`, or `xs.len()` if there is no such element. Assumes `xs` is
+/// sorted.
+pub fn upper_bound<T: PartialOrd>(xs: &[T], search: &T) -> usize {
+    partition_point(xs, |x| x <= search)
+}
+
+/// Returns the index of the first element of `xs` for which `pred` is false,
+/// assuming `xs` is partitioned: `pred` holds for a prefix of `xs` and fails
+/// for the rest of it.
+///
+/// Takes time `O(log(n))` where `n = xs.len()`.
+fn partition_point<T, P: Fn(&T) -> bool>(xs: &[T], pred: P) -> usize {
+    // Invariant: `pred` holds for `xs[..low]` and fails for `xs[top..]`
+    let (mut low, mut top) = (0, xs.len());
+    while low < top {
+        let mid = low + (top - low) / 2;
+        if pred(&xs[mid]) {
+            // Search the right slice
+            low = mid + 1;
+        } else {
+            // Search the left slice
+            top = mid;
+        }
+    }
+    low
+}
+
+/// Interpolation search for the `search`. Assumes `xs` is sorted. If the
+/// element is found at `xs[idx]`, returns `Some(idx)`, otherwise, returns
+/// `None`.
+///
+/// Instead of probing the middle of the current range, we estimate the
+/// position of `search` by linearly interpolating between the endpoints of
+/// the range. For uniformly distributed keys this takes expected time
+/// `O(log(log(n)))`, although the worst case is `O(n)`.
+pub fn interpolation_search<T: ToF64>(xs: &[T], search: &T) -> Option<usize> {
+    if xs.is_empty() {
         return None;
     }
 
-    let mid = (top + low) / 2;
-    let x = &xs[mid];
+    let (mut low, mut top) = (0, xs.len() - 1);
+    while low <= top && xs[low] <= *search && *search <= xs[top] {
+        let (x_low, x_top) = (xs[low].to_f64(), xs[top].to_f64());
+        let mid = if x_top == x_low {
+            low
+        } else {
+            let ratio = (search.to_f64() - x_low) / (x_top - x_low);
+            low + (ratio * (top - low) as f64) as usize
+        };
 
-    if x == search {
-        // Element found
-        Some(mid)
-    } else if x < search {
-        // Search the right slice
-        _binary_search(xs, mid + 1, top, search)
-    } else {
-        // Search the left slice
-        _binary_search(xs, low, mid - 1, search)
+        if xs[mid] == *search {
+            return Some(mid);
+        } else if xs[mid] < *search {
+            low = mid + 1;
+        } else if mid == 0 {
+            return None;
+        } else {
+            top = mid - 1;
+        }
+    }
+    None
+}
+
+/// Numbers that can be interpolated between, through their conversion to
+/// `f64`, which rounds the integers wider than 53 bits.
+pub trait ToF64: PartialOrd + Copy {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! to_f64_impl {
+    ($($t: ty)*) => ($(
+        impl ToF64 for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    )*)
+}
+
+to_f64_impl!(usize u8 u16 u32 u64 u128);
+to_f64_impl!(isize i8 i16 i32 i64 i128);
+to_f64_impl!(f32 f64);
+
+/// Exponential (galloping) search for the `search`. Assumes `xs` is sorted.
+/// If the element is found at `xs[idx]`, returns `Some(idx)`, otherwise,
+/// returns `None`. If `search` occurs more than once, the index of its first
+/// occurrence is returned.
+///
+/// Takes time `O(log(i))` where `i` is the position of `search`, which beats
+/// binary search when the element is close to the start of `xs`.
+pub fn exponential_search<T: PartialOrd>(
+    xs: &[T],
+    search: &T,
+) -> Option<usize> {
+    exponential_search_unbounded(|idx| xs.get(idx), &search)
+}
+
+/// Exponential search over an unbounded sorted sequence, whose elements are
+/// given by `get`, which should return `None` past the end of the sequence
+/// (if there is one). Returns the index of the first occurrence of `search`,
+/// if any.
+///
+/// Example:
+/// ```
+/// use ralg::sorting::search::exponential_search_unbounded;
+///
+/// // Search the infinite sequence of squares
+/// assert_eq!(exponential_search_unbounded(|i| Some(i * i), &1369), Some(37));
+/// assert_eq!(exponential_search_unbounded(|i| Some(i * i), &1368), None);
+/// ```
+pub fn exponential_search_unbounded<T, F>(get: F, search: &T) -> Option<usize>
+where
+    T: PartialOrd,
+    F: Fn(usize) -> Option<T>,
+{
+    // Gallop over the indices `2^k - 1` until we either pass the end of the
+    // sequence or find an element not less than `search`. The last index is
+    // `usize::MAX` itself, past which there is nothing left to search.
+    let mut i: usize = 0;
+    loop {
+        match get(i) {
+            Some(x) if x < *search => match i.checked_mul(2) {
+                Some(j) => i = j + 1,
+                None => return None,
+            },
+            _ => break,
+        }
+    }
+
+    // The first occurrence of `search` can only be in `[i / 2, i]`, which we
+    // binary search, stopping at the end of the sequence
+    let (mut low, mut top) = (i / 2, i);
+    while low < top {
+        let mid = low + (top - low) / 2;
+        match get(mid) {
+            Some(x) if x < *search => low = mid + 1,
+            Some(_) => top = mid,
+            None => top = mid,
+        }
+    }
+    match get(low) {
+        Some(x) if x == *search => Some(low),
+        _ => None,
     }
 }
 
 /// Given `sum`, determines if there exists at least one pair of distinct
-/// elements in `xs` whose sum is equal to `sum`.
-pub fn has_two_sum<T: PartialOrd>(xs: &[T], sum: T) -> bool {
-    todo!()
+/// elements in `xs` whose sum is equal to `sum`. The slice `xs` need not be
+/// sorted.
+pub fn has_two_sum<T: Num + PartialOrd + Copy + CheckedAdd>(
+    xs: &[T],
+    sum: T,
+) -> bool {
+    two_sum_indices(xs, sum).is_some()
+}
+
+/// Given `sum`, returns the indices `(i, j)`, with `i < j`, of a pair of
+/// elements of `xs` such that `xs[i] + xs[j] == sum`, if any.
+///
+/// The indices of `xs` are sorted by their values and two pointers walk from
+/// both ends towards each other, taking time `O(n log(n))`.
+///
+/// Example:
+/// ```
+/// use ralg::sorting::search::two_sum_indices;
+///
+/// let xs = vec![8, 3, 5, 1];
+/// assert_eq!(two_sum_indices(&xs, 9), Some((0, 3)));
+/// assert_eq!(two_sum_indices(&xs, 10), None);
+/// ```
+pub fn two_sum_indices<T: Num + PartialOrd + Copy + CheckedAdd>(
+    xs: &[T],
+    sum: T,
+) -> Option<(usize, usize)> {
+    let idx = sorted_indices(xs);
+    two_pointers(xs, &idx, 0, T::zero(), sum).map(|(i, j)| ordered_pair(i, j))
+}
+
+/// Given `sum`, returns the indices `(i, j, k)`, with `i < j < k`, of three
+/// elements of `xs` such that `xs[i] + xs[j] + xs[k] == sum`, if any.
+///
+/// For each element, we look for a two-sum among the greater elements using
+/// two pointers, taking time `O(n^2)`.
+pub fn three_sum<T: Num + PartialOrd + Copy + CheckedAdd>(
+    xs: &[T],
+    sum: T,
+) -> Option<(usize, usize, usize)> {
+    let idx = sorted_indices(xs);
+    for (a, &i) in idx.iter().enumerate() {
+        if let Some((j, k)) = two_pointers(xs, &idx, a + 1, xs[i], sum) {
+            let mut triple = [i, j, k];
+            triple.sort_unstable();
+            return Some((triple[0], triple[1], triple[2]));
+        }
+    }
+    None
+}
+
+/// Compares `x + y + z` with `sum` as if there were no overflow, provided
+/// that `x` is zero or `x <= y <= z`.
+///
+/// A sum only overflows when both terms have the same sign, which tells the
+/// side it overflowed to. Adding `x` and `z` first, an overflow means that
+/// `y` also has their sign, hence so does the whole sum.
+fn cmp_sum<T: Num + PartialOrd + Copy + CheckedAdd>(
+    x: T,
+    y: T,
+    z: T,
+    sum: T,
+) -> Option<Ordering> {
+    let side = |t: T| {
+        if t > T::zero() {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        }
+    };
+    let Some(xz) = x.checked_add(z) else {
+        return Some(side(z));
+    };
+    match xz.checked_add(y) {
+        Some(s) => s.partial_cmp(&sum),
+        None => Some(side(y)),
+    }
+}
+
+/// Indices of `xs` ordered by their corresponding values.
+fn sorted_indices<T: PartialOrd>(xs: &[T]) -> Vec<usize> {
+    let mut idx: Vec<usize> = (0..xs.len()).collect();
+    MergeSort::sort_by(&mut idx, |&i, &j| partial_cmp(&xs[i], &xs[j]));
+    idx
+}
+
+/// Searches `idx[from..]`, the indices of `xs` sorted by value, for a pair of
+/// distinct indices whose values add up to `sum` when added to `base`, which
+/// is either zero or a value preceding those of `idx[from..]`. We avoid
+/// subtracting from `sum`, which could underflow for unsigned types.
+fn two_pointers<T: Num + PartialOrd + Copy + CheckedAdd>(
+    xs: &[T],
+    idx: &[usize],
+    from: usize,
+    base: T,
+    sum: T,
+) -> Option<(usize, usize)> {
+    if idx.len() < from + 2 {
+        return None;
+    }
+
+    let (mut low, mut top) = (from, idx.len() - 1);
+    while low < top {
+        match cmp_sum(base, xs[idx[low]], xs[idx[top]], sum) {
+            Some(Ordering::Equal) => return Some((idx[low], idx[top])),
+            Some(Ordering::Less) => low += 1,
+            _ => top -= 1,
+        }
+    }
+    None
+}
+
+fn ordered_pair(i: usize, j: usize) -> (usize, usize) {
+    if i < j {
+        (i, j)
+    } else {
+        (j, i)
+    }
 }
 
 #[cfg(test)]
@@ -50,5 +326,243 @@ mod tests {
         let xs = vec![3, 5, 7, 11, 35, 90];
         assert_eq!(super::binary_search(&xs, &90), Some(xs.len() - 1));
         assert_eq!(super::binary_search(&xs, &6), None);
+
+        // Empty slices and elements smaller than every other
+        let xs: Vec<i32> = vec![];
+        assert_eq!(super::binary_search(&xs, &3), None);
+        let xs = vec![3, 4];
+        assert_eq!(super::binary_search(&xs, &1), None);
+        assert_eq!(super::binary_search(&xs, &5), None);
+
+        // Repeated elements
+        let xs = vec![1, 2, 2, 2, 3];
+        assert_eq!(super::binary_search(&xs, &2), Some(1));
+    }
+
+    #[test]
+    fn binary_search_insertion_point() {
+        let xs: Vec<i32> = vec![];
+        assert_eq!(super::binary_search_insertion_point(&xs, &3), Err(0));
+
+        let xs = vec![3, 5, 7, 11, 35, 90];
+        assert_eq!(super::binary_search_insertion_point(&xs, &3), Ok(0));
+        assert_eq!(super::binary_search_insertion_point(&xs, &1), Err(0));
+        assert_eq!(super::binary_search_insertion_point(&xs, &12), Err(4));
+        assert_eq!(super::binary_search_insertion_point(&xs, &91), Err(6));
+    }
+
+    #[test]
+    fn has_two_sum() {
+        let xs: Vec<i32> = vec![];
+        assert!(!super::has_two_sum(&xs, 0));
+
+        // The same element cannot be used twice
+        let xs = vec![4];
+        assert!(!super::has_two_sum(&xs, 8));
+        let xs = vec![4, 4];
+        assert!(super::has_two_sum(&xs, 8));
+
+        let xs = vec![10, -3, 7, 22, 5, 0];
+        assert!(super::has_two_sum(&xs, 19));
+        assert!(super::has_two_sum(&xs, -3));
+        assert!(!super::has_two_sum(&xs, 100));
+
+        let xs = vec![0.5, 1.25, 2.0];
+        assert!(super::has_two_sum(&xs, 3.25));
+    }
+
+    #[test]
+    fn sums_overflow() {
+        assert!(!super::has_two_sum(&[i32::MAX, 1], 5));
+        assert!(!super::has_two_sum(&[i32::MIN, -1], 5));
+        assert!(super::has_two_sum(&[i32::MAX, 7, i32::MIN], -1));
+        assert!(!super::has_two_sum(&[u8::MAX, 200, 3], 2));
+        assert_eq!(super::two_sum_indices(&[250u8, 9, 6], 15), Some((1, 2)));
+
+        // Partial sums overflow in either direction
+        let xs = [i64::MAX, i64::MAX, 3, i64::MIN, -4];
+        for sum in [i64::MAX - 1, 2, -5] {
+            let (i, j, k) = super::three_sum(&xs, sum).unwrap();
+            let exact = xs[i] as i128 + xs[j] as i128 + xs[k] as i128;
+            assert_eq!(exact, sum as i128);
+        }
+        assert_eq!(super::three_sum(&xs, 0), None);
+        assert_eq!(super::three_sum(&xs, -1), None);
+
+        let xs = [i8::MIN, 100, 27, -127];
+        assert_eq!(super::three_sum(&xs, -1), Some((0, 1, 2)));
+        assert_eq!(super::three_sum(&xs, 0), Some((1, 2, 3)));
+    }
+
+    #[test]
+    fn two_sum_indices() {
+        let xs = vec![10, -3, 7, 22, 5, 0];
+        assert_eq!(super::two_sum_indices(&xs, 19), Some((1, 3)));
+        assert_eq!(super::two_sum_indices(&xs, 12), Some((2, 4)));
+        assert_eq!(super::two_sum_indices(&xs, 1), None);
+    }
+
+    #[test]
+    fn three_sum() {
+        let xs: Vec<i32> = vec![1, 2];
+        assert_eq!(super::three_sum(&xs, 3), None);
+
+        let xs = vec![12, 3, 4, 1, 6, 9];
+        let (i, j, k) = super::three_sum(&xs, 24).unwrap();
+        assert!(i < j && j < k);
+        assert_eq!(xs[i] + xs[j] + xs[k], 24);
+        assert_eq!(super::three_sum(&xs, 100), None);
+
+        let xs = vec![-1, 0, 1, 2, -1, -4];
+        let (i, j, k) = super::three_sum(&xs, 0).unwrap();
+        assert_eq!(xs[i] + xs[j] + xs[k], 0);
+
+        let xs: Vec<u32> = vec![9, 1, 2, 30];
+        assert_eq!(super::three_sum(&xs, 12), Some((0, 1, 2)));
+        assert_eq!(super::three_sum(&xs, 4), None);
+    }
+
+    #[test]
+    fn interpolation_search() {
+        let xs: Vec<i32> = vec![];
+        assert_eq!(super::interpolation_search(&xs, &3), None);
+
+        let xs = vec![4];
+        assert_eq!(super::interpolation_search(&xs, &4), Some(0));
+        assert_eq!(super::interpolation_search(&xs, &2), None);
+
+        // Compare against binary search on uniform and skewed keys
+        let uniform: Vec<i32> = (0..500).map(|x| 3 * x + 1).collect();
+        let skewed: Vec<i32> = (0..60).map(|x| x * x * x).collect();
+        for xs in [uniform, skewed] {
+            for search in -5..xs[xs.len() - 1] + 5 {
+                assert_eq!(
+                    super::interpolation_search(&xs, &search),
+                    super::binary_search(&xs, &search)
+                );
+            }
+        }
+
+        let xs = vec![0.5, 1.5, 2.25, 8.0];
+        assert_eq!(super::interpolation_search(&xs, &2.25), Some(2));
+        assert_eq!(super::interpolation_search(&xs, &2.0), None);
+    }
+
+    #[test]
+    fn interpolation_search_wide() {
+        let xs: Vec<u64> = (0..200).map(|x| x * 1000).collect();
+        assert_eq!(super::interpolation_search(&xs, &57000), Some(57));
+        let xs: Vec<usize> = vec![2, 3, 5, 7, 11];
+        assert_eq!(super::interpolation_search(&xs, &7), Some(3));
+        let xs: Vec<isize> = vec![-8, -2, 0, 4];
+        assert_eq!(super::interpolation_search(&xs, &-2), Some(1));
+
+        // Neighbours which are equal once rounded to `f64`
+        let big = i64::MAX - 10;
+        let xs: Vec<i64> = (0..10).map(|x| big + x).chain([i64::MAX]).collect();
+        for (i, x) in xs.iter().enumerate() {
+            assert_eq!(super::interpolation_search(&xs, x), Some(i));
+        }
+        assert_eq!(super::interpolation_search(&xs, &(i64::MAX - 11)), None);
+        let xs = [u128::MAX - 3, u128::MAX - 1, u128::MAX];
+        assert_eq!(super::interpolation_search(&xs, &(u128::MAX - 1)), Some(1));
+        assert_eq!(super::interpolation_search(&xs, &(u128::MAX - 2)), None);
+    }
+
+    #[test]
+    fn exponential_search() {
+        let xs: Vec<i32> = vec![];
+        assert_eq!(super::exponential_search(&xs, &3), None);
+
+        let xs: Vec<i32> = (0..300).map(|x| 2 * x).collect();
+        for search in -3..xs[xs.len() - 1] + 3 {
+            assert_eq!(
+                super::exponential_search(&xs, &search),
+                super::binary_search(&xs, &search)
+            );
+        }
+
+        let xs = vec![1, 2, 2, 2, 2, 2, 3];
+        assert_eq!(super::exponential_search(&xs, &2), Some(1));
+    }
+
+    #[test]
+    fn exponential_search_unbounded() {
+        // Bounded sequences which never reach `search`
+        let get = |i: usize| Some(1.0 - 1.0 / (i as f64 + 1.0));
+        assert_eq!(super::exponential_search_unbounded(get, &2.0), None);
+        let get = |i: usize| Some(i);
+        assert_eq!(
+            super::exponential_search_unbounded(get, &usize::MAX),
+            Some(usize::MAX)
+        );
+        let get = |i: usize| Some(i / 2);
+        assert_eq!(super::exponential_search_unbounded(get, &usize::MAX), None);
+
+        // Finite sequences of every small length
+        for n in 0..40 {
+            let get = |i: usize| (i < n).then_some(3 * i);
+            for search in 0..3 * n + 2 {
+                let found =
+                    (search % 3 == 0 && search < 3 * n).then_some(search / 3);
+                assert_eq!(
+                    super::exponential_search_unbounded(get, &search),
+                    found
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn bounds() {
+        let xs = vec![1, 2, 2, 2, 3, 5];
+        assert_eq!(super::lower_bound(&xs, &2), 1);
+        assert_eq!(super::upper_bound(&xs, &2), 4);
+        assert_eq!(super::lower_bound(&xs, &4), 5);
+        assert_eq!(super::upper_bound(&xs, &4), 5);
+        assert_eq!(super::lower_bound(&xs, &0), 0);
+        assert_eq!(super::upper_bound(&xs, &9), xs.len());
+
+        let xs: Vec<f64> = vec![];
+        assert_eq!(super::lower_bound(&xs, &1.0), 0);
+        assert_eq!(super::upper_bound(&xs, &1.0), 0);
+    }
+
+    #[test]
+    fn random_inputs() {
+        use crate::random::Pcg32;
+
+        let mut rng = Pcg32::seed(0x5ea7c4);
+        for _ in 0..300 {
+            let n = rng.gen_range(0..100);
+            let range = rng.gen_range(1..200);
+            let mut xs: Vec<i32> =
+                (0..n).map(|_| rng.gen_range(0..range)).collect();
+            xs.sort();
+            for _ in 0..20 {
+                let search = rng.gen_range(-1..range + 1);
+                let lower = xs.partition_point(|&x| x < search);
+                let upper = xs.partition_point(|&x| x <= search);
+                assert_eq!(super::lower_bound(&xs, &search), lower);
+                assert_eq!(super::upper_bound(&xs, &search), upper);
+
+                // The first occurrence, if any
+                let found = (lower < upper).then_some(lower);
+                assert_eq!(super::binary_search(&xs, &search), found);
+                assert_eq!(super::exponential_search(&xs, &search), found);
+                let hit = super::interpolation_search(&xs, &search);
+                assert_eq!(hit.map(|i| xs[i]), found.map(|i| xs[i]));
+                let insertion =
+                    super::binary_search_insertion_point(&xs, &search);
+                assert_eq!(insertion.is_ok(), found.is_some());
+            }
+
+            // A pair summing to the target exists exactly when brute force
+            // finds one
+            let sum = rng.gen_range(0..2 * range);
+            let brute = (0..xs.len())
+                .any(|i| (i + 1..xs.len()).any(|j| xs[i] + xs[j] == sum));
+            assert_eq!(super::has_two_sum(&xs, sum), brute);
+        }
     }
 }
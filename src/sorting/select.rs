@@ -0,0 +1,257 @@
+//! Selection of the `k`th smallest element of a slice in linear time. All
+//! functions take `k` zero-based and rearrange the slice in place, leaving
+//! the selected element at index `k`, every element before it less than or
+//! equal to it, and every element after it greater than or equal to it.
+use super::insertion::InsertionSort;
+use super::{partial_cmp, Sorter};
+use crate::random::Pcg32;
+use core::cmp::Ordering;
+
+/// Returns the `k`th smallest element of `xs`, or `None` if `k` is out of
+/// bounds, using randomized quickselect. Takes expected time `O(n)`.
+///
+/// Example:
+/// ```
+/// use ralg::sorting::select::kth_smallest;
+///
+/// let mut xs = vec![7, 1, 5, 3, 9];
+/// assert_eq!(kth_smallest(&mut xs, 0), Some(&1));
+/// assert_eq!(kth_smallest(&mut xs, 3), Some(&7));
+/// assert_eq!(kth_smallest(&mut xs, 5), None);
+/// ```
+pub fn kth_smallest<T: PartialOrd>(xs: &mut [T], k: usize) -> Option<&T> {
+    kth_smallest_by(xs, k, partial_cmp)
+}
+
+/// Same as `kth_smallest`, with the order given by the comparator `cmp`.
+pub fn kth_smallest_by<T, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    k: usize,
+    mut cmp: F,
+) -> Option<&T> {
+    if k >= xs.len() {
+        return None;
+    }
+
+    let mut rng = Pcg32::new();
+    select(xs, k, &mut cmp, &mut |ys, _| rng.gen_range(0..ys.len()));
+    Some(&xs[k])
+}
+
+/// Returns the `k`th smallest element of `xs`, or `None` if `k` is out of
+/// bounds, using the deterministic median-of-medians algorithm. Takes time
+/// `O(n)` in the worst case.
+pub fn median_of_medians<T: PartialOrd>(xs: &mut [T], k: usize) -> Option<&T> {
+    median_of_medians_by(xs, k, partial_cmp)
+}
+
+/// Same as `median_of_medians`, with the order given by the comparator
+/// `cmp`.
+pub fn median_of_medians_by<T, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    k: usize,
+    mut cmp: F,
+) -> Option<&T> {
+    if k >= xs.len() {
+        return None;
+    }
+
+    mom_select(xs, k, &mut cmp);
+    Some(&xs[k])
+}
+
+/// Narrows down the part of `xs` containing the index `k` by partitioning it
+/// around the pivot index chosen by `choose_pivot`, until `k` falls into the
+/// elements equal to the pivot.
+fn select<T, F, P>(xs: &mut [T], k: usize, cmp: &mut F, choose_pivot: &mut P)
+where
+    F: FnMut(&T, &T) -> Ordering,
+    P: FnMut(&mut [T], &mut F) -> usize,
+{
+    let (mut low, mut top) = (0, xs.len());
+    while top - low > 1 {
+        let part = &mut xs[low..top];
+        let pivot = choose_pivot(part, cmp);
+        let (lt, gt) = partition3(part, pivot, cmp);
+
+        if k < low + lt {
+            top = low + lt;
+        } else if k >= low + gt {
+            low += gt;
+        } else {
+            return;
+        }
+    }
+}
+
+fn mom_select<T, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    k: usize,
+    cmp: &mut F,
+) {
+    select(xs, k, cmp, &mut median_of_medians_pivot)
+}
+
+/// Splits `xs` into groups of 5 elements, moves the median of each group to
+/// the front of `xs` and recursively selects the median of these medians,
+/// returning its index.
+fn median_of_medians_pivot<T, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    cmp: &mut F,
+) -> usize {
+    let n = xs.len();
+    if n <= 5 {
+        InsertionSort::sort_by(xs, &mut *cmp);
+        return n / 2;
+    }
+
+    let groups = n.div_ceil(5);
+    for g in 0..groups {
+        let group = &mut xs[5 * g..n.min(5 * g + 5)];
+        InsertionSort::sort_by(group, &mut *cmp);
+        let median = 5 * g + group.len() / 2;
+        xs.swap(g, median);
+    }
+
+    mom_select(&mut xs[..groups], groups / 2, cmp);
+    groups / 2
+}
+
+/// Three-way partition of `xs` around the element at index `pivot`. Returns
+/// `(lt, gt)` such that `xs[..lt]` are less than the pivot, `xs[lt..gt]` are
+/// equal to it and `xs[gt..]` are greater than it.
+///
+/// Takes time `O(n)` where `n = xs.len()`.
+pub(super) fn partition3<T, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    pivot: usize,
+    cmp: &mut F,
+) -> (usize, usize) {
+    xs.swap(0, pivot);
+
+    // Invariant: `xs[..lt] < p`, `xs[lt..i] == p` and `xs[gt..] > p`, where
+    // the pivot `p` lives at `xs[p_idx]` inside the equal block
+    let (mut lt, mut i, mut gt) = (0, 1, xs.len());
+    let mut p_idx = 0;
+    while i < gt {
+        match cmp(&xs[i], &xs[p_idx]) {
+            Ordering::Less => {
+                xs.swap(lt, i);
+                if p_idx == lt {
+                    p_idx = i;
+                }
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                xs.swap(i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Checks every `k` of `xs` against its sorted version using `select`.
+    fn check_all<F>(xs: &[i32], mut select: F)
+    where
+        F: FnMut(&mut [i32], usize) -> Option<i32>,
+    {
+        let mut sorted = xs.to_vec();
+        sorted.sort();
+        for (k, expected) in sorted.iter().enumerate() {
+            let mut ys = xs.to_vec();
+            assert_eq!(select(&mut ys, k), Some(*expected));
+            assert!(ys[..k].iter().all(|y| y <= expected));
+            assert!(ys[k + 1..].iter().all(|y| y >= expected));
+        }
+        let mut ys = xs.to_vec();
+        assert_eq!(select(&mut ys, xs.len()), None);
+    }
+
+    fn inputs() -> Vec<Vec<i32>> {
+        vec![
+            vec![],
+            vec![42],
+            vec![3, 1, 2],
+            vec![5, 5, 5, 5, 5],
+            (0..50).collect(),
+            (0..50).rev().collect(),
+            (0..97).map(|x| (x * 31) % 17 - 8).collect(),
+        ]
+    }
+
+    #[test]
+    fn kth_smallest() {
+        for xs in inputs() {
+            check_all(&xs, |ys, k| super::kth_smallest(ys, k).copied());
+        }
+    }
+
+    #[test]
+    fn median_of_medians() {
+        for xs in inputs() {
+            check_all(&xs, |ys, k| super::median_of_medians(ys, k).copied());
+        }
+
+        // Large input to exercise the recursion on the medians
+        let mut xs: Vec<i64> =
+            (0..10_000).map(|x| (x * 7919) % 10007).collect();
+        let mut sorted = xs.clone();
+        sorted.sort();
+        assert_eq!(
+            super::median_of_medians(&mut xs, 5000),
+            Some(&sorted[5000])
+        );
+    }
+
+    #[test]
+    fn select_by() {
+        let mut xs: Vec<String> =
+            ["pear", "fig", "banana", "kiwi"].map(String::from).to_vec();
+        let longest = kth_smallest_by(&mut xs, 0, |a, b| b.len().cmp(&a.len()));
+        assert_eq!(longest.map(|s| s.as_str()), Some("banana"));
+
+        let second = median_of_medians_by(&mut xs, 1, |a, b| a.cmp(b));
+        assert_eq!(second.map(|s| s.as_str()), Some("fig"));
+    }
+
+    #[test]
+    fn random_inputs() {
+        let mut rng = Pcg32::seed(0x5e1ec7);
+        for _ in 0..300 {
+            let n = rng.gen_range(1..200);
+            let range = rng.gen_range(1..50);
+            let xs: Vec<i32> =
+                (0..n).map(|_| rng.gen_range(-range..range)).collect();
+            let mut sorted = xs.clone();
+            sorted.sort();
+            let k = rng.gen_range(0..n);
+
+            let mut ys = xs.clone();
+            assert_eq!(super::kth_smallest(&mut ys, k), Some(&sorted[k]));
+            assert!(ys[..k].iter().all(|y| *y <= sorted[k]));
+            assert!(ys[k + 1..].iter().all(|y| *y >= sorted[k]));
+
+            let mut ys = xs.clone();
+            assert_eq!(super::median_of_medians(&mut ys, k), Some(&sorted[k]));
+            assert!(ys[..k].iter().all(|y| *y <= sorted[k]));
+            assert!(ys[k + 1..].iter().all(|y| *y >= sorted[k]));
+        }
+    }
+
+    #[test]
+    fn partition3() {
+        let mut xs = vec![4, 1, 4, 7, 0, 4, 9, 2];
+        let (lt, gt) = super::partition3(&mut xs, 0, &mut i32::cmp);
+        assert_eq!((lt, gt), (3, 6));
+        assert!(xs[..lt].iter().all(|&x| x < 4));
+        assert!(xs[lt..gt].iter().all(|&x| x == 4));
+        assert!(xs[gt..].iter().all(|&x| x > 4));
+    }
+}
@@ -1,9 +1,227 @@
-use super::Sorter;
+use super::insertion::InsertionSort;
+use super::merge::{merge, permute};
+use super::{Sorter, StableSorter};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 
 pub struct TimSort;
 
 impl Sorter for TimSort {
-    fn sort<T: PartialOrd + Copy>(xs: &mut [T]) {
-        todo!()
+    /// Splits `xs` into its natural runs, extending the short ones to a
+    /// minimum length with insertion sort, and merges neighbouring runs while
+    /// keeping their lengths decreasing exponentially along the run stack.
+    /// Takes time `O(n log(n))`, and `O(n)` on inputs made of few runs.
+    ///
+    /// As in `MergeSort`, the indices of `xs` are sorted instead, so that no
+    /// bounds are needed on `T`.
+    fn sort_by<T, F: FnMut(&T, &T) -> Ordering>(xs: &mut [T], mut cmp: F) {
+        if xs.len() < 2 {
+            return;
+        }
+
+        let mut idx: Vec<usize> = (0..xs.len()).collect();
+        tim_sort(&mut idx, &mut |&i, &j| cmp(&xs[i], &xs[j]));
+        permute(xs, idx);
+    }
+}
+
+impl StableSorter for TimSort {}
+
+/// Run of `xs` starting at index `start`, already in sorted order.
+#[derive(Clone, Copy, Debug)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+fn tim_sort<T: Clone, F: FnMut(&T, &T) -> Ordering>(xs: &mut [T], cmp: &mut F) {
+    let n = xs.len();
+    let min_len = min_run(n);
+    let mut runs: Vec<Run> = Vec::new();
+    let mut start = 0;
+    while start < n {
+        let mut len = natural_run(&mut xs[start..], cmp);
+        if len < min_len {
+            len = min_len.min(n - start);
+            InsertionSort::sort_by(&mut xs[start..start + len], &mut *cmp);
+        }
+        runs.push(Run { start, len });
+        collapse(xs, &mut runs, cmp);
+        start += len;
+    }
+
+    while runs.len() > 1 {
+        let at = runs.len() - 2;
+        merge_at(xs, &mut runs, at, cmp);
+    }
+}
+
+/// Minimum length of the runs to be merged for an array of length `n`. It is
+/// chosen in `[32, 64]` so that `n / min_run(n)` is a power of two or
+/// slightly less than one, which keeps the merges balanced.
+fn min_run(mut n: usize) -> usize {
+    let mut odd = 0;
+    while n >= 64 {
+        odd |= n & 1;
+        n >>= 1;
+    }
+    n + odd
+}
+
+/// Length of the run at the start of `xs`. Strictly descending runs are
+/// reversed in place: since they have no equal elements, this keeps the sort
+/// stable.
+fn natural_run<T, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    cmp: &mut F,
+) -> usize {
+    if xs.len() < 2 {
+        return xs.len();
+    }
+
+    let mut len = 2;
+    if cmp(&xs[0], &xs[1]) == Ordering::Greater {
+        while len < xs.len() && cmp(&xs[len - 1], &xs[len]) == Ordering::Greater
+        {
+            len += 1;
+        }
+        xs[..len].reverse();
+    } else {
+        while len < xs.len() && cmp(&xs[len - 1], &xs[len]) != Ordering::Greater
+        {
+            len += 1;
+        }
+    }
+    len
+}
+
+/// Merges the runs at the top of the stack until, for every three
+/// consecutive runs `x`, `y` and `z`, `x.len > y.len + z.len` and
+/// `y.len > z.len`. The invariant is also checked one run deeper, since
+/// merging in the middle of the stack may break it below the top three.
+fn collapse<T: Clone, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    runs: &mut Vec<Run>,
+    cmp: &mut F,
+) {
+    loop {
+        let n = runs.len();
+        let len = |i: usize| runs[i].len;
+        let at = if (n >= 3 && len(n - 3) <= len(n - 2) + len(n - 1))
+            || (n >= 4 && len(n - 4) <= len(n - 3) + len(n - 2))
+        {
+            // Merge the middle run with its smallest neighbour
+            if len(n - 3) < len(n - 1) {
+                n - 3
+            } else {
+                n - 2
+            }
+        } else if n >= 2 && len(n - 2) <= len(n - 1) {
+            n - 2
+        } else {
+            return;
+        };
+        merge_at(xs, runs, at, cmp);
+    }
+}
+
+/// Merges the runs `runs[i]` and `runs[i + 1]` into a single run.
+fn merge_at<T: Clone, F: FnMut(&T, &T) -> Ordering>(
+    xs: &mut [T],
+    runs: &mut Vec<Run>,
+    i: usize,
+    cmp: &mut F,
+) {
+    let Run { start, len } = runs[i];
+    let right = runs.remove(i + 1);
+    let mid = start + len - 1;
+    let top = right.start + right.len - 1;
+    // Runs already in order need no merging
+    if cmp(&xs[mid], &xs[mid + 1]) == Ordering::Greater {
+        merge(xs, start, mid, top, cmp);
+    }
+    runs[i].len += right.len;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use crate::sorting;
+
+    #[test]
+    fn sort() {
+        let mut xs = vec![123, 91847, 1, 0, -1, -450, 800, 555];
+        TimSort::sort(&mut xs);
+        assert!(sorting::is_sorted(&xs));
+
+        let mut xs: Vec<i32> = vec![];
+        TimSort::sort(&mut xs);
+        assert!(xs.is_empty());
+    }
+
+    #[test]
+    fn sort_by() {
+        let mut xs: Vec<String> =
+            ["pear", "fig", "banana", "kiwi"].map(String::from).to_vec();
+        TimSort::sort_by(&mut xs, |a, b| b.cmp(a));
+        assert_eq!(xs, ["pear", "kiwi", "fig", "banana"]);
+
+        TimSort::sort_by_key(&mut xs, |s| s.len());
+        assert_eq!(xs, ["fig", "pear", "kiwi", "banana"]);
+    }
+
+    #[test]
+    fn min_run() {
+        assert_eq!(super::min_run(0), 0);
+        assert_eq!(super::min_run(63), 63);
+        assert_eq!(super::min_run(64), 32);
+        assert_eq!(super::min_run(65), 33);
+        assert_eq!(super::min_run(2048), 32);
+        for n in 64..5000 {
+            assert!((32..=64).contains(&super::min_run(n)));
+        }
+    }
+
+    #[test]
+    fn natural_run() {
+        let mut xs = [1, 2, 2, 5, 3];
+        assert_eq!(super::natural_run(&mut xs, &mut i32::cmp), 4);
+
+        // Descending runs must be strict, lest equal elements be reordered
+        let mut xs = [9, 7, 4, 4, 1];
+        assert_eq!(super::natural_run(&mut xs, &mut i32::cmp), 3);
+        assert_eq!(xs, [4, 7, 9, 4, 1]);
+
+        let mut xs = [3];
+        assert_eq!(super::natural_run(&mut xs, &mut i32::cmp), 1);
+    }
+
+    /// Inputs made of long ascending and descending runs, of random lengths,
+    /// exercise the merging of the run stack.
+    #[test]
+    fn runs() {
+        let mut rng = Pcg32::seed(0x7135);
+        for _ in 0..100 {
+            let mut xs: Vec<i32> = Vec::new();
+            let m = rng.gen_range(1..20);
+            for _ in 0..m {
+                let len = rng.gen_range(1..300);
+                let from = rng.gen_range(-1000..1000);
+                if rng.gen_range(0..2) == 0 {
+                    xs.extend(from..from + len);
+                } else {
+                    xs.extend((from..from + len).rev());
+                }
+            }
+            let mut expected = xs.clone();
+            expected.sort();
+
+            let mut keys = sorting::indexed(&xs);
+            TimSort::sort(&mut keys);
+            assert!(sorting::is_stable_sorted(&keys));
+            TimSort::sort(&mut xs);
+            assert_eq!(xs, expected);
+        }
     }
 }
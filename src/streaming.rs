@@ -0,0 +1,215 @@
+//! Order statistics over streams
+//!
+//! The structures of this module are fed one value at a time and answer
+//! queries about the values seen so far, or about the most recent ones,
+//! without keeping them sorted.
+use crate::ds::monotonic::MonotonicDeque;
+use crate::heap::{MaxHeap, MinHeap};
+
+/// Median of a stream of values, kept by two heaps: a max-heap with the
+/// smaller half of the values and a min-heap with the larger half, the former
+/// holding one more value when their number is odd. Hence the middle values
+/// are at the roots of the heaps, pushing a value takes O(log(n)) time and
+/// finding the median O(1).
+///
+/// Example:
+/// ```
+/// use ralg::streaming::RunningMedian;
+///
+/// let mut median = RunningMedian::new();
+/// median.push(5);
+/// median.push(1);
+/// assert_eq!(median.medians(), Some((&1, &5)));
+/// median.push(3);
+/// assert_eq!(median.median(), Some(&3));
+/// ```
+pub struct RunningMedian<T: PartialOrd> {
+    lower: MaxHeap<T>,
+    upper: MinHeap<T>,
+}
+
+impl<T: PartialOrd> RunningMedian<T> {
+    pub fn new() -> Self {
+        Self {
+            lower: MaxHeap::new(),
+            upper: MinHeap::new(),
+        }
+    }
+
+    /// Number of values pushed.
+    pub fn len(&self) -> usize {
+        self.lower.len() + self.upper.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+
+    pub fn push(&mut self, x: T) {
+        match self.lower.peek() {
+            Some(top) if x > *top => self.upper.push(x),
+            _ => self.lower.push(x),
+        }
+
+        // Restore the sizes of the halves
+        if self.lower.len() > self.upper.len() + 1 {
+            let top = self.lower.pop().expect("the lower half is not empty");
+            self.upper.push(top);
+        } else if self.upper.len() > self.lower.len() {
+            let top = self.upper.pop().expect("the upper half is not empty");
+            self.lower.push(top);
+        }
+    }
+
+    /// Lower median of the values: the middle one if their number is odd,
+    /// the smaller of the two middle ones otherwise.
+    pub fn median(&self) -> Option<&T> {
+        self.lower.peek()
+    }
+
+    /// Both middle values, in order, which are the same value when their
+    /// number is odd. The median of an even number of values is usually taken
+    /// to be the mean of the two.
+    pub fn medians(&self) -> Option<(&T, &T)> {
+        let low = self.lower.peek()?;
+        if self.lower.len() > self.upper.len() {
+            Some((low, low))
+        } else {
+            self.upper.peek().map(|high| (low, high))
+        }
+    }
+}
+
+impl<T: PartialOrd> Default for RunningMedian<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd> Extend<T> for RunningMedian<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Minimum and maximum of the last `window` values of a stream.
+///
+/// Each extreme is kept by a `MonotonicDeque` of the values in the window,
+/// holding only the candidates to be that extreme. Once the window is full,
+/// pushing a value pops the oldest one from both queues, so that each push
+/// takes O(1) amortized time.
+///
+/// Example:
+/// ```
+/// use ralg::streaming::RunningMinMax;
+///
+/// let mut window = RunningMinMax::new(3);
+/// let mut extremes = Vec::new();
+/// for x in [4, 2, 12, 3, 8, 1] {
+///     window.push(x);
+///     extremes.push((*window.min().unwrap(), *window.max().unwrap()));
+/// }
+/// assert_eq!(extremes, [(4, 4), (2, 4), (2, 12), (2, 12), (3, 12), (1, 8)]);
+/// ```
+pub struct RunningMinMax<T: PartialOrd> {
+    window: usize,
+    mins: MonotonicDeque<T>,
+    maxs: MonotonicDeque<T>,
+}
+
+impl<T: PartialOrd + Clone> RunningMinMax<T> {
+    /// Panics if `window == 0`.
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "window of length zero");
+        Self {
+            window,
+            mins: MonotonicDeque::minima(),
+            maxs: MonotonicDeque::maxima(),
+        }
+    }
+
+    /// Maximum number of values in the window.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Number of values in the window.
+    pub fn len(&self) -> usize {
+        self.mins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mins.is_empty()
+    }
+
+    /// Pushes `x`, evicting the oldest value if the window is full.
+    pub fn push(&mut self, x: T) {
+        if self.len() == self.window {
+            self.mins.pop_front();
+            self.maxs.pop_front();
+        }
+        self.mins.push_back(x.clone());
+        self.maxs.push_back(x);
+    }
+
+    /// Smallest value in the window.
+    pub fn min(&self) -> Option<&T> {
+        self.mins.front()
+    }
+
+    /// Greatest value in the window.
+    pub fn max(&self) -> Option<&T> {
+        self.maxs.front()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    #[test]
+    fn median_against_sorting() {
+        let mut rng = Pcg32::seed(0x3ed1);
+        let mut median = RunningMedian::new();
+        assert_eq!(median.medians(), None);
+        let mut seen = Vec::new();
+        for _ in 0..500 {
+            let x = rng.gen_range(-50..50);
+            median.push(x);
+            seen.push(x);
+            seen.sort();
+
+            let n = seen.len();
+            assert_eq!(median.len(), n);
+            let expected = (&seen[(n - 1) / 2], &seen[n / 2]);
+            assert_eq!(median.medians(), Some(expected));
+            assert_eq!(median.median(), Some(expected.0));
+        }
+    }
+
+    #[test]
+    fn min_max_against_naive() {
+        let mut rng = Pcg32::seed(0x51d3);
+        for window in [1, 2, 5, 17] {
+            let mut extremes = RunningMinMax::new(window);
+            assert_eq!((extremes.min(), extremes.max()), (None, None));
+            let xs: Vec<i32> = (0..300).map(|_| rng.gen_range(0..20)).collect();
+            for (i, &x) in xs.iter().enumerate() {
+                extremes.push(x);
+                let last = &xs[(i + 1).saturating_sub(window)..=i];
+                assert_eq!(extremes.len(), last.len());
+                assert_eq!(extremes.min(), last.iter().min());
+                assert_eq!(extremes.max(), last.iter().max());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_window() {
+        RunningMinMax::<f64>::new(0);
+    }
+}
@@ -0,0 +1,312 @@
+//! Longest common subsequences and edit distances
+//!
+//! Both are computed by dynamic programming over the prefixes of the two
+//! sequences, in O(nm) time and space, and the whole table is kept so that an
+//! optimal solution can be traced back, not only its length.
+use alloc::{vec, vec::Vec};
+
+/// A longest common subsequence of `a` and `b`.
+///
+/// Example:
+/// ```
+/// use ralg::strings::lcs;
+///
+/// assert_eq!(lcs(b"ABCBDAB", b"BDCABA").len(), 4);
+/// assert_eq!(lcs(b"AGGTAB", b"GXTXAYB"), b"GTAB");
+/// ```
+pub fn lcs<T: PartialEq + Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    let (n, m) = (a.len(), b.len());
+    // Length of a longest common subsequence of a[..i] and b[..j]
+    let mut len = vec![vec![0; m + 1]; n + 1];
+    for i in 1..=n {
+        for j in 1..=m {
+            len[i][j] = if a[i - 1] == b[j - 1] {
+                len[i - 1][j - 1] + 1
+            } else {
+                len[i - 1][j].max(len[i][j - 1])
+            };
+        }
+    }
+
+    let mut common = Vec::with_capacity(len[n][m]);
+    let (mut i, mut j) = (n, m);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            common.push(a[i - 1].clone());
+            i -= 1;
+            j -= 1;
+        } else if len[i - 1][j] >= len[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    common.reverse();
+    common
+}
+
+/// Operations allowed by an edit distance, each of unit cost.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Insertions, deletions and substitutions.
+    Levenshtein,
+    /// Levenshtein operations, plus transpositions of two adjacent elements.
+    /// This is the restricted variant, also known as optimal string
+    /// alignment, where no element is edited more than once.
+    Damerau,
+}
+
+/// Step of an edit script, turning `a` into `b`, with the positions in `a`
+/// and `b` it concerns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// `a[i]` is kept as `b[j]`, at no cost.
+    Keep(usize, usize),
+    /// `a[i]` is replaced by `b[j]`.
+    Substitute(usize, usize),
+    /// `b[j]` is inserted.
+    Insert(usize),
+    /// `a[i]` is deleted.
+    Delete(usize),
+    /// `a[i]` and `a[i + 1]` are swapped, giving `b[j]` and `b[j + 1]`.
+    Transpose(usize, usize),
+}
+
+impl Edit {
+    pub fn cost(self) -> usize {
+        match self {
+            Edit::Keep(..) => 0,
+            _ => 1,
+        }
+    }
+}
+
+/// Minimum number of edits turning one sequence into another, along with a
+/// script achieving it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alignment {
+    pub distance: usize,
+    /// Edits in order of position, covering every element of both sequences.
+    pub script: Vec<Edit>,
+}
+
+/// Edit distance from `a` to `b` with respect to `metric`, and an edit script
+/// turning `a` into `b`.
+///
+/// Example:
+/// ```
+/// use ralg::strings::{
+///     edit::{Edit, Metric},
+///     edit_distance,
+/// };
+///
+/// let kitten = edit_distance(b"kitten", b"sitting", Metric::Levenshtein);
+/// assert_eq!(kitten.distance, 3);
+/// assert_eq!(kitten.script[0], Edit::Substitute(0, 0));
+///
+/// assert_eq!(edit_distance(b"ab", b"ba", Metric::Levenshtein).distance, 2);
+/// let swap = edit_distance(b"ab", b"ba", Metric::Damerau);
+/// assert_eq!(swap.script, [Edit::Transpose(0, 0)]);
+/// ```
+pub fn edit_distance<T: PartialEq>(
+    a: &[T],
+    b: &[T],
+    metric: Metric,
+) -> Alignment {
+    let (n, m) = (a.len(), b.len());
+    let transposes = |i: usize, j: usize| {
+        metric == Metric::Damerau
+            && i > 1
+            && j > 1
+            && a[i - 1] == b[j - 2]
+            && a[i - 2] == b[j - 1]
+    };
+
+    // Edit distance from a[..i] to b[..j]
+    let mut dist = vec![vec![0; m + 1]; n + 1];
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, d) in dist[0].iter_mut().enumerate() {
+        *d = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitution = usize::from(a[i - 1] != b[j - 1]);
+            let mut d = (dist[i - 1][j - 1] + substitution)
+                .min(dist[i - 1][j] + 1)
+                .min(dist[i][j - 1] + 1);
+            if transposes(i, j) {
+                d = d.min(dist[i - 2][j - 2] + 1);
+            }
+            dist[i][j] = d;
+        }
+    }
+
+    // Trace back one of the choices made for each entry
+    let mut script = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        let d = dist[i][j];
+        let edit = if i > 0
+            && j > 0
+            && a[i - 1] == b[j - 1]
+            && d == dist[i - 1][j - 1]
+        {
+            Edit::Keep(i - 1, j - 1)
+        } else if i > 0 && j > 0 && d == dist[i - 1][j - 1] + 1 {
+            Edit::Substitute(i - 1, j - 1)
+        } else if transposes(i, j) && d == dist[i - 2][j - 2] + 1 {
+            Edit::Transpose(i - 2, j - 2)
+        } else if i > 0 && d == dist[i - 1][j] + 1 {
+            Edit::Delete(i - 1)
+        } else {
+            Edit::Insert(j - 1)
+        };
+        match edit {
+            Edit::Keep(..) | Edit::Substitute(..) => (i, j) = (i - 1, j - 1),
+            Edit::Transpose(..) => (i, j) = (i - 2, j - 2),
+            Edit::Delete(_) => i -= 1,
+            Edit::Insert(_) => j -= 1,
+        }
+        script.push(edit);
+    }
+    script.reverse();
+    Alignment {
+        distance: dist[n][m],
+        script,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    fn is_subsequence<T: PartialEq>(sub: &[T], xs: &[T]) -> bool {
+        let mut rest = xs.iter();
+        sub.iter().all(|x| rest.any(|y| y == x))
+    }
+
+    /// Runs the edit script on `a`, checking that it visits both sequences
+    /// in order.
+    fn apply(a: &[u8], b: &[u8], script: &[Edit]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        for &edit in script {
+            match edit {
+                Edit::Keep(x, y) | Edit::Substitute(x, y) => {
+                    assert_eq!((x, y), (i, j));
+                    out.push(if let Edit::Keep(..) = edit {
+                        a[x]
+                    } else {
+                        b[y]
+                    });
+                    (i, j) = (i + 1, j + 1);
+                }
+                Edit::Transpose(x, y) => {
+                    assert_eq!((x, y), (i, j));
+                    out.extend([a[x + 1], a[x]]);
+                    (i, j) = (i + 2, j + 2);
+                }
+                Edit::Insert(y) => {
+                    assert_eq!(y, j);
+                    out.push(b[y]);
+                    j += 1;
+                }
+                Edit::Delete(x) => {
+                    assert_eq!(x, i);
+                    i += 1;
+                }
+            }
+        }
+        assert_eq!((i, j), (a.len(), b.len()));
+        out
+    }
+
+    #[test]
+    fn examples() {
+        assert!(lcs::<u8>(b"", b"abc").is_empty());
+        assert_eq!(lcs(b"abc", b"abc"), b"abc");
+        assert!(lcs(b"abc", b"def").is_empty());
+
+        let empty = edit_distance(b"", b"abc", Metric::Levenshtein);
+        assert_eq!(
+            empty.script,
+            [Edit::Insert(0), Edit::Insert(1), Edit::Insert(2)]
+        );
+        assert_eq!(edit_distance(b"abc", b"", Metric::Damerau).distance, 3);
+        assert_eq!(edit_distance(b"", b"", Metric::Damerau).script, []);
+
+        // The restricted distance cannot edit the transposed pair again
+        assert_eq!(edit_distance(b"ca", b"abc", Metric::Damerau).distance, 3);
+        let swaps = edit_distance(b"abcdef", b"badcfe", Metric::Damerau);
+        assert_eq!(swaps.distance, 3);
+        assert_eq!(apply(b"abcdef", b"badcfe", &swaps.script), b"badcfe");
+    }
+
+    #[test]
+    fn lcs_brute_force() {
+        let mut rng = Pcg32::seed(0x9b05688c2b3e6c1f);
+        for _ in 0..300 {
+            let a: Vec<u8> = (0..rng.gen_range(0..9))
+                .map(|_| rng.gen_range(0..3))
+                .collect();
+            let b: Vec<u8> = (0..rng.gen_range(0..12))
+                .map(|_| rng.gen_range(0..3))
+                .collect();
+            let common = lcs(&a, &b);
+            assert!(is_subsequence(&common, &a));
+            assert!(is_subsequence(&common, &b));
+
+            // Longest among the subsequences of a
+            let longest = (0..1_u32 << a.len())
+                .map(|mask| {
+                    let sub: Vec<u8> = (0..a.len())
+                        .filter(|&i| mask >> i & 1 == 1)
+                        .map(|i| a[i])
+                        .collect();
+                    sub
+                })
+                .filter(|sub| is_subsequence(sub, &b))
+                .map(|sub| sub.len())
+                .max();
+            assert_eq!(Some(common.len()), longest);
+        }
+    }
+
+    #[test]
+    fn edit_scripts() {
+        let mut rng = Pcg32::seed(0x1f83d9abfb41bd6b);
+        for _ in 0..500 {
+            let a: Vec<u8> = (0..rng.gen_range(0..15))
+                .map(|_| rng.gen_range(0..4))
+                .collect();
+            let b: Vec<u8> = (0..rng.gen_range(0..15))
+                .map(|_| rng.gen_range(0..4))
+                .collect();
+            let lev = edit_distance(&a, &b, Metric::Levenshtein);
+            let dam = edit_distance(&a, &b, Metric::Damerau);
+            for alignment in [&lev, &dam] {
+                assert_eq!(apply(&a, &b, &alignment.script), b);
+                let cost: usize =
+                    alignment.script.iter().map(|e| e.cost()).sum();
+                assert_eq!(cost, alignment.distance);
+            }
+            assert!(dam.distance <= lev.distance);
+            assert!(!lev
+                .script
+                .iter()
+                .any(|e| matches!(e, Edit::Transpose(..))));
+            assert_eq!(
+                edit_distance(&b, &a, Metric::Damerau).distance,
+                dam.distance
+            );
+
+            // Without substitutions, the distance would be n + m - 2 lcs
+            let common = lcs(&a, &b).len();
+            assert!(lev.distance <= a.len() + b.len() - 2 * common);
+            assert!(lev.distance >= a.len().max(b.len()) - common);
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Exact string matching
+//!
+//! The functions in this module find every occurrence of a pattern in a text,
+//! both given as slices, so that they work on bytes, characters or any other
+//! alphabet. Occurrences are reported by their starting position in the text,
+//! in increasing order, and may overlap. The empty pattern occurs at every
+//! position, the end of the text included.
+use crate::math::modular::Zmod;
+use alloc::{vec, vec::Vec};
+
+/// Prefix function of `pattern`: the length of the longest proper prefix of
+/// `pattern[..=i]` which is also a suffix of it, at index `i`.
+///
+/// Example:
+/// ```
+/// use ralg::strings::matching::prefix_function;
+///
+/// assert_eq!(prefix_function(b"abacaba"), [0, 0, 1, 0, 1, 2, 3]);
+/// ```
+pub fn prefix_function<T: PartialEq>(pattern: &[T]) -> Vec<usize> {
+    let mut pi = vec![0; pattern.len()];
+    let mut k = 0;
+    for i in 1..pattern.len() {
+        while k > 0 && pattern[i] != pattern[k] {
+            k = pi[k - 1];
+        }
+        if pattern[i] == pattern[k] {
+            k += 1;
+        }
+        pi[i] = k;
+    }
+    pi
+}
+
+/// Knuth-Morris-Pratt algorithm: scans the text once, falling back along the
+/// prefix function of the pattern on mismatches, so that it runs in
+/// O(n + m) time.
+///
+/// Example:
+/// ```
+/// use ralg::strings::kmp_search;
+///
+/// assert_eq!(kmp_search(b"ana", b"bananas"), [1, 3]);
+/// assert_eq!(kmp_search(&[1, 1], &[1, 1, 1]), [0, 1]);
+/// ```
+pub fn kmp_search<T: PartialEq>(pattern: &[T], text: &[T]) -> Vec<usize> {
+    let m = pattern.len();
+    if m == 0 {
+        return (0..=text.len()).collect();
+    }
+    let pi = prefix_function(pattern);
+    let mut matches = Vec::new();
+    let mut k = 0;
+    for (i, x) in text.iter().enumerate() {
+        while k > 0 && *x != pattern[k] {
+            k = pi[k - 1];
+        }
+        if *x == pattern[k] {
+            k += 1;
+        }
+        if k == m {
+            matches.push(i + 1 - m);
+            k = pi[k - 1];
+        }
+    }
+    matches
+}
+
+/// Z-array of `s`: the length of the longest common prefix of `s` and
+/// `s[i..]`, at index `i`. Computed in O(n) time by reusing the rightmost
+/// match found so far.
+///
+/// Example:
+/// ```
+/// use ralg::strings::z_array;
+///
+/// assert_eq!(z_array(b"aabxaab"), [7, 1, 0, 0, 3, 1, 0]);
+/// ```
+pub fn z_array<T: PartialEq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+    // The match s[l..r] == s[..r - l] reaching furthest to the right
+    let (mut l, mut r) = (0, 0);
+    for i in 1..n {
+        let mut k = if i < r { z[i - l].min(r - i) } else { 0 };
+        while i + k < n && s[k] == s[i + k] {
+            k += 1;
+        }
+        z[i] = k;
+        if i + k > r {
+            (l, r) = (i, i + k);
+        }
+    }
+    z
+}
+
+/// Matching with the Z-algorithm: the pattern occurs wherever the Z-array of
+/// the pattern, followed by a separator and the text, reaches the length of
+/// the pattern. Takes O(n + m) time.
+///
+/// Example:
+/// ```
+/// use ralg::strings::z_search;
+///
+/// let text: Vec<char> = "mississippi".chars().collect();
+/// assert_eq!(z_search(&['s', 's', 'i'], &text), [2, 5]);
+/// ```
+pub fn z_search<T: PartialEq>(pattern: &[T], text: &[T]) -> Vec<usize> {
+    let m = pattern.len();
+    if m == 0 {
+        return (0..=text.len()).collect();
+    }
+    // The separator `None` matches nothing in the text
+    let joined: Vec<Option<&T>> = pattern
+        .iter()
+        .map(Some)
+        .chain([None])
+        .chain(text.iter().map(Some))
+        .collect();
+    z_array(&joined)[m + 1..]
+        .iter()
+        .enumerate()
+        .filter(|&(_, &z)| z == m)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Mersenne prime `2^61 - 1`, modulus of the rolling hash.
+const MODULUS: u64 = (1 << 61) - 1;
+
+/// Base of the rolling hash, as a polynomial evaluated modulo `MODULUS`.
+const BASE: u64 = 0x9e37_79b9_7f4a_7c15 % MODULUS;
+
+/// Rabin-Karp algorithm: slides a window over the text, keeping a rolling
+/// hash of it, and only compares the window with the pattern when their
+/// hashes agree. Runs in O(n + m) expected time, since collisions are rare,
+/// and never reports a false match.
+///
+/// Example:
+/// ```
+/// use ralg::strings::rabin_karp;
+///
+/// assert_eq!(rabin_karp(b"ss", b"mississippi"), [2, 5]);
+/// assert_eq!(rabin_karp(&[3_u32, 1], &[3, 1, 4, 1, 5]), [0]);
+/// ```
+pub fn rabin_karp<T>(pattern: &[T], text: &[T]) -> Vec<usize>
+where
+    T: Copy + PartialEq + Into<u64>,
+{
+    type Residue = Zmod<MODULUS>;
+    let m = pattern.len();
+    if m == 0 {
+        return (0..=text.len()).collect();
+    }
+    if m > text.len() {
+        return Vec::new();
+    }
+
+    let base = Residue::new(BASE);
+    let digit = |x: T| Residue::new(x.into());
+    let hash =
+        |xs: &[T]| xs.iter().fold(Residue::new(0), |h, &x| h * base + digit(x));
+    // Weight of the first element of the window
+    let top = base.pow_mod(m as u64 - 1);
+
+    let target = hash(pattern);
+    let mut window = hash(&text[..m]);
+    let mut matches = Vec::new();
+    for i in 0..=text.len() - m {
+        if window == target && text[i..i + m] == *pattern {
+            matches.push(i);
+        }
+        if i + m < text.len() {
+            window =
+                (window - digit(text[i]) * top) * base + digit(text[i + m]);
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    fn naive<T: PartialEq>(pattern: &[T], text: &[T]) -> Vec<usize> {
+        (0..=text.len())
+            .filter(|&i| text[i..].starts_with(pattern))
+            .collect()
+    }
+
+    #[test]
+    fn edge_cases() {
+        let text = b"abc";
+        for search in [kmp_search, z_search, rabin_karp] {
+            assert_eq!(search(b"", text), [0, 1, 2, 3]);
+            assert_eq!(search(b"", b""), [0]);
+            assert!(search(b"abcd", text).is_empty());
+            assert_eq!(search(text, text), [0]);
+            assert!(search(b"c", b"").is_empty());
+        }
+        assert!(z_array::<u8>(&[]).is_empty());
+        assert_eq!(z_array(b"aaaa"), [4, 3, 2, 1]);
+        assert_eq!(prefix_function(b"aabaaab"), [0, 1, 0, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn wide_alphabet() {
+        // Elements beyond the modulus of the rolling hash
+        let text = [u64::MAX, 7, u64::MAX - 1, u64::MAX, 7];
+        assert_eq!(rabin_karp(&[u64::MAX, 7], &text), [0, 3]);
+        assert_eq!(kmp_search(&[u64::MAX, 7], &text), [0, 3]);
+    }
+
+    #[test]
+    fn fuzz_against_naive() {
+        let mut rng = Pcg32::seed(0x510e527fade682d1);
+        for _ in 0..2000 {
+            // Small alphabets make for many overlapping matches
+            let sigma = rng.gen_range(1..4);
+            let text: Vec<u8> = (0..rng.gen_range(0..40))
+                .map(|_| rng.gen_range(0..sigma))
+                .collect();
+            let pattern: Vec<u8> = (0..rng.gen_range(0..6))
+                .map(|_| rng.gen_range(0..sigma))
+                .collect();
+            let expected = naive(&pattern, &text);
+            assert_eq!(kmp_search(&pattern, &text), expected);
+            assert_eq!(z_search(&pattern, &text), expected);
+            assert_eq!(rabin_karp(&pattern, &text), expected);
+
+            let z = z_array(&text);
+            for (i, &k) in z.iter().enumerate() {
+                let lcp =
+                    text[i..].iter().zip(&text).take_while(|(a, b)| a == b);
+                assert_eq!(lcp.count(), k);
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod edit;
+pub mod matching;
+pub mod suffix;
+
+pub use edit::{edit_distance, lcs};
+pub use matching::{kmp_search, rabin_karp, z_array, z_search};
+pub use suffix::{suffix_array, SuffixArray};
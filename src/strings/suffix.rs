@@ -0,0 +1,255 @@
+//! Suffix arrays and longest common prefixes
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+/// Suffix array of `s`: the starting positions of the non-empty suffixes of
+/// `s`, in lexicographic order of the suffixes.
+///
+/// Built by prefix doubling: the suffixes are sorted by their first `k`
+/// elements, then by their first `2k` from the ranks of both halves, using
+/// two counting sort passes per round, so that it takes O(n log(n)) time.
+///
+/// Example:
+/// ```
+/// use ralg::strings::suffix_array;
+///
+/// assert_eq!(suffix_array(b"banana"), [5, 3, 1, 0, 4, 2]);
+/// ```
+pub fn suffix_array<T: Ord>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    sa.sort_by(|&i, &j| s[i].cmp(&s[j]));
+    if n == 0 {
+        return sa;
+    }
+
+    // Rank of each suffix among the prefixes of length k, equal prefixes
+    // sharing their rank
+    let mut rank = vec![0; n];
+    for w in 1..n {
+        let (i, j) = (sa[w - 1], sa[w]);
+        rank[j] = rank[i] + usize::from(s[i] != s[j]);
+    }
+    let mut next_rank = vec![0; n];
+    let mut second = Vec::with_capacity(n);
+    let mut start = vec![0; n];
+    let mut k = 1;
+    while rank[sa[n - 1]] < n - 1 {
+        // Order by the rank of the second half, the suffixes without one
+        // coming first
+        second.clear();
+        second.extend(n.saturating_sub(k)..n);
+        second.extend(sa.iter().filter(|&&i| i >= k).map(|&i| i - k));
+
+        // Stable counting sort by the rank of the first half
+        start.fill(0);
+        for &i in &second {
+            start[rank[i]] += 1;
+        }
+        let mut sum = 0;
+        for count in start.iter_mut() {
+            (*count, sum) = (sum, sum + *count);
+        }
+        for &i in &second {
+            sa[start[rank[i]]] = i;
+            start[rank[i]] += 1;
+        }
+
+        let key = |i: usize| (rank[i], rank.get(i + k));
+        next_rank[sa[0]] = 0;
+        for w in 1..n {
+            let (i, j) = (sa[w - 1], sa[w]);
+            next_rank[j] = next_rank[i] + usize::from(key(i) != key(j));
+        }
+        mem::swap(&mut rank, &mut next_rank);
+        k *= 2;
+    }
+    sa
+}
+
+/// Kasai's algorithm: the length of the longest common prefix of the suffixes
+/// at `sa[i - 1]` and `sa[i]`, at index `i`, where `sa` is the suffix array of
+/// `s`. The first entry is zero.
+///
+/// Going through the suffixes from the longest, the common prefix with the
+/// preceding suffix in `sa` shrinks by at most one at each step, so that it
+/// takes O(n) time.
+///
+/// Example:
+/// ```
+/// use ralg::strings::suffix::{lcp_array, suffix_array};
+///
+/// let sa = suffix_array(b"banana");
+/// assert_eq!(lcp_array(b"banana", &sa), [0, 1, 3, 0, 0, 2]);
+/// ```
+pub fn lcp_array<T: PartialEq>(s: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut rank = vec![0; n];
+    for (r, &i) in sa.iter().enumerate() {
+        rank[i] = r;
+    }
+    let mut lcp = vec![0; n];
+    let mut h: usize = 0;
+    for (i, &r) in rank.iter().enumerate() {
+        if r == 0 {
+            h = 0;
+            continue;
+        }
+        let j = sa[r - 1];
+        while i + h < n && j + h < n && s[i + h] == s[j + h] {
+            h += 1;
+        }
+        lcp[r] = h;
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Suffix array of a text along with its LCP array, answering substring
+/// queries.
+///
+/// Example:
+/// ```
+/// use ralg::strings::SuffixArray;
+///
+/// let index = SuffixArray::new(b"mississippi");
+/// assert_eq!(index.find(b"issi"), [1, 4]);
+/// assert!(!index.contains(b"spi"));
+/// assert_eq!(index.longest_repeated_substring(), Some(&b"issi"[..]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SuffixArray<'a, T> {
+    text: &'a [T],
+    sa: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl<'a, T: Ord> SuffixArray<'a, T> {
+    pub fn new(text: &'a [T]) -> Self {
+        let sa = suffix_array(text);
+        let lcp = lcp_array(text, &sa);
+        Self { text, sa, lcp }
+    }
+
+    pub fn text(&self) -> &'a [T] {
+        self.text
+    }
+
+    /// The suffix array, as given by `suffix_array`.
+    pub fn suffixes(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// The LCP array, as given by `lcp_array`.
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// Range of `self.sa` holding the suffixes starting with `pattern`,
+    /// found by binary search in O(m log(n)) time.
+    fn range(&self, pattern: &[T]) -> (usize, usize) {
+        let prefix = |i: usize| {
+            let suffix = &self.text[i..];
+            &suffix[..pattern.len().min(suffix.len())]
+        };
+        let lo = self.sa.partition_point(|&i| prefix(i) < pattern);
+        let hi = self.sa.partition_point(|&i| prefix(i) <= pattern);
+        (lo, hi)
+    }
+
+    /// Every position of the text where `pattern` starts, in increasing
+    /// order. The empty pattern occurs everywhere, the end of the text
+    /// included.
+    pub fn find(&self, pattern: &[T]) -> Vec<usize> {
+        if pattern.is_empty() {
+            return (0..=self.text.len()).collect();
+        }
+        let (lo, hi) = self.range(pattern);
+        let mut positions = self.sa[lo..hi].to_vec();
+        positions.sort_unstable();
+        positions
+    }
+
+    pub fn contains(&self, pattern: &[T]) -> bool {
+        let (lo, hi) = self.range(pattern);
+        pattern.is_empty() || lo < hi
+    }
+
+    /// Longest substring occurring at least twice in the text, possibly
+    /// overlapping, or `None` if no element repeats. It is the longest common
+    /// prefix of two suffixes adjacent in the suffix array.
+    pub fn longest_repeated_substring(&self) -> Option<&'a [T]> {
+        let (r, &len) =
+            self.lcp.iter().enumerate().max_by_key(|&(_, &len)| len)?;
+        let start = self.sa[r];
+        (len > 0).then(|| &self.text[start..start + len])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use crate::strings::kmp_search;
+
+    #[test]
+    fn small_texts() {
+        assert!(suffix_array::<u8>(&[]).is_empty());
+        assert_eq!(suffix_array(b"a"), [0]);
+        assert_eq!(suffix_array(b"aaaa"), [3, 2, 1, 0]);
+        assert_eq!(lcp_array(b"aaaa", &[3, 2, 1, 0]), [0, 1, 2, 3]);
+
+        let empty = SuffixArray::<u8>::new(&[]);
+        assert_eq!(empty.find(&[]), [0]);
+        assert_eq!(empty.longest_repeated_substring(), None);
+        let distinct = SuffixArray::new(b"abc");
+        assert_eq!(distinct.longest_repeated_substring(), None);
+        assert_eq!(distinct.find(b"abcd"), []);
+        assert!(distinct.contains(b"bc"));
+
+        let words = ["to", "be", "or", "not", "to", "be"];
+        let index = SuffixArray::new(&words);
+        assert_eq!(index.longest_repeated_substring(), Some(&words[..2]));
+        assert_eq!(index.find(&["be"]), [1, 5]);
+    }
+
+    #[test]
+    fn against_naive() {
+        let mut rng = Pcg32::seed(0x5be0cd19137e2179);
+        for _ in 0..500 {
+            let sigma = rng.gen_range(1..5);
+            let text: Vec<u8> = (0..rng.gen_range(0..60))
+                .map(|_| rng.gen_range(0..sigma))
+                .collect();
+            let index = SuffixArray::new(&text);
+
+            let mut naive: Vec<usize> = (0..text.len()).collect();
+            naive.sort_by_key(|&i| &text[i..]);
+            assert_eq!(index.suffixes(), naive);
+            for r in 1..text.len() {
+                let (a, b) = (&text[naive[r - 1]..], &text[naive[r]..]);
+                let common = a.iter().zip(b).take_while(|(x, y)| x == y);
+                assert_eq!(index.lcp()[r], common.count());
+            }
+
+            for _ in 0..5 {
+                let pattern: Vec<u8> = (0..rng.gen_range(0..4))
+                    .map(|_| rng.gen_range(0..sigma))
+                    .collect();
+                let expected = kmp_search(&pattern, &text);
+                assert_eq!(index.contains(&pattern), !expected.is_empty());
+                assert_eq!(index.find(&pattern), expected);
+            }
+
+            // No substring one element longer repeats
+            let repeated = index.longest_repeated_substring();
+            let len = repeated.map_or(0, |r| r.len());
+            if let Some(r) = repeated {
+                assert!(index.find(r).len() >= 2);
+            }
+            for w in text.windows(len + 1) {
+                assert_eq!(index.find(w).len(), 1);
+            }
+        }
+    }
+}
@@ -0,0 +1,487 @@
+//! AVL tree augmented with subtree sizes
+use alloc::{boxed::Box, vec::Vec};
+use core::cmp::Ordering;
+
+/// Ordered map over an AVL tree: a binary search tree where the heights of
+/// the two subtrees of any node differ by at most one, which keeps the height
+/// below `1.45 log2(n + 2)`. Every insertion or removal restores this balance
+/// with at most O(log n) rotations on its way back to the root.
+///
+/// Each node also stores the size of its subtree, so that the `k`th smallest
+/// key (`select`) and the number of keys less than a given one (`rank`) are
+/// found in O(log n).
+///
+/// Example:
+/// ```
+/// use ralg::tree::avl::AvlTree;
+///
+/// let tree: AvlTree<i32, char> =
+///     [(30, 'c'), (10, 'a'), (20, 'b'), (40, 'd')].into_iter().collect();
+/// assert_eq!(tree.select(1), Some((&20, &'b')));
+/// assert_eq!(tree.rank(&35), 3);
+/// // Inserting 30, 10, 20 required a double rotation
+/// assert_eq!(tree.rotations(), 2);
+/// ```
+pub struct AvlTree<K: Ord, V> {
+    root: Link<K, V>,
+    rotations: usize,
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    height: usize,
+    size: usize,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+fn height<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+fn size<K, V>(link: &Link<K, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+impl<K, V> Node<K, V> {
+    fn new(key: K, value: V) -> Box<Self> {
+        Box::new(Node {
+            key,
+            value,
+            height: 1,
+            size: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    /// Recomputes the height and size from the children.
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+
+    /// Height of the left subtree minus the height of the right one.
+    fn balance_factor(&self) -> isize {
+        height(&self.left) as isize - height(&self.right) as isize
+    }
+}
+
+//     y              x
+//    / \            / \
+//   x   c   <->    a   y
+//  / \                / \
+// a   b              b   c
+
+/// Rotates `y` to the right, making its left child `x` the new root.
+fn rotate_right<K, V>(mut y: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut x = y.left.take().expect("rotation needs a left child");
+    y.left = x.right.take();
+    y.update();
+    x.right = Some(y);
+    x.update();
+    x
+}
+
+/// Rotates `x` to the left, making its right child `y` the new root.
+fn rotate_left<K, V>(mut x: Box<Node<K, V>>) -> Box<Node<K, V>> {
+    let mut y = x.right.take().expect("rotation needs a right child");
+    x.right = y.left.take();
+    x.update();
+    y.left = Some(x);
+    y.update();
+    y
+}
+
+/// Restores the AVL property at `node`, whose subtrees are balanced and
+/// differ in height by at most two, counting the rotations performed.
+fn rebalance<K, V>(
+    mut node: Box<Node<K, V>>,
+    rotations: &mut usize,
+) -> Box<Node<K, V>> {
+    node.update();
+    match node.balance_factor() {
+        2 => {
+            let left = node.left.take().unwrap();
+            node.left = Some(if left.balance_factor() < 0 {
+                // Left-right case
+                *rotations += 1;
+                rotate_left(left)
+            } else {
+                left
+            });
+            *rotations += 1;
+            rotate_right(node)
+        }
+        -2 => {
+            let right = node.right.take().unwrap();
+            node.right = Some(if right.balance_factor() > 0 {
+                // Right-left case
+                *rotations += 1;
+                rotate_right(right)
+            } else {
+                right
+            });
+            *rotations += 1;
+            rotate_left(node)
+        }
+        _ => node,
+    }
+}
+
+fn insert<K: Ord, V>(
+    link: Link<K, V>,
+    key: K,
+    value: V,
+    rotations: &mut usize,
+) -> (Box<Node<K, V>>, Option<V>) {
+    let mut node = match link {
+        None => return (Node::new(key, value), None),
+        Some(node) => node,
+    };
+    let old = match key.cmp(&node.key) {
+        Ordering::Less => {
+            let (left, old) = insert(node.left.take(), key, value, rotations);
+            node.left = Some(left);
+            old
+        }
+        Ordering::Greater => {
+            let (right, old) = insert(node.right.take(), key, value, rotations);
+            node.right = Some(right);
+            old
+        }
+        Ordering::Equal => {
+            let old = core::mem::replace(&mut node.value, value);
+            return (node, Some(old));
+        }
+    };
+    (rebalance(node, rotations), old)
+}
+
+/// Detaches the node with the smallest key from the subtree rooted at
+/// `node`, returning it along with the rest of the subtree.
+fn remove_min<K, V>(
+    mut node: Box<Node<K, V>>,
+    rotations: &mut usize,
+) -> (Box<Node<K, V>>, Link<K, V>) {
+    match node.left.take() {
+        None => {
+            let rest = node.right.take();
+            (node, rest)
+        }
+        Some(left) => {
+            let (min, rest) = remove_min(left, rotations);
+            node.left = rest;
+            (min, Some(rebalance(node, rotations)))
+        }
+    }
+}
+
+fn remove<K: Ord, V>(
+    link: &mut Link<K, V>,
+    key: &K,
+    rotations: &mut usize,
+) -> Option<V> {
+    let node = link.as_mut()?;
+    let removed = match key.cmp(&node.key) {
+        Ordering::Less => remove(&mut node.left, key, rotations),
+        Ordering::Greater => remove(&mut node.right, key, rotations),
+        Ordering::Equal => {
+            let mut node = link.take().unwrap();
+            *link = match (node.left.take(), node.right.take()) {
+                (None, None) => None,
+                (Some(child), None) | (None, Some(child)) => Some(child),
+                (Some(left), Some(right)) => {
+                    // Replace the node by its successor
+                    let (mut min, rest) = remove_min(right, rotations);
+                    min.left = Some(left);
+                    min.right = rest;
+                    Some(rebalance(min, rotations))
+                }
+            };
+            return Some(node.value);
+        }
+    };
+    if removed.is_some() {
+        let node = link.take().unwrap();
+        *link = Some(rebalance(node, rotations));
+    }
+    removed
+}
+
+impl<K: Ord, V> AvlTree<K, V> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            rotations: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Number of nodes on the longest path from the root to a leaf.
+    pub fn height(&self) -> usize {
+        height(&self.root)
+    }
+
+    /// Total number of rotations performed so far to keep the tree balanced,
+    /// a double rotation counting as two.
+    pub fn rotations(&self) -> usize {
+        self.rotations
+    }
+
+    /// Inserts `value` under `key`, returning the value previously stored
+    /// under it, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (root, old) =
+            insert(self.root.take(), key, value, &mut self.rotations);
+        self.root = Some(root);
+        old
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        remove(&mut self.root, key, &mut self.rotations)
+    }
+
+    fn find(&self, key: &K) -> Option<&Node<K, V>> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(node),
+            };
+        }
+        None
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|node| &node.value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Entry with the `k`th smallest key, counting from zero.
+    pub fn select(&self, mut k: usize) -> Option<(&K, &V)> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            let left = size(&node.left);
+            current = match k.cmp(&left) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => {
+                    k -= left + 1;
+                    node.right.as_deref()
+                }
+            };
+        }
+        None
+    }
+
+    /// Number of keys in the tree less than `key`, which need not be in the
+    /// tree. For keys in the tree, this is their position in sorted order.
+    pub fn rank(&self, key: &K) -> usize {
+        let mut rank = 0;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Equal => return rank + size(&node.left),
+                Ordering::Greater => {
+                    rank += size(&node.left) + 1;
+                    node.right.as_deref()
+                }
+            };
+        }
+        rank
+    }
+
+    /// Iterates over the entries in increasing order of keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left(self.root.as_deref());
+        iter
+    }
+
+    /// Iterates over the keys visiting each node before its subtrees, along
+    /// with the height of the node, which shows the shape of the tree.
+    pub fn preorder(&self) -> impl Iterator<Item = (&K, usize)> + '_ {
+        let mut stack: Vec<&Node<K, V>> =
+            self.root.as_deref().into_iter().collect();
+        core::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.right.as_deref());
+            stack.extend(node.left.as_deref());
+            Some((&node.key, node.height))
+        })
+    }
+}
+
+impl<K: Ord, V> Default for AvlTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for AvlTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = AvlTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left(&mut self, mut node: Option<&'a Node<K, V>>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use std::collections::BTreeMap;
+
+    /// Checks the ordering, heights, sizes and balance of every subtree,
+    /// returning the height.
+    fn check<K: Ord, V>(link: &Link<K, V>) -> usize {
+        let node = match link {
+            None => return 0,
+            Some(node) => node,
+        };
+        if let Some(left) = &node.left {
+            assert!(left.key < node.key);
+        }
+        if let Some(right) = &node.right {
+            assert!(right.key > node.key);
+        }
+        let (l, r) = (check(&node.left), check(&node.right));
+        assert!(l.abs_diff(r) <= 1, "unbalanced node");
+        assert_eq!(node.height, 1 + l.max(r));
+        assert_eq!(node.size, 1 + size(&node.left) + size(&node.right));
+        node.height
+    }
+
+    #[test]
+    fn rotations() {
+        // Right-right case: a single left rotation
+        let tree: AvlTree<i32, ()> = (1..=3).map(|k| (k, ())).collect();
+        assert_eq!(tree.rotations(), 1);
+        let shape: Vec<_> = tree.preorder().collect();
+        assert_eq!(shape, [(&2, 2), (&1, 1), (&3, 1)]);
+
+        // Left-left case
+        let tree: AvlTree<i32, ()> = (1..=3).rev().map(|k| (k, ())).collect();
+        assert_eq!(tree.rotations(), 1);
+        assert_eq!(tree.preorder().next(), Some((&2, 2)));
+
+        // Left-right case: a double rotation
+        let tree: AvlTree<i32, ()> =
+            [3, 1, 2].into_iter().map(|k| (k, ())).collect();
+        assert_eq!(tree.rotations(), 2);
+        assert_eq!(tree.preorder().next(), Some((&2, 2)));
+
+        // Sorted insertions stay logarithmic
+        let tree: AvlTree<i32, ()> = (0..1023).map(|k| (k, ())).collect();
+        assert_eq!(tree.height(), 10);
+        check(&tree.root);
+    }
+
+    #[test]
+    fn select_and_rank() {
+        let tree: AvlTree<i32, i32> = (0..100).map(|k| (3 * k, k)).collect();
+        for k in 0..100 {
+            assert_eq!(tree.select(k as usize), Some((&(3 * k), &k)));
+            assert_eq!(tree.rank(&(3 * k)), k as usize);
+            assert_eq!(tree.rank(&(3 * k + 1)), k as usize + 1);
+        }
+        assert_eq!(tree.select(100), None);
+        assert_eq!(tree.rank(&-1), 0);
+        assert_eq!(tree.rank(&1000), 100);
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        let mut tree = AvlTree::new();
+        let mut expected = BTreeMap::new();
+        let mut rng = Pcg32::seed(0x243f6a8885a308d3);
+        for step in 0..5000 {
+            let key = rng.gen_range(0..400_u64);
+            if rng.gen_range(0..3) == 0 {
+                assert_eq!(tree.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(tree.insert(key, step), expected.insert(key, step));
+            }
+            assert_eq!(tree.len(), expected.len());
+            if step % 500 == 0 {
+                check(&tree.root);
+            }
+        }
+        check(&tree.root);
+        assert!(tree.iter().eq(expected.iter()));
+
+        for (i, (k, _)) in expected.iter().enumerate() {
+            assert_eq!(tree.select(i).map(|(k, _)| k), Some(k));
+            assert_eq!(tree.rank(k), i);
+        }
+    }
+
+    #[test]
+    fn get_and_remove() {
+        let mut tree: AvlTree<i32, i32> = (0..10).map(|k| (k, k * k)).collect();
+        assert_eq!(tree.get(&3), Some(&9));
+        *tree.get_mut(&3).unwrap() = 0;
+        assert_eq!(tree.insert(3, 1), Some(0));
+        assert!(tree.contains_key(&9));
+        assert_eq!(tree.remove(&10), None);
+        for k in 0..10 {
+            assert!(tree.remove(&k).is_some());
+            check(&tree.root);
+        }
+        assert!(tree.is_empty());
+    }
+}
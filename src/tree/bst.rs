@@ -0,0 +1,460 @@
+//! Unbalanced binary search tree
+use alloc::{boxed::Box, vec::Vec};
+use core::cmp::Ordering;
+
+/// Ordered map over a binary search tree: every key in the left subtree of a
+/// node is less than the key of the node, and every key in its right subtree
+/// is greater. Operations take time proportional to the height of the tree,
+/// which is O(log n) for random insertions but O(n) in the worst case, as no
+/// rebalancing is done.
+///
+/// Example:
+/// ```
+/// use ralg::tree::bst::BinarySearchTree;
+///
+/// let mut tree = BinarySearchTree::new();
+/// for (k, v) in [(5, 'e'), (2, 'b'), (8, 'h'), (1, 'a')] {
+///     tree.insert(k, v);
+/// }
+/// assert_eq!(tree.get(&2), Some(&'b'));
+/// assert_eq!(tree.successor(&2), Some((&5, &'e')));
+/// assert_eq!(tree.remove(&5), Some('e'));
+/// assert_eq!(tree.keys().collect::<Vec<_>>(), [&1, &2, &8]);
+/// ```
+pub struct BinarySearchTree<K: Ord, V> {
+    root: Link<K, V>,
+    len: usize,
+}
+
+type Link<K, V> = Option<Box<Node<K, V>>>;
+
+struct Node<K, V> {
+    key: K,
+    value: V,
+    left: Link<K, V>,
+    right: Link<K, V>,
+}
+
+impl<K: Ord, V> BinarySearchTree<K, V> {
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Number of nodes on the longest path from the root to a leaf, which is
+    /// zero for the empty tree.
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut stack: Vec<(&Node<K, V>, usize)> = Vec::new();
+        stack.extend(self.root.as_deref().map(|root| (root, 1)));
+        while let Some((node, depth)) = stack.pop() {
+            height = height.max(depth);
+            for child in [&node.left, &node.right] {
+                stack.extend(child.as_deref().map(|c| (c, depth + 1)));
+            }
+        }
+        height
+    }
+
+    /// Link holding `key`, or the empty link where it would be inserted.
+    fn find_link(&mut self, key: &K) -> &mut Link<K, V> {
+        let mut link = &mut self.root;
+        while let Some(ord) = link.as_ref().map(|node| key.cmp(&node.key)) {
+            link = match ord {
+                Ordering::Less => &mut link.as_mut().unwrap().left,
+                Ordering::Greater => &mut link.as_mut().unwrap().right,
+                Ordering::Equal => break,
+            };
+        }
+        link
+    }
+
+    fn find(&self, key: &K) -> Option<&Node<K, V>> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(node),
+            };
+        }
+        None
+    }
+
+    /// Inserts `value` under `key`, returning the value previously stored
+    /// under it, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let link = self.find_link(&key);
+        match link {
+            Some(node) => Some(core::mem::replace(&mut node.value, value)),
+            None => {
+                *link = Some(Box::new(Node {
+                    key,
+                    value,
+                    left: None,
+                    right: None,
+                }));
+                self.len += 1;
+                None
+            }
+        }
+    }
+
+    /// Removes `key`, returning its value if it was present. A node with at
+    /// most one child is replaced by that child, while a node with two
+    /// children is replaced by its successor, the minimum of its right
+    /// subtree.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let link = self.find_link(key);
+        let mut node = link.take()?;
+        *link = match (node.left.take(), node.right.take()) {
+            (None, None) => None,
+            (Some(child), None) | (None, Some(child)) => Some(child),
+            (Some(left), Some(right)) => {
+                let mut right = Some(right);
+                let mut successor = take_min(&mut right);
+                successor.left = Some(left);
+                successor.right = right;
+                Some(successor)
+            }
+        };
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.find(key).map(|node| &node.value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.find_link(key).as_mut().map(|node| &mut node.value)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Entry with the smallest key.
+    pub fn min(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    /// Entry with the greatest key.
+    pub fn max(&self) -> Option<(&K, &V)> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some((&node.key, &node.value))
+    }
+
+    /// Entry with the smallest key greater than `key`, which need not be in
+    /// the tree.
+    pub fn successor(&self, key: &K) -> Option<(&K, &V)> {
+        let mut best = None;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            if node.key > *key {
+                best = Some(node);
+                current = node.left.as_deref();
+            } else {
+                current = node.right.as_deref();
+            }
+        }
+        best.map(|node| (&node.key, &node.value))
+    }
+
+    /// Entry with the greatest key less than `key`, which need not be in the
+    /// tree.
+    pub fn predecessor(&self, key: &K) -> Option<(&K, &V)> {
+        let mut best = None;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            if node.key < *key {
+                best = Some(node);
+                current = node.right.as_deref();
+            } else {
+                current = node.left.as_deref();
+            }
+        }
+        best.map(|node| (&node.key, &node.value))
+    }
+
+    /// Iterates over the entries in increasing order of keys.
+    pub fn iter(&self) -> InOrder<'_, K, V> {
+        let mut iter = InOrder { stack: Vec::new() };
+        iter.push_left(self.root.as_deref());
+        iter
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// Iterates over the entries visiting each node before its left and
+    /// right subtrees.
+    pub fn preorder(&self) -> PreOrder<'_, K, V> {
+        PreOrder {
+            stack: self.root.as_deref().into_iter().collect(),
+        }
+    }
+
+    /// Iterates over the entries visiting each node after its left and right
+    /// subtrees.
+    pub fn postorder(&self) -> PostOrder<'_, K, V> {
+        PostOrder {
+            stack: self
+                .root
+                .as_deref()
+                .map(|root| (root, false))
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// Detaches the node with the smallest key from the nonempty subtree `link`,
+/// putting its right subtree in its place.
+fn take_min<K, V>(mut link: &mut Link<K, V>) -> Box<Node<K, V>> {
+    while link.as_ref().unwrap().left.is_some() {
+        link = &mut link.as_mut().unwrap().left;
+    }
+    let mut node = link.take().unwrap();
+    *link = node.right.take();
+    node
+}
+
+impl<K: Ord, V> Default for BinarySearchTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for BinarySearchTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = BinarySearchTree::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+impl<K: Ord, V> Drop for BinarySearchTree<K, V> {
+    /// Frees the nodes iteratively, since degenerate trees may be too deep
+    /// for a recursive drop.
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<K, V>>> =
+            self.root.take().into_iter().collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+pub struct InOrder<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> InOrder<'a, K, V> {
+    fn push_left(&mut self, mut node: Option<&'a Node<K, V>>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for InOrder<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+pub struct PreOrder<'a, K, V> {
+    stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iterator for PreOrder<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        // The left subtree is pushed last so that it is visited first
+        self.stack.extend(node.right.as_deref());
+        self.stack.extend(node.left.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+pub struct PostOrder<'a, K, V> {
+    // Nodes along with whether their subtrees were already pushed
+    stack: Vec<(&'a Node<K, V>, bool)>,
+}
+
+impl<'a, K, V> Iterator for PostOrder<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, expanded)) = self.stack.pop() {
+            if expanded {
+                return Some((&node.key, &node.value));
+            }
+            self.stack.push((node, true));
+            self.stack.extend(node.right.as_deref().map(|n| (n, false)));
+            self.stack.extend(node.left.as_deref().map(|n| (n, false)));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use std::collections::BTreeMap;
+
+    //        5
+    //      /   \
+    //     2     8
+    //    / \   / \
+    //   1   4 7   9
+    //      /
+    //     3
+    fn sample() -> BinarySearchTree<i32, i32> {
+        [5, 2, 8, 1, 4, 7, 9, 3]
+            .into_iter()
+            .map(|k| (k, 10 * k))
+            .collect()
+    }
+
+    fn keys(tree: &BinarySearchTree<i32, i32>) -> Vec<i32> {
+        tree.keys().copied().collect()
+    }
+
+    #[test]
+    fn insert_and_get() {
+        let mut tree = sample();
+        assert_eq!(tree.len(), 8);
+        assert_eq!(tree.height(), 4);
+        assert_eq!(tree.get(&4), Some(&40));
+        assert_eq!(tree.get(&6), None);
+        assert!(tree.contains_key(&9));
+
+        assert_eq!(tree.insert(4, 0), Some(40));
+        assert_eq!(tree.len(), 8);
+        *tree.get_mut(&4).unwrap() += 1;
+        assert_eq!(tree.get(&4), Some(&1));
+        assert_eq!(tree.get_mut(&6), None);
+    }
+
+    #[test]
+    fn traversals() {
+        let tree = sample();
+        assert_eq!(keys(&tree), [1, 2, 3, 4, 5, 7, 8, 9]);
+        let pre: Vec<i32> = tree.preorder().map(|(k, _)| *k).collect();
+        assert_eq!(pre, [5, 2, 1, 4, 3, 8, 7, 9]);
+        let post: Vec<i32> = tree.postorder().map(|(k, _)| *k).collect();
+        assert_eq!(post, [1, 3, 4, 2, 7, 9, 8, 5]);
+
+        let empty: BinarySearchTree<i32, i32> = BinarySearchTree::new();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.preorder().next(), None);
+        assert_eq!(empty.postorder().next(), None);
+        assert_eq!(empty.height(), 0);
+    }
+
+    #[test]
+    fn extrema_and_neighbours() {
+        let tree = sample();
+        assert_eq!(tree.min(), Some((&1, &10)));
+        assert_eq!(tree.max(), Some((&9, &90)));
+
+        assert_eq!(tree.successor(&4), Some((&5, &50)));
+        assert_eq!(tree.successor(&5), Some((&7, &70)));
+        assert_eq!(tree.successor(&6), Some((&7, &70)));
+        assert_eq!(tree.successor(&9), None);
+        assert_eq!(tree.predecessor(&5), Some((&4, &40)));
+        assert_eq!(tree.predecessor(&3), Some((&2, &20)));
+        assert_eq!(tree.predecessor(&7), Some((&5, &50)));
+        assert_eq!(tree.predecessor(&1), None);
+
+        let empty: BinarySearchTree<i32, i32> = BinarySearchTree::new();
+        assert_eq!((empty.min(), empty.max()), (None, None));
+        assert_eq!(empty.successor(&0), None);
+    }
+
+    #[test]
+    fn remove() {
+        // Leaf
+        let mut tree = sample();
+        assert_eq!(tree.remove(&3), Some(30));
+        assert_eq!(keys(&tree), [1, 2, 4, 5, 7, 8, 9]);
+
+        // Single child
+        let mut tree = sample();
+        assert_eq!(tree.remove(&4), Some(40));
+        assert_eq!(keys(&tree), [1, 2, 3, 5, 7, 8, 9]);
+        let pre: Vec<i32> = tree.preorder().map(|(k, _)| *k).collect();
+        assert_eq!(pre, [5, 2, 1, 3, 8, 7, 9]);
+
+        // Two children: replaced by the successor
+        let mut tree = sample();
+        assert_eq!(tree.remove(&2), Some(20));
+        let pre: Vec<i32> = tree.preorder().map(|(k, _)| *k).collect();
+        assert_eq!(pre, [5, 3, 1, 4, 8, 7, 9]);
+        assert_eq!(tree.remove(&5), Some(50));
+        let pre: Vec<i32> = tree.preorder().map(|(k, _)| *k).collect();
+        assert_eq!(pre, [7, 3, 1, 4, 8, 9]);
+
+        assert_eq!(tree.remove(&5), None);
+        assert_eq!(tree.len(), 6);
+        for k in [1, 3, 4, 7, 8, 9] {
+            assert_eq!(tree.remove(&k), Some(10 * k));
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.len(), 0);
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        let mut tree = BinarySearchTree::new();
+        let mut expected = BTreeMap::new();
+        let mut rng = Pcg32::seed(0x9e3779b97f4a7c15);
+        for step in 0..5000 {
+            let key = rng.gen_range(0..300_u64);
+            if rng.gen_range(0..3) == 0 {
+                assert_eq!(tree.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(tree.insert(key, step), expected.insert(key, step));
+            }
+        }
+        assert_eq!(tree.len(), expected.len());
+        assert!(tree.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn degenerate() {
+        // Sorted insertions build a path, which must still drop fine
+        let tree: BinarySearchTree<u32, ()> =
+            (0..20_000).map(|k| (k, ())).collect();
+        assert_eq!(tree.height(), 20_000);
+        assert_eq!(tree.max(), Some((&19_999, &())));
+    }
+}
@@ -0,0 +1,476 @@
+//! B-tree with a configurable minimum degree
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Ordered map over a B-tree of minimum degree `t`, following Cormen et al.
+/// Every node but the root holds between `t - 1` and `2t - 1` entries, and an
+/// internal node with `m` entries has `m + 1` children whose keys are
+/// separated by these entries. All leaves lie at the same depth, which is
+/// thus at most `log_t((n + 1) / 2)`.
+///
+/// Insertions split full nodes on the way down and removals make sure every
+/// visited child can spare an entry, by borrowing from a sibling or merging
+/// with it, so that both run in a single pass from the root.
+///
+/// Example:
+/// ```
+/// use ralg::tree::btree::BTree;
+///
+/// let mut tree = BTree::new(2);
+/// for k in 0..10 {
+///     tree.insert(k, k * k);
+/// }
+/// assert_eq!(tree.get(&7), Some(&49));
+/// assert_eq!(tree.remove(&3), Some(9));
+/// assert_eq!(tree.iter().map(|(k, _)| *k).take(4).collect::<Vec<_>>(), [0, 1, 2, 4]);
+/// ```
+pub struct BTree<K: Ord, V> {
+    root: Node<K, V>,
+    /// Minimum degree.
+    t: usize,
+    len: usize,
+}
+
+struct Node<K, V> {
+    entries: Vec<(K, V)>,
+    /// Empty for leaves.
+    children: Vec<Node<K, V>>,
+}
+
+impl<K: Ord, V> Node<K, V> {
+    fn leaf() -> Self {
+        Node {
+            entries: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        self.entries.binary_search_by(|(k, _)| k.cmp(key))
+    }
+
+    /// Splits the full child `i` around its median entry, which moves up to
+    /// this node.
+    fn split_child(&mut self, i: usize, t: usize) {
+        let child = &mut self.children[i];
+        let entries = child.entries.split_off(t);
+        let children = if child.is_leaf() {
+            Vec::new()
+        } else {
+            child.children.split_off(t)
+        };
+        let median = child.entries.pop().expect("full node");
+        self.entries.insert(i, median);
+        self.children.insert(i + 1, Node { entries, children });
+    }
+
+    /// Inserts a key absent from the subtree, assuming this node is not full.
+    fn insert_nonfull(&mut self, key: K, value: V, t: usize) {
+        let mut node = self;
+        loop {
+            let mut i = node.search(&key).unwrap_err();
+            if node.is_leaf() {
+                node.entries.insert(i, (key, value));
+                return;
+            }
+            if node.children[i].entries.len() == 2 * t - 1 {
+                node.split_child(i, t);
+                if key > node.entries[i].0 {
+                    i += 1;
+                }
+            }
+            node = &mut node.children[i];
+        }
+    }
+
+    /// Merges child `i + 1` and the entry separating it into child `i`.
+    fn merge_children(&mut self, i: usize) {
+        let right = self.children.remove(i + 1);
+        let separator = self.entries.remove(i);
+        let left = &mut self.children[i];
+        left.entries.push(separator);
+        left.entries.extend(right.entries);
+        left.children.extend(right.children);
+    }
+
+    /// Makes sure child `i` has at least `t` entries before descending into
+    /// it, returning the position the child ends up at.
+    fn fill_child(&mut self, i: usize, t: usize) -> usize {
+        if self.children[i].entries.len() >= t {
+            return i;
+        }
+        if i > 0 && self.children[i - 1].entries.len() >= t {
+            // Borrow through the separator from the left sibling
+            let (left, right) = self.children.split_at_mut(i);
+            let (left, child) = (&mut left[i - 1], &mut right[0]);
+            let entry = left.entries.pop().unwrap();
+            let separator = core::mem::replace(&mut self.entries[i - 1], entry);
+            child.entries.insert(0, separator);
+            if let Some(grandchild) = left.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+            i
+        } else if i + 1 < self.children.len()
+            && self.children[i + 1].entries.len() >= t
+        {
+            // Borrow through the separator from the right sibling
+            let (left, right) = self.children.split_at_mut(i + 1);
+            let (child, right) = (&mut left[i], &mut right[0]);
+            let entry = right.entries.remove(0);
+            let separator = core::mem::replace(&mut self.entries[i], entry);
+            child.entries.push(separator);
+            if !right.is_leaf() {
+                child.children.push(right.children.remove(0));
+            }
+            i
+        } else if i + 1 < self.children.len() {
+            self.merge_children(i);
+            i
+        } else {
+            self.merge_children(i - 1);
+            i - 1
+        }
+    }
+
+    fn remove_min(&mut self, t: usize) -> (K, V) {
+        if self.is_leaf() {
+            return self.entries.remove(0);
+        }
+        let i = self.fill_child(0, t);
+        self.children[i].remove_min(t)
+    }
+
+    fn remove_max(&mut self, t: usize) -> (K, V) {
+        if self.is_leaf() {
+            return self.entries.pop().expect("non-empty leaf");
+        }
+        let i = self.fill_child(self.children.len() - 1, t);
+        self.children[i].remove_max(t)
+    }
+
+    /// Removes `key` from the subtree, assuming this node is the root or has
+    /// at least `t` entries.
+    fn remove(&mut self, key: &K, t: usize) -> Option<V> {
+        match self.search(key) {
+            Ok(i) if self.is_leaf() => Some(self.entries.remove(i).1),
+            Ok(i) => {
+                let replacement = if self.children[i].entries.len() >= t {
+                    self.children[i].remove_max(t)
+                } else if self.children[i + 1].entries.len() >= t {
+                    self.children[i + 1].remove_min(t)
+                } else {
+                    self.merge_children(i);
+                    return self.children[i].remove(key, t);
+                };
+                Some(core::mem::replace(&mut self.entries[i], replacement).1)
+            }
+            Err(_) if self.is_leaf() => None,
+            Err(i) => {
+                let i = self.fill_child(i, t);
+                self.children[i].remove(key, t)
+            }
+        }
+    }
+}
+
+impl<K: Ord, V> BTree<K, V> {
+    /// Empty tree of minimum degree `t`.
+    ///
+    /// Panics if `t < 2`.
+    pub fn new(t: usize) -> Self {
+        assert!(t >= 2, "the minimum degree of a B-tree is at least 2");
+        Self {
+            root: Node::leaf(),
+            t,
+            len: 0,
+        }
+    }
+
+    pub fn min_degree(&self) -> usize {
+        self.t
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of levels of nodes, zero for an empty tree.
+    pub fn height(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let mut height = 1;
+        let mut node = &self.root;
+        while let Some(child) = node.children.first() {
+            node = child;
+            height += 1;
+        }
+        height
+    }
+
+    /// Inserts `value` under `key`, returning the value previously stored
+    /// under it, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(core::mem::replace(old, value));
+        }
+        if self.root.entries.len() == 2 * self.t - 1 {
+            // The tree grows at the root
+            let old_root = core::mem::replace(&mut self.root, Node::leaf());
+            self.root.children.push(old_root);
+            self.root.split_child(0, self.t);
+        }
+        self.root.insert_nonfull(key, value, self.t);
+        self.len += 1;
+        None
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let removed = self.root.remove(key, self.t);
+        if self.root.entries.is_empty() {
+            // The tree shrinks at the root
+            if let Some(child) = self.root.children.pop() {
+                self.root = child;
+            }
+        }
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut node = &self.root;
+        loop {
+            match node.search(key) {
+                Ok(i) => return Some(&node.entries[i].1),
+                Err(_) if node.is_leaf() => return None,
+                Err(i) => node = &node.children[i],
+            }
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut node = &mut self.root;
+        loop {
+            match node.search(key) {
+                Ok(i) => return Some(&mut node.entries[i].1),
+                Err(_) if node.is_leaf() => return None,
+                Err(i) => node = &mut node.children[i],
+            }
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Iterates over the entries in increasing order of keys.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        if !self.is_empty() {
+            iter.push_left(&self.root);
+        }
+        iter
+    }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for BTree<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Ord + fmt::Debug, V: fmt::Debug> fmt::Debug for BTree<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    /// Nodes on the path to the next entry, along with the position of their
+    /// next entry.
+    stack: Vec<(&'a Node<K, V>, usize)>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left(&mut self, mut node: &'a Node<K, V>) {
+        loop {
+            self.stack.push((node, 0));
+            match node.children.first() {
+                Some(child) => node = child,
+                None => return,
+            }
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, i) = self.stack.last_mut()?;
+            let node: &'a Node<K, V> = node;
+            if *i < node.entries.len() {
+                let (key, value) = &node.entries[*i];
+                *i += 1;
+                if let Some(child) = node.children.get(*i) {
+                    self.push_left(child);
+                }
+                return Some((key, value));
+            }
+            self.stack.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+    use std::collections::BTreeMap;
+
+    /// Checks the entry counts, key ordering and leaf depths of the subtree,
+    /// returning its height.
+    fn check<K: Ord + Copy, V>(
+        node: &Node<K, V>,
+        t: usize,
+        is_root: bool,
+        bounds: (Option<K>, Option<K>),
+    ) -> usize {
+        let m = node.entries.len();
+        assert!(m < 2 * t);
+        assert!(is_root || m >= t - 1, "underfull node");
+        assert!(node.entries.windows(2).all(|w| w[0].0 < w[1].0));
+        if let (Some(low), Some(first)) = (bounds.0, node.entries.first()) {
+            assert!(low < first.0);
+        }
+        if let (Some(high), Some(last)) = (bounds.1, node.entries.last()) {
+            assert!(last.0 < high);
+        }
+        if node.is_leaf() {
+            return 1;
+        }
+        assert_eq!(node.children.len(), m + 1);
+        let heights: Vec<_> = (0..=m)
+            .map(|i| {
+                let low = if i == 0 {
+                    bounds.0
+                } else {
+                    Some(node.entries[i - 1].0)
+                };
+                let high = if i == m {
+                    bounds.1
+                } else {
+                    Some(node.entries[i].0)
+                };
+                check(&node.children[i], t, false, (low, high))
+            })
+            .collect();
+        assert!(heights.windows(2).all(|w| w[0] == w[1]), "uneven leaves");
+        heights[0] + 1
+    }
+
+    fn check_tree<V>(tree: &BTree<u64, V>) {
+        let height = check(&tree.root, tree.t, true, (None, None));
+        if !tree.is_empty() {
+            assert_eq!(height, tree.height());
+        }
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let mut tree = BTree::new(3);
+        assert_eq!(tree.get(&1), None);
+        assert_eq!(tree.remove(&1), None);
+        assert_eq!(tree.height(), 0);
+
+        assert_eq!(tree.insert(1, 'a'), None);
+        assert_eq!(tree.insert(1, 'b'), Some('a'));
+        *tree.get_mut(&1).unwrap() = 'c';
+        tree.extend([(0, 'x'), (2, 'y')]);
+        assert_eq!(format!("{:?}", tree), "{0: 'x', 1: 'c', 2: 'y'}");
+        assert!(tree.contains_key(&2));
+        assert_eq!(tree.len(), 3);
+
+        assert_eq!(tree.remove(&1), Some('c'));
+        assert_eq!(tree.remove(&1), None);
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn degree_one() {
+        BTree::<i32, i32>::new(1);
+    }
+
+    #[test]
+    fn splits_and_merges() {
+        for t in 2..6 {
+            let mut tree = BTree::new(t);
+            let n = 2000;
+            // Ascending and descending insertions split along one side
+            for k in 0..n {
+                tree.insert(k, k);
+                tree.insert(2 * n - 1 - k, k);
+            }
+            check_tree(&tree);
+            assert_eq!(tree.len(), 2 * n as usize);
+            assert!(tree.iter().map(|(k, _)| *k).eq(0..2 * n));
+
+            // Removing everything merges down to the root
+            for k in (0..2 * n).step_by(2) {
+                assert!(tree.remove(&k).is_some());
+                assert!(tree.remove(&k).is_none());
+                if k % 97 == 0 {
+                    check_tree(&tree);
+                }
+            }
+            check_tree(&tree);
+            for k in (0..n).rev().map(|k| 2 * k + 1) {
+                assert!(tree.remove(&k).is_some());
+            }
+            assert!(tree.is_empty());
+            assert_eq!(tree.height(), 0);
+            assert!(tree.root.is_leaf());
+        }
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        for t in [2, 3, 7] {
+            let mut tree = BTree::new(t);
+            let mut expected = BTreeMap::new();
+            let mut rng = Pcg32::seed(0x9e3779b97f4a7c15);
+            for step in 0..10_000 {
+                let key = rng.gen_range(0..700_u64);
+                if rng.gen_range(0..3) == 0 {
+                    assert_eq!(tree.remove(&key), expected.remove(&key));
+                } else {
+                    assert_eq!(
+                        tree.insert(key, step),
+                        expected.insert(key, step)
+                    );
+                }
+                assert_eq!(tree.len(), expected.len());
+                if step % 1000 == 0 {
+                    check_tree(&tree);
+                }
+            }
+            check_tree(&tree);
+            assert!(tree.iter().eq(expected.iter()));
+        }
+    }
+}
@@ -0,0 +1,412 @@
+//! Interval trees and interval scheduling
+//!
+//! Intervals are half-open, `start..end` holding the points `p` with
+//! `start <= p < end`, so that two intervals where one ends as the other
+//! starts do not overlap.
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::Range;
+
+/// Multimap from intervals to values over an AVL tree ordered by the start of
+/// the intervals, then by their end. Each node also stores the greatest end
+/// in its subtree, so that a query skips the subtrees ending before it: it
+/// takes O(log(n) + k) time to report the `k` intervals found.
+///
+/// Example:
+/// ```
+/// use ralg::tree::interval::IntervalTree;
+///
+/// let mut tree = IntervalTree::new();
+/// tree.insert(1..5, "a");
+/// tree.insert(3..8, "b");
+/// tree.insert(6..7, "c");
+/// let at_4: Vec<_> = tree.stab(&4).into_iter().map(|(_, v)| *v).collect();
+/// assert_eq!(at_4, ["a", "b"]);
+/// assert_eq!(tree.overlapping(&(5..6)).len(), 1);
+/// assert_eq!(tree.overlap(&(8..10)), None);
+/// ```
+pub struct IntervalTree<T: Ord + Clone, V> {
+    root: Link<T, V>,
+    len: usize,
+}
+
+type Link<T, V> = Option<Box<Node<T, V>>>;
+
+struct Node<T, V> {
+    interval: Range<T>,
+    value: V,
+    /// Greatest end of the intervals in the subtree.
+    max_end: T,
+    height: usize,
+    left: Link<T, V>,
+    right: Link<T, V>,
+}
+
+fn height<T, V>(link: &Link<T, V>) -> usize {
+    link.as_ref().map_or(0, |node| node.height)
+}
+
+impl<T: Ord + Clone, V> Node<T, V> {
+    fn new(interval: Range<T>, value: V) -> Box<Self> {
+        Box::new(Node {
+            max_end: interval.end.clone(),
+            interval,
+            value,
+            height: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    /// Recomputes the height and greatest end from the children.
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        let mut max_end = &self.interval.end;
+        for child in [&self.left, &self.right].into_iter().flatten() {
+            max_end = max_end.max(&child.max_end);
+        }
+        self.max_end = max_end.clone();
+    }
+
+    /// Height of the left subtree minus the height of the right one.
+    fn balance_factor(&self) -> isize {
+        height(&self.left) as isize - height(&self.right) as isize
+    }
+}
+
+/// Rotates `y` to the right, making its left child `x` the new root.
+fn rotate_right<T: Ord + Clone, V>(mut y: Box<Node<T, V>>) -> Box<Node<T, V>> {
+    let mut x = y.left.take().expect("rotation needs a left child");
+    y.left = x.right.take();
+    y.update();
+    x.right = Some(y);
+    x.update();
+    x
+}
+
+/// Rotates `x` to the left, making its right child `y` the new root.
+fn rotate_left<T: Ord + Clone, V>(mut x: Box<Node<T, V>>) -> Box<Node<T, V>> {
+    let mut y = x.right.take().expect("rotation needs a right child");
+    x.right = y.left.take();
+    x.update();
+    y.left = Some(x);
+    y.update();
+    y
+}
+
+/// Restores the AVL property at `node`, whose subtrees are balanced and
+/// differ in height by at most two.
+fn rebalance<T: Ord + Clone, V>(mut node: Box<Node<T, V>>) -> Box<Node<T, V>> {
+    node.update();
+    match node.balance_factor() {
+        2 => {
+            let left = node.left.take().unwrap();
+            node.left = Some(if left.balance_factor() < 0 {
+                rotate_left(left)
+            } else {
+                left
+            });
+            rotate_right(node)
+        }
+        -2 => {
+            let right = node.right.take().unwrap();
+            node.right = Some(if right.balance_factor() > 0 {
+                rotate_right(right)
+            } else {
+                right
+            });
+            rotate_left(node)
+        }
+        _ => node,
+    }
+}
+
+fn insert<T: Ord + Clone, V>(
+    link: Link<T, V>,
+    interval: Range<T>,
+    value: V,
+) -> Box<Node<T, V>> {
+    let mut node = match link {
+        None => return Node::new(interval, value),
+        Some(node) => node,
+    };
+    let key = (&interval.start, &interval.end);
+    if key < (&node.interval.start, &node.interval.end) {
+        node.left = Some(insert(node.left.take(), interval, value));
+    } else {
+        node.right = Some(insert(node.right.take(), interval, value));
+    }
+    rebalance(node)
+}
+
+/// Collects, in order, the intervals of the subtree at `link` ending after
+/// `after` and whose start satisfies `starts_before`, which must hold for a
+/// prefix of the starts.
+fn query<'a, T: Ord, V, F: Fn(&T) -> bool>(
+    link: &'a Link<T, V>,
+    after: &T,
+    starts_before: &F,
+    found: &mut Vec<(&'a Range<T>, &'a V)>,
+) {
+    let node = match link {
+        Some(node) if node.max_end > *after => node,
+        _ => return,
+    };
+    query(&node.left, after, starts_before, found);
+    if starts_before(&node.interval.start) {
+        if node.interval.end > *after {
+            found.push((&node.interval, &node.value));
+        }
+        query(&node.right, after, starts_before, found);
+    }
+}
+
+impl<T: Ord + Clone, V> IntervalTree<T, V> {
+    pub fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Inserts `value` under `interval`, which may already be in the tree.
+    ///
+    /// Panics if the interval is empty.
+    pub fn insert(&mut self, interval: Range<T>, value: V) {
+        assert!(interval.start < interval.end, "empty interval");
+        self.root = Some(insert(self.root.take(), interval, value));
+        self.len += 1;
+    }
+
+    /// Intervals containing `point`, by increasing start.
+    pub fn stab(&self, point: &T) -> Vec<(&Range<T>, &V)> {
+        let mut found = Vec::new();
+        query(&self.root, point, &|start| start <= point, &mut found);
+        found
+    }
+
+    /// Intervals overlapping `range`, by increasing start.
+    pub fn overlapping(&self, range: &Range<T>) -> Vec<(&Range<T>, &V)> {
+        let mut found = Vec::new();
+        if range.start < range.end {
+            let before = |start: &T| *start < range.end;
+            query(&self.root, &range.start, &before, &mut found);
+        }
+        found
+    }
+
+    /// Some interval overlapping `range`, found in O(log(n)) time by going
+    /// down a single path: left whenever the left subtree ends after the
+    /// start of `range`, since otherwise none of its intervals overlap it.
+    pub fn overlap(&self, range: &Range<T>) -> Option<(&Range<T>, &V)> {
+        if range.start >= range.end {
+            return None;
+        }
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            let interval = &node.interval;
+            if interval.start < range.end && range.start < interval.end {
+                return Some((interval, &node.value));
+            }
+            current = match node.left.as_deref() {
+                Some(left) if left.max_end > range.start => Some(left),
+                _ => node.right.as_deref(),
+            };
+        }
+        None
+    }
+
+    /// Iterates over the intervals by increasing start, then end.
+    pub fn iter(&self) -> impl Iterator<Item = (&Range<T>, &V)> + '_ {
+        let mut stack = Vec::new();
+        let mut current = self.root.as_deref();
+        core::iter::from_fn(move || {
+            while let Some(node) = current {
+                stack.push(node);
+                current = node.left.as_deref();
+            }
+            let node = stack.pop()?;
+            current = node.right.as_deref();
+            Some((&node.interval, &node.value))
+        })
+    }
+}
+
+impl<T: Ord + Clone, V> Default for IntervalTree<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone, V> FromIterator<(Range<T>, V)> for IntervalTree<T, V> {
+    fn from_iter<I: IntoIterator<Item = (Range<T>, V)>>(iter: I) -> Self {
+        let mut tree = IntervalTree::new();
+        for (interval, value) in iter {
+            tree.insert(interval, value);
+        }
+        tree
+    }
+}
+
+/// Indices of a largest set of pairwise non-overlapping `intervals`, by
+/// increasing end. Greedily taking the interval that ends first among those
+/// starting after the last one taken is optimal, since it leaves the most
+/// room for the rest. Takes O(n log(n)) time.
+///
+/// Example:
+/// ```
+/// use ralg::tree::interval::interval_scheduling;
+///
+/// let talks = [0..6, 1..4, 3..5, 4..7, 5..9, 8..10];
+/// assert_eq!(interval_scheduling(&talks), [1, 3, 5]);
+/// ```
+pub fn interval_scheduling<T: Ord>(intervals: &[Range<T>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_by(|&i, &j| intervals[i].end.cmp(&intervals[j].end));
+
+    let mut chosen: Vec<usize> = Vec::new();
+    for i in order {
+        let free = match chosen.last() {
+            Some(&last) => intervals[last].end <= intervals[i].start,
+            None => true,
+        };
+        if free && intervals[i].start < intervals[i].end {
+            chosen.push(i);
+        }
+    }
+    chosen
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::random::Pcg32;
+
+    fn overlaps(a: &Range<i32>, b: &Range<i32>) -> bool {
+        a.start < b.end && b.start < a.end
+    }
+
+    /// Checks the ordering, heights, greatest ends and balance of every
+    /// subtree, returning the height.
+    fn check<V>(link: &Link<i32, V>) -> usize {
+        let node = match link {
+            None => return 0,
+            Some(node) => node,
+        };
+        let key = (node.interval.start, node.interval.end);
+        if let Some(left) = &node.left {
+            assert!((left.interval.start, left.interval.end) <= key);
+        }
+        if let Some(right) = &node.right {
+            assert!((right.interval.start, right.interval.end) >= key);
+        }
+        let (l, r) = (check(&node.left), check(&node.right));
+        assert!(l.abs_diff(r) <= 1, "unbalanced node");
+        assert_eq!(node.height, 1 + l.max(r));
+        let children = [&node.left, &node.right].into_iter().flatten();
+        let max_end = children.map(|c| c.max_end).max().unwrap_or(i32::MIN);
+        assert_eq!(node.max_end, node.interval.end.max(max_end));
+        node.height
+    }
+
+    #[test]
+    fn queries_against_naive() {
+        let mut rng = Pcg32::seed(0x1e7a);
+        let mut tree = IntervalTree::new();
+        let mut intervals = Vec::new();
+        for id in 0..300 {
+            let start = rng.gen_range(0..200);
+            let interval = start..start + rng.gen_range(1..30);
+            tree.insert(interval.clone(), id);
+            intervals.push((interval, id));
+        }
+        check(&tree.root);
+        assert_eq!(tree.len(), 300);
+        intervals.sort_by_key(|(r, id)| (r.start, r.end, *id));
+        let sorted: Vec<_> = tree.iter().map(|(r, _)| r.clone()).collect();
+        assert!(sorted.iter().eq(intervals.iter().map(|(r, _)| r)));
+
+        for _ in 0..200 {
+            let p = rng.gen_range(-5..240);
+            let mut stabbed: Vec<_> =
+                tree.stab(&p).into_iter().map(|(_, &id)| id).collect();
+            let mut expected: Vec<_> = intervals
+                .iter()
+                .filter(|(r, _)| r.contains(&p))
+                .map(|(_, id)| *id)
+                .collect();
+            stabbed.sort();
+            expected.sort();
+            assert_eq!(stabbed, expected);
+
+            let q = p..p + rng.gen_range(1..20);
+            let found = tree.overlapping(&q);
+            assert!(found.windows(2).all(|w| w[0].0.start <= w[1].0.start));
+            assert_eq!(
+                found.len(),
+                intervals.iter().filter(|(r, _)| overlaps(r, &q)).count()
+            );
+            match tree.overlap(&q) {
+                Some((r, _)) => assert!(overlaps(r, &q)),
+                None => assert!(found.is_empty()),
+            }
+        }
+    }
+
+    #[test]
+    fn half_open_bounds() {
+        let tree: IntervalTree<i32, ()> =
+            [(0..2, ()), (2..4, ())].into_iter().collect();
+        assert_eq!(tree.stab(&2).len(), 1);
+        assert!(tree.stab(&4).is_empty());
+        assert!(tree.overlapping(&(1..1)).is_empty());
+        assert_eq!(tree.overlap(&(4..5)), None);
+        assert!(IntervalTree::<i32, ()>::new().is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_interval() {
+        IntervalTree::new().insert(3..3, ());
+    }
+
+    #[test]
+    fn scheduling_is_maximum() {
+        assert!(interval_scheduling::<i32>(&[]).is_empty());
+        // Empty intervals are never taken
+        assert_eq!(interval_scheduling(&[2..2, 0..1]), [1]);
+
+        let mut rng = Pcg32::seed(0x5c4ed);
+        for _ in 0..200 {
+            let n = rng.gen_range(0..10);
+            let intervals: Vec<Range<i32>> = (0..n)
+                .map(|_| {
+                    let start = rng.gen_range(0..20);
+                    start..start + rng.gen_range(1..8)
+                })
+                .collect();
+            let chosen = interval_scheduling(&intervals);
+            for w in chosen.windows(2) {
+                assert!(intervals[w[0]].end <= intervals[w[1]].start);
+            }
+
+            // Largest subset without overlaps, by brute force
+            let best = (0..1_u32 << n)
+                .filter(|mask| {
+                    let mut taken: Vec<&Range<i32>> = (0..n)
+                        .filter(|&i| mask >> i & 1 == 1)
+                        .map(|i| &intervals[i])
+                        .collect();
+                    taken.sort_by_key(|r| r.start);
+                    taken.windows(2).all(|w| w[0].end <= w[1].start)
+                })
+                .map(|mask| mask.count_ones() as usize)
+                .max();
+            assert_eq!(Some(chosen.len()), best);
+        }
+    }
+}
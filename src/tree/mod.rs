@@ -0,0 +1,5 @@
+pub mod avl;
+pub mod bst;
+pub mod btree;
+pub mod interval;
+pub mod treap;
@@ -0,0 +1,519 @@
+//! Treap: randomized binary search tree with split and merge
+use crate::random::Pcg32;
+use alloc::{boxed::Box, vec::Vec};
+use core::cmp::Ordering;
+
+/// Ordered map over a treap: a binary search tree on the keys which is also a
+/// heap on random priorities drawn for each node. The tree has the shape it
+/// would have by inserting the keys in random order, so that its height is
+/// O(log n) in expectation whatever the order of the operations.
+///
+/// Everything is built on two primitives, both exposed: `split_off`, cutting
+/// the tree by key or by position, and `append`, concatenating two trees.
+/// Since the subtree sizes are also stored, a treap can serve as a sequence
+/// indexed by position, ignoring the keys, for instance with `()` keys.
+///
+/// Example:
+/// ```
+/// use ralg::tree::treap::Treap;
+///
+/// let mut tree: Treap<i32, char> = (0..6).zip("abcdef".chars()).collect();
+/// assert_eq!(tree.remove(&2), Some('c'));
+/// let high = tree.split_off(&3);
+/// assert_eq!(tree.iter().map(|(_, &c)| c).collect::<String>(), "ab");
+/// assert_eq!(high.select(0), Some((&3, &'d')));
+///
+/// // Moving the first two elements of a sequence to its back
+/// let mut seq: Treap<(), char> = Treap::new();
+/// for c in "rotate".chars() {
+///     seq.push_back((), c);
+/// }
+/// let mut front = seq.split_off_at(2);
+/// std::mem::swap(&mut seq, &mut front);
+/// seq.append(&mut front);
+/// assert_eq!(seq.iter().map(|(_, &c)| c).collect::<String>(), "tatero");
+/// ```
+pub struct Treap<K: Ord, V> {
+    root: EntryLink<K, V>,
+    rng: Pcg32,
+}
+
+struct Entry<K, V> {
+    key: K,
+    value: V,
+}
+
+type EntryLink<K, V> = Link<Entry<K, V>>;
+
+impl<K, V> Weighted for Entry<K, V> {
+    fn weight(&self) -> usize {
+        1
+    }
+}
+
+/// Items of a treap, each taking up `weight` positions of the sequence of
+/// the tree. Positions are then found by the weights of the subtrees, as for
+/// the characters of the chunks of a rope.
+pub(crate) trait Weighted {
+    fn weight(&self) -> usize;
+}
+
+pub(crate) type Link<T> = Option<Box<Node<T>>>;
+
+/// Node of a treap, storing the total weight of its subtree.
+pub(crate) struct Node<T> {
+    pub(crate) item: T,
+    pub(crate) priority: u32,
+    pub(crate) weight: usize,
+    pub(crate) left: Link<T>,
+    pub(crate) right: Link<T>,
+}
+
+pub(crate) fn weight<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.weight)
+}
+
+impl<T: Weighted> Node<T> {
+    pub(crate) fn new(item: T, priority: u32) -> Box<Self> {
+        Box::new(Node {
+            weight: item.weight(),
+            item,
+            priority,
+            left: None,
+            right: None,
+        })
+    }
+
+    /// Recomputes the weight of the subtree from those of the children.
+    pub(crate) fn update(&mut self) {
+        self.weight =
+            weight(&self.left) + self.item.weight() + weight(&self.right);
+    }
+}
+
+/// Concatenates the trees `a` and `b`, every item of `a` coming before those
+/// of `b`: the root of greater priority stays on top.
+pub(crate) fn merge<T: Weighted>(a: Link<T>, b: Link<T>) -> Link<T> {
+    match (a, b) {
+        (None, b) => b,
+        (a, None) => a,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority > b.priority {
+                a.right = merge(a.right.take(), Some(b));
+                a.update();
+                Some(a)
+            } else {
+                b.left = merge(Some(a), b.left.take());
+                b.update();
+                Some(b)
+            }
+        }
+    }
+}
+
+/// Splits `link` into the nodes for which `goes_left` holds, a prefix of the
+/// in-order traversal, and the remaining ones. `goes_left` is also given the
+/// position of the node, that is, `before` plus the weight of the nodes
+/// preceding it in the traversal of `link`.
+pub(crate) fn split<T: Weighted, F: FnMut(&Node<T>, usize) -> bool>(
+    link: Link<T>,
+    before: usize,
+    goes_left: &mut F,
+) -> (Link<T>, Link<T>) {
+    let mut node = match link {
+        None => return (None, None),
+        Some(node) => node,
+    };
+    let position = before + weight(&node.left);
+    if goes_left(&node, position) {
+        let next = position + node.item.weight();
+        let (low, high) = split(node.right.take(), next, goes_left);
+        node.right = low;
+        node.update();
+        (Some(node), high)
+    } else {
+        let (low, high) = split(node.left.take(), before, goes_left);
+        node.left = high;
+        node.update();
+        (low, Some(node))
+    }
+}
+
+/// Splits `link` into the keys less than `key` and the others.
+fn split_by_key<K: Ord, V>(
+    link: EntryLink<K, V>,
+    key: &K,
+) -> (EntryLink<K, V>, EntryLink<K, V>) {
+    split(link, 0, &mut |node, _| node.item.key < *key)
+}
+
+/// Splits `link` into its first `at` nodes and the others.
+fn split_at<K, V>(
+    link: EntryLink<K, V>,
+    at: usize,
+) -> (EntryLink<K, V>, EntryLink<K, V>) {
+    split(link, 0, &mut |_, position| position < at)
+}
+
+impl<K: Ord, V> Treap<K, V> {
+    /// Empty treap, whose priorities are drawn from a randomly seeded
+    /// generator.
+    pub fn new() -> Self {
+        Self::with_rng(Pcg32::new())
+    }
+
+    /// Empty treap drawing its priorities from `rng`, which makes its shape
+    /// reproducible.
+    pub fn with_rng(rng: Pcg32) -> Self {
+        Self { root: None, rng }
+    }
+
+    /// Treap of the given nodes, with a generator seeded from this one.
+    fn with_root(&mut self, root: EntryLink<K, V>) -> Self {
+        let rng = Pcg32::seed(self.rng.next_u64());
+        Self { root, rng }
+    }
+
+    pub fn len(&self) -> usize {
+        weight(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Number of nodes on the longest path from the root to a leaf.
+    pub fn height(&self) -> usize {
+        let mut stack: Vec<(&Node<Entry<K, V>>, usize)> = self
+            .root
+            .as_deref()
+            .map(|root| (root, 1))
+            .into_iter()
+            .collect();
+        let mut height = 0;
+        while let Some((node, depth)) = stack.pop() {
+            height = height.max(depth);
+            for child in [&node.left, &node.right].into_iter().flatten() {
+                stack.push((child, depth + 1));
+            }
+        }
+        height
+    }
+
+    /// Inserts `value` under `key`, returning the value previously stored
+    /// under it, if any.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(&key) {
+            return Some(core::mem::replace(old, value));
+        }
+        let (low, high) = split_by_key(self.root.take(), &key);
+        let node = Node::new(Entry { key, value }, self.rng.next_u32());
+        self.root = merge(merge(low, Some(node)), high);
+        None
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (low, rest) = split_by_key(self.root.take(), key);
+        let (first, high) = split_at(rest, 1);
+        match first {
+            Some(node) if node.item.key == *key => {
+                self.root = merge(low, high);
+                Some(node.item.value)
+            }
+            first => {
+                self.root = merge(low, merge(first, high));
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.item.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Greater => node.right.as_deref(),
+                Ordering::Equal => return Some(&node.item.value),
+            };
+        }
+        None
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            current = match key.cmp(&node.item.key) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Greater => node.right.as_deref_mut(),
+                Ordering::Equal => return Some(&mut node.item.value),
+            };
+        }
+        None
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Entry at position `k` in key order, counting from zero.
+    pub fn select(&self, mut k: usize) -> Option<(&K, &V)> {
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            let left = weight(&node.left);
+            current = match k.cmp(&left) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Equal => {
+                    return Some((&node.item.key, &node.item.value))
+                }
+                Ordering::Greater => {
+                    k -= left + 1;
+                    node.right.as_deref()
+                }
+            };
+        }
+        None
+    }
+
+    /// Entry at position `k`, with a mutable value.
+    pub fn select_mut(&mut self, mut k: usize) -> Option<(&K, &mut V)> {
+        let mut current = self.root.as_deref_mut();
+        while let Some(node) = current {
+            let left = weight(&node.left);
+            current = match k.cmp(&left) {
+                Ordering::Less => node.left.as_deref_mut(),
+                Ordering::Equal => {
+                    return Some((&node.item.key, &mut node.item.value))
+                }
+                Ordering::Greater => {
+                    k -= left + 1;
+                    node.right.as_deref_mut()
+                }
+            };
+        }
+        None
+    }
+
+    /// Number of keys in the tree less than `key`.
+    pub fn rank(&self, key: &K) -> usize {
+        let mut rank = 0;
+        let mut current = self.root.as_deref();
+        while let Some(node) = current {
+            current = match key.cmp(&node.item.key) {
+                Ordering::Less => node.left.as_deref(),
+                Ordering::Equal => return rank + weight(&node.left),
+                Ordering::Greater => {
+                    rank += weight(&node.left) + 1;
+                    node.right.as_deref()
+                }
+            };
+        }
+        rank
+    }
+
+    /// Splits the tree, returning the entries whose keys are at least `key`
+    /// as a new treap, in O(log n) expected time.
+    pub fn split_off(&mut self, key: &K) -> Self {
+        let (low, high) = split_by_key(self.root.take(), key);
+        self.root = low;
+        self.with_root(high)
+    }
+
+    /// Splits the tree, returning the entries from position `at` onwards as
+    /// a new treap, in O(log n) expected time. Panics if `at > len`.
+    pub fn split_off_at(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "cannot split off at a nonexistent index");
+        let (low, high) = split_at(self.root.take(), at);
+        self.root = low;
+        self.with_root(high)
+    }
+
+    /// Moves every entry of `other` after those of the tree in O(log n)
+    /// expected time, leaving `other` empty.
+    ///
+    /// Panics if a key of `other` is less than a key of the tree, equal keys
+    /// being allowed so that sequences can be built with `()` keys.
+    pub fn append(&mut self, other: &mut Self) {
+        if let (Some(last), Some(first)) = (self.last_key(), other.first_key())
+        {
+            assert!(last <= first, "appended keys out of order");
+        }
+        self.root = merge(self.root.take(), other.root.take());
+    }
+
+    /// Appends the entry to the back, as does `append`.
+    pub fn push_back(&mut self, key: K, value: V) {
+        if let Some(last) = self.last_key() {
+            assert!(*last <= key, "appended keys out of order");
+        }
+        let node = Node::new(Entry { key, value }, self.rng.next_u32());
+        self.root = merge(self.root.take(), Some(node));
+    }
+
+    fn first_key(&self) -> Option<&K> {
+        let mut node = self.root.as_deref()?;
+        while let Some(left) = node.left.as_deref() {
+            node = left;
+        }
+        Some(&node.item.key)
+    }
+
+    fn last_key(&self) -> Option<&K> {
+        let mut node = self.root.as_deref()?;
+        while let Some(right) = node.right.as_deref() {
+            node = right;
+        }
+        Some(&node.item.key)
+    }
+
+    /// Iterates over the entries in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        let mut stack = Vec::new();
+        let mut current = self.root.as_deref();
+        core::iter::from_fn(move || {
+            while let Some(node) = current {
+                stack.push(node);
+                current = node.left.as_deref();
+            }
+            let node = stack.pop()?;
+            current = node.right.as_deref();
+            Some((&node.item.key, &node.item.value))
+        })
+    }
+}
+
+impl<K: Ord, V> Default for Treap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for Treap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut tree = Treap::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    /// Checks the ordering, heap property and sizes of every subtree.
+    fn check<K: Ord, V>(link: &EntryLink<K, V>) {
+        let node = match link {
+            None => return,
+            Some(node) => node,
+        };
+        for child in [&node.left, &node.right].into_iter().flatten() {
+            assert!(child.priority <= node.priority);
+        }
+        if let Some(left) = &node.left {
+            assert!(left.item.key <= node.item.key);
+        }
+        if let Some(right) = &node.right {
+            assert!(right.item.key >= node.item.key);
+        }
+        check(&node.left);
+        check(&node.right);
+        assert_eq!(node.weight, 1 + weight(&node.left) + weight(&node.right));
+    }
+
+    #[test]
+    fn matches_btree_map() {
+        let mut rng = Pcg32::seed(0x7a3b);
+        let mut tree = Treap::with_rng(Pcg32::seed(1));
+        let mut expected = BTreeMap::new();
+        for step in 0..5000 {
+            let key = rng.gen_range(0..400);
+            if rng.gen_range(0..3) == 0 {
+                assert_eq!(tree.remove(&key), expected.remove(&key));
+            } else {
+                assert_eq!(tree.insert(key, step), expected.insert(key, step));
+            }
+        }
+        check(&tree.root);
+        assert_eq!(tree.len(), expected.len());
+        assert!(tree.iter().eq(expected.iter()));
+        for (k, (key, value)) in expected.iter().enumerate() {
+            assert_eq!(tree.select(k), Some((key, value)));
+            assert_eq!(tree.rank(key), k);
+            assert_eq!(tree.get(key), Some(value));
+        }
+        assert_eq!(tree.select(expected.len()), None);
+    }
+
+    #[test]
+    fn stays_shallow() {
+        let tree: Treap<i32, ()> = (0..4096).map(|k| (k, ())).collect();
+        check(&tree.root);
+        // Expected around 2 ln(n) = 17, the worst case being 4096
+        assert!(tree.height() < 50);
+    }
+
+    #[test]
+    fn split_and_append() {
+        let mut tree: Treap<i32, i32> = (0..100).map(|k| (2 * k, k)).collect();
+        let mut high = tree.split_off(&101);
+        assert_eq!(tree.len(), 51);
+        assert_eq!(high.select(0), Some((&102, &51)));
+        let mut middle = tree.split_off_at(50);
+        assert_eq!(middle.iter().collect::<Vec<_>>(), [(&100, &50)]);
+        check(&tree.root);
+        check(&high.root);
+
+        tree.append(&mut middle);
+        tree.append(&mut high);
+        assert!(middle.is_empty() && high.is_empty());
+        assert_eq!(tree.len(), 100);
+        check(&tree.root);
+        assert!(tree.iter().map(|(&k, _)| k).eq((0..100).map(|k| 2 * k)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn append_out_of_order() {
+        let mut low: Treap<i32, ()> = [(5, ())].into_iter().collect();
+        let mut high: Treap<i32, ()> = [(3, ())].into_iter().collect();
+        low.append(&mut high);
+    }
+
+    #[test]
+    fn implicit_sequence() {
+        let mut rng = Pcg32::seed(0x7ea9);
+        let mut seq: Treap<(), usize> = Treap::with_rng(Pcg32::seed(2));
+        let mut naive: Vec<usize> = Vec::new();
+        for step in 0..2000 {
+            let at = rng.gen_range(0..naive.len() + 1);
+            match rng.gen_range(0..3) {
+                // Insertion at a position
+                0 => {
+                    let mut rest = seq.split_off_at(at);
+                    seq.push_back((), step);
+                    seq.append(&mut rest);
+                    naive.insert(at, step);
+                }
+                // Rotation of the sequence
+                1 => {
+                    let mut rest = seq.split_off_at(at);
+                    rest.append(&mut seq);
+                    seq = rest;
+                    naive.rotate_left(at);
+                }
+                _ => {
+                    if let Some((_, value)) = seq.select_mut(at) {
+                        *value += 1;
+                        naive[at] += 1;
+                    }
+                }
+            }
+            assert_eq!(seq.len(), naive.len());
+        }
+        check(&seq.root);
+        assert!(seq.iter().map(|(_, &v)| v).eq(naive.iter().copied()));
+    }
+}